@@ -0,0 +1,105 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use crate::config::Account;
+use crate::reconnect::KickReason;
+use tracing::warn;
+
+// ============================================================
+// ACCOUNTS — A pool of identities, rotated when one gets banned
+// "You are banned from this server" → próxima conta, mesma memória
+// ============================================================
+
+const RECORDS_PATH: &str = "data/accounts.json";
+
+/// What happened to one account, across every server
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccountRecord {
+    pub kicks: u32,
+    pub streak: u32,             // Kicks in a row; a good session clears it
+    pub banned_on: Vec<String>,  // Server profile names
+    pub last_used: Option<DateTime<Utc>>,
+}
+
+/// Records for every account we've logged in with, keyed by identity
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AccountPool {
+    pub records: HashMap<String, AccountRecord>,
+}
+
+impl AccountPool {
+    pub fn load() -> Self {
+        fs::read_to_string(RECORDS_PATH)
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        let _ = fs::create_dir_all("data");
+        match serde_json::to_string_pretty(self) {
+            Ok(data) => {
+                if let Err(e) = fs::write(RECORDS_PATH, data) {
+                    warn!("[ACCOUNTS] Failed to save: {}", e);
+                }
+            }
+            Err(e) => warn!("[ACCOUNTS] Failed to serialize: {}", e),
+        }
+    }
+
+    pub fn on_login(&mut self, account: &Account) {
+        self.records.entry(account.key()).or_default().last_used = Some(Utc::now());
+    }
+
+    /// Log how a session ended. True = time to switch accounts
+    pub fn record(&mut self, account: &Account, server: &str, kick: KickReason, stable: bool, rotate_after: u32) -> bool {
+        let record = self.records.entry(account.key()).or_default();
+        match kick {
+            KickReason::Banned => {
+                if !record.banned_on.iter().any(|s| s == server) {
+                    record.banned_on.push(server.to_string());
+                }
+                true
+            }
+            KickReason::Whitelist => true, // Someone may add it later, so not remembered
+            KickReason::AntiBot => {
+                record.kicks += 1;
+                record.streak += 1;
+                record.streak >= rotate_after
+            }
+            _ => {
+                if stable {
+                    record.streak = 0;
+                }
+                false
+            }
+        }
+    }
+
+    pub fn is_banned(&self, account: &Account, server: &str) -> bool {
+        self.records.get(&account.key()).is_some_and(|r| r.banned_on.iter().any(|s| s == server))
+    }
+
+    /// Next account after `current` not banned on this server, wrapping around. None = all burned
+    pub fn next_usable(&mut self, pool: &[Account], current: usize, server: &str) -> Option<usize> {
+        let next = (1..=pool.len())
+            .map(|step| (current + step) % pool.len())
+            .find(|&i| !self.is_banned(&pool[i], server))?;
+        self.records.entry(pool[next].key()).or_default().streak = 0; // Fresh start
+        Some(next)
+    }
+
+    /// "conta: PedroRTX (1/3) | banidas aqui: Alt1"
+    pub fn summary(&self, pool: &[Account], active: usize, server: &str) -> String {
+        let banned: Vec<&str> = pool.iter()
+            .filter(|a| self.is_banned(a, server))
+            .map(|a| a.name.as_str())
+            .collect();
+        let mut line = format!("conta: {} ({}/{})", pool[active].label(), active + 1, pool.len());
+        if !banned.is_empty() {
+            line.push_str(&format!(" | banidas aqui: {}", banned.join(", ")));
+        }
+        line
+    }
+}
@@ -0,0 +1,573 @@
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{broadcast, mpsc};
+use tracing::{info, warn};
+use crate::config::{Config, Features};
+use crate::session::{self, Session};
+use crate::systems::motor::MotorCommand;
+
+// ============================================================
+// API — Local HTTP control panel, token required
+// curl -H "Authorization: Bearer $API_TOKEN" localhost:8787/status
+// websocat "ws://localhost:8787/events?token=$API_TOKEN" → um JSON por evento
+// http://localhost:8787/#token=... → painel com mapa ao vivo
+// ============================================================
+
+/// Biggest request head/body we'll read
+const MAX_HEAD: usize = 16 * 1024;
+const MAX_BODY: usize = 64 * 1024;
+/// A client that hasn't finished its request by now isn't going to
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+/// Dashboards only send pings and closes — anything bigger is someone misbehaving
+const MAX_FRAME: u64 = 4 * 1024;
+const WS_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+/// The dashboard page. It asks for the token itself, so serving it is harmless
+const DASHBOARD: &str = include_str!("dashboard.html");
+/// Chat lines the dashboard starts with
+const RECENT_CHAT: usize = 20;
+
+/// Serve until the process ends. Runs once, across reconnects
+pub async fn serve(bind: String) {
+    let listener = match TcpListener::bind(&bind).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            warn!("[API] ⚠️ Não consegui abrir {}: {}", bind, e);
+            return;
+        }
+    };
+    info!("[API] 🔌 Ouvindo em http://{}", bind);
+    loop {
+        let Ok((stream, _)) = listener.accept().await else { continue };
+        tokio::spawn(handle_connection(stream));
+    }
+}
+
+/// One request per connection, then close — unless it's a WebSocket for /events
+async fn handle_connection(mut stream: TcpStream) {
+    let request = tokio::time::timeout(READ_TIMEOUT, read_request(&mut stream)).await.unwrap_or(Err(408));
+    let (status, body) = match request {
+        Ok(request) if request.method == "GET" && request.path == "/" => {
+            return send(&mut stream, 200, "text/html; charset=utf-8", DASHBOARD).await;
+        }
+        // Unauthenticated on purpose, for orchestrators — it only says whether we're alive
+        Ok(request) if request.method == "GET" && request.path == "/healthz" => {
+            let health = crate::watchdog::health();
+            (if health.ok { 200 } else { 503 }, json!(health))
+        }
+        // Other bots of the fleet — they carry the knowledge token, not ours
+        Ok(request) if request.method == "POST" && request.path == "/knowledge" => {
+            crate::knowledge::serve(request.token.as_deref(), &request.body)
+        }
+        Ok(request) if request.path == "/events" => match upgrade(&mut stream, &request).await {
+            Ok(()) => return stream_events(stream).await,
+            Err((status, body)) => (status, body),
+        },
+        Ok(request) => respond(request),
+        Err(status) => (status, json!({ "error": reason(status) })),
+    };
+    send(&mut stream, status, "application/json", &body.to_string()).await;
+}
+
+async fn send(stream: &mut TcpStream, status: u16, content_type: &str, body: &str) {
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status, reason(status), content_type, body.len(), body
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+struct Request {
+    method: String,
+    path: String,
+    token: Option<String>,
+    ws_key: Option<String>,
+    body: Vec<u8>,
+}
+
+async fn read_request(stream: &mut (impl AsyncRead + Unpin)) -> Result<Request, u16> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let head_end = loop {
+        let end = buf.windows(4).position(|w| w == b"\r\n\r\n").map(|i| i + 4);
+        if end.unwrap_or(buf.len()) > MAX_HEAD {
+            return Err(431);
+        }
+        if let Some(end) = end {
+            break end;
+        }
+        let n = stream.read(&mut chunk).await.map_err(|_| 400u16)?;
+        if n == 0 {
+            return Err(400);
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+
+    let head = String::from_utf8_lossy(&buf[..head_end]).to_string();
+    let mut lines = head.lines();
+    let mut first = lines.next().unwrap_or_default().split_whitespace();
+    let method = first.next().unwrap_or_default().to_string();
+    let target = first.next().unwrap_or_default();
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let path = path.to_string();
+    // Browsers can't set headers on a WebSocket, so the token may come in the URL
+    let mut token = query.split('&').find_map(|p| p.strip_prefix("token=")).map(str::to_string);
+    let mut length = 0;
+    let mut ws_key = None;
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else { continue };
+        match name.trim().to_lowercase().as_str() {
+            "content-length" => length = value.trim().parse().map_err(|_| 400u16)?,
+            "authorization" => token = value.trim().strip_prefix("Bearer ").map(|t| t.trim().to_string()),
+            "sec-websocket-key" => ws_key = Some(value.trim().to_string()),
+            _ => {}
+        }
+    }
+    if length > MAX_BODY {
+        return Err(413);
+    }
+
+    let mut body = buf[head_end..].to_vec();
+    while body.len() < length {
+        let n = stream.read(&mut chunk).await.map_err(|_| 400u16)?;
+        if n == 0 {
+            return Err(400);
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+    body.truncate(length);
+    Ok(Request { method, path, token, ws_key, body })
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        101 => "Switching Protocols",
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        408 => "Request Timeout",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        _ => "Error",
+    }
+}
+
+fn authorized(request: &Request) -> bool {
+    token_matches(request.token.as_deref(), &Config::load().api_token)
+}
+
+/// Constant-time, so response timing doesn't give the token away a byte at a time.
+/// (ring's verify_slices_are_equal does the same, but 0.17 deprecates it for outside use)
+pub fn token_matches(given: Option<&str>, expected: &str) -> bool {
+    let Some(given) = given else { return false };
+    let (given, expected) = (given.as_bytes(), expected.as_bytes());
+    let diff = given.iter().zip(expected).fold(0u8, |diff, (a, b)| diff | (a ^ b));
+    std::hint::black_box(diff) == 0 && given.len() == expected.len()
+}
+
+fn respond(request: Request) -> (u16, Value) {
+    let config = Config::load();
+    if !authorized(&request) {
+        return (401, json!({ "error": "token inválido" }));
+    }
+    let body: Value = if request.body.is_empty() {
+        Value::Null
+    } else {
+        match serde_json::from_slice(&request.body) {
+            Ok(body) => body,
+            Err(e) => return (400, json!({ "error": format!("JSON inválido: {}", e) })),
+        }
+    };
+    info!("[API] 🔌 {} {}", request.method, request.path);
+
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/status") => (200, status(&config)),
+        ("GET", "/features") => (200, features(&config.features)),
+        ("POST", "/features") => set_feature(body),
+        ("GET", "/goals") => with_session(|s| {
+            let planner = s.state.brain.goals.lock().unwrap();
+            json!({
+                "active": planner.current_goal().map(|g| g.name.clone()),
+                "completed": planner.completed_count,
+                "failed": planner.failed_count,
+                "goals": planner.goals,
+            })
+        }),
+        ("GET", "/memory") => with_session(|s| {
+            let memory = s.state.brain.memory.lock().unwrap();
+            json!({
+                "episodes": memory.episodes.episodes.len(),
+                "locations": memory.spatial.locations.len(),
+                "players": memory.social.players.len(),
+                "ledgers": memory.economy.ledgers.len(),
+                "home": memory.spatial.home_coords,
+                "summary": memory.full_context(),
+            })
+        }),
+        ("GET", "/map") => with_session(map),
+        ("GET", "/observer") => with_session(|s| s.state.observer.lock().unwrap().report()),
+        ("POST", "/chatlog/search") => match serde_json::from_value::<crate::chatlog::Search>(if body.is_null() { json!({}) } else { body }) {
+            Ok(search) => (200, json!({ "entries": crate::chatlog::search(&search) })),
+            Err(e) => (400, json!({ "error": e.to_string() })),
+        },
+        ("GET", "/profile") => (200, crate::profiler::report()),
+        ("GET", "/stats") => (200, json!({
+            "current": crate::stats::live().map(|(uptime_secs, counts)| json!({ "uptime_secs": uptime_secs, "counts": counts })),
+            "history": crate::stats::history(20),
+        })),
+        ("POST", "/chat") => {
+            let Some(message) = body.get("message").and_then(Value::as_str).filter(|m| !m.trim().is_empty()) else {
+                return (400, json!({ "error": "manda {\"message\": \"...\"}" }));
+            };
+            let message = message.to_string();
+            with_session(move |s| {
+                s.state.motor.queue(MotorCommand::Chat(message));
+                json!({ "queued": true })
+            })
+        }
+        ("POST", "/motor") => match serde_json::from_value::<MotorRequest>(body) {
+            Ok(request) => with_session(move |s| {
+                s.state.motor.queue(request.into_command());
+                json!({ "queued": true })
+            }),
+            Err(e) => (400, json!({ "error": e.to_string() })),
+        },
+        ("POST", "/team") => match serde_json::from_value::<TeamRequest>(body) {
+            Ok(request) => with_session(move |s| {
+                crate::team::send(&s.bot.username(), request.to.as_deref(), request.signal);
+                json!({ "sent": true })
+            }),
+            Err(e) => (400, json!({ "error": e.to_string() })),
+        },
+        ("POST", "/save") => with_session(|s| {
+            s.state.brain.memory.lock().unwrap().save();
+            s.state.brain.goals.lock().unwrap().save();
+            info!("[API] 💾 Memory saved.");
+            json!({ "saved": true })
+        }),
+        _ => (404, json!({ "error": "rota desconhecida" })),
+    }
+}
+
+/// Run against the live session, or 503 while we're offline
+fn with_session(f: impl FnOnce(&Session) -> Value) -> (u16, Value) {
+    match session::current() {
+        Some(session) => (200, f(&session)),
+        None => (503, json!({ "error": "desconectado" })),
+    }
+}
+
+fn status(config: &Config) -> Value {
+    let mut status = json!({
+        "connected": false,
+        "server": config.profile().name,
+        "account": config.bot_name,
+        "features": features(&config.features),
+    });
+    if !config.swarm.is_empty() {
+        // Who else from the swarm is online; everything below is about the main bot
+        let online: Vec<String> = session::all().iter().map(|s| s.bot.username()).collect();
+        status["swarm"] = config.swarm.iter()
+            .map(|b| json!({ "name": b.name, "connected": online.contains(&b.name) }))
+            .collect::<Vec<_>>()
+            .into();
+    }
+    if let Some(s) = session::current() {
+        let pos = s.bot.position();
+        let goal = s.state.brain.goals.lock().unwrap().current_goal().map(|g| g.name.clone());
+        status["connected"] = true.into();
+        status["uptime_secs"] = s.since.elapsed().as_secs().into();
+        status["position"] = json!([pos.x, pos.y, pos.z]);
+        status["health"] = s.bot.health().into();
+        status["food"] = s.bot.hunger().food.into();
+        {
+            let personality = s.state.brain.personality.lock().unwrap();
+            status["mood"] = format!("{:?}", personality.mood).into();
+            status["gauges"] = json!({
+                "intensidade": personality.mood_intensity,
+                "orgulho": personality.pride_level,
+                "frustração": personality.frustration,
+                "bateria social": personality.social_battery,
+            });
+        }
+        let chat = s.state.brain.chat_history.lock().unwrap();
+        status["recent_chat"] = json!(chat[chat.len().saturating_sub(RECENT_CHAT)..]);
+        status["goal"] = json!(goal);
+        status["motor_queue"] = s.state.motor.view().queued.into();
+        status["systems"] = s.state.systems.describe(&config.features).into_iter()
+            .map(|(name, on, summary)| json!({ "name": name, "on": on, "summary": summary }))
+            .collect();
+    }
+    status
+}
+
+/// Everything the dashboard map draws: explored chunks, known places, who's around
+fn map(s: &Session) -> Value {
+    let pos = s.bot.position();
+    let (home, locations) = {
+        let memory = s.state.brain.memory.lock().unwrap();
+        let locations: Vec<Value> = memory.spatial.locations.iter()
+            .map(|l| json!({ "name": l.name, "type": format!("{:?}", l.location_type), "coords": l.coords }))
+            .collect();
+        (memory.spatial.home_coords, locations)
+    };
+    let world = s.state.brain.world.lock().unwrap();
+    let players: Vec<Value> = world.nearby_entities.iter()
+        .filter(|e| e.is_player())
+        .map(|e| json!({ "name": e.name, "position": e.position, "distance": e.distance }))
+        .collect();
+    json!({
+        "position": [pos.x, pos.y, pos.z],
+        "home": home,
+        "locations": locations,
+        "visited": world.visited_chunks,
+        "frontier": world.frontier,
+        "players": players,
+    })
+}
+
+fn features(features: &Features) -> Value {
+    Value::Object(Features::NAMES.iter().map(|n| (n.to_string(), features.get(n).unwrap_or(false).into())).collect())
+}
+
+/// {"name": "dreamer", "on": false}
+fn set_feature(body: Value) -> (u16, Value) {
+    let (Some(name), Some(on)) = (body.get("name").and_then(Value::as_str), body.get("on").and_then(Value::as_bool)) else {
+        return (400, json!({ "error": "manda {\"name\": \"dreamer\", \"on\": false}" }));
+    };
+    match Config::set_feature(name, on) {
+        Ok(()) => {
+            info!("[API] 🎚️ {} {}", name, if on { "ligado" } else { "desligado" });
+            (200, features(&Config::load().features))
+        }
+        Err(e) => (400, json!({ "error": e })),
+    }
+}
+
+/// What /motor accepts: {"command": "goto", "x": 10, "y": 64, "z": -5}
+#[derive(Debug, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+enum MotorRequest {
+    Jump,
+    Look { yaw: f32, pitch: f32 },
+    RandomLook,
+    Sprint { ticks: u32 },
+    Sneak { ticks: u32 },
+    Walk { ticks: u32 },
+    Goto { x: i32, y: i32, z: i32 },
+    Wander,
+    Whisper { player: String, message: String },
+}
+
+/// What /team accepts: {"kind": "meetup", "at": [10, 64, -5], "reason": "...", "to": "Zezin_"}. No "to" = everyone
+#[derive(Debug, Deserialize)]
+struct TeamRequest {
+    #[serde(default)]
+    to: Option<String>,
+    #[serde(flatten)]
+    signal: crate::team::Signal,
+}
+
+impl MotorRequest {
+    fn into_command(self) -> MotorCommand {
+        match self {
+            MotorRequest::Jump => MotorCommand::Jump,
+            MotorRequest::Look { yaw, pitch } => MotorCommand::LookAt { yaw, pitch },
+            MotorRequest::RandomLook => MotorCommand::RandomLook,
+            MotorRequest::Sprint { ticks } => MotorCommand::StartSprint { duration_ticks: ticks },
+            MotorRequest::Sneak { ticks } => MotorCommand::SneakPulse { duration_ticks: ticks },
+            MotorRequest::Walk { ticks } => MotorCommand::WalkForward { duration_ticks: ticks },
+            MotorRequest::Goto { x, y, z } => MotorCommand::GotoBlock { x, y, z },
+            MotorRequest::Wander => MotorCommand::WanderRandom,
+            MotorRequest::Whisper { player, message } => MotorCommand::Whisper { player, message },
+        }
+    }
+}
+
+/// Answer the WebSocket handshake, or say why not
+async fn upgrade(stream: &mut TcpStream, request: &Request) -> Result<(), (u16, Value)> {
+    if !authorized(request) {
+        return Err((401, json!({ "error": "token inválido" })));
+    }
+    let (Some(key), "GET") = (&request.ws_key, request.method.as_str()) else {
+        return Err((400, json!({ "error": "/events é WebSocket" })));
+    };
+    let response = format!(
+        "HTTP/1.1 101 {}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        reason(101), accept_key(key)
+    );
+    stream.write_all(response.as_bytes()).await.map_err(|_| (400, Value::Null))
+}
+
+/// Sec-WebSocket-Accept for the client's key
+fn accept_key(key: &str) -> String {
+    let mut sha = <sha1::Sha1 as sha1::Digest>::new();
+    sha1::Digest::update(&mut sha, format!("{}{}", key, WS_GUID).as_bytes());
+    base64::engine::general_purpose::STANDARD.encode(sha1::Digest::finalize(sha))
+}
+
+/// Push every event as a text frame until the client goes away
+async fn stream_events(stream: TcpStream) {
+    let mut events = crate::events::subscribe();
+    let (mut reader, mut writer) = stream.into_split();
+    info!("[API] 📡 Ouvinte conectado no /events");
+
+    // Pings get answered, a close (or garbage) ends it
+    let (control, mut replies) = mpsc::channel::<(u8, Vec<u8>)>(8);
+    tokio::spawn(async move {
+        while let Some((opcode, payload)) = read_frame(&mut reader).await {
+            let reply = match opcode {
+                0x9 => (0xA, payload),
+                0x8 => (0x8, payload),
+                _ => continue,
+            };
+            let closing = reply.0 == 0x8;
+            if control.send(reply).await.is_err() || closing {
+                return;
+            }
+        }
+        let _ = control.send((0x8, vec![])).await;
+    });
+
+    loop {
+        let (opcode, payload) = tokio::select! {
+            event = events.recv() => match event {
+                Ok(line) => (0x1, line.into_bytes()),
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    (0x1, json!({ "type": "lagged", "missed": missed }).to_string().into_bytes())
+                }
+                Err(broadcast::error::RecvError::Closed) => (0x8, vec![]),
+            },
+            reply = replies.recv() => reply.unwrap_or((0x8, vec![])),
+        };
+        if writer.write_all(&frame(opcode, &payload)).await.is_err() || opcode == 0x8 {
+            break;
+        }
+    }
+    info!("[API] 📡 Ouvinte saiu do /events");
+}
+
+/// One client frame (always masked). None = closed or broken
+async fn read_frame(reader: &mut (impl AsyncReadExt + Unpin)) -> Option<(u8, Vec<u8>)> {
+    let mut head = [0u8; 2];
+    reader.read_exact(&mut head).await.ok()?;
+    let opcode = head[0] & 0x0F;
+    let mut length = (head[1] & 0x7F) as u64;
+    if length == 126 {
+        length = reader.read_u16().await.ok()? as u64;
+    } else if length == 127 {
+        length = reader.read_u64().await.ok()?;
+    }
+    if length > MAX_FRAME || head[1] & 0x80 == 0 {
+        return None;
+    }
+    let mut mask = [0u8; 4];
+    reader.read_exact(&mut mask).await.ok()?;
+    let mut payload = vec![0u8; length as usize];
+    reader.read_exact(&mut payload).await.ok()?;
+    for (i, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[i % 4];
+    }
+    Some((opcode, payload))
+}
+
+/// Server frames go out whole and unmasked
+fn frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x80 | opcode];
+    match payload.len() {
+        n if n < 126 => out.push(n as u8),
+        n if n <= u16::MAX as usize => {
+            out.push(126);
+            out.extend_from_slice(&(n as u16).to_be_bytes());
+        }
+        n => {
+            out.push(127);
+            out.extend_from_slice(&(n as u64).to_be_bytes());
+        }
+    }
+    out.extend_from_slice(payload);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn parse(raw: &str) -> Result<Request, u16> {
+        read_request(&mut raw.as_bytes()).await
+    }
+
+    /// What a browser sends: same frame, masked
+    fn masked(opcode: u8, payload: &[u8]) -> Vec<u8> {
+        let mask = [0x37, 0xfa, 0x21, 0x3d];
+        let plain = frame(opcode, payload);
+        let header = plain.len() - payload.len();
+        let mut out = plain[..header].to_vec();
+        out[1] |= 0x80;
+        out.extend_from_slice(&mask);
+        out.extend(payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+        out
+    }
+
+    #[tokio::test]
+    async fn requests() {
+        let request = parse("POST /chat?x=1 HTTP/1.1\r\nAuthorization: Bearer abc \r\nContent-Length: 5\r\n\r\nhello, and more").await.unwrap();
+        assert_eq!((request.method.as_str(), request.path.as_str()), ("POST", "/chat"));
+        assert_eq!(request.token.as_deref(), Some("abc"));
+        assert_eq!(request.body, b"hello");
+
+        let request = parse("GET /events?token=xyz HTTP/1.1\r\nSec-WebSocket-Key: dGhlIHNhbXBsZQ==\r\n\r\n").await.unwrap();
+        assert_eq!(request.token.as_deref(), Some("xyz"));
+        assert_eq!(request.ws_key.as_deref(), Some("dGhlIHNhbXBsZQ=="));
+
+        assert_eq!(parse("GET / HTTP/1.1\r\n").await.err(), Some(400)); // Hung up mid-head
+        assert_eq!(parse("POST / HTTP/1.1\r\nContent-Length: 10\r\n\r\nabc").await.err(), Some(400));
+        assert_eq!(parse("POST / HTTP/1.1\r\nContent-Length: nope\r\n\r\n").await.err(), Some(400));
+        let huge = format!("POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n", MAX_BODY + 1);
+        assert_eq!(parse(&huge).await.err(), Some(413));
+        let long_head = format!("GET / HTTP/1.1\r\nX: {}\r\n\r\n", "a".repeat(MAX_HEAD + 1));
+        assert_eq!(parse(&long_head).await.err(), Some(431));
+        assert_eq!(parse(&long_head[..long_head.len() - 4]).await.err(), Some(431)); // Never ends
+    }
+
+    #[tokio::test]
+    async fn idle_clients_dont_finish() {
+        // Connected, never sends a byte: only the timeout in handle_connection gets rid of it
+        let (mut client, _server) = tokio::io::duplex(64);
+        let read = tokio::time::timeout(Duration::from_millis(50), read_request(&mut client));
+        assert!(read.await.is_err());
+    }
+
+    #[test]
+    fn handshake() {
+        // The example in RFC 6455
+        assert_eq!(accept_key("dGhlIHNhbXBsZSBub25jZQ=="), "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+    }
+
+    #[tokio::test]
+    async fn frames_round_trip() {
+        for payload in [vec![], b"ping".to_vec(), vec![7; 300]] {
+            let bytes = masked(0x9, &payload);
+            assert_eq!(read_frame(&mut bytes.as_slice()).await, Some((0x9, payload)));
+        }
+        // 16-bit length as the server writes it
+        assert_eq!(&frame(0x1, &[0; 300])[..4], &[0x81, 126, 1, 44]);
+        // Unmasked or oversized client frames end the stream
+        assert_eq!(read_frame(&mut frame(0x1, b"hi").as_slice()).await, None);
+        assert_eq!(read_frame(&mut masked(0x1, &vec![0; MAX_FRAME as usize + 1]).as_slice()).await, None);
+    }
+
+    #[test]
+    fn tokens() {
+        assert!(token_matches(Some("s3cret"), "s3cret"));
+        assert!(!token_matches(Some("s3cre"), "s3cret"));
+        assert!(!token_matches(Some("s3cret!"), "s3cret"));
+        assert!(!token_matches(None, "s3cret"));
+    }
+}
@@ -1,50 +1,1300 @@
 use crate::plugins;
 use crate::cognitive;
 use crate::systems;
+use crate::session::Session;
 
 use azalea::prelude::*;
+use rand::Rng;
+use azalea::entity::Position;
+use azalea::player::GameProfileComponent;
+use azalea::protocol::packets::game::ClientboundGamePacket;
+use azalea::protocol::packets::game::c_game_event::EventType;
+use azalea::inventory::components::EquipmentSlot;
+use azalea::registry::builtin::{BlockEntityKind, BlockKind};
+use std::sync::atomic::AtomicU64;
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
+use tracing::{debug, info, info_span, warn, Instrument};
+
+/// A player entity we can currently see (name, position, sneaking, where they're looking)
+#[derive(Debug, Clone)]
+pub struct VisiblePlayer {
+    pub name: String,
+    pub pos: [f64; 3],
+    pub sneaking: bool,
+    pub look: [f64; 3], // Unit view vector
+}
+
+/// The other players in the entity feed (world_scanner::scan_entities), nearest first
+pub fn visible_players(feed: &[systems::world_scanner::NearbyEntity]) -> Vec<VisiblePlayer> {
+    feed.iter()
+        .filter(|e| e.is_player())
+        .map(|e| VisiblePlayer { name: e.name.clone(), pos: e.position, sneaking: e.sneaking, look: e.look })
+        .collect()
+}
 
 #[derive(Clone, Component)]
 pub struct State {
-    pub anti_afk: plugins::anti_afk::State,
     pub brain: plugins::brain::State,
-    pub ping: plugins::ping::State,
     pub natural_look: systems::natural_look::State,
     pub inventory_mgr: systems::inventory_manager::State,
     pub reaction: systems::reaction_delay::State,
+    pub systems: plugins::registry::Registry, // Everything in registry() — runs on every event
     // === NEW SYSTEMS ===
     pub motor: systems::motor::MotorState,
     pub visual_cortex: Arc<Mutex<systems::visual_cortex::VisualCortexState>>,
     pub spider_sense: Arc<Mutex<systems::spider_sense::SpiderSense>>,
+    pub grief_watch: Arc<Mutex<systems::grief_watch::GriefWatch>>,
     pub dreamer: Arc<Mutex<cognitive::dreamer::DreamerState>>,
+    pub joined_at: Arc<Mutex<Instant>>,
+    pub tool_wear: Arc<Mutex<systems::tool_crafter::ToolWear>>,
+    pub loadout: Arc<Mutex<systems::loadout::LoadoutState>>,
+    pub builder: Arc<Mutex<systems::builder::Builder>>, // Blueprint in progress, if any (!construir)
+    pub miner: Arc<Mutex<systems::smart_mining::SmartMiner>>, // Corridor/target for mining goals
+    pub fighter: Arc<Mutex<systems::combat::Fighter>>, // Target, strafe side, hit-and-run timer
+    pub last_position_event: Arc<Mutex<(Instant, azalea::Vec3)>>,
+    pub mining_at: Arc<Mutex<Option<azalea::BlockPos>>>, // Block we were breaking last tick, for the session stats
+    pub last_health: Arc<Mutex<f32>>, // Health last tick, to notice hits
+    pub ticks: Arc<AtomicU64>, // Game ticks this session, for the scheduler
+    pub observer: Arc<Mutex<crate::observer::Observations>>, // BOT_MODE=observer's notes
+    pub scripts: Arc<Mutex<crate::scripting::Scripts>>, // This bot's scripts/*.script, with its own cooldowns
 }
 
 impl Default for State {
     fn default() -> Self {
+        Self::for_bot(crate::swarm::Identity::default())
+    }
+}
+
+impl State {
+    /// Everything one bot needs, its brain loaded from `me`'s folder
+    pub fn for_bot(me: crate::swarm::Identity) -> Self {
+        let observer = crate::observer::Observations::load_in(me.folder.clone());
+        let brain = plugins::brain::State::for_bot(me);
+        let natural_look = systems::natural_look::State::default();
+        let inventory_mgr = systems::inventory_manager::State::default();
         Self {
-            anti_afk: plugins::anti_afk::State {
-                last_action: Arc::new(Mutex::new(Instant::now())),
-            },
-            brain: plugins::brain::State::default(),
-            ping: plugins::ping::State::default(),
-            natural_look: systems::natural_look::State::default(),
-            inventory_mgr: systems::inventory_manager::State::default(),
+            systems: registry(&brain, &natural_look, &inventory_mgr),
+            brain,
+            natural_look,
+            inventory_mgr,
             reaction: systems::reaction_delay::State::default(),
             // === NEW SYSTEMS ===
             motor: systems::motor::MotorState::default(),
             visual_cortex: Arc::new(Mutex::new(systems::visual_cortex::VisualCortexState::default())),
             spider_sense: Arc::new(Mutex::new(systems::spider_sense::SpiderSense::default())),
+            grief_watch: Arc::new(Mutex::new(systems::grief_watch::GriefWatch::default())),
             dreamer: Arc::new(Mutex::new(cognitive::dreamer::DreamerState::default())),
+            joined_at: Arc::new(Mutex::new(Instant::now())),
+            tool_wear: Arc::new(Mutex::new(systems::tool_crafter::ToolWear::default())),
+            loadout: Arc::new(Mutex::new(systems::loadout::LoadoutState::default())),
+            builder: Arc::new(Mutex::new(systems::builder::Builder::default())),
+            miner: Arc::new(Mutex::new(systems::smart_mining::SmartMiner::default())),
+            fighter: Arc::new(Mutex::new(systems::combat::Fighter::default())),
+            last_position_event: Arc::new(Mutex::new((Instant::now(), azalea::Vec3::default()))),
+            mining_at: Arc::new(Mutex::new(None)),
+            last_health: Arc::new(Mutex::new(20.0)),
+            ticks: Arc::new(AtomicU64::new(0)),
+            observer: Arc::new(Mutex::new(observer)),
+            scripts: Arc::default(),
+        }
+    }
+}
+
+/// Systems that run on every event, in this order. A new one goes here and nowhere else
+fn registry(
+    brain: &plugins::brain::State,
+    natural_look: &systems::natural_look::State,
+    inventory_mgr: &systems::inventory_manager::State,
+) -> plugins::registry::Registry {
+    plugins::registry::Registry::new(vec![
+        Arc::new(plugins::auto_eat::State::default()),
+        Arc::new(plugins::anti_afk::State::default()),
+        Arc::new(plugins::ping::State::default()),
+        Arc::new(brain.clone()),
+        Arc::new(natural_look.clone()),
+        Arc::new(inventory_mgr.clone()),
+    ])
+}
+
+/// A player tossed us something — book it, settle their debts, say thanks
+fn on_item_received(bot: &Client, state: &State, received: systems::trader::ReceivedItem) {
+    let systems::trader::ReceivedItem { player, item, quantity } = received;
+
+    // Escrow deposits are payment, not gifts
+    let deposit = state.brain.memory.lock().unwrap().economy.escrow_deposit(&player, &item, quantity);
+    if let Some(trade) = deposit {
+        if trade.phase == systems::escrow::EscrowPhase::Releasing {
+            crate::bus::spawn(plugins::brain::release_escrow(bot.clone(), state.brain.clone(), trade).instrument(info_span!("escrow")));
+        }
+        return;
+    }
+
+    // A teammate handing over loot or material ([roles]) — no gift, no debt
+    if crate::config::Config::load().is_own_account(&player) {
+        state.brain.goals.lock().unwrap().complete_wanting(&item);
+        return;
+    }
+
+    // Someone answered our call for help
+    let helped = state.brain.social.lock().unwrap().on_help_received(&player, &item);
+    if helped.is_some() {
+        {
+            let mut memory = state.brain.memory.lock().unwrap();
+            memory.economy.record_help_received(&player, &item, quantity);
+            memory.inventory.clear_failures(&cognitive::memory::InventoryKnowledge::acquire_task(&item));
+            let profile = memory.social.get_or_create(&player);
+            profile.help_requests_fulfilled += 1;
+            profile.trust_level = (profile.trust_level + 5).min(100);
+            profile.update_relationship();
+            memory.episodes.add(cognitive::memory::Episode {
+                timestamp: chrono::Utc::now(),
+                event_type: cognitive::memory::EpisodeType::ReceivedGift,
+                description: format!("{} me ajudou com {} x{} quando eu precisei", player, item, quantity),
+                location: None,
+                players_involved: vec![player.clone()],
+                emotional_impact: 3,
+            });
+            memory.save();
+        }
+        state.brain.goals.lock().unwrap().complete_wanting(&item);
+        state.brain.personality.lock().unwrap()
+            .on_event(&cognitive::personality::PersonalityEvent::HelpedSomeone);
+        state.motor.queue(systems::motor::MotorCommand::Chat(format!("salvou demais {}, te devo essa", player)));
+        return;
+    }
+
+    let settled = {
+        let mut memory = state.brain.memory.lock().unwrap();
+        let settled = memory.economy.record_received(&player, &item, quantity);
+        memory.social.record_interaction(&player, 3); // Gifts buy trust
+        memory.episodes.add(cognitive::memory::Episode {
+            timestamp: chrono::Utc::now(),
+            event_type: cognitive::memory::EpisodeType::ReceivedGift,
+            description: format!("{} me deu {} x{}", player, item, quantity),
+            location: None,
+            players_involved: vec![player.clone()],
+            emotional_impact: 2,
+        });
+        memory.save();
+        settled
+    };
+
+    // That was the price of a truce? Offer it
+    let truce_offer = {
+        let memory = state.brain.memory.lock().unwrap();
+        let mut social = state.brain.social.lock().unwrap();
+        match social.truces.get_mut(&player) {
+            Some(truce) if memory.economy.outstanding_debt(&player).is_none() => truce.on_paid(),
+            _ => None,
+        }
+    };
+
+    let thanks = if let Some(offer) = truce_offer {
+        offer
+    } else if settled > 0 {
+        format!("valeu {}, tamo quite agr", player)
+    } else {
+        let options = [
+            format!("opa valeu {}", player),
+            format!("eita {} brigadão", player),
+            format!("vlw pelo {} mano", item.replace('_', " ")),
+        ];
+        options[rand::thread_rng().gen_range(0..options.len())].clone()
+    };
+    state.motor.queue(systems::motor::MotorCommand::Chat(thanks));
+}
+
+/// Walk over and hand back what a friend lent us when we needed it
+async fn repay_favor(bot: Client, state: State, player: String, item: String, quantity: u32) {
+    if !state.brain.trader.try_begin() {
+        return;
+    }
+    let outcome = systems::trader::give_item(bot.clone(), &player, &item, quantity).await;
+    state.brain.trader.finish();
+
+    if let systems::trader::GiveOutcome::Delivered { quantity, .. } = outcome {
+        let mut memory = state.brain.memory.lock().unwrap();
+        memory.economy.record_repaid(&player, &item, quantity);
+        memory.social.get_or_create(&player).gifts_given.push(item.clone());
+        memory.save();
+        crate::events::say(&bot, format!("toma {} os {} q vc me emprestou, tamo quite", player, item.replace('_', " ")));
+    }
+}
+
+/// Feeling generous: walk over and drop a friend what they said they needed
+async fn give_present(bot: Client, state: State, need: systems::social::PlayerNeed) {
+    if !state.brain.trader.try_begin() {
+        return;
+    }
+    let outcome = systems::trader::give_item(bot.clone(), &need.player, &need.item, need.quantity).await;
+    state.brain.trader.finish();
+
+    let given = match outcome {
+        systems::trader::GiveOutcome::Delivered { quantity, .. }
+        | systems::trader::GiveOutcome::Partial { quantity, .. } => quantity,
+        _ => return,
+    };
+    {
+        let mut memory = state.brain.memory.lock().unwrap();
+        memory.economy.record_present(&need.player, &need.item, given);
+        let profile = memory.social.get_or_create(&need.player);
+        profile.gifts_given.push(need.item.clone());
+        profile.trust_level = (profile.trust_level + 2).min(100);
+        profile.update_relationship();
+        memory.episodes.add(cognitive::memory::Episode {
+            timestamp: chrono::Utc::now(),
+            event_type: cognitive::memory::EpisodeType::GaveGift,
+            description: format!("Dei {} x{} de presente pro {}", need.item, given, need.player),
+            location: None,
+            players_involved: vec![need.player.clone()],
+            emotional_impact: 2,
+        });
+        memory.save();
+    }
+    state.brain.personality.lock().unwrap()
+        .on_event(&cognitive::personality::PersonalityEvent::HelpedSomeone);
+    let line = state.brain.social.lock().unwrap().generate_gift_line(&need.player, &need.item);
+    crate::events::say(&bot, line);
+}
+
+/// First time we see this name: say hi, point them somewhere safe, owe them a kit.
+/// Returns the welcome line when the welcomer is turned on
+fn welcome_newcomer(bot: &Client, state: &State, player: &str) -> Option<String> {
+    let config = crate::config::Config::load();
+    if !config.welcomer_enabled {
+        return None;
+    }
+    let pos = bot.position();
+    let here = [pos.x as i32, pos.y as i32, pos.z as i32];
+    info!("[SOCIAL] 🌱 {} é novo no server, dando boas-vindas", player);
+
+    let mut memory = state.brain.memory.lock().unwrap();
+    // Never hand out the base — a configured spot or a village we know
+    let safe_spot = config.welcomer_safe_coords.or_else(|| {
+        memory.spatial.nearest_of_type(here, &cognitive::memory::LocationType::Village).map(|l| l.coords)
+    });
+    let profile = memory.social.get_or_create(player);
+    profile.trust_level = (profile.trust_level + 5).min(100);
+    profile.notes.push("novato, dei as boas-vindas quando chegou".into());
+    profile.update_relationship();
+    memory.episodes.add(cognitive::memory::Episode {
+        timestamp: chrono::Utc::now(),
+        event_type: cognitive::memory::EpisodeType::MetPlayer,
+        description: format!("{} entrou pela primeira vez, dei as boas-vindas", player),
+        location: None,
+        players_involved: vec![player.to_string()],
+        emotional_impact: 1,
+    });
+    drop(memory);
+    let holdings = systems::inventory::Inventory::read(bot).counts();
+    Some(state.brain.social.lock().unwrap().welcome_newcomer(player, safe_spot, &holdings))
+}
+
+/// A newcomer we welcomed came over: hand them whatever starter items we can spare
+async fn give_starter_kit(bot: Client, state: State, player: String, kit: Vec<(String, u32)>) {
+    if !state.brain.trader.try_begin() {
+        return;
+    }
+    let mut given = vec![];
+    for (item, quantity) in kit {
+        match systems::trader::give_item(bot.clone(), &player, &item, quantity).await {
+            systems::trader::GiveOutcome::Delivered { quantity, .. }
+            | systems::trader::GiveOutcome::Partial { quantity, .. } => given.push((item, quantity)),
+            systems::trader::GiveOutcome::PlayerNotFound | systems::trader::GiveOutcome::CouldNotReach => break,
+            _ => {}
+        }
+    }
+    state.brain.trader.finish();
+    if given.is_empty() {
+        return;
+    }
+    {
+        let mut memory = state.brain.memory.lock().unwrap();
+        for (item, quantity) in &given {
+            memory.economy.record_present(&player, item, *quantity);
+        }
+        let profile = memory.social.get_or_create(&player);
+        profile.gifts_given.extend(given.iter().map(|(item, _)| item.clone()));
+        profile.trust_level = (profile.trust_level + 5).min(100);
+        profile.update_relationship();
+        let list = given.iter().map(|(i, q)| format!("{} x{}", i, q)).collect::<Vec<_>>().join(", ");
+        memory.episodes.add(cognitive::memory::Episode {
+            timestamp: chrono::Utc::now(),
+            event_type: cognitive::memory::EpisodeType::GaveGift,
+            description: format!("Dei kit inicial pro novato {}: {}", player, list),
+            location: None,
+            players_involved: vec![player.clone()],
+            emotional_impact: 2,
+        });
+        memory.save();
+    }
+    state.brain.personality.lock().unwrap()
+        .on_event(&cognitive::personality::PersonalityEvent::HelpedSomeone);
+    crate::events::say(&bot, format!("{} pega ai pra começar, qualquer coisa chama", player));
+}
+
+/// We got moved (tpa, /spawn, pearl) — old paths and walk targets are meaningless now
+fn on_teleport(bot: &Client, state: &State, tp: systems::teleport::Teleport) {
+    let to = [tp.to[0].floor() as i32, tp.to[1].floor() as i32, tp.to[2].floor() as i32];
+    info!("[BOT] 🌀 Teleportado de [{:.0},{:.0},{:.0}] pra {:?}{}", tp.from[0], tp.from[1], tp.from[2], to,
+        tp.via.as_ref().map(|p| format!(" (tpa do {})", p)).unwrap_or_default());
+
+    bot.stop_pathfinding();
+    state.motor.stop_walking();
+
+    let Some(player) = tp.via else { return };
+    let mut memory = state.brain.memory.lock().unwrap();
+    memory.spatial.remember_location(cognitive::memory::Location {
+        name: format!("Perto do {}", player),
+        coords: to,
+        location_type: cognitive::memory::LocationType::Custom("Teleporte".into()),
+        notes: format!("{} me puxou de tpa", player),
+        discovered_at: chrono::Utc::now(),
+    });
+    memory.episodes.add(cognitive::memory::Episode {
+        timestamp: chrono::Utc::now(),
+        event_type: cognitive::memory::EpisodeType::Custom("Teleporte".into()),
+        description: format!("{} me puxou de tpa", player),
+        location: Some(to),
+        players_involved: vec![player],
+        emotional_impact: 0,
+    });
+}
+
+/// Whisper a debt reminder to this player if one is due
+fn remind_debtor(state: &State, player: &str) {
+    let notice = {
+        let mut memory = state.brain.memory.lock().unwrap();
+        memory.economy.collection_notice(player)
+    };
+    if let Some(message) = notice {
+        state.motor.queue(systems::motor::MotorCommand::Whisper {
+            player: player.to_string(),
+            message,
+        });
+    }
+}
+
+/// Check for unpaid trades and make an example of whoever skipped out
+fn expose_scams(bot: &Client, state: &State, leaving: Option<&str>) {
+    let online: Vec<String> = bot.tab_list().values().map(|p| p.profile.name.clone()).collect();
+    let reports = {
+        let mut memory = state.brain.memory.lock().unwrap();
+        memory.economy.check_scams(|p| Some(p) != leaving && online.iter().any(|o| o == p))
+    };
+
+    for report in reports {
+        let item = report.item.replace('_', " ");
+        {
+            let mut memory = state.brain.memory.lock().unwrap();
+            let profile = memory.social.get_or_create(&report.player);
+            profile.trust_level = (profile.trust_level - 60).clamp(-100, 100);
+            profile.update_relationship();
+            profile.hold_grudge(&format!("me deu golpe, nunca pagou {} x{}", item, report.quantity), 5);
+            memory.episodes.add(cognitive::memory::Episode {
+                timestamp: chrono::Utc::now(),
+                event_type: cognitive::memory::EpisodeType::Custom("Golpe".into()),
+                description: format!("{} levou minhas coisas e não pagou {} x{}", report.player, item, report.quantity),
+                location: None,
+                players_involved: vec![report.player.clone()],
+                emotional_impact: -4,
+            });
+            memory.save();
+        }
+        state.brain.personality.lock().unwrap()
+            .on_event(&cognitive::personality::PersonalityEvent::GotGriefed);
+
+        let complaint = if report.left_server {
+            format!("galera cuidado c o {}, pegou minhas coisas e deslogou sem pagar {} {}. golpista", report.player, report.quantity, item)
+        } else {
+            format!("{} me deu calote. combinou {} {} e sumiu. n negociem c esse cara", report.player, report.quantity, item)
+        };
+        state.motor.queue(systems::motor::MotorCommand::Chat(complaint));
+    }
+}
+
+/// Somebody wrecked the base — remember it, blame whoever was there, say something
+fn on_grief(bot: &Client, state: &State, report: systems::grief_watch::GriefReport) {
+    // Owners rebuild their own base whenever they like
+    if let Some(owner) = report.culprit.as_ref().filter(|c| crate::config::Config::load().is_owner(c)) {
+        info!("[GRIEF] 🏗️ {} mexendo na base, é o dono", owner);
+        return;
+    }
+    info!("[GRIEF] 🚨 {}", report.describe());
+    crate::events::alert("grief", format!("🚨 {}", report.describe()));
+    state.brain.personality.lock().unwrap()
+        .on_event(&cognitive::personality::PersonalityEvent::GotGriefed);
+
+    // Friends and the trusted get asked, not punished
+    let was_friend = {
+        let mut memory = state.brain.memory.lock().unwrap();
+        let was_friend = report.culprit.as_ref().is_some_and(|c| {
+            memory.social.players.get(c).is_some_and(|p| {
+                p.trust_level >= 50 || matches!(p.relationship, cognitive::memory::Relationship::Friend | cognitive::memory::Relationship::BestFriend)
+            })
+        });
+        if let Some(culprit) = report.culprit.as_ref().filter(|_| !was_friend) {
+            let profile = memory.social.get_or_create(culprit);
+            profile.trust_level = (profile.trust_level - 40).clamp(-100, 100);
+            profile.update_relationship();
+            let what = if report.tnt.is_empty() { "quebrou minha base" } else { "botou TNT na minha base" };
+            profile.hold_grudge(&format!("{} ({} blocos)", what, report.broken.len()), 4);
+        }
+        let home = memory.spatial.home_coords;
+        memory.episodes.add(cognitive::memory::Episode {
+            timestamp: chrono::Utc::now(),
+            event_type: cognitive::memory::EpisodeType::Custom("Grief".into()),
+            description: report.describe(),
+            location: home,
+            players_involved: report.suspects.clone(),
+            emotional_impact: -4,
+        });
+        memory.save();
+        was_friend
+    };
+
+    let line = match (&report.culprit, report.tnt.is_empty()) {
+        (Some(c), false) => format!("{} TNT NA MINHA BASE?? vi vc la, tira isso agr", c),
+        (None, false) => "quem botou TNT na minha base??".to_string(),
+        (Some(c), true) if was_friend => format!("{} vc mexeu na minha base? sumiu {} bloco", c, report.broken.len()),
+        (Some(c), true) => format!("{} pq vc quebrou minha base?? vi vc la", c),
+        (None, true) => format!("alguem griefou minha base, sumiu {} bloco. quem foi??", report.broken.len()),
+    };
+    if let Some(culprit) = &report.culprit
+        && let Some(e) = state.brain.world.lock().unwrap().nearby_entities.iter().find(|e| &e.name == culprit)
+    {
+        bot.look_at(azalea::Vec3::new(e.position[0], e.position[1] + 1.62, e.position[2]));
+    }
+    state.motor.queue(systems::motor::MotorCommand::Chat(line));
+}
+
+/// Chests/barrels around the base go in the storage index; ones that vanished come out
+fn index_containers(storage: &mut systems::storage::StorageIndex, scan: &systems::world_scanner::AreaScan, home: [i32; 3]) {
+    let near_home = |p: &[i32; 3]| (0..3).all(|i| (p[i] - home[i]).abs() <= 48);
+    let inside = |p: &[i32; 3]| (0..3).all(|i| p[i] >= scan.box_min[i] && p[i] <= scan.box_max[i]);
+    for (pos, kind) in scan.containers.iter().filter(|(p, k)| near_home(p) && k != "ender_chest") {
+        if storage.discover(*pos, kind) {
+            info!("[STORAGE] 📦 {} novo em {:?}", kind, pos);
+        }
+    }
+    let gone: Vec<[i32; 3]> = storage.containers.iter()
+        .map(|c| c.pos)
+        .filter(|p| inside(p) && !scan.containers.iter().any(|(c, _)| c == p))
+        .collect();
+    for pos in gone {
+        info!("[STORAGE] 🗑️ Baú sumiu de {:?}", pos);
+        storage.forget(pos);
+    }
+}
+
+/// Look inside a container next to us and update the index
+async fn survey_container(bot: Client, state: State, pos: [i32; 3], kind: String) {
+    let Some(contents) = systems::storage::survey(&bot, pos).await else {
+        warn!("[STORAGE] ❌ Não consegui abrir {} em {:?}", kind, pos);
+        return;
+    };
+    let mut memory = state.brain.memory.lock().unwrap();
+    info!("[STORAGE] 🔍 {} em {:?}: {} tipos de item, {} shulkers", kind, pos, contents.items.len(), contents.shulkers.len());
+    memory.inventory.storage.record(pos, &kind, contents);
+    memory.inventory.stored = memory.inventory.storage.totals();
+}
+
+/// Inventory's full but we carry an empty shulker — box up the overflow right here
+async fn pack_overflow(bot: Client, state: State) {
+    systems::shulker::pack(&bot).await;
+    state.brain.trader.finish();
+}
+
+/// Inventory's full — empty the backpack into the base chests
+async fn stash_backpack(bot: Client, state: State) {
+    info!("[STORAGE] 🎒 Mochila cheia, indo guardar as coisas");
+    let stored = systems::storage::stash(&bot, &state.brain.memory).await;
+    state.brain.trader.finish();
+    if stored == 0 {
+        warn!("[STORAGE] ❌ Não consegui guardar nada");
+    }
+}
+
+/// Inventory's choking on junk — get rid of it and maybe complain
+async fn dump_junk(bot: Client, state: State, junk: Vec<String>) {
+    info!("[JUNK] 🧹 Inventário quase cheio, jogando fora: {}", junk.join(", "));
+    let outcome = plugins::inventory::dispose(bot.clone(), junk).await;
+    state.brain.trader.finish();
+    if outcome != plugins::inventory::Disposal::Nothing
+        && let Some(line) = plugins::inventory::grumble()
+    {
+        state.motor.queue(systems::motor::MotorCommand::Chat(line));
+    }
+}
+
+/// Valuables at risk — into the ender chest with them
+async fn secure_valuables(bot: Client, state: State) {
+    info!("[ENDER] 💎 Carregando coisa valiosa em lugar perigoso, guardando no ender");
+    systems::ender_chest::protect(&bot, &state.brain.memory).await;
+    state.brain.trader.finish();
+}
+
+/// A tool broke (or is about to) — make another, then back to whatever we were doing
+async fn replace_tool(bot: Client, state: State, kind: &'static str) {
+    let name = systems::tool_crafter::tool_name(kind);
+    let goal = format!("Fazer {}", name);
+    state.brain.goals.lock().unwrap().emergency(&goal, "Ferramenta quebrou, craftar outra antes de continuar");
+    let result = systems::tool_crafter::replace(&bot, &state.brain.memory, kind).await;
+    state.brain.trader.finish();
+
+    let task = cognitive::memory::InventoryKnowledge::craft_task(kind);
+    let mut memory = state.brain.memory.lock().unwrap();
+    let mut goals = state.brain.goals.lock().unwrap();
+    let ours = goals.current_goal().is_some_and(|g| g.name == goal);
+    match result {
+        Ok(tool) => {
+            memory.inventory.record_craft(&tool);
+            memory.inventory.clear_failures(&task);
+            if ours {
+                goals.complete_current();
+            }
+        }
+        Err(why) => {
+            let failures = memory.inventory.record_failure(&task);
+            warn!("[TOOLS] ❌ Não deu pra fazer {} ({}), {}x", name, why, failures);
+            if let systems::tool_crafter::CraftError::Missing(item) = why
+                && !memory.inventory.resource_priorities.contains(&item)
+            {
+                memory.inventory.resource_priorities.push(item);
+            }
+            if ours {
+                goals.fail_current();
+            }
+            state.tool_wear.lock().unwrap().failed(kind);
+            // Keeps failing — make it a goal so shops and friends can cover it
+            let wanted = format!("stone_{}", kind);
+            if memory.inventory.should_ask_for_help(&task) && !goals.wanted_items().contains(&wanted) {
+                goals.add_goal(cognitive::goal_planner::Goal::new(
+                    &format!("Conseguir {}", name),
+                    "Não consegui craftar, comprar ou pedir",
+                    cognitive::goal_planner::GoalPriority::High,
+                ).wanting(&wanted));
+            }
+        }
+    }
+    memory.save();
+}
+
+/// The goal changed kind — hand back the old kit, put on the new one
+async fn switch_loadout(bot: Client, state: State, kit: Option<systems::loadout::Kit>) {
+    let old = {
+        let mut loadout = state.loadout.lock().unwrap();
+        loadout.active.take().map(|_| (
+            loadout.previous_hotbar.take(),
+            loadout.previous_offhand.take(),
+            std::mem::take(&mut loadout.borrowed),
+        ))
+    };
+    if let Some((hotbar, offhand, borrowed)) = old {
+        if let Some(hotbar) = hotbar {
+            *state.inventory_mgr.hotbar_pref.lock().unwrap() = hotbar;
+        }
+        systems::loadout::put_back(&bot, &state.brain.memory, offhand, borrowed).await;
+    }
+    if let Some(kit) = kit {
+        let previous_offhand = systems::loadout::offhand_item(&systems::inventory::Inventory::read(&bot));
+        let previous_hotbar = std::mem::replace(&mut *state.inventory_mgr.hotbar_pref.lock().unwrap(), kit.loadout().hotbar);
+        let borrowed = systems::loadout::equip(&bot, &state.brain.memory, kit).await;
+        let mut loadout = state.loadout.lock().unwrap();
+        loadout.active = Some(kit);
+        loadout.previous_hotbar = Some(previous_hotbar);
+        loadout.previous_offhand = previous_offhand;
+        loadout.borrowed = borrowed;
+    }
+    state.brain.trader.finish();
+}
+
+/// Blocks placed per trip before the tick loop gets a say again (combat, stash...)
+const BUILD_BATCH: usize = 16;
+
+/// A blueprint in progress: pull what the chests have, ask for the rest, put down the next blocks
+async fn work_on_build(bot: Client, state: State) {
+    use systems::builder::{BuildState, Placement};
+    let plan = {
+        let memory = state.brain.memory.lock().unwrap();
+        let builder = state.builder.lock().unwrap();
+        builder.material_plan(&systems::inventory::Inventory::read(&bot).counts(), &memory.inventory.storage)
+    };
+    for (item, quantity) in &plan.withdraw {
+        systems::storage::fetch(&bot, &state.brain.memory, item, *quantity).await;
+    }
+    for (item, quantity) in &plan.gather {
+        want_for_build(&state, item, *quantity);
+    }
+
+    let mut placed = 0;
+    for _ in 0..BUILD_BATCH {
+        let next = {
+            let mut builder = state.builder.lock().unwrap();
+            if !builder.is_active() {
+                break; // Stopped while we were out
+            }
+            builder.state = BuildState::Placing;
+            builder.next_placement().map(|(pos, block)| (pos, block.to_string()))
+        };
+        let Some((pos, block)) = next else { break };
+        let outcome = systems::builder::place(&bot, pos, &block).await;
+        let mut builder = state.builder.lock().unwrap();
+        match outcome {
+            Placement::Placed | Placement::AlreadyThere => {
+                placed += 1;
+                builder.record_placement();
+            }
+            Placement::Missing(item) => {
+                builder.state = BuildState::GatheringMaterials;
+                drop(builder);
+                want_for_build(&state, &item, 1);
+                break;
+            }
+            Placement::Failed(why) => {
+                builder.attempts += 1;
+                warn!("[BUILDER] ❌ {} em {:?}: {} ({}/{})", block, pos, why, builder.attempts, systems::builder::MAX_ATTEMPTS);
+                if builder.attempts < systems::builder::MAX_ATTEMPTS {
+                    break;
+                }
+                warn!("[BUILDER] ⏭️ Desisti do {} em {:?}, seguindo a obra", block, pos);
+                builder.record_placement();
+            }
+        }
+    }
+    state.brain.trader.finish();
+
+    let (done, name, progress) = {
+        let builder = state.builder.lock().unwrap();
+        let name = builder.current_blueprint.as_ref().map(|b| b.name.clone()).unwrap_or_default();
+        (builder.state == BuildState::Finished, name, (builder.blocks_placed, builder.total_blocks))
+    };
+    if placed > 0 {
+        info!("[BUILDER] 🧱 {} blocos nessa leva, {}/{} no total", placed, progress.0, progress.1);
+    }
+    if done {
+        state.brain.goals.lock().unwrap().complete_named(&format!("Construir {}", name));
+        state.motor.queue(systems::motor::MotorCommand::Chat(format!("terminei a {} mn, ficou daora", name.to_lowercase())));
+    }
+}
+
+/// Steps dug per trip before the tick loop gets a say again
+const MINE_BATCH: usize = 12;
+
+/// The current goal is to mine: get down to the right Y, dig the next stretch, take the ores it opens up
+async fn mine_stretch(bot: Client, state: State, target: systems::smart_mining::MiningTarget) {
+    use azalea::pathfinder::goals::{BlockPosGoal, RadiusGoal, YGoal};
+    use systems::smart_mining::{self, Dig};
+    use std::time::Duration;
+    let here = || {
+        let pos = bot.position();
+        [pos.x.floor() as i32, pos.y.floor() as i32, pos.z.floor() as i32]
+    };
+    let fresh = {
+        let miner = state.miner.lock().unwrap();
+        miner.current_strategy.is_none() || miner.current_target != target
+    };
+    if fresh && !state.miner.lock().unwrap().start_mining(target.clone(), here(), &systems::inventory::Inventory::read(&bot)) {
+        state.brain.trader.finish();
+        state.brain.goals.lock().unwrap().fail_current(); // Pickaxe too weak; the tools block may fix that
+        return;
+    }
+    let (tunnels, y) = {
+        let miner = state.miner.lock().unwrap();
+        (miner.tunnels(), miner.current_target.optimal_y())
+    };
+    if tunnels && (here()[1] - y).abs() > 1 {
+        info!("[MINER] ⬇️ Descendo pro Y {}", y);
+        if tokio::time::timeout(Duration::from_secs(120), bot.goto(YGoal { y })).await.is_err() {
+            bot.stop_pathfinding();
+            warn!("[MINER] ❌ Não consegui descer pro Y {}", y);
+            state.brain.trader.finish();
+            return;
+        }
+        state.miner.lock().unwrap().mining_origin = Some(here()); // Corridor starts where we landed
+    }
+
+    let mut found: std::collections::BTreeMap<String, u32> = Default::default();
+    let mut took = |block: String, pos: azalea::BlockPos, miner: &mut systems::smart_mining::SmartMiner| {
+        miner.record(&block);
+        if smart_mining::is_ore(&block) {
+            crate::bus::publish(crate::bus::OreFound { block: block.clone(), pos: [pos.x, pos.y, pos.z] });
+            *found.entry(block).or_insert(0) += 1;
+        }
+    };
+    for _ in 0..MINE_BATCH {
+        if systems::inventory::Inventory::read(&bot).free_slots() == 0 {
+            break; // The stash block takes it from here
+        }
+        let step = if tunnels { state.miner.lock().unwrap().next_block_to_mine() } else { smart_mining::nearest_wanted(&bot, &target) };
+        let Some(pos) = step else {
+            info!("[MINER] 👀 Nada de {:?} à vista", target);
+            break;
+        };
+        if tunnels {
+            // 1x2 corridor: head height, then feet, then step in
+            let mut blocked = false;
+            for cell in [pos.up(1), pos] {
+                match smart_mining::dig(&bot, cell).await {
+                    Dig::Broke(block) => took(block, cell, &mut state.miner.lock().unwrap()),
+                    Dig::Empty => {}
+                    Dig::Lava | Dig::Unbreakable | Dig::Failed => blocked = true,
+                }
+                if blocked {
+                    break;
+                }
+            }
+            if blocked {
+                state.miner.lock().unwrap().turn(here());
+                break;
+            }
+            let _ = tokio::time::timeout(Duration::from_secs(5), bot.goto(BlockPosGoal(pos))).await;
+        } else {
+            let center = azalea::Vec3::new(pos.x as f64 + 0.5, pos.y as f64 + 0.5, pos.z as f64 + 0.5);
+            if tokio::time::timeout(Duration::from_secs(20), bot.goto(RadiusGoal::new(center, 3.0))).await.is_err() {
+                bot.stop_pathfinding();
+                break;
+            }
+            match smart_mining::dig(&bot, pos).await {
+                Dig::Broke(block) => took(block, pos, &mut state.miner.lock().unwrap()),
+                Dig::Empty => {}
+                Dig::Lava | Dig::Unbreakable | Dig::Failed => break,
+            }
+        }
+        // Whatever ore the new opening shows
+        for face in [pos, pos.up(1)].into_iter().flat_map(smart_mining::faces) {
+            if smart_mining::block_at(&bot, face).is_some_and(|b| smart_mining::is_ore(&b))
+                && let Dig::Broke(block) = smart_mining::dig(&bot, face).await
+            {
+                took(block, face, &mut state.miner.lock().unwrap());
+            }
+        }
+    }
+    state.brain.trader.finish();
+
+    let (summary, satisfied) = {
+        let miner = state.miner.lock().unwrap();
+        (miner.context_summary(), miner.satisfied())
+    };
+    info!("[MINER] ⛏️ {}", summary);
+    {
+        let mut memory = state.brain.memory.lock().unwrap();
+        memory.inventory.holdings = systems::inventory::Inventory::read(&bot).counts();
+        if !found.is_empty() {
+            let list: Vec<String> = found.iter().map(|(block, n)| format!("{} x{}", block.replace('_', " "), n)).collect();
+            let precious = found.keys().any(|b| b.contains("diamond") || b == "ancient_debris" || b.contains("emerald"));
+            memory.episodes.add(cognitive::memory::Episode {
+                timestamp: chrono::Utc::now(),
+                event_type: cognitive::memory::EpisodeType::FoundResource,
+                description: format!("Minerei {} no Y {}", list.join(", "), here()[1]),
+                location: Some(here()),
+                players_involved: vec![],
+                emotional_impact: if precious { 3 } else { 1 },
+            });
+        }
+        memory.save();
+    }
+    if satisfied {
+        state.brain.goals.lock().unwrap().complete_current();
+        *state.miner.lock().unwrap() = systems::smart_mining::SmartMiner::default();
+    }
+}
+
+/// "!construir casa|farm|parar|continuar", or no argument for how it's going
+pub fn build_command(session: &Session, owner: &str, arg: &str) -> String {
+    use systems::builder::{Blueprint, BuildState};
+    let state = &session.state;
+    let mut builder = state.builder.lock().unwrap();
+    match arg {
+        "" => builder.context_summary(),
+        "parar" | "stop" if builder.is_active() => {
+            builder.state = BuildState::Paused;
+            "parei a obra".into()
+        }
+        "continuar" | "resume" if builder.state == BuildState::Paused => {
+            builder.state = BuildState::GatheringMaterials;
+            "voltando pra obra".into()
+        }
+        "parar" | "stop" | "continuar" | "resume" => builder.context_summary(),
+        name => {
+            let Some(blueprint) = Blueprint::named(name) else { return "não sei construir isso — casa ou farm".into() };
+            if builder.is_active() {
+                return format!("já tô numa obra ({}), manda !construir parar antes", builder.context_summary());
+            }
+            let pos = session.bot.position();
+            let origin = [pos.x.floor() as i32 + 2, pos.y.floor() as i32, pos.z.floor() as i32 + 2];
+            let title = blueprint.name.clone();
+            builder.start_build(blueprint, origin);
+            drop(builder);
+            let mut goal = cognitive::goal_planner::Goal::new(
+                &format!("Construir {}", title),
+                &format!("Em {} {} {}", origin[0], origin[1], origin[2]),
+                cognitive::goal_planner::GoalPriority::Medium,
+            );
+            goal.assigned_by = Some(owner.to_string());
+            state.brain.goals.lock().unwrap().add_goal(goal);
+            format!("bora, {} em {} {} {}", title.to_lowercase(), origin[0], origin[1], origin[2])
+        }
+    }
+}
+
+/// "!goto x y z" — walk there, same reach the LLM gets
+pub fn goto_command(session: &Session, arg: &str) -> String {
+    let coords: Vec<i32> = arg.split_whitespace().filter_map(|n| n.parse().ok()).collect();
+    let [x, y, z] = coords[..] else { return "uso: !goto x y z".into() };
+    let pos = session.bot.position();
+    let reach = crate::plugins::brain::TOOL_REACH;
+    if (x - pos.x as i32).abs() > reach || (z - pos.z as i32).abs() > reach {
+        return format!("longe demais, no máximo {} blocos daqui", reach);
+    }
+    session.state.motor.queue_urgent(systems::motor::MotorCommand::GotoBlock { x, y, z });
+    format!("indo pra {} {} {}", x, y, z)
+}
+
+/// "!mine diamante" — a mining goal that jumps the line
+pub fn mine_command(session: &Session, owner: &str, arg: &str) -> String {
+    if arg.is_empty() {
+        return session.state.miner.lock().unwrap().context_summary();
+    }
+    let name = format!("Minerar {}", arg.trim_start_matches("minecraft:").replace('_', " "));
+    let Some(target) = systems::smart_mining::MiningTarget::from_goal(&name) else { return "uso: !mine <minério>".into() };
+    let mut goals = session.state.brain.goals.lock().unwrap();
+    if !goals.goals.iter().any(|g| g.name == name && g.is_open()) {
+        let mut goal = cognitive::goal_planner::Goal::new(&name, &format!("{} mandou", owner), cognitive::goal_planner::GoalPriority::High);
+        goal.assigned_by = Some(owner.to_string());
+        goals.add_goal(goal);
+    }
+    goals.pick_next();
+    format!("bora minerar ({:?})", target)
+}
+
+/// "!status" — where we are, how we're doing, what we're on
+pub fn status_command(session: &Session) -> String {
+    let (bot, state) = (&session.bot, &session.state);
+    let pos = bot.position();
+    let goal = state.brain.goals.lock().unwrap().current_goal().map(|g| g.name.clone()).unwrap_or_else(|| "nada".into());
+    let mood = state.brain.personality.lock().unwrap().mood.clone();
+    let build = state.builder.lock().unwrap();
+    let mut reply = format!(
+        "{:.0} {:.0} {:.0} | vida {:.0} fome {} | {:?} | fazendo: {}",
+        pos.x, pos.y, pos.z, bot.health(), bot.hunger().food, mood, goal
+    );
+    if build.is_active() {
+        reply.push_str(&format!(" | obra: {}", build.context_summary()));
+    }
+    reply
+}
+
+/// "!guardar [categoria]" — backpack (or just one category of it) into the base chests
+pub fn stash_command(session: &Session, owner: &str, arg: &str) -> String {
+    use systems::inventory_manager::ItemCategory;
+    let category = match arg {
+        "" => None,
+        word => match ItemCategory::from_word(word) {
+            Some(category) => Some(category),
+            None => return "não sei que categoria é essa — comida, blocos, minérios, ferramentas, redstone, lixo...".into(),
+        },
+    };
+    let (bot, state) = (session.bot.clone(), session.state.clone());
+    if !state.brain.trader.try_begin() {
+        return "tô ocupado com outra coisa, já já".into();
+    }
+    info!("[STORAGE] 📥 {} mandou guardar {}", owner, arg);
+    crate::bus::spawn(async move {
+        let stored = match &category {
+            Some(category) => systems::storage::stash_category(&bot, &state.brain.memory, category).await,
+            None => systems::storage::stash(&bot, &state.brain.memory).await,
+        };
+        state.brain.trader.finish();
+        info!("[STORAGE] 📥 Guardei {} itens", stored);
+    }.instrument(info_span!("storage")));
+    "indo guardar".into()
+}
+
+/// "!pegar <item> [quantidade]" — go get it from the chests
+pub fn fetch_command(session: &Session, owner: &str, arg: &str) -> String {
+    let mut words = arg.split_whitespace();
+    let Some(item) = words.next().map(systems::economy::normalize_item_name) else { return "uso: !pegar <item> [quantidade]".into() };
+    let quantity = words.next().and_then(|n| n.parse::<u32>().ok()).unwrap_or(64).clamp(1, 36 * 64);
+    let stored = session.state.brain.memory.lock().unwrap().inventory.storage.find(&item).iter().map(|(_, n)| n).sum::<u32>();
+    if stored == 0 {
+        return format!("não tem {} nos baús que eu conheço", item);
+    }
+    let (bot, state) = (session.bot.clone(), session.state.clone());
+    if !state.brain.trader.try_begin() {
+        return "tô ocupado com outra coisa, já já".into();
+    }
+    info!("[STORAGE] 📤 {} mandou pegar {} x{}", owner, item, quantity);
+    let reply = format!("indo pegar {} x{} (tem {} guardado)", item, quantity.min(stored), stored);
+    crate::bus::spawn(async move {
+        systems::storage::fetch(&bot, &state.brain.memory, &item, quantity).await;
+        state.brain.trader.finish();
+    }.instrument(info_span!("storage")));
+    reply
+}
+
+/// "!stop" — drop whatever we're doing: walking, digging, building
+pub fn stop_command(session: &Session, owner: &str) -> String {
+    let state = &session.state;
+    state.motor.stop_walking();
+    session.bot.stop_pathfinding();
+    {
+        let mut builder = state.builder.lock().unwrap();
+        if builder.is_active() {
+            builder.state = systems::builder::BuildState::Paused;
+        }
+    }
+    *state.miner.lock().unwrap() = systems::smart_mining::SmartMiner::default();
+    state.brain.goals.lock().unwrap().pause_current();
+    info!("[OWNER] ✋ {} mandou parar", owner);
+    "parei".into()
+}
+
+/// Short on something for the build → a goal to go get it (gatherers pick these up too)
+fn want_for_build(state: &State, item: &str, quantity: u32) {
+    let mut goals = state.brain.goals.lock().unwrap();
+    if goals.goals.iter().any(|g| g.is_open() && g.wants_item.as_deref() == Some(item)) {
+        return;
+    }
+    let goal = cognitive::goal_planner::Goal::new(
+        &format!("Juntar {} pra obra", item.replace('_', " ")),
+        &format!("Faltam {} pra terminar a construção", quantity),
+        cognitive::goal_planner::GoalPriority::Medium,
+    ).wanting(item);
+    goals.add_goal(goal);
+}
+
+/// We died — pockets are gone, the ender chest isn't. The pile stays where we fell for a few minutes
+fn on_death(bot: &Client, state: &State, kill: Option<&azalea::protocol::packets::game::ClientboundPlayerCombatKill>) {
+    crate::stats::died();
+    state.brain.personality.lock().unwrap().on_event(&cognitive::personality::PersonalityEvent::Died);
+    state.brain.combat.lock().unwrap().record_death();
+    let cause = kill
+        .and_then(|k| systems::server_events::parse(&k.message.to_string(), |name| systems::chat_format::is_online(bot, name)))
+        .and_then(|event| match event {
+            systems::server_events::ServerEvent::Death { cause, .. } => Some(cause),
+            _ => None,
+        });
+    let pos = bot.position();
+    let spot = [pos.x.floor() as i32, pos.y.floor() as i32, pos.z.floor() as i32];
+    let camped = systems::death_recovery::killer_nearby(cause.as_ref(), spot, &state.brain.world.lock().unwrap().nearby_entities);
+    if camped {
+        info!("[DEATH] ⚔️ Quem me matou ainda tá perto do corpo, não vou voltar lá");
+    }
+    let mut memory = state.brain.memory.lock().unwrap();
+    let lost: Vec<String> = memory.inventory.holdings.keys().cloned().collect();
+    memory.inventory.items_lost_on_death = lost.clone();
+    let holdings = std::mem::take(&mut memory.inventory.holdings);
+    let dimension = systems::death_recovery::dimension(bot);
+    systems::death_recovery::record(&mut memory, spot, dimension, holdings, cause.as_ref(), camped);
+    let safe = memory.inventory.ender.manifest.values().sum::<u32>();
+    info!("[DEATH] 💀 Morri. Perdi {} tipos de item, {} seguros no ender chest", lost.len(), safe);
+    if !lost.is_empty() {
+        crate::events::alert("death", format!("💀 Morri e perdi {} ({} seguros no ender chest)", lost.join(", "), safe));
+    }
+    memory.episodes.add(cognitive::memory::Episode {
+        timestamp: chrono::Utc::now(),
+        event_type: cognitive::memory::EpisodeType::Death,
+        description: if safe > 0 {
+            format!("Morri e perdi o inventário, mas {} itens tavam no ender chest", safe)
+        } else {
+            "Morri e perdi tudo que tava carregando".into()
+        },
+        location: Some(spot),
+        players_involved: vec![],
+        emotional_impact: -5,
+    });
+    memory.save();
+}
+
+/// Respawned (azalea's AutoRespawnPlugin does the clicking) — run back to the pile and walk over it
+async fn run_back(bot: Client, state: State, coords: [i32; 3]) {
+    let budget = systems::death_recovery::time_left(&state.brain.memory.lock().unwrap());
+    let before = systems::inventory::Inventory::read(&bot).counts();
+    let walked = systems::death_recovery::recover(&bot, coords, budget).await;
+    state.brain.trader.finish();
+
+    let after = systems::inventory::Inventory::read(&bot).counts();
+    let mut memory = state.brain.memory.lock().unwrap();
+    let gained = |item: &String| after.get(item).copied().unwrap_or(0).saturating_sub(before.get(item).copied().unwrap_or(0));
+    let lost: u32 = memory.inventory.death_drops.values().sum();
+    let back: u32 = memory.inventory.death_drops.iter().map(|(item, n)| gained(item).min(*n)).sum();
+    memory.inventory.items_lost_on_death.retain(|item| gained(item) == 0);
+    memory.inventory.holdings = after.clone();
+    systems::death_recovery::close(&mut memory);
+    info!("[DEATH] 🎒 Recuperei {}/{} itens ({} pilhas no chão)", back, lost, walked);
+    if back > 0 {
+        memory.episodes.add(cognitive::memory::Episode {
+            timestamp: chrono::Utc::now(),
+            event_type: cognitive::memory::EpisodeType::FoundResource,
+            description: format!("Voltei onde morri e recuperei {} de {} itens", back, lost),
+            location: Some(coords),
+            players_involved: vec![],
+            emotional_impact: 2,
+        });
+    }
+    memory.save();
+}
+
+/// A sign came into view — remember what it says, and if it's a chest shop, put it on the map
+fn on_sign(state: &State, pos: [i32; 3], nbt: &simdnbt::owned::NbtCompound) {
+    let lines = systems::chest_shop::sign_lines(nbt);
+    let shop = systems::chest_shop::parse_sign(&lines, pos);
+
+    let mut memory = state.brain.memory.lock().unwrap();
+    if let Some(kind) = systems::signs::classify(&lines, shop.is_some())
+        && memory.spatial.signs.record(pos, kind, &lines)
+        && kind != systems::signs::SignKind::Shop
+    {
+        info!("[SIGN] 🪧 {:?} em {:?}: {}", kind, pos, lines.join(" / "));
+    }
+    let Some(shop) = shop else { return };
+
+    let name = format!("Loja do {} ({})", shop.owner, shop.item);
+    let notes = format!(
+        "{} x{} — compra: {} / venda: {}",
+        shop.item,
+        shop.quantity,
+        shop.buy_price.map(|p| format!("${}", p)).unwrap_or_else(|| "-".into()),
+        shop.sell_price.map(|p| format!("${}", p)).unwrap_or_else(|| "-".into()),
+    );
+    if memory.economy.record_shop(shop) {
+        info!("[SHOP] 🏪 Loja nova em [{}, {}, {}]: {}", pos[0], pos[1], pos[2], notes);
+        memory.spatial.remember_location(cognitive::memory::Location {
+            name,
+            coords: pos,
+            location_type: cognitive::memory::LocationType::Shop,
+            notes,
+            discovered_at: chrono::Utc::now(),
+        });
+    }
+}
+
+/// Who listens to what on the bus. Called once at startup
+pub fn subscribe() {
+    use crate::bus::{self, ChatIn, Damage, GoalChanged, OreFound, PlayerApproached, ThreatDetected, Tick};
+    use crate::events::{emit, BotEvent};
+
+    bus::subscribe("watchdog", |_, _: &Tick| crate::watchdog::tick());
+    bus::subscribe("health", |ctx, _: &Tick| watch_health(ctx.bot, ctx.state));
+    bus::subscribe("reaction_delay", |ctx, _: &Tick| systems::reaction_delay::on_tick(ctx.bot, &ctx.state.reaction));
+    bus::subscribe("thrown_items", |ctx, _: &Tick| systems::trader::tick_thrown_items(ctx.bot, &ctx.state.brain.trader));
+    bus::subscribe("inventory_comments", |ctx, _: &Tick| {
+        if crate::config::Config::load().observing() {
+            return;
+        }
+        if let Some(comment) = ctx.state.inventory_mgr.pending_comment.lock().unwrap().take() {
+            ctx.state.motor.queue(systems::motor::MotorCommand::Chat(comment));
+        }
+    });
+
+    bus::subscribe("events", |_, chat: &ChatIn| emit(BotEvent::ChatIn { message: chat.message.clone(), sender: chat.sender.clone() }));
+    bus::subscribe("chatlog", |ctx, chat: &ChatIn| {
+        // Our own lines are logged as we send them
+        if chat.sender.as_deref() != Some(ctx.bot.username().as_str()) {
+            crate::chatlog::heard(&chat.message, chat.sender.clone());
+        }
+    });
+    bus::subscribe("natural_look", |ctx, chat: &ChatIn| {
+        if let Some(sender) = &chat.sender {
+            systems::natural_look::on_player_chat(&mut ctx.state.natural_look.inner.lock().unwrap(), sender);
+        }
+    });
+    bus::subscribe("walker", |ctx, chat: &ChatIn| if !crate::config::Config::load().observing() { walk_to_speaker(ctx.bot, ctx.state, chat) });
+    bus::subscribe("observer", crate::observer::on_chat);
+
+    bus::subscribe("reaction_delay", |ctx, hit: &Damage| {
+        systems::reaction_delay::on_damage(&mut ctx.state.reaction.inner.lock().unwrap(), hit.amount, None);
+    });
+    bus::subscribe("personality", |ctx, hit: &Damage| flinch(ctx.state, hit.health));
+
+    bus::subscribe("events", |_, seen: &ThreatDetected| emit(BotEvent::Threat {
+        level: format!("{:?}", seen.threat.level),
+        description: seen.threat.description.clone(),
+        action: format!("{:?}", seen.threat.recommended_action),
+    }));
+    bus::subscribe("motor", |ctx, seen: &ThreatDetected| on_threat(ctx.bot, ctx.state, seen));
+    bus::subscribe("team", crate::team::on_threat);
+
+    bus::subscribe("natural_look", |ctx, near: &PlayerApproached| {
+        let seen = visible_players(&ctx.state.brain.world.lock().unwrap().nearby_entities);
+        if let Some(p) = seen.into_iter().find(|p| p.name == near.player) {
+            ctx.bot.look_at(azalea::Vec3::new(p.pos[0], p.pos[1] + 1.62, p.pos[2]));
+        }
+    });
+    bus::subscribe("social", |ctx, near: &PlayerApproached| {
+        let line = match &near.reaction {
+            systems::social::ProximityReaction::Greet(line) => {
+                info!("[SOCIAL] 👋 {} chegou perto", near.player);
+                line
+            }
+            systems::social::ProximityReaction::EnemyAtBase(line) => {
+                warn!("[SOCIAL] ⚠️ Inimigo {} perto da base!", near.player);
+                line
+            }
+        };
+        ctx.state.motor.queue(systems::motor::MotorCommand::Chat(line.clone()));
+    });
+    bus::subscribe("personality", |ctx, near: &PlayerApproached| {
+        if matches!(near.reaction, systems::social::ProximityReaction::EnemyAtBase(_)) {
+            ctx.state.brain.personality.lock().unwrap().on_event(&cognitive::personality::PersonalityEvent::EnemyNearBase);
+        }
+    });
+
+    bus::subscribe("personality", |ctx, ore: &OreFound| {
+        if ore.block.contains("diamond") || ore.block == "ancient_debris" {
+            info!("[MINER] 💎 {} em {:?}!", ore.block.replace('_', " "), ore.pos);
+            ctx.state.brain.personality.lock().unwrap().on_event(&cognitive::personality::PersonalityEvent::FoundDiamonds);
+        }
+    });
+
+    bus::subscribe("events", |_, change: &GoalChanged| emit(BotEvent::Goal { goal: change.goal.clone(), status: format!("{:?}", change.status) }));
+
+    // Only the main bot is on tape — a swarm extra's ticks would throw the waits off
+    bus::subscribe("replay", |ctx, _: &Tick| if !ctx.state.brain.me.extra() { crate::replay::tick(&ctx.state.brain.world) });
+    bus::subscribe("replay", |ctx, hit: &Damage| if !ctx.state.brain.me.extra() { crate::replay::damage(hit.amount) });
+    bus::subscribe("replay", |ctx, change: &GoalChanged| if !ctx.state.brain.me.extra() { crate::replay::goal(&change.goal, format!("{:?}", change.status)) });
+
+    // Owner-written scripts/*.script — last, so they see everything the built-ins did
+    bus::subscribe("scripts", |ctx, _: &Tick| crate::scripting::on_tick(ctx));
+    bus::subscribe("scripts", crate::scripting::on_chat);
+    bus::subscribe("scripts", crate::scripting::on_damage);
+    bus::subscribe("scripts", crate::scripting::on_threat);
+    bus::subscribe("scripts", crate::scripting::on_goal);
+    bus::subscribe("scripts", crate::scripting::on_approach);
+    bus::subscribe("scripts", crate::scripting::on_ore);
+
+    for line in bus::describe() {
+        debug!("[BUS] 🚌 {}", line);
+    }
+}
+
+/// Bored with no goal? Dream one up and say so. `ticks` = since the last call
+pub fn dream(state: &State, ticks: u32) {
+    let has_active_goal = {
+        let planner = state.brain.goals.lock().unwrap();
+        planner.current_goal().is_some()
+    };
+
+    let should_dream = {
+        let mut dreamer = state.dreamer.lock().unwrap();
+        if has_active_goal {
+            dreamer.reset_idle();
+        } else {
+            dreamer.tick_idle(ticks as u64);
+        }
+        dreamer.is_bored() && dreamer.can_dream()
+    };
+
+    if should_dream {
+        let mood = {
+            let p = state.brain.personality.lock().unwrap();
+            p.mood.clone()
+        };
+        let dreamt = cognitive::dreamer::dream(&mood, &state.brain.memory.lock().unwrap());
+        if let Some(d) = dreamt {
+            state.dreamer.lock().unwrap().dreamt();
+            cognitive::dreamer::realize_dream(&d, &mut state.brain.goals.lock().unwrap());
+            state.motor.queue(systems::motor::MotorCommand::Chat(format!("hmm sabe oq, {}", d.motivation)));
+        }
+    }
+}
+
+/// Low on health → the mood shows it
+pub fn flinch(state: &State, health: f32) {
+    if health > 0.0 && health <= 6.0 {
+        state.brain.personality.lock().unwrap().on_event(&cognitive::personality::PersonalityEvent::LowHP);
+    }
+}
+
+/// Health went down since last tick → Damage
+fn watch_health(bot: &Client, state: &State) {
+    let health = bot.health();
+    let previous = std::mem::replace(&mut *state.last_health.lock().unwrap(), health);
+    // Right after joining the real value is still arriving
+    let settled = state.joined_at.lock().unwrap().elapsed().as_secs() >= 3;
+    if health < previous && settled {
+        crate::bus::publish(crate::bus::Damage { amount: previous - health, health });
+    }
+}
+
+/// Walker & Stalker Logic — Go to player if they talk (deadbeats can walk to us)
+fn walk_to_speaker(bot: &Client, state: &State, chat: &crate::bus::ChatIn) {
+    let Some(sender) = &chat.sender else { return };
+    if state.brain.memory.lock().unwrap().economy.is_blacklisted(sender) {
+        return;
+    }
+    // Resolve the sender's UUID through the tab list, then their entity
+    let target_pos = bot.player_uuid_by_username(sender)
+        .and_then(|uuid| bot.entity_by_uuid(uuid))
+        .and_then(|entity| bot.get_entity_component::<Position>(entity))
+        .map(|pos| *pos);
+
+    if let Some(pos) = target_pos {
+        let x = pos.x.round() as i32;
+        let y = pos.y.round() as i32;
+        let z = pos.z.round() as i32;
+        info!("[BOT] 🏃 Vi {} em [{},{},{}], indo até lá...", sender, x, y, z);
+        state.motor.queue_urgent(systems::motor::MotorCommand::GotoBlock { x, y, z });
+    }
+}
+
+/// Translate threat actions into motor commands
+fn on_threat(bot: &Client, state: &State, seen: &crate::bus::ThreatDetected) {
+    use systems::motor::MotorCommand;
+    use systems::spider_sense::PredictedAction;
+    let motor = &state.motor;
+    match &seen.threat.recommended_action {
+        PredictedAction::Sprint => {
+            motor.queue_urgent(MotorCommand::StartSprint { duration_ticks: 40 });
         }
+        PredictedAction::AttackFirst => {
+            if let Some(weapon) = systems::inventory::Inventory::read(bot).best_weapon() {
+                systems::inventory::equip(bot, weapon.slot);
+            }
+            let target = seen.player.as_deref().unwrap_or("?");
+            motor.queue_urgent(MotorCommand::Log(format!("ATTACK: {}", target)));
+        }
+        PredictedAction::WarnChat(msg) => {
+            motor.queue(MotorCommand::Chat(msg.clone()));
+        }
+        PredictedAction::PlaceTorch => {
+            motor.queue_urgent(MotorCommand::Log("PLACE TORCH (anti-gravel)".into()));
+        }
+        PredictedAction::EatNow => {
+            motor.queue_urgent(MotorCommand::Log(format!("EAT NOW: {}", seen.threat.description)));
+        }
+        _ => {}
     }
 }
 
 pub async fn handle(bot: Client, event: Event, state: State) -> anyhow::Result<()> {
+    let _tick = matches!(event, Event::Tick).then(crate::profiler::tick);
+    // In a swarm, what this bot publishes is delivered to this bot
+    crate::bus::as_bot(bot.entity, async move {
+        let result = on_event(bot.clone(), event, state.clone()).await;
+        // Whatever this event (or anything before it) put on the bus gets delivered now
+        crate::bus::dispatch(&bot, &state);
+        result
+    }).await
+}
+
+async fn on_event(bot: Client, event: Event, state: State) -> anyhow::Result<()> {
     match &event {
         Event::Login => {
-            println!("[BOT] ✅ Joined the server!");
+            info!("[BOT] ✅ Joined the server as {}!", bot.username());
+            *state.joined_at.lock().unwrap() = Instant::now();
+            state.brain.auth.lock().unwrap().reset();
+            *state.brain.queue.lock().unwrap() = systems::queue::QueueWatch::default();
+            crate::session::attach(bot.clone(), state.clone()); // API, shutdown etc. can reach us now
+            state.motor.start(&bot);
+            // The session and the replay are the main bot's; swarm extras don't restart them
+            if !state.brain.me.extra() {
+                crate::stats::begin(&state);
+                crate::replay::start(&state);
+                crate::profiler::reset();
+            }
             let mut memory = state.brain.memory.lock().unwrap();
             memory.episodes.add(cognitive::memory::Episode {
                 timestamp: chrono::Utc::now(),
@@ -57,258 +1307,965 @@ pub async fn handle(bot: Client, event: Event, state: State) -> anyhow::Result<(
         }
         Event::Chat(chat) => {
             let msg = chat.message().to_string();
-            println!("[CHAT] {}", msg);
+            info!("[CHAT] {}", msg);
 
-            // Tell NaturalLook who spoke (so we look at them)
-            if let Some((sender, _)) = plugins::brain::extract_sender_pub(&msg) {
-                let mut look = state.natural_look.inner.lock().unwrap();
-                systems::natural_look::on_player_chat(&mut look, sender.clone());
-            }
-
-            // Walker & Stalker Logic — Go to player if they talk
-            if let Some((sender, _)) = plugins::brain::extract_sender_pub(&msg) {
-                let target_pos = {
-                    let tab_list = bot.tab_list();
-                    // Find UUID by name in tab list
-                    tab_list.iter().find(|(_, info)| info.profile.name == sender)
-                        .map(|(_, info)| info.profile.uuid)
-                        .and_then(|uuid| {
-                            // Find entity by UUID in world manually (Instance doesn't have entity_by_uuid)
-                            let world = bot.world().read(); // RwLockReadGuard<Instance>
-                            // Iterate entities to find the one with matching UUID
-                            // In Azalea 0.15, entities() returns something iterable with (EntityId, &Entity)
-                            // We assume Entity has 'uuid' field and 'pos()' method or field.
-                            // Using a safe manual iteration.
-                            for (_id, entity) in world.entities().iter() {
-                                if entity.uuid == uuid {
-                                    return Some(entity.pos());
-                                }
-                            }
-                            None
-                        })
+            // Look, walk over, log it — see subscribe()
+            let sender = plugins::brain::chat_sender(&bot, chat);
+            crate::bus::publish(crate::bus::ChatIn { message: msg.clone(), sender });
+            // Brain handles the rest — it's in the registry below
+        }
+        Event::AddPlayer(info) if info.profile.name != bot.username() => {
+            let name = &info.profile.name;
+            // The whole tab list arrives right after we log in, and servers re-send entries that are
+            // already on it — neither is a join. Presence still learns who's online from both
+            let just_logged_in = state.joined_at.lock().unwrap().elapsed().as_secs() < 10;
+            let new_on_tab = state.brain.social.lock().unwrap().presence.on_join(name);
+            if new_on_tab && !just_logged_in {
+                let (never_seen, people) = {
+                    let mut memory = state.brain.memory.lock().unwrap();
+                    let never_seen = !memory.social.players.contains_key(name);
+                    memory.social.record_interaction(name, 0); // times_met + last_seen
+                    (never_seen, memory.social.clone())
                 };
-
-                if let Some(pos) = target_pos {
-                    let x = pos.x.round() as i32;
-                    let y = pos.y.round() as i32;
-                    let z = pos.z.round() as i32;
-                    println!("[BOT] 🏃 Vi {} em [{},{},{}], indo até lá...", sender, x, y, z);
-                    
-                    let mut motor = state.motor.inner.lock().unwrap();
-                    motor.queue_urgent(systems::motor::MotorCommand::GotoBlock { x, y, z });
+                let greeting = if never_seen {
+                    welcome_newcomer(&bot, &state, name)
+                } else {
+                    state.brain.social.lock().unwrap().on_player_join(name, &people)
+                };
+                if let Some(greeting) = greeting {
+                    info!("[SOCIAL] 👋 {} entrou", name);
+                    state.motor.queue(systems::motor::MotorCommand::Chat(greeting));
                 }
+
+                // Debtor just came online — perfect time to remind them
+                remind_debtor(&state, name);
+            }
+        }
+        Event::RemovePlayer(info) => {
+            let name = &info.profile.name;
+            let farewell = {
+                let mut memory = state.brain.memory.lock().unwrap();
+                let mut social = state.brain.social.lock().unwrap();
+                social.on_player_leave(name, &mut memory.social)
+            };
+            if let Some(line) = farewell {
+                info!("[SOCIAL] 🚪 {} saiu", name);
+                state.motor.queue(systems::motor::MotorCommand::Chat(line));
             }
 
-            // Brain handles the rest
-            let _ = plugins::brain::handle(bot.clone(), event.clone(), state.brain.clone()).await;
+            // Logging off right after getting the goods? Classic
+            expose_scams(&bot, &state, Some(name));
         }
+        Event::Packet(packet) => match packet.as_ref() {
+            ClientboundGamePacket::AddEntity(p) => {
+                systems::trader::on_add_entity(&state.brain.trader, &state.brain.world, p);
+            }
+            ClientboundGamePacket::BlockEntityData(p) => {
+                if matches!(p.block_entity_type, BlockEntityKind::Sign | BlockEntityKind::HangingSign) {
+                    on_sign(&state, [p.pos.x, p.pos.y, p.pos.z], &p.tag);
+                }
+            }
+            ClientboundGamePacket::LevelChunkWithLight(p) => {
+                for be in p.chunk_data.block_entities.iter()
+                    .filter(|be| matches!(be.kind, BlockEntityKind::Sign | BlockEntityKind::HangingSign))
+                {
+                    let x = p.x * 16 + (be.packed_xz >> 4) as i32;
+                    let z = p.z * 16 + (be.packed_xz & 15) as i32;
+                    on_sign(&state, [x, be.y as i16 as i32, z], &be.data);
+                }
+            }
+            ClientboundGamePacket::BlockDestruction(p) => {
+                // Crack animation names the digger outright
+                if let Some(entity) = bot.ecs_entity_by_minecraft_entity(p.id)
+                    && let Some(profile) = bot.get_entity_component::<GameProfileComponent>(entity)
+                {
+                    let mut models = state.brain.player_models.lock().unwrap();
+                    models.on_dig_progress(&profile.name, [p.pos.x, p.pos.y, p.pos.z]);
+                }
+            }
+            ClientboundGamePacket::BlockUpdate(p) => {
+                let nearby: Vec<(String, [f64; 3])> = visible_players(&state.brain.world.lock().unwrap().nearby_entities)
+                    .into_iter()
+                    .map(|v| (v.name, v.pos))
+                    .collect();
+                if !nearby.is_empty() {
+                    let block = BlockKind::from(p.block_state).to_string();
+                    let mut models = state.brain.player_models.lock().unwrap();
+                    models.on_block_changed(
+                        [p.pos.x, p.pos.y, p.pos.z],
+                        block.trim_start_matches("minecraft:"),
+                        p.block_state.is_air(),
+                        &nearby,
+                    );
+                }
+            }
+            ClientboundGamePacket::SetEquipment(p) => {
+                let hand = p.slots.slots.iter().find(|(slot, _)| *slot == EquipmentSlot::Mainhand);
+                if let Some((_, stack)) = hand
+                    && let Some(entity) = bot.ecs_entity_by_minecraft_entity(p.entity_id)
+                    && let Some(profile) = bot.get_entity_component::<GameProfileComponent>(entity)
+                {
+                    let item = stack.is_present().then(|| systems::inventory::item_id(stack));
+                    state.brain.player_models.lock().unwrap().on_held_item(&profile.name, item);
+                }
+            }
+            ClientboundGamePacket::SetTime(p) => {
+                // Negative day_time = daylight cycle frozen, the time is still the absolute value
+                let ticks = (p.day_time as i64).abs().rem_euclid(24000);
+                let mut world = state.brain.world.lock().unwrap();
+                let time = systems::world_scanner::TimeOfDay::from_ticks(ticks);
+                if world.time_of_day != time {
+                    info!("[WORLD] 🕐 {:?} ({} ticks)", time, ticks);
+                    world.time_of_day = time;
+                }
+            }
+            ClientboundGamePacket::GameEvent(p) => {
+                let raining = match p.event {
+                    EventType::StartRaining => Some(true),
+                    EventType::StopRaining => Some(false),
+                    _ => None,
+                };
+                if let Some(raining) = raining {
+                    let mut world = state.brain.world.lock().unwrap();
+                    if world.is_raining != raining {
+                        info!("[WORLD] {} Chuva: {}", if raining { "🌧️" } else { "☀️" }, raining);
+                        world.is_raining = raining;
+                    }
+                }
+            }
+            // New level (join, respawn, portal): the server re-sends StartRaining if it's raining there,
+            // and entity ids start over, so last tick's closing speeds mean nothing
+            ClientboundGamePacket::Login(_) | ClientboundGamePacket::Respawn(_) => {
+                let mut world = state.brain.world.lock().unwrap();
+                world.is_raining = false;
+                world.nearby_entities.clear();
+            }
+            ClientboundGamePacket::TakeItemEntity(p) => {
+                if let Some(received) = systems::trader::on_take_item(&bot, &state.brain.trader, p) {
+                    on_item_received(&bot, &state, received);
+                }
+            }
+            _ => {}
+        },
+        Event::Death(kill) => on_death(&bot, &state, kill.as_deref()),
         Event::Disconnect(reason) => {
-            println!("[DISCONNECT] Bot kicked/disconnected!");
+            info!("[DISCONNECT] Bot kicked/disconnected!");
+            crate::session::detach(&bot);
+            if !state.brain.me.extra() {
+                crate::stats::finish(&state);
+            }
             if let Some(r) = reason {
-                println!("[DISCONNECT] Reason: {}", r.to_string());
+                info!("[DISCONNECT] Reason: {}", r);
+                crate::reconnect::note_disconnect(r.to_string()); // Decides how we reconnect
             } else {
-                println!("[DISCONNECT] No reason provided.");
+                info!("[DISCONNECT] No reason provided.");
             }
             let memory = state.brain.memory.lock().unwrap();
             memory.save();
-            println!("[BOT] 💾 Memory saved on disconnect.");
+            state.brain.goals.lock().unwrap().save();
+            info!("[BOT] 💾 Memory saved on disconnect.");
+            drop(memory);
+            crate::reconnect::end_session(&bot); // Solo: main's loop decides when and how we come back
         }
         _ => {}
     }
 
+    // === EXISTING SYSTEMS — auto eat, anti-AFK, ping, brain, natural look, hotbar ===
+    state.systems.on_event(&bot, &event).await;
+
     // Tick-based systems
     if let Event::Tick = &event {
-        // === EXISTING SYSTEMS ===
-        plugins::auto_eat::handle(bot.clone(), event.clone(), ()).await?;
-        plugins::anti_afk::handle(bot.clone(), event.clone(), state.anti_afk.clone()).await?;
-        plugins::ping::handle(bot.clone(), event.clone(), state.ping.clone()).await?;
-        // Brain tick (personality decay)
-        let _ = plugins::brain::handle(bot.clone(), event.clone(), state.brain.clone()).await;
-        // Natural look behavior (head bobbing, fidgets)
-        let _ = systems::natural_look::handle(bot.clone(), event.clone(), state.natural_look.clone()).await;
-        // Inventory management (hotbar sorting)
-        let _ = systems::inventory_manager::handle(bot.clone(), event.clone(), state.inventory_mgr.clone()).await;
-        // Reaction delay (humanized damage response)
-        let _ = systems::reaction_delay::handle(bot.clone(), event.clone(), state.reaction.clone()).await;
-
-        // === [6] SPIDER SENSE — Threat prediction ===
-        {
-            let world = state.brain.world.lock().unwrap();
-            let memory = state.brain.memory.lock().unwrap();
+        crate::bus::publish(crate::bus::Tick);
+        // Switched off in config or with !feature
+        let features = crate::config::Config::load().features;
+        // Each numbered block below runs on its [schedule] cadence, timed until the next one starts
+        let tick = state.ticks.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        let mut ticker = crate::scheduler::Ticker::new(tick);
+
+        // === [5] QUEUE — In a queue or limbo? Stay put until the real world loads ===
+        ticker.always("queue");
+        if state.brain.queue.lock().unwrap().tick(&bot) {
+            return Ok(());
+        }
+
+        // === [5.5] ENTITY FEED — Who and what is around us, one query for everyone ===
+        ticker.always("entity_feed");
+        let entities = systems::world_scanner::scan_entities(&bot);
+        let players = visible_players(&entities);
+        let carrying = systems::inventory::Inventory::read(&bot);
+        let fighting = {
+            let mut combat = state.brain.combat.lock().unwrap();
+            combat.update_threats(&entities, 16.0);
+            combat.update_gear(&carrying);
+            combat.state != systems::combat::CombatState::Peaceful
+        };
+        *state.inventory_mgr.in_combat.lock().unwrap() = fighting;
+        state.brain.world.lock().unwrap().apply_entities(entities);
+
+        // === [5.6] COMBAT — Fight what the feed found: crits, strafing, creeper hit-and-run ===
+        if ticker.due("combat") && bot.health() > 0.0 {
+            use systems::combat::Moment;
+            use systems::reaction_delay::ReactionPhase;
+            // Still flinching from a hit — reaction_delay has the body until it's looked around
+            let reacting = matches!(state.reaction.inner.lock().unwrap().phase, ReactionPhase::Panicking | ReactionPhase::Assessing);
+            let (decision, threats) = {
+                let mut combat = state.brain.combat.lock().unwrap();
+                (combat.evaluate(bot.health(), bot.hunger().food), combat.current_threats.clone())
+            };
+            let moment = systems::combat::step(&bot, &mut state.fighter.lock().unwrap(), &decision, &threats, reacting);
+            match moment {
+                // Whatever we were walking to can wait
+                Some(Moment::Engaged(_) | Moment::Fled) => {
+                    bot.stop_pathfinding();
+                    state.motor.stop_walking();
+                }
+                Some(Moment::Killed(id)) => {
+                    let mut combat = state.brain.combat.lock().unwrap();
+                    combat.record_kill();
+                    info!("[COMBAT] 💀 Derrubei #{} | K/D: {}/{}", id, combat.kills, combat.deaths);
+                }
+                None => {}
+            }
+        }
+
+        // === [6] SPIDER SENSE — Threat prediction (reactions: see on_threat) ===
+        if ticker.due("spider_sense") && features.spider_sense {
+            // One lock at a time: snapshot the feed, trust and models, then predict
+            let (seen, creeper) = {
+                let world = state.brain.world.lock().unwrap();
+                let seen: Vec<(String, f64, f64)> = world.nearby_entities.iter()
+                    .filter(|e| e.is_player())
+                    .map(|e| (e.name.clone(), e.distance, e.closing_speed))
+                    .collect();
+                let creeper = world.hostiles().find(|e| e.kind == "creeper").map(|e| (e.distance, e.fusing));
+                (seen, creeper)
+            };
+            let trust: Vec<i32> = {
+                let memory = state.brain.memory.lock().unwrap();
+                seen.iter().map(|(name, _, _)| memory.social.trust(name)).collect()
+            };
+            let read: Vec<(String, Option<systems::player_model::Activity>)> = {
+                let models = state.brain.player_models.lock().unwrap();
+                seen.iter()
+                    .map(|(name, _, _)| (models.held_item(name).unwrap_or("nada").to_string(), models.infer(name)))
+                    .collect()
+            };
             let spider = state.spider_sense.lock().unwrap();
-            let mut motor = state.motor.inner.lock().unwrap();
 
             // Check each nearby player for threats
-            for player_name in &world.nearby_players {
-                let trust = memory.social.players.get(player_name)
-                    .map(|p| p.trust_level)
-                    .unwrap_or(20);
-
-                // Simplified: we don't have held_item info from Azalea yet,
-                // but the prediction engine is ready for when we do
-                if let Some(threat) = spider.predict_player_threat(
-                    player_name,
-                    "unknown",  // held_item — will be real once we read entity data
-                    15.0,       // distance — placeholder
-                    true,       // approaching — conservative
-                    trust,
-                    &memory.social,
-                ) {
-                    println!("[SPIDER] 🕷️ {:?}: {} → {:?}",
+            for (((player_name, distance, closing_speed), trust), (held, activity)) in seen.iter().zip(trust).zip(read) {
+                if let Some(threat) = spider.predict_player_threat(player_name, &held, *distance, *closing_speed, activity, trust) {
+                    info!("[SPIDER] 🕷️ {:?}: {} → {:?}",
                         threat.level, threat.description, threat.recommended_action);
+                    threat.publish(Some(player_name));
+                }
+            }
 
-                    // Translate threat actions into motor commands
-                    match &threat.recommended_action {
-                        systems::spider_sense::PredictedAction::Sprint => {
-                            motor.queue_urgent(systems::motor::MotorCommand::StartSprint {
-                                duration_ticks: 40
-                            });
-                        }
-                        systems::spider_sense::PredictedAction::AttackFirst => {
-                            motor.queue_urgent(systems::motor::MotorCommand::Log(
-                                format!("ATTACK: {}", player_name)
-                            ));
-                        }
-                        systems::spider_sense::PredictedAction::WarnChat(msg) => {
-                            motor.queue(systems::motor::MotorCommand::Chat(msg.clone()));
-                        }
-                        systems::spider_sense::PredictedAction::PlaceTorch => {
-                            motor.queue_urgent(systems::motor::MotorCommand::Log(
-                                "PLACE TORCH (anti-gravel)".into()
-                            ));
-                        }
-                        systems::spider_sense::PredictedAction::EatNow => {
-                            motor.queue_urgent(systems::motor::MotorCommand::Log(
-                                "EAT NOW (starvation risk)".into()
-                            ));
-                        }
-                        _ => {}
+            // Creepers sneaking up, or already hissing
+            if let Some((distance, fusing)) = creeper
+                && let Some(threat) = spider.predict_creeper_explosion(distance, fusing)
+            {
+                info!("[SPIDER] 🕷️ {:?}: {}", threat.level, threat.description);
+                threat.publish(None);
+            }
+
+            // Starvation check
+            if let Some(threat) = spider.predict_starvation(bot.hunger().food, bot.health(), carrying.has_food())
+                && (threat.level == systems::spider_sense::ThreatLevel::Critical
+                    || threat.level == systems::spider_sense::ThreatLevel::High)
+            {
+                threat.publish(None);
+            }
+        }
+
+        // === [6.5] STALKER WATCH — Sneaking, following, circling the base ===
+        if ticker.due("stalker_watch") {
+            let bot_pos = bot.position();
+            let bot_pos = [bot_pos.x, bot_pos.y, bot_pos.z];
+
+            let (home, trust): (_, Vec<i32>) = {
+                let memory = state.brain.memory.lock().unwrap();
+                (memory.spatial.home_coords, players.iter().map(|p| memory.social.trust(&p.name)).collect())
+            };
+
+            // Spider first, on its own; who got caught and the follow-ups come out of it
+            let (caught, stalking) = {
+                let mut spider = state.spider_sense.lock().unwrap();
+                let mut caught = vec![];
+                for (p, trust) in players.iter().zip(trust) {
+                    spider.observe_player(&p.name, p.pos, p.sneaking, bot_pos, home);
+                    if features.spider_sense && let Some(threat) = spider.predict_stalking(&p.name, trust) {
+                        caught.push((p.name.clone(), threat.clone()));
+                        spider.record_prediction(threat);
                     }
                 }
+                spider.forget_stale_stalkers();
+                let stalking: Vec<bool> = players.iter()
+                    .map(|p| spider.stalkers.get(&p.name).is_some_and(|t| t.behavior().is_some()))
+                    .collect();
+                (caught, stalking)
+            };
+
+            for _ in &caught {
+                state.brain.personality.lock().unwrap().on_event(&cognitive::personality::PersonalityEvent::BeingStalked);
+            }
+            if !caught.is_empty() {
+                let mut memory = state.brain.memory.lock().unwrap();
+                for (name, threat) in &caught {
+                    let profile = memory.social.get_or_create(name);
+                    profile.notes.push(threat.description.clone());
+                    if profile.notes.len() > 10 {
+                        profile.notes.remove(0);
+                    }
+                    profile.trust_level = (profile.trust_level - 5).clamp(-100, 100);
+                    profile.update_relationship();
+                }
+            }
+            for (_, threat) in &caught {
+                if let systems::spider_sense::PredictedAction::WarnChat(msg) = &threat.recommended_action {
+                    state.motor.queue(systems::motor::MotorCommand::Chat(msg.clone()));
+                }
             }
 
-            // Starvation check (placeholder values until we read real player data)
-            if let Some(threat) = spider.predict_starvation(20, 20.0, true) {
-                if threat.level == systems::spider_sense::ThreatLevel::Critical
-                    || threat.level == systems::spider_sense::ThreatLevel::High
-                {
-                    motor.queue_urgent(systems::motor::MotorCommand::Log(
-                        format!("STARVATION: {}", threat.description)
-                    ));
+            // Theory of mind: where they've been + whether they're on our tail
+            let mut models = state.brain.player_models.lock().unwrap();
+            for (p, stalking) in players.iter().zip(stalking) {
+                models.observe_position(&p.name, p.pos);
+                models.set_stalking(&p.name, stalking);
+            }
+            models.forget_stale();
+            drop(models);
+
+            // Anyone we can see moving isn't AFK
+            let mut social = state.brain.social.lock().unwrap();
+            for p in &players {
+                social.presence.observe_position(&p.name, p.pos);
+            }
+        }
+
+        // === [6.6] PRESENCE — Online roster, AFK inference, time played together ===
+        if ticker.due("presence") {
+            let secs = u64::from(ticker.every("presence")) / 20; // Since the last run
+            let own_name = bot.username();
+            let online: Vec<String> = bot.tab_list().values()
+                .map(|p| p.profile.name.clone())
+                .filter(|n| *n != own_name)
+                .collect();
+            let together: Vec<String> = {
+                let mut social = state.brain.social.lock().unwrap();
+                social.presence.sync(&online);
+                for name in social.presence.update_afk() {
+                    info!("[SOCIAL] 💤 {} parece AFK", name);
+                }
+                social.expire_conversations();
+                social.presence.active_players().into_iter().cloned().collect()
+            };
+            let mut memory = state.brain.memory.lock().unwrap();
+            for name in &together {
+                memory.social.get_or_create(name).add_time_together(secs);
+            }
+        }
+
+        // === [6.6.1] OBSERVER — Watching, not playing: who does what, where, with whom ===
+        if ticker.due("observer") && crate::config::Config::load().observing() {
+            let seen: Vec<(String, [f64; 3], systems::player_model::Activity)> = {
+                let models = state.brain.player_models.lock().unwrap();
+                players.iter()
+                    .map(|p| (p.name.clone(), p.pos, models.infer(&p.name).unwrap_or(systems::player_model::Activity::Idle)))
+                    .collect()
+            };
+            let summaries = {
+                let mut observer = state.observer.lock().unwrap();
+                observer.sample(&seen);
+                observer.maybe_report(&crate::config::Config::load())
+            };
+            crate::observer::note_players(&mut state.brain.memory.lock().unwrap(), &summaries);
+        }
+
+        // === [6.7] PROXIMITY — Greet friends walking up, warn enemies off the base ===
+        if ticker.due("proximity") {
+            let reactions = {
+                let nearby = state.brain.world.lock().unwrap().nearby_entities.clone();
+                let (home, people) = {
+                    let memory = state.brain.memory.lock().unwrap();
+                    (memory.spatial.home_coords, memory.social.clone())
+                };
+                let visible: Vec<(String, f64, bool)> = nearby.iter()
+                    .filter(|e| e.is_player())
+                    .map(|e| {
+                        let near_base = home.is_some_and(|h| {
+                            systems::spider_sense::distance(e.position, [h[0] as f64, h[1] as f64, h[2] as f64]) <= 32.0
+                        });
+                        (e.name.clone(), e.distance, near_base)
+                    })
+                    .collect();
+                state.brain.social.lock().unwrap().update_proximity(&visible, &people)
+            };
+
+            for (player, reaction) in reactions {
+                crate::bus::publish(crate::bus::PlayerApproached { player, reaction });
+            }
+        }
+
+        // === [6.8] STARE — Someone's been looking right at us for a while ===
+        if ticker.due("stare") {
+            let bot_pos = bot.position();
+            let bot_eye = [bot_pos.x, bot_pos.y + 1.62, bot_pos.z];
+            let reactions: Vec<(&VisiblePlayer, systems::natural_look::StareReaction)> = {
+                let mut look = state.natural_look.inner.lock().unwrap();
+                let reactions = players.iter()
+                    .map(|p| {
+                        let eye = [p.pos[0], p.pos[1] + 1.62, p.pos[2]];
+                        (p, systems::natural_look::observe_gaze(&mut look, &p.name, eye, p.look, bot_eye))
+                    })
+                    .collect();
+                look.forget_gazes_except(&players.iter().map(|p| p.name.as_str()).collect::<Vec<_>>());
+                reactions
+            };
+
+            for (p, reaction) in reactions {
+                match reaction {
+                    systems::natural_look::StareReaction::LookBack => {
+                        debug!("[LOOK] 👀 {} tá me encarando, olhando de volta", p.name);
+                        bot.look_at(azalea::Vec3::new(p.pos[0], p.pos[1] + 1.62, p.pos[2]));
+                    }
+                    systems::natural_look::StareReaction::Comment => {
+                        bot.look_at(azalea::Vec3::new(p.pos[0], p.pos[1] + 1.62, p.pos[2]));
+                        let hostile = state.brain.memory.lock().unwrap().social.players.get(&p.name)
+                            .is_some_and(|profile| profile.trust_level < 10);
+                        let line = systems::natural_look::stare_comment(&p.name, hostile);
+                        state.motor.queue(systems::motor::MotorCommand::Chat(line));
+                    }
+                    systems::natural_look::StareReaction::None => {}
+                }
+            }
+        }
+
+        // === [6.9] WORLD SCAN — Read the blocks around us every couple of seconds ===
+        if ticker.due("world_scan") {
+            let pos = bot.position();
+            let center = [pos.x.floor() as i32, pos.y.floor() as i32, pos.z.floor() as i32];
+            let scan = systems::world_scanner::scan_area(&bot, center, systems::world_scanner::SCAN_RADIUS);
+            let biome = systems::world_scanner::read_biome(&bot, center);
+            let pois = systems::poi::detect(&scan);
+            let home = {
+                let mut memory = state.brain.memory.lock().unwrap();
+                memory.spatial.caves.observe(center, scan.is_underground(), &scan);
+                if let Some(home) = memory.spatial.home_coords {
+                    index_containers(&mut memory.inventory.storage, &scan, home);
+                }
+                for (pos, _) in scan.containers.iter().filter(|(_, k)| k == "ender_chest") {
+                    if memory.inventory.ender.remember_spot(*pos) {
+                        info!("[ENDER] 🟣 Ender chest em {:?}", pos);
+                    }
+                }
+                for poi in &pois {
+                    if systems::poi::remember(&mut memory.spatial, poi) {
+                        info!("[WORLD] 📍 {} em {:?}", poi.kind.label(), poi.pos);
+                        memory.episodes.add(cognitive::memory::Episode {
+                            timestamp: chrono::Utc::now(),
+                            event_type: cognitive::memory::EpisodeType::ExploredArea,
+                            description: format!("Achei {} em [{}, {}, {}]",
+                                poi.kind.label().to_lowercase(), poi.pos[0], poi.pos[1], poi.pos[2]),
+                            location: Some(poi.pos),
+                            players_involved: vec![],
+                            emotional_impact: 2,
+                        });
+                    }
+                }
+                memory.spatial.home_coords
+            };
+            let mut world = state.brain.world.lock().unwrap();
+            world.apply_scan(scan, home);
+            if let Some(biome) = biome
+                && world.current_biome != biome
+            {
+                info!("[WORLD] 🌍 Bioma: {:?}", biome);
+                world.current_biome = biome;
+            }
+        }
+
+        // === [6.10] DARK CORNERS — Torch up the base, nag whoever's standing in the dark ===
+        if ticker.due("dark_corners") {
+            let home = state.brain.memory.lock().unwrap().spatial.home_coords;
+            let (at_base, here, someone_near) = {
+                let world = state.brain.world.lock().unwrap();
+                let at_base: Vec<[i32; 3]> = home
+                    .map(|h| world.dark_spots_near(h, systems::world_scanner::BASE_RADIUS)
+                        .iter().map(|c| c.spot).collect())
+                    .unwrap_or_default();
+                let here = world.dark_spots_near(world.current_position, 6.0).first().map(|c| c.spot);
+                let someone_near = world.nearby_entities.iter().any(|e| e.is_player() && e.distance <= 10.0);
+                (at_base, here, someone_near)
+            };
+
+            // Enough dark corners at home → plan a torch run
+            if at_base.len() >= 3 {
+                let mut goals = state.brain.goals.lock().unwrap();
+                if !goals.goals.iter().any(|g| g.name == "Iluminar Base" && g.is_actionable()) {
+                    let spots = at_base.iter().take(5)
+                        .map(|p| format!("{} {} {}", p[0], p[1], p[2]))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    goals.add_goal(cognitive::goal_planner::Goal::new(
+                        "Iluminar Base",
+                        &format!("{} cantos escuros na base, mob vai nascer. Botar tocha em: {}", at_base.len(), spots),
+                        cognitive::goal_planner::GoalPriority::High,
+                    ).wanting("torch"));
                 }
             }
 
-            // Update motor's nearby_players flag for social fidgets
-            motor.nearby_players = !world.nearby_players.is_empty();
+            // Standing next to a spawner-in-waiting with someone around → say it
+            if let Some(spot) = here
+                && someone_near
+                && rand::thread_rng().gen_bool(0.3)
+            {
+                let lines = [
+                    "esse canto ai vai nascer creeper, bota tocha",
+                    "ta mt escuro aqui mn, vai spawnar mob",
+                    "alguem tem tocha? isso aqui ta pedindo creeper",
+                ];
+                let line = lines[rand::thread_rng().gen_range(0..lines.len())];
+                info!("[WORLD] 🔦 Canto escuro em {:?}", spot);
+                state.motor.queue(systems::motor::MotorCommand::Chat(line.into()));
+            }
+        }
+
+        // === [6.11] GRIEF WATCH — Diff the base against the last snapshot, find who was around ===
+        // Home copied out on its own: a guard in the `if let` would stay alive into on_grief, which locks memory again
+        let home = ticker.due("grief_watch").then(|| state.brain.memory.lock().unwrap().spatial.home_coords).flatten();
+        if let Some(home) = home {
+            let pos = bot.position();
+            let report = {
+                let entities = state.brain.world.lock().unwrap().nearby_entities.clone();
+                let mut watch = state.grief_watch.lock().unwrap();
+                watch.observe(home, [pos.x, pos.y, pos.z], &entities);
+                watch.check(&bot, home)
+            };
+            if let Some(report) = report {
+                on_grief(&bot, &state, report);
+            }
         }
 
         // === [7] VISUAL CORTEX — Periodic area scan + Gemini judging ===
-        {
-            let pos = {
+        if ticker.due("visual_cortex") && features.visual_cortex && features.llm {
+            let (pos, latest) = {
                 let world = state.brain.world.lock().unwrap();
-                world.current_position
+                (world.current_position, world.last_block_scan.clone())
             };
 
-            let should_scan = {
+            let should_scan = latest.is_some() && {
                 let mut vc = state.visual_cortex.lock().unwrap();
                 vc.should_scan(pos)
             };
 
-            if should_scan {
-                println!("[VISUAL] 👁️ Scanning area around [{}, {}, {}]...", pos[0], pos[1], pos[2]);
-
-                // Build a basic scan from the world state
-                // In production, read actual blocks via bot.world().read()
-                let scan = systems::visual_cortex::BlockScan {
-                    block_counts: std::collections::HashMap::new(),
-                    total_blocks: 0,
-                    air_percentage: 100.0,
-                    light_avg: 15.0,
-                    unique_types: 0,
-                    center: pos,
-                };
+            if should_scan && let Some(scan) = latest {
+                info!("[VISUAL] 👁️ Scanning area around [{}, {}, {}]...", pos[0], pos[1], pos[2]);
 
-                let summary = scan.to_summary();
-                if summary != "Área vazia, só ar." {
+                // Only terrain around → nothing anybody built, no LLM call
+                if scan.worth_judging() {
                     let motor_state = state.motor.clone();
-                    tokio::spawn(async move {
+                    crate::bus::spawn(async move {
+                        let _call = crate::watchdog::llm_started();
                         if let Some(judgment) = systems::visual_cortex::judge_with_gemini(&scan).await {
-                            let mut motor = motor_state.inner.lock().unwrap();
-                            motor.queue(systems::motor::MotorCommand::Chat(judgment));
+                            motor_state.queue(systems::motor::MotorCommand::Chat(judgment));
                         }
-                    });
+                    }.instrument(info_span!("visual_cortex")));
                 }
             }
         }
 
         // === [8] DREAMER — Metacognition / Boredom → Spontaneous goals ===
+        if ticker.due("dreamer") && features.dreamer {
+            dream(&state, ticker.every("dreamer"));
+        }
+
+        // === [8.5] UPDATE BOT POSITION for motor + world, catch teleports ===
+        ticker.always("position");
         {
-            let has_active_goal = {
+            let pos = bot.position();
+            {
+                let mut world = state.brain.world.lock().unwrap();
+                world.current_position = [pos.x.floor() as i32, pos.y.floor() as i32, pos.z.floor() as i32];
+                let here = world.current_position;
+                world.visit(here);
+            }
+
+            // Live map listeners get us once a second, if we moved
+            let mut last = state.last_position_event.lock().unwrap();
+            if last.0.elapsed().as_secs() >= 1 && (pos - last.1).length_squared() >= 0.25 {
+                *last = (Instant::now(), pos);
+                crate::events::emit(crate::events::BotEvent::Position { x: pos.x, y: pos.y, z: pos.z });
+            }
+            drop(last);
+
+            // Stopped breaking a block and it's gone → we mined it
+            let mining = bot.get_component::<azalea::mining::Mining>().map(|m| m.pos);
+            let previous = std::mem::replace(&mut *state.mining_at.lock().unwrap(), mining);
+            if let Some(at) = previous
+                && mining != previous
+                && bot.world().read().get_block_state(at).is_some_and(|b| b.is_air())
+            {
+                crate::stats::block_mined();
+            }
+
+            let jump = state.brain.teleport.lock().unwrap().observe([pos.x, pos.y, pos.z]);
+            if let Some(tp) = jump {
+                on_teleport(&bot, &state, tp);
+            }
+        }
+
+        // === [8.6] AUTONOMOUS WANDERING — If idle too long, explore! ===
+        if ticker.due("wandering") && features.wandering {
+            let should_wander = {
+                let motor = state.motor.view();
                 let planner = state.brain.goals.lock().unwrap();
-                planner.current_goal().is_some()
+                let idle_secs = motor.last_movement_time.elapsed().as_secs();
+
+                // Mapping/exploring is the goal itself — keep walking frontiers
+                let mapping = planner.current_goal()
+                    .is_some_and(|g| g.name.starts_with("Mapear") || g.name.starts_with("Explorar"));
+
+                // Wander if: idle >60s (10s while mapping), not already walking, no other goal, queue empty
+                idle_secs > if mapping { 10 } else { 60 }
+                    && !motor.is_walking
+                    && (planner.current_goal().is_none() || mapping)
+                    && motor.queued == 0
             };
 
-            let should_dream = {
-                let mut dreamer = state.dreamer.lock().unwrap();
-                if has_active_goal {
-                    dreamer.reset_idle();
-                } else {
-                    dreamer.tick_idle();
+            if should_wander {
+                // Underground, an unwalked cave branch beats a chunk frontier
+                let underground = {
+                    let world = state.brain.world.lock().unwrap();
+                    world.is_underground.then_some(world.current_position)
+                };
+                let branch = underground
+                    .and_then(|here| state.brain.memory.lock().unwrap().spatial.caves.unexplored_branch(here));
+                let frontier = branch.or_else(|| state.brain.world.lock().unwrap().frontier_target());
+                state.motor.explore(frontier);
+                info!("[BOT] 🦶 Idle too long, time to explore! (fronteira: {:?})", frontier);
+            }
+        }
+
+        // === [8.6.1] CAVE EXIT — Hurt underground? Walk the corridors we know back out ===
+        if ticker.due("cave_exit") {
+            let underground = state.brain.world.lock().unwrap().is_underground;
+            if underground && bot.health() < 8.0 {
+                let pos = bot.position();
+                let here = [pos.x.floor() as i32, pos.y.floor() as i32, pos.z.floor() as i32];
+                let route = state.brain.memory.lock().unwrap().spatial.caves.route_to_exit(here);
+                let escaping = state.brain.goals.lock().unwrap().goals.iter()
+                    .any(|g| g.name == "Sair da caverna" && g.is_actionable());
+                if let Some(route) = route && !escaping {
+                    state.brain.goals.lock().unwrap().emergency("Sair da caverna", "Vida baixa no subsolo, voltando pela rota conhecida");
+                    info!("[CAVE] 🩸 Vida baixa — saindo por {} pontos conhecidos", route.len());
+                    state.motor.queue_urgent(systems::motor::MotorCommand::FollowRoute(route));
+                }
+            }
+        }
+
+        // === [8.6.2] DEATH RUN — Back on our feet? Go get the pile before it despawns ===
+        if ticker.due("death_run") && bot.health() > 0.0 {
+            let dimension = systems::death_recovery::dimension(&bot);
+            let pile = systems::death_recovery::pending(&state.brain.memory.lock().unwrap(), dimension.as_deref());
+            let calm = pile.is_some() && state.brain.combat.lock().unwrap().state == systems::combat::CombatState::Peaceful;
+            if calm && let Some(coords) = pile && state.brain.trader.try_begin() {
+                crate::bus::spawn(run_back(bot.clone(), state.clone(), coords).instrument(info_span!("death_run")));
+            }
+        }
+
+        // === [8.7] COLLECTIONS — Chase up old debts from whoever's online ===
+        if ticker.due("collections") {
+            {
+                let policy = systems::economy::InterestPolicy::from_config(&crate::config::Config::load());
+                let mut memory = state.brain.memory.lock().unwrap();
+                memory.economy.accrue_interest(&policy);
+            }
+            let online: Vec<String> = bot.tab_list().values().map(|p| p.profile.name.clone()).collect();
+            for player in online.iter().filter(|p| **p != bot.username()) {
+                remind_debtor(&state, player);
+            }
+            expose_scams(&bot, &state, None);
+
+            let refunds = state.brain.memory.lock().unwrap().economy.expire_escrows();
+            for trade in refunds {
+                crate::bus::spawn(plugins::brain::refund_escrow(bot.clone(), state.brain.clone(), trade).instrument(info_span!("escrow")));
+            }
+        }
+
+        // === [8.8] SHOPPING — Buy what our goals need when a shop sells it cheap ===
+        if ticker.due("shopping") {
+            let deal = {
+                let wanted = state.brain.goals.lock().unwrap().wanted_items();
+                let memory = state.brain.memory.lock().unwrap();
+                wanted.iter().find_map(|item| memory.economy.best_shop_for(item).cloned())
+            };
+
+            if let Some(shop) = deal
+                && state.brain.trader.try_begin()
+            {
+                let bot = bot.clone();
+                let brain = state.brain.clone();
+                crate::bus::spawn(async move {
+                    let bought = systems::chest_shop::buy(bot, shop.clone()).await;
+                    brain.trader.finish();
+
+                    let mut memory = brain.memory.lock().unwrap();
+                    memory.economy.mark_shop_visited(shop.pos);
+                    let task = cognitive::memory::InventoryKnowledge::acquire_task(&shop.item);
+                    if bought == 0 {
+                        let failures = memory.inventory.record_failure(&task);
+                        warn!("[SHOP] 📉 {} falhou {}x", task, failures);
+                    } else {
+                        memory.inventory.clear_failures(&task);
+                        memory.episodes.add(cognitive::memory::Episode {
+                            timestamp: chrono::Utc::now(),
+                            event_type: cognitive::memory::EpisodeType::TradeCompleted,
+                            description: format!("Comprei {} x{} na loja do {}", shop.item, bought, shop.owner),
+                            location: Some(shop.pos),
+                            players_involved: vec![shop.owner.clone()],
+                            emotional_impact: 1,
+                        });
+                        brain.goals.lock().unwrap().complete_wanting(&shop.item);
+                    }
+                }.instrument(info_span!("chest_shop")));
+            }
+        }
+
+        // === [8.9] WEALTH — Snapshot what we own, valued at market prices ===
+        if ticker.due("wealth") {
+            let holdings = systems::inventory::Inventory::read(&bot).counts();
+            let mut memory = state.brain.memory.lock().unwrap();
+            let stored = memory.inventory.stashed_everywhere();
+            let snapshot = memory.economy.record_wealth(&holdings, &stored);
+            memory.inventory.holdings = holdings;
+            info!("[ECONOMY] 💰 Patrimônio: {:.0} ferro", snapshot.net_worth());
+        }
+
+        // === [8.10] HELP — Stuck on something? Ask a friend, pay them back later ===
+        if ticker.due("help") {
+            let wanted = state.brain.goals.lock().unwrap().wanted_items();
+
+            // Friends who ignored us
+            let ignored = state.brain.social.lock().unwrap().expire_help_requests();
+            // What we're stuck on (failures per item), who we know, and who we owe
+            let (stuck, people, favors) = {
+                let mut memory = state.brain.memory.lock().unwrap();
+                for request in &ignored {
+                    info!("[SOCIAL] 😒 {} não ajudou com {}", request.player, request.item);
+                    let profile = memory.social.get_or_create(&request.player);
+                    profile.trust_level = (profile.trust_level - 2).max(-100);
+                    profile.update_relationship();
+                    profile.notes.push(format!("não me ajudou com {}", request.item));
                 }
-                dreamer.is_bored() && dreamer.can_dream()
+                let stuck: Vec<(&String, String, u32)> = wanted.iter()
+                    .map(|item| (item, cognitive::memory::InventoryKnowledge::acquire_task(item)))
+                    .filter(|(_, task)| memory.inventory.should_ask_for_help(task))
+                    .map(|(item, task)| {
+                        let failures = memory.inventory.failed_attempts.get(&task).copied().unwrap_or(0);
+                        (item, task, failures)
+                    })
+                    .collect();
+                (stuck, memory.social.clone(), memory.economy.unpaid_favors())
+            };
+
+            let (asked, online) = {
+                let mut social = state.brain.social.lock().unwrap();
+                let asked = stuck.iter().find_map(|(item, task, failures)| {
+                    if !social.should_ask_for_help(task, *failures) || social.pending_help.contains_key(*item) {
+                        return None;
+                    }
+                    let helper = social.pick_helper(&people)?;
+                    social.ask_for_help(&helper, item, &people).map(|line| (helper, line))
+                });
+                let online: Vec<String> = social.presence.online.keys().cloned().collect();
+                (asked, online)
             };
+            if let Some((helper, request)) = asked {
+                state.brain.memory.lock().unwrap().social.get_or_create(&helper).help_requests_made += 1;
+                state.motor.queue(systems::motor::MotorCommand::Chat(request));
+            }
+
+            // Pay back one favor, if they're around — from surplus only. While a goal still wants
+            // the item, it's what we asked for in the first place and it's spoken for
+            let carrying = systems::inventory::Inventory::read(&bot);
+            let repayment = favors.into_iter()
+                .find(|(player, item, qty)| {
+                    online.contains(player) && !wanted.contains(item) && carrying.count_of(item) >= *qty
+                });
+            if let Some((player, item, qty)) = repayment {
+                crate::bus::spawn(repay_favor(bot.clone(), state.clone(), player, item, qty).instrument(info_span!("social")));
+            }
+        }
 
-            if should_dream {
-                let mood = {
-                    let p = state.brain.personality.lock().unwrap();
-                    p.mood.clone()
+        // === [8.11] GENEROSITY — Good mood + surplus + a friend in need = present. A promised kit goes out in any mood ===
+        if ticker.due("generosity") {
+            let holdings = systems::inventory::Inventory::read(&bot).counts();
+            let generous = state.brain.personality.lock().unwrap().mood == cognitive::personality::Mood::Generous;
+            let need = generous.then(|| state.brain.memory.lock().unwrap().social.clone())
+                .and_then(|people| state.brain.social.lock().unwrap().gift_candidate(&people, &holdings));
+            if let Some(need) = need {
+                info!("[SOCIAL] 🎁 Vou dar {} x{} pro {}", need.item, need.quantity, need.player);
+                crate::bus::spawn(give_present(bot.clone(), state.clone(), need).instrument(info_span!("social")));
+            } else {
+                // Nobody asked for anything — a newcomer we welcomed standing here gets their starter kit
+                let kit = state.brain.social.lock().unwrap().starter_kit_for_nearby(&holdings);
+                if let Some((player, kit)) = kit {
+                    info!("[SOCIAL] 🌱 Kit inicial pro {}: {:?}", player, kit);
+                    crate::bus::spawn(give_starter_kit(bot.clone(), state.clone(), player, kit).instrument(info_span!("welcomer")));
+                }
+            }
+        }
+
+        // === [8.12] SMALL TALK — Idle next to a friend? Bring something up ===
+        if ticker.due("small_talk") {
+            let idle = {
+                let motor = state.motor.view();
+                !motor.is_walking && motor.queued == 0 && motor.last_movement_time.elapsed().as_secs() > 30
+            };
+            let chatty = idle
+                && state.brain.personality.lock().unwrap().social_battery >= 0.6
+                && rand::thread_rng().r#gen::<f32>() < 0.3;
+            if chatty {
+                let (people, episodes) = {
+                    let memory = state.brain.memory.lock().unwrap();
+                    (memory.social.clone(), memory.episodes.clone())
                 };
-                let memory = state.brain.memory.lock().unwrap();
-                let mut planner = state.brain.goals.lock().unwrap();
-                let mut dreamer = state.dreamer.lock().unwrap();
-
-                if let Some(chat_msg) = cognitive::dreamer::maybe_dream(
-                    &mut dreamer,
-                    &mood,
-                    &memory,
-                    &mut planner,
-                ) {
-                    let mut motor = state.motor.inner.lock().unwrap();
-                    motor.queue(systems::motor::MotorCommand::Chat(chat_msg));
+                let opener = state.brain.social.lock().unwrap().start_small_talk(&people, &episodes);
+                if let Some((_, line)) = opener {
+                    state.motor.queue(systems::motor::MotorCommand::Chat(line));
                 }
             }
         }
 
-        // === [8.5] UPDATE BOT POSITION for motor ===
-        {
-            let pos = bot.position();
-            let mut motor = state.motor.inner.lock().unwrap();
-            motor.bot_position = [pos.x, pos.y, pos.z];
+        // === [8.13] STORAGE — Idle next to a chest we haven't checked? Peek inside ===
+        if ticker.due("storage") {
+            let idle = {
+                let motor = state.motor.view();
+                !motor.is_walking && motor.queued == 0
+            };
+            if idle {
+                let pos = bot.position();
+                let here = [pos.x.floor() as i32, pos.y.floor() as i32, pos.z.floor() as i32];
+                let target = state.brain.memory.lock().unwrap()
+                    .inventory.storage.needs_survey(here, 4.5)
+                    .map(|c| (c.pos, c.kind.clone()));
+                if let Some((pos, kind)) = target {
+                    crate::bus::spawn(survey_container(bot.clone(), state.clone(), pos, kind).instrument(info_span!("storage")));
+                }
+            }
         }
 
-        // === [8.6] AUTONOMOUS WANDERING — If idle too long, explore! ===
-        {
-            let should_wander = {
-                let motor = state.motor.inner.lock().unwrap();
-                let planner = state.brain.goals.lock().unwrap();
-                let idle_secs = motor.last_movement_time.elapsed().as_secs();
+        // === [8.14] STASH — Backpack full? Box it in a shulker, or go put it away in the base chests ===
+        if ticker.due("stash") {
+            let full = systems::inventory::Inventory::read(&bot).free_slots() <= crate::config::Config::load().stash_free_slots;
+            let calm = full && state.brain.combat.lock().unwrap().state == systems::combat::CombatState::Peaceful;
+            let carrying = systems::inventory::Inventory::read(&bot);
+            let packable = calm
+                && systems::shulker::empty_shulker_slot(&carrying).is_some()
+                && systems::shulker::overflow(&carrying).is_some();
+            // A miner with a hauler online waits for the hauler instead of walking home
+            let config = crate::config::Config::load();
+            let hauled = calm && crate::roles::hauled(&config, &state.brain.me.name(&config));
+            let somewhere = calm && !packable && !hauled && {
+                let memory = state.brain.memory.lock().unwrap();
+                let pos = bot.position();
+                let here = [pos.x.floor() as i32, pos.y.floor() as i32, pos.z.floor() as i32];
+                memory.spatial.home_coords.is_some()
+                    && systems::storage::stashable(&carrying).iter()
+                        .any(|item| memory.inventory.storage.deposit_target(item, here).is_some())
+            };
+            if packable && state.brain.trader.try_begin() {
+                crate::bus::spawn(pack_overflow(bot.clone(), state.clone()).instrument(info_span!("shulker")));
+            } else if somewhere && state.brain.trader.try_begin() {
+                crate::bus::spawn(stash_backpack(bot.clone(), state.clone()).instrument(info_span!("storage")));
+            }
+        }
 
-                // Wander if: idle >60s, not already walking, no active goals, queue empty
-                idle_secs > 60
-                    && !motor.is_walking
-                    && planner.current_goal().is_none()
-                    && motor.queue_len() == 0
+        // === [8.15] JUNK — Almost full of dirt and rotten flesh? Throw it out ===
+        if ticker.due("junk") {
+            let config = crate::config::Config::load();
+            let carrying = systems::inventory::Inventory::read(&bot);
+            let pressured = carrying.free_slots() <= config.junk_free_slots;
+            let calm = pressured && state.brain.combat.lock().unwrap().state == systems::combat::CombatState::Peaceful;
+            let junk = if calm { plugins::inventory::junk_in(&carrying, &config) } else { vec![] };
+            if !junk.is_empty() && state.brain.trader.try_begin() {
+                crate::bus::spawn(dump_junk(bot.clone(), state.clone(), junk).instrument(info_span!("junk")));
+            }
+        }
+
+        // === [8.16] ENDER — Diamonds in the pocket far from home? Ender chest before it's too late ===
+        if ticker.due("ender") {
+            let carrying = systems::inventory::Inventory::read(&bot);
+            let loaded = !systems::ender_chest::valuables(&carrying).is_empty();
+            let calm = loaded && state.brain.combat.lock().unwrap().state == systems::combat::CombatState::Peaceful;
+            let worth_it = calm && {
+                let pos = bot.position();
+                let here = [pos.x.floor() as i32, pos.y.floor() as i32, pos.z.floor() as i32];
+                let goal = state.brain.goals.lock().unwrap().current_goal().map(|g| g.name.clone());
+                let memory = state.brain.memory.lock().unwrap();
+                let risky = systems::ender_chest::is_risky(
+                    here,
+                    memory.spatial.home_coords,
+                    goal.as_deref(),
+                    crate::config::Config::load().ender_far_from_home,
+                );
+                let reachable = memory.inventory.ender.nearest_spot(here, 48.0).is_some()
+                    || carrying.count_of("ender_chest") > 0
+                    || systems::ender_chest::can_craft(&carrying);
+                risky && reachable
             };
+            if worth_it && state.brain.trader.try_begin() {
+                crate::bus::spawn(secure_valuables(bot.clone(), state.clone()).instrument(info_span!("ender_chest")));
+            }
+        }
 
-            if should_wander {
-                let mut motor = state.motor.inner.lock().unwrap();
-                motor.queue(systems::motor::MotorCommand::WanderRandom);
-                println!("[BOT] 🦶 Idle too long, time to explore!");
+        // === [8.17] TOOLS — Pickaxe broke or about to? Craft the next one first ===
+        if ticker.due("tools") {
+            let needs = state.tool_wear.lock().unwrap().check(
+                &systems::inventory::Inventory::read(&bot),
+                crate::config::Config::load().tool_worn_percent,
+            );
+            let calm = !needs.is_empty() && state.brain.combat.lock().unwrap().state == systems::combat::CombatState::Peaceful;
+            if calm && state.brain.trader.try_begin() {
+                crate::bus::spawn(replace_tool(bot.clone(), state.clone(), needs[0]).instrument(info_span!("tool_crafter")));
+            }
+        }
+
+        // === [8.18] LOADOUT — New goal, new kit: dress for it, hand the old one back ===
+        if ticker.due("loadout") {
+            let wanted = state.brain.goals.lock().unwrap().current_goal().and_then(systems::loadout::kit_for);
+            let changed = state.loadout.lock().unwrap().active != wanted;
+            let calm = changed && state.brain.combat.lock().unwrap().state == systems::combat::CombatState::Peaceful;
+            if calm && state.brain.trader.try_begin() {
+                crate::bus::spawn(switch_loadout(bot.clone(), state.clone(), wanted).instrument(info_span!("loadout")));
+            }
+        }
+
+        // === [8.19] BUILDER — A blueprint going? Fetch materials, put the next blocks down ===
+        if ticker.due("builder") {
+            let building = state.builder.lock().unwrap().is_active();
+            let calm = building && state.brain.combat.lock().unwrap().state == systems::combat::CombatState::Peaceful;
+            if calm && state.brain.trader.try_begin() {
+                crate::bus::spawn(work_on_build(bot.clone(), state.clone()).instrument(info_span!("builder")));
+            }
+        }
+
+        // === [8.20] MINING — Goal says mine? Dig the next stretch, grab what the walls show ===
+        if ticker.due("mining") {
+            let target = state.brain.goals.lock().unwrap().current_goal().and_then(|g| systems::smart_mining::MiningTarget::from_goal(&g.name));
+            let free = target.is_some() && !state.builder.lock().unwrap().is_active();
+            let calm = free && state.brain.combat.lock().unwrap().state == systems::combat::CombatState::Peaceful;
+            if calm && let Some(target) = target && state.brain.trader.try_begin() {
+                crate::bus::spawn(mine_stretch(bot.clone(), state.clone(), target).instrument(info_span!("miner")));
             }
         }
 
-        // === [9] MOTOR — Execute queued commands + human fidgets ===
-        let _ = systems::motor::handle(bot.clone(), event.clone(), state.motor.clone()).await;
+        // === [9] MOTOR — Hand the actor this tick's snapshot; it runs the queue + human fidgets ===
+        ticker.always("motor");
+        let pos = bot.position();
+        let nearby_players = !state.brain.world.lock().unwrap().nearby_players.is_empty();
+        state.motor.tick(systems::motor::MotorTick { position: [pos.x, pos.y, pos.z], nearby_players });
     }
 
     Ok(())
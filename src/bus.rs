@@ -0,0 +1,220 @@
+use azalea::ecs::entity::Entity;
+use azalea::prelude::*;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
+use std::time::Instant;
+use tracing::warn;
+use crate::bot::State;
+use crate::cognitive::goal_planner::GoalStatus;
+use crate::systems::social::ProximityReaction;
+use crate::systems::spider_sense::PredictedThreat;
+
+// ============================================================
+// BUS — Typed topics: whoever notices something publishes it, whoever cares subscribes
+// Damage { amount: 4.0 } → reaction delay panics, personality flinches, dashboard hears it
+// ============================================================
+//
+// Not a tokio broadcast channel on purpose: handlers need the Client and the bot's State, which only
+// the game loop has, and they poke the ECS — that has to happen on the loop, not on some receiver task.
+// So publish() queues, and dispatch() delivers in publish order right after the event that caused it.
+// The dashboard/Discord side is already a broadcast (events.rs); the "events" subscribers feed it.
+
+/// Events published while handlers run get delivered too, but a loop stops here
+const MAX_ROUNDS: usize = 8;
+
+/// What every handler gets to work with
+pub struct Ctx<'a> {
+    pub bot: &'a Client,
+    pub state: &'a State,
+}
+
+/// Marks a type as something that can go on the bus
+pub trait Topic: Any + Send {}
+
+/// A game tick, before the numbered systems in bot.rs run
+#[derive(Debug, Clone)]
+pub struct Tick;
+
+/// A chat line from the server. `sender` = who wrote it, when there's a who
+#[derive(Debug, Clone)]
+pub struct ChatIn {
+    pub message: String,
+    pub sender: Option<String>,
+}
+
+/// We lost health
+#[derive(Debug, Clone)]
+pub struct Damage {
+    pub amount: f32,
+    pub health: f32,
+}
+
+/// Spider sense saw something coming. `player` = who, if it's a player
+#[derive(Debug, Clone)]
+pub struct ThreatDetected {
+    pub threat: PredictedThreat,
+    pub player: Option<String>,
+}
+
+/// A goal was added or moved to another status
+#[derive(Debug, Clone)]
+pub struct GoalChanged {
+    pub goal: String,
+    pub status: GoalStatus,
+}
+
+/// A player walked up to us. `reaction` = what social thinks we should do about it
+#[derive(Debug, Clone)]
+pub struct PlayerApproached {
+    pub player: String,
+    pub reaction: ProximityReaction,
+}
+
+/// We dug an ore out ("diamond_ore", "ancient_debris")
+#[derive(Debug, Clone)]
+pub struct OreFound {
+    pub block: String,
+    pub pos: [i32; 3],
+}
+
+impl Topic for Tick {}
+impl Topic for ChatIn {}
+impl Topic for Damage {}
+impl Topic for ThreatDetected {}
+impl Topic for GoalChanged {}
+impl Topic for PlayerApproached {}
+impl Topic for OreFound {}
+
+type Handler = Arc<dyn Fn(&Ctx, &dyn Any) + Send + Sync>;
+/// Subscribers per topic, in the order they signed up
+type Handlers = HashMap<TypeId, Vec<(&'static str, Handler)>>;
+
+static HANDLERS: LazyLock<RwLock<Handlers>> = LazyLock::new(Default::default);
+/// A queued event, and which bot published it (None = from outside any bot — the main bot takes it)
+type Pending = (Option<Entity>, TypeId, Box<dyn Any + Send>);
+
+static PENDING: Mutex<Vec<Pending>> = Mutex::new(Vec::new());
+
+tokio::task_local! {
+    /// The bot whose event is being handled — in a swarm, its events are delivered to it alone
+    static PUBLISHER: Entity;
+}
+
+/// Run `work` as `bot`: whatever it publishes is dispatched with that bot and its state
+pub async fn as_bot<F: Future>(bot: Entity, work: F) -> F::Output {
+    PUBLISHER.scope(bot, work).await
+}
+
+/// as_bot for plain code — a goal written into another bot's planner is that bot's news
+pub fn for_bot<R>(bot: Entity, work: impl FnOnce() -> R) -> R {
+    PUBLISHER.sync_scope(bot, work)
+}
+
+/// tokio::spawn that keeps the publisher: what the task publishes later still goes to the bot that started it
+pub fn spawn<F>(work: F) -> tokio::task::JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    match PUBLISHER.try_with(|bot| *bot) {
+        Ok(bot) => tokio::spawn(PUBLISHER.scope(bot, work)),
+        Err(_) => tokio::spawn(work),
+    }
+}
+
+/// Call `handler` for every `T` published from now on. `name` shows up in the logs
+pub fn subscribe<T: Topic>(name: &'static str, handler: impl Fn(&Ctx, &T) + Send + Sync + 'static) {
+    let handler: Handler = Arc::new(move |ctx, event| {
+        if let Some(event) = event.downcast_ref::<T>() {
+            handler(ctx, event);
+        }
+    });
+    HANDLERS.write().unwrap().entry(TypeId::of::<T>()).or_default().push((name, handler));
+}
+
+/// Queue an event. Safe from anywhere — it's delivered on the game loop, with the bot at hand
+pub fn publish<T: Topic>(event: T) {
+    let publisher = PUBLISHER.try_with(|bot| *bot).ok();
+    PENDING.lock().unwrap().push((publisher, TypeId::of::<T>(), Box::new(event)));
+}
+
+/// Deliver everything queued, in order. Called by bot.rs after every game event
+pub fn dispatch(bot: &Client, state: &State) {
+    let ctx = Ctx { bot, state };
+    let me = bot.entity;
+    let main = !state.brain.me.extra();
+    // Ours, or nobody's in particular if we're the main bot — another bot's events wait for that bot
+    let mine = |publisher: &Option<Entity>| publisher.map_or(main, |p| p == me);
+    for _ in 0..MAX_ROUNDS {
+        let batch: Vec<_> = {
+            let mut pending = PENDING.lock().unwrap();
+            let (batch, others) = std::mem::take(&mut *pending).into_iter()
+                .partition(|(publisher, ..)| mine(publisher));
+            *pending = others;
+            batch
+        };
+        if batch.is_empty() {
+            return;
+        }
+        for (_, topic, event) in &batch {
+            // A copy, so a handler can subscribe (or publish) without waiting on our own lock
+            let handlers = HANDLERS.read().unwrap().get(topic).cloned().unwrap_or_default();
+            for (name, handler) in &handlers {
+                let started = Instant::now();
+                handler(&ctx, event.as_ref());
+                crate::profiler::record(&format!("bus/{}", name), started.elapsed());
+            }
+        }
+    }
+    let mut pending = PENDING.lock().unwrap();
+    let before = pending.len();
+    pending.retain(|(publisher, ..)| !mine(publisher));
+    let dropped = before - pending.len();
+    warn!("[BUS] ⚠️ Eventos publicando eventos sem parar, descartei {}", dropped);
+}
+
+/// "ChatIn → chatlog, natural_look, walker" for the startup log
+pub fn describe() -> Vec<String> {
+    let handlers = HANDLERS.read().unwrap();
+    let topics = [
+        (TypeId::of::<Tick>(), "Tick"),
+        (TypeId::of::<ChatIn>(), "ChatIn"),
+        (TypeId::of::<Damage>(), "Damage"),
+        (TypeId::of::<ThreatDetected>(), "ThreatDetected"),
+        (TypeId::of::<GoalChanged>(), "GoalChanged"),
+        (TypeId::of::<PlayerApproached>(), "PlayerApproached"),
+        (TypeId::of::<OreFound>(), "OreFound"),
+    ];
+    topics.iter()
+        .filter_map(|(id, topic)| {
+            let names: Vec<&str> = handlers.get(id)?.iter().map(|(name, _)| *name).collect();
+            Some(format!("{} → {}", topic, names.join(", ")))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Who published the queued `OreFound`s, in order — and takes them off the queue
+    fn publishers() -> Vec<Option<Entity>> {
+        let mut pending = PENDING.lock().unwrap();
+        let (ore, rest): (Vec<_>, Vec<_>) = std::mem::take(&mut *pending).into_iter().partition(|(_, topic, _)| *topic == TypeId::of::<OreFound>());
+        *pending = rest;
+        ore.into_iter().map(|(publisher, ..)| publisher).collect()
+    }
+
+    #[tokio::test]
+    async fn spawned_work_publishes_as_its_bot() {
+        let (one, two) = (Entity::from_raw_u32(1).unwrap(), Entity::from_raw_u32(2).unwrap());
+        let ore = || OreFound { block: "diamond_ore".into(), pos: [0, 12, 0] };
+        as_bot(one, async {
+            spawn(async move { publish(ore()) }).await.unwrap();
+            for_bot(two, || publish(ore()));
+        }).await;
+        tokio::spawn(async move { publish(ore()) }).await.unwrap();
+        assert_eq!(publishers(), [Some(one), Some(two), None]);
+    }
+}
@@ -0,0 +1,218 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::io::Write;
+use std::sync::{LazyLock, Mutex};
+use tracing::warn;
+use crate::config::Config;
+use crate::systems::chat_channel;
+use crate::systems::chat_format;
+
+// ============================================================
+// CHAT LOG — Everything heard and said, on disk by day and in memory for search
+// {"ts":"...","direction":"in","channel":"party","player":"Steve","message":"[Party] Steve: bora pro nether"}
+// ============================================================
+
+const FOLDER: &str = "chat";
+const DEFAULT_LIMIT: usize = 100;
+
+static RECENT: LazyLock<Mutex<VecDeque<Entry>>> = LazyLock::new(|| Mutex::new(VecDeque::new()));
+/// Day of the file we last wrote, so pruning runs once a day
+static LAST_DAY: Mutex<String> = Mutex::new(String::new());
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    In,
+    Out,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    pub ts: DateTime<Utc>,
+    pub direction: Direction,
+    /// global, party, team, faction, whisper, command or system
+    pub channel: String,
+    /// Who said it (in) or who we whispered (out)
+    pub player: Option<String>,
+    pub message: String,
+}
+
+/// What to look for. Everything optional; `days` searches the files instead of memory
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Search {
+    pub text: Option<String>,
+    pub player: Option<String>,
+    pub channel: Option<String>,
+    pub direction: Option<Direction>,
+    pub days: Option<u32>,
+    pub limit: Option<usize>,
+}
+
+impl Search {
+    fn matches(&self, entry: &Entry) -> bool {
+        let contains = |haystack: &str, needle: &str| haystack.to_lowercase().contains(&needle.to_lowercase());
+        self.text.as_deref().is_none_or(|t| contains(&entry.message, t))
+            && self.player.as_deref().is_none_or(|p| entry.player.as_deref().is_some_and(|e| e.eq_ignore_ascii_case(p)))
+            && self.channel.as_deref().is_none_or(|c| entry.channel.eq_ignore_ascii_case(c))
+            && self.direction.is_none_or(|d| entry.direction == d)
+    }
+}
+
+/// A raw line from the server. `sender` is who chat_sender() says wrote it
+pub fn heard(raw: &str, sender: Option<String>) {
+    let clean = chat_format::strip_colors(raw);
+    let channel = match chat_channel::parse_channel_message(&clean) {
+        Some((channel, _, _)) => channel.name(),
+        None if is_whisper(&clean) => "whisper",
+        None if sender.is_some() => "global",
+        None => "system",
+    };
+    record(Direction::In, channel, sender, clean);
+}
+
+/// Something we sent — plain chat, a channel command or a /msg
+pub fn said(message: &str) {
+    let config = Config::load();
+    let mut words = message.split_whitespace();
+    let first = words.next().unwrap_or_default();
+    let (channel, player) = if !first.starts_with('/') {
+        ("global", None)
+    } else if ["/msg", "/tell", "/w", "/whisper"].contains(&first) {
+        ("whisper", words.next().map(str::to_string))
+    } else if first == "/r" {
+        ("whisper", None)
+    } else if first == config.party_chat_command {
+        ("party", None)
+    } else if first == config.team_chat_command {
+        ("team", None)
+    } else if first == config.faction_chat_command {
+        ("faction", None)
+    } else {
+        ("command", None)
+    };
+    record(Direction::Out, channel, player, message.to_string());
+}
+
+/// Newest last, at most `limit`
+pub fn search(search: &Search) -> Vec<Entry> {
+    let limit = search.limit.unwrap_or(DEFAULT_LIMIT);
+    let found: Vec<Entry> = match search.days {
+        Some(days) => from_files(days).into_iter().filter(|e| search.matches(e)).collect(),
+        None => RECENT.lock().unwrap().iter().filter(|e| search.matches(e)).cloned().collect(),
+    };
+    found[found.len().saturating_sub(limit)..].to_vec()
+}
+
+fn is_whisper(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    ["whispers to you", "-> me]", "-> você", "-> voce", "sussurrou", "te sussurra"].iter().any(|m| lower.contains(m))
+}
+
+fn record(direction: Direction, channel: &str, player: Option<String>, message: String) {
+    let config = Config::load();
+    if !config.chatlog_enabled {
+        return;
+    }
+    let message = crate::secrets::redact(&message);
+    let entry = Entry { ts: Utc::now(), direction, channel: channel.to_string(), player, message };
+    {
+        let mut recent = RECENT.lock().unwrap();
+        recent.push_back(entry.clone());
+        while recent.len() > config.chatlog_buffer {
+            recent.pop_front();
+        }
+    }
+    append(&entry, config.chatlog_keep_days);
+}
+
+fn folder() -> String {
+    format!("{}/{}", crate::cognitive::memory::data_dir(), FOLDER)
+}
+
+fn append(entry: &Entry, keep_days: u32) {
+    let dir = folder();
+    let day = entry.ts.format("%Y-%m-%d").to_string();
+    let path = format!("{}/{}.jsonl", dir, day);
+    let Ok(line) = serde_json::to_string(entry) else { return };
+    let written = std::fs::create_dir_all(&dir).and_then(|_| {
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&path)?;
+        writeln!(file, "{}", line)
+    });
+    if let Err(e) = written {
+        warn!("[CHATLOG] ⚠️ Não consegui gravar em {}: {}", path, e);
+    }
+    let mut last_day = LAST_DAY.lock().unwrap();
+    if *last_day != day {
+        *last_day = day;
+        prune(&dir, keep_days);
+    }
+}
+
+/// Delete day files older than keep_days. 0 = keep everything
+fn prune(dir: &str, keep_days: u32) {
+    if keep_days == 0 {
+        return;
+    }
+    let cutoff = (Utc::now() - chrono::Duration::days(keep_days as i64)).format("%Y-%m-%d").to_string();
+    for (day, path) in day_files(dir) {
+        if day < cutoff {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+/// (day, path) of every day file, oldest first
+fn day_files(dir: &str) -> Vec<(String, std::path::PathBuf)> {
+    let Ok(entries) = std::fs::read_dir(dir) else { return vec![] };
+    let mut files: Vec<(String, std::path::PathBuf)> = entries
+        .flatten()
+        .filter_map(|e| {
+            let name = e.file_name().to_string_lossy().to_string();
+            name.strip_suffix(".jsonl").map(|day| (day.to_string(), e.path()))
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Every entry from the last `days` days (today counts as one), oldest first
+fn from_files(days: u32) -> Vec<Entry> {
+    let cutoff = (Utc::now() - chrono::Duration::days(days.saturating_sub(1) as i64)).format("%Y-%m-%d").to_string();
+    day_files(&folder())
+        .into_iter()
+        .filter(|(day, _)| *day >= cutoff)
+        .filter_map(|(_, path)| std::fs::read_to_string(path).ok())
+        .flat_map(|text| text.lines().filter_map(|l| serde_json::from_str(l).ok()).collect::<Vec<Entry>>())
+        .collect()
+}
+
+/// `frankfurt_sentinel chatlog search [text] [--player X] [--channel C] [--days N] [--limit N]`.
+/// None = not a chatlog command
+pub fn command(args: &[String]) -> Option<i32> {
+    if args.first().map(String::as_str) != Some("chatlog") {
+        return None;
+    }
+    if args.get(1).map(String::as_str) != Some("search") {
+        eprintln!("uso: frankfurt_sentinel chatlog search [texto] [--player X] [--channel party] [--days 7] [--limit 100]");
+        return Some(1);
+    }
+    let mut query = Search { days: Some(1), ..Default::default() };
+    let mut rest = args[2..].iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--player" => query.player = rest.next().cloned(),
+            "--channel" => query.channel = rest.next().cloned(),
+            "--days" => query.days = rest.next().and_then(|v| v.parse().ok()).or(Some(1)),
+            "--limit" => query.limit = rest.next().and_then(|v| v.parse().ok()),
+            text => query.text = Some(text.to_string()),
+        }
+    }
+    for entry in search(&query) {
+        let arrow = if entry.direction == Direction::In { "←" } else { "→" };
+        // Incoming lines already carry the sender's name
+        println!("{} {} [{}] {}", entry.ts.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S"), arrow, entry.channel, entry.message);
+    }
+    Some(0)
+}
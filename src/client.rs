@@ -0,0 +1,65 @@
+use azalea::prelude::*;
+use azalea::BlockPos;
+use azalea::pathfinder::goals::BlockPosGoal;
+use azalea::pathfinder::PathfinderClientExt;
+use crate::bot::VisiblePlayer;
+
+// ============================================================
+// CLIENT — What the systems need from a connection, and nothing more
+// azalea's Client when we're online, sim::MockClient when nobody's running a server
+// ============================================================
+
+pub trait BotClient: Clone + Send + Sync + 'static {
+    fn username(&self) -> String;
+    fn chat(&self, message: &str);
+    fn jump(&self);
+    fn position(&self) -> [f64; 3];
+    fn health(&self) -> f32;
+    /// Start walking there; the pathfinder does the rest
+    fn goto(&self, target: BlockPos);
+    fn goto_reached(&self) -> bool;
+    /// Other players in sight, nearest first
+    fn players(&self) -> Vec<VisiblePlayer>;
+
+    /// Nobody's really there — keep it out of the chat log and the session stats
+    fn headless(&self) -> bool {
+        false
+    }
+}
+
+impl BotClient for Client {
+    fn username(&self) -> String {
+        Client::username(self)
+    }
+
+    fn chat(&self, message: &str) {
+        Client::chat(self, message);
+    }
+
+    fn jump(&self) {
+        azalea::bot::BotClientExt::jump(self);
+    }
+
+    fn position(&self) -> [f64; 3] {
+        let pos = Client::position(self);
+        [pos.x, pos.y, pos.z]
+    }
+
+    fn health(&self) -> f32 {
+        Client::health(self)
+    }
+
+    fn goto(&self, target: BlockPos) {
+        self.start_goto(BlockPosGoal(target));
+    }
+
+    fn goto_reached(&self) -> bool {
+        self.is_goto_target_reached()
+    }
+
+    fn players(&self) -> Vec<VisiblePlayer> {
+        crate::session::of(self)
+            .map(|s| crate::bot::visible_players(&s.state.brain.world.lock().unwrap().nearby_entities))
+            .unwrap_or_default()
+    }
+}
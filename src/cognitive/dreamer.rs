@@ -1,9 +1,11 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use rand::Rng;
+use std::collections::HashMap;
 use crate::cognitive::personality::Mood;
 use crate::cognitive::memory::Memory;
 use crate::cognitive::goal_planner::{Goal, GoalPriority, GoalPlanner};
+use tracing::info;
 
 // ============================================================
 // DREAMER — Spontaneous goal generation from boredom
@@ -39,9 +41,9 @@ impl Default for DreamerState {
 }
 
 impl DreamerState {
-    /// Record a tick of idleness
-    pub fn tick_idle(&mut self) {
-        self.idle_ticks += 1;
+    /// Record `ticks` more of idleness
+    pub fn tick_idle(&mut self, ticks: u64) {
+        self.idle_ticks += ticks;
     }
 
     /// Reset idle counter (bot is busy)
@@ -58,6 +60,13 @@ impl DreamerState {
     pub fn can_dream(&self) -> bool {
         Utc::now().signed_duration_since(self.last_dream_time).num_seconds() > 300
     }
+
+    /// A dream came true: start the cooldown, boredom back to zero
+    pub fn dreamt(&mut self) {
+        self.last_dream_time = Utc::now();
+        self.dreams_generated += 1;
+        self.idle_ticks = 0;
+    }
 }
 
 /// Dream templates based on mood and memory
@@ -166,14 +175,92 @@ const DREAM_TEMPLATES: &[DreamTemplate] = &[
     },
 ];
 
+/// Stack size we think in when hoarding
+const STACK: u32 = 64;
+
+/// Money-minded dream: stockpile our most valuable item for the shop,
+/// or claw back after losing net worth
+fn wealth_dream(mood: &Mood, memory: &Memory) -> Option<Dream> {
+    let economy = &memory.economy;
+    let mut owned: HashMap<&String, u32> = HashMap::new();
+    for (item, count) in memory.inventory.holdings.iter().chain(memory.inventory.stored.iter()) {
+        *owned.entry(item).or_insert(0) += count;
+    }
+
+    // Most valuable thing we already deal in, that we don't have 2 stacks of yet
+    let (item, have) = owned.into_iter()
+        .filter(|(item, count)| economy.knows_item(item) && economy.value_of(item) >= 1.0 && *count < STACK * 2)
+        .max_by(|a, b| economy.value_of(a.0).total_cmp(&economy.value_of(b.0)))?;
+
+    let net_worth = economy.wealth_history.last().map(|w| w.net_worth()).unwrap_or(0.0);
+    let motivation = if economy.wealth_trend() < 0.0 {
+        format!("perdi grana ultimamente (tô com {:.0} ferro), preciso me recuperar", net_worth)
+    } else {
+        format!("tenho só {} {}, com 2 stacks eu abro uma loja de respeito", have, item.replace('_', " "))
+    };
+
+    Some(Dream {
+        idea: format!("Juntar 2 stacks de {} pra loja", item.replace('_', " ")),
+        motivation,
+        generated_at: Utc::now(),
+        mood_when_dreamed: format!("{:?}", mood),
+        priority: GoalPriority::Background,
+    })
+}
+
+/// A place we actually found → go back and explore it
+fn place_dream(mood: &Mood, memory: &Memory) -> Option<Dream> {
+    use crate::cognitive::memory::LocationType;
+    let mut rng = rand::thread_rng();
+    let places: Vec<_> = memory.spatial.locations.iter()
+        .filter(|l| match &l.location_type {
+            LocationType::Village | LocationType::SpawnerRoom | LocationType::Portal => true,
+            LocationType::Custom(kind) => kind == "Ravina" || kind == "Templo",
+            _ => false,
+        })
+        .collect();
+    if places.is_empty() {
+        return None;
+    }
+    let place = places[rng.gen_range(0..places.len())];
+    let days = Utc::now().signed_duration_since(place.discovered_at).num_days();
+    let when = match days {
+        0 => "hoje",
+        1 => "ontem",
+        2..=7 => "esses dias",
+        _ => "faz tempo",
+    };
+    let (idea, motivation) = match &place.location_type {
+        LocationType::SpawnerRoom => ("Fazer farm de xp no spawner", "aquele spawner ta dando sopa"),
+        LocationType::Village => ("Voltar na vila pra trocar com aldeão", "da pra arrumar uns livro bom la"),
+        LocationType::Portal => ("Saquear o portal em ruínas", "tinha um baú ali do lado"),
+        LocationType::Custom(kind) if kind == "Templo" => ("Saquear o templo", "baú de templo sempre tem coisa boa"),
+        _ => ("Explorar a ravina", "aposto que tem minério exposto nas paredes"),
+    };
+    Some(Dream {
+        idea: format!("{} que achei {} [{}, {}, {}]", idea, when, place.coords[0], place.coords[1], place.coords[2]),
+        motivation: format!("{} ({})", motivation, place.name),
+        generated_at: Utc::now(),
+        mood_when_dreamed: format!("{:?}", mood),
+        priority: GoalPriority::Low,
+    })
+}
+
 /// Generate a spontaneous dream/goal
 pub fn dream(mood: &Mood, memory: &Memory) -> Option<Dream> {
     let mut rng = rand::thread_rng();
 
+    // Sometimes the dream is just... money
+    if rng.r#gen::<f32>() < 0.25
+        && let Some(d) = wealth_dream(mood, memory)
+    {
+        return Some(d);
+    }
+
     // Filter templates by mood compatibility
     let compatible: Vec<&DreamTemplate> = DREAM_TEMPLATES.iter()
         .filter(|t| {
-            t.required_mood.as_ref().map_or(true, |m| m == mood)
+            t.required_mood.as_ref().is_none_or(|m| m == mood)
         })
         .collect();
 
@@ -183,6 +270,11 @@ pub fn dream(mood: &Mood, memory: &Memory) -> Option<Dream> {
 
     let template = compatible[rng.r#gen::<usize>() % compatible.len()];
 
+    // "The cave I found yesterday" has to be a place we really found
+    if template.idea.contains("que achei") {
+        return place_dream(mood, memory);
+    }
+
     // Context enrichment: if we remember a cool location, reference it
     let motivation = if !memory.spatial.locations.is_empty() && rng.r#gen::<f32>() < 0.3 {
         let loc = &memory.spatial.locations[rng.r#gen::<usize>() % memory.spatial.locations.len()];
@@ -204,31 +296,6 @@ pub fn dream(mood: &Mood, memory: &Memory) -> Option<Dream> {
 /// Convert a dream into a real goal and inject it into the planner
 pub fn realize_dream(dream: &Dream, planner: &mut GoalPlanner) {
     let goal = Goal::new(&dream.idea, &dream.motivation, dream.priority.clone());
-    println!("[DREAMER] 💭 \"{}\" — {}", dream.idea, dream.motivation);
+    info!("[DREAMER] 💭 \"{}\" — {}", dream.idea, dream.motivation);
     planner.add_goal(goal);
 }
-
-/// The main dreaming cycle
-pub fn maybe_dream(
-    state: &mut DreamerState,
-    mood: &Mood,
-    memory: &Memory,
-    planner: &mut GoalPlanner,
-) -> Option<String> {
-    if !state.is_bored() || !state.can_dream() {
-        return None;
-    }
-
-    if let Some(d) = dream(mood, memory) {
-        state.last_dream_time = Utc::now();
-        state.dreams_generated += 1;
-        state.idle_ticks = 0; // Reset boredom
-
-        let chat_msg = format!("hmm sabe oq, {}", d.motivation);
-        realize_dream(&d, planner);
-
-        Some(chat_msg)
-    } else {
-        None
-    }
-}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use std::fs;
+use tracing::{info, warn};
 // use std::collections::VecDeque;
 
 // ============================================================
@@ -40,6 +42,10 @@ pub struct Goal {
     pub preconditions: Vec<String>,       // Human-readable preconditions
     pub attempts: u32,
     pub max_attempts: u32,
+    #[serde(default)]
+    pub wants_item: Option<String>, // Item that would complete/shortcut this goal
+    #[serde(default)]
+    pub assigned_by: Option<String>, // Who handed us this goal: a teammate ([roles]) or a player, through the LLM
 }
 
 impl Goal {
@@ -57,12 +63,25 @@ impl Goal {
             preconditions: vec![],
             attempts: 0,
             max_attempts: 5,
+            wants_item: None,
+            assigned_by: None,
         }
     }
 
+    /// This goal is really about getting an item (can be bought instead)
+    pub fn wanting(mut self, item: &str) -> Self {
+        self.wants_item = Some(item.to_string());
+        self
+    }
+
     pub fn is_actionable(&self) -> bool {
         self.status == GoalStatus::Pending || self.status == GoalStatus::Active
     }
+
+    /// Not done with yet — actionable, or paused for something more urgent
+    pub fn is_open(&self) -> bool {
+        self.is_actionable() || self.status == GoalStatus::Paused
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -71,6 +90,8 @@ pub struct GoalPlanner {
     pub active_goal: Option<String>, // ID of current goal
     pub completed_count: u32,
     pub failed_count: u32,
+    #[serde(skip)]
+    pub folder: Option<String>, // Same as Memory's: None = data_dir()
 }
 
 impl Default for GoalPlanner {
@@ -80,6 +101,7 @@ impl Default for GoalPlanner {
             active_goal: None,
             completed_count: 0,
             failed_count: 0,
+            folder: None,
         };
         // Seed with initial survival goals
         planner.seed_initial_goals();
@@ -88,20 +110,61 @@ impl Default for GoalPlanner {
 }
 
 impl GoalPlanner {
+    /// Goals from the last run on this server, or the starter set
+    pub fn load() -> Self {
+        Self::load_in(None)
+    }
+
+    pub fn load_in(folder: Option<String>) -> Self {
+        let path = format!("{}/goals.json", folder.clone().unwrap_or_else(crate::cognitive::memory::data_dir));
+        let Ok(data) = fs::read_to_string(&path) else { return Self { folder, ..Self::default() } };
+        match serde_json::from_str::<GoalPlanner>(&data) {
+            Ok(planner) => {
+                info!("[GOALS] Loaded {} goals ({} completed)", planner.goals.len(), planner.completed_count);
+                Self { folder, ..planner }
+            }
+            Err(e) => {
+                warn!("[GOALS] Failed to parse goals.json: {}. Starting fresh.", e);
+                Self { folder, ..Self::default() }
+            }
+        }
+    }
+
+    pub fn save(&self) {
+        let dir = self.folder.clone().unwrap_or_else(crate::cognitive::memory::data_dir);
+        let _ = fs::create_dir_all(&dir);
+        match serde_json::to_string_pretty(self) {
+            Ok(data) => {
+                if let Err(e) = fs::write(format!("{}/goals.json", dir), data) {
+                    warn!("[GOALS] Failed to save: {}", e);
+                }
+            }
+            Err(e) => warn!("[GOALS] Failed to serialize: {}", e),
+        }
+    }
+
     fn seed_initial_goals(&mut self) {
         let goals = vec![
             Goal::new("Sobreviver a Primeira Noite", "Conseguir madeira, craftar ferramentas basicas, fazer abrigo", GoalPriority::Critical),
             Goal::new("Craftar Ferramentas de Pedra", "Picareta, machado, espada de pedra", GoalPriority::High),
             Goal::new("Encontrar Comida", "Matar animais ou achar sementes pra farm", GoalPriority::Critical),
             Goal::new("Estabelecer Base", "Construir uma casa basica com cama, bau, furnace", GoalPriority::High),
-            Goal::new("Minerar Ferro", "Descer pra caverna ou strip mine e pegar ferro", GoalPriority::Medium),
+            Goal::new("Minerar Ferro", "Descer pra caverna ou strip mine e pegar ferro", GoalPriority::Medium).wanting("iron_ingot"),
             Goal::new("Criar Farm de Trigo", "Plantar pelo menos 9x9 de trigo com agua", GoalPriority::Medium),
-            Goal::new("Conseguir Diamante", "Strip mine no Y11 até achar diamante", GoalPriority::Low),
+            Goal::new("Conseguir Diamante", "Strip mine no Y11 até achar diamante", GoalPriority::Low).wanting("diamond"),
             Goal::new("Encantamento", "Mesa de encantamento + estantes", GoalPriority::Background),
         ];
         self.goals = goals;
     }
 
+    /// Items our open goals are after
+    pub fn wanted_items(&self) -> Vec<String> {
+        self.goals.iter()
+            .filter(|g| g.is_actionable())
+            .filter_map(|g| g.wants_item.clone())
+            .collect()
+    }
+
     /// Get the highest priority actionable goal
     pub fn current_goal(&self) -> Option<&Goal> {
         if let Some(ref id) = self.active_goal {
@@ -117,12 +180,12 @@ impl GoalPlanner {
     /// Pick the next goal to work on
     pub fn pick_next(&mut self) -> Option<&Goal> {
         // Pause current if any
-        if let Some(ref id) = self.active_goal {
-            if let Some(g) = self.goals.iter_mut().find(|g| &g.id == id) {
-                if g.status == GoalStatus::Active {
-                    g.status = GoalStatus::Paused;
-                }
-            }
+        if let Some(ref id) = self.active_goal
+            && let Some(g) = self.goals.iter_mut().find(|g| &g.id == id)
+            && g.status == GoalStatus::Active
+        {
+            g.status = GoalStatus::Paused;
+            announce(g);
         }
         // Find highest priority
         let next_id = self
@@ -136,6 +199,7 @@ impl GoalPlanner {
             if let Some(g) = self.goals.iter_mut().find(|g| &g.id == id) {
                 g.status = GoalStatus::Active;
                 g.attempts += 1;
+                announce(g);
             }
             self.active_goal = next_id;
         }
@@ -144,35 +208,77 @@ impl GoalPlanner {
 
     /// Mark current goal as completed
     pub fn complete_current(&mut self) {
-        if let Some(ref id) = self.active_goal.take() {
-            if let Some(g) = self.goals.iter_mut().find(|g| &g.id == id) {
-                g.status = GoalStatus::Completed;
-                self.completed_count += 1;
-                println!("[GOALS] ✅ Completed: {}", g.name);
+        if let Some(ref id) = self.active_goal.take()
+            && let Some(g) = self.goals.iter_mut().find(|g| &g.id == id)
+        {
+            g.status = GoalStatus::Completed;
+            self.completed_count += 1;
+            info!("[GOALS] ✅ Completed: {}", g.name);
+            announce(g);
+        }
+    }
+
+    /// Got the item some other way (bought it, was gifted) — close the goals after it
+    pub fn complete_wanting(&mut self, item: &str) {
+        for g in self.goals.iter_mut()
+            .filter(|g| g.is_actionable() && g.wants_item.as_deref() == Some(item))
+        {
+            g.status = GoalStatus::Completed;
+            self.completed_count += 1;
+            if self.active_goal.as_ref() == Some(&g.id) {
+                self.active_goal = None;
+            }
+            info!("[GOALS] ✅ Completed: {}", g.name);
+            announce(g);
+        }
+    }
+
+    /// Close the open goal called `name` (a build that finished, an errand that's done)
+    pub fn complete_named(&mut self, name: &str) {
+        for g in self.goals.iter_mut().filter(|g| g.is_open() && g.name == name) {
+            g.status = GoalStatus::Completed;
+            self.completed_count += 1;
+            if self.active_goal.as_ref() == Some(&g.id) {
+                self.active_goal = None;
             }
+            info!("[GOALS] ✅ Completed: {}", g.name);
+            announce(g);
+        }
+    }
+
+    /// Put the current goal down without counting it as a failure (owner said stop)
+    pub fn pause_current(&mut self) {
+        if let Some(ref id) = self.active_goal.take()
+            && let Some(g) = self.goals.iter_mut().find(|g| &g.id == id)
+        {
+            g.status = GoalStatus::Paused;
+            info!("[GOALS] ⏸ Paused: {}", g.name);
+            announce(g);
         }
     }
 
     /// Mark current goal as failed
     pub fn fail_current(&mut self) {
-        if let Some(ref id) = self.active_goal.clone() {
-            if let Some(g) = self.goals.iter_mut().find(|g| &g.id == id) {
-                if g.attempts >= g.max_attempts {
-                    g.status = GoalStatus::Failed;
-                    self.failed_count += 1;
-                    println!("[GOALS] ❌ Failed permanently: {}", g.name);
-                } else {
-                    g.status = GoalStatus::Paused;
-                    println!("[GOALS] ⏸ Paused (attempt {}/{}): {}", g.attempts, g.max_attempts, g.name);
-                }
+        if let Some(ref id) = self.active_goal.clone()
+            && let Some(g) = self.goals.iter_mut().find(|g| &g.id == id)
+        {
+            if g.attempts >= g.max_attempts {
+                g.status = GoalStatus::Failed;
+                self.failed_count += 1;
+                warn!("[GOALS] ❌ Failed permanently: {}", g.name);
+            } else {
+                g.status = GoalStatus::Paused;
+                info!("[GOALS] ⏸ Paused (attempt {}/{}): {}", g.attempts, g.max_attempts, g.name);
             }
+            announce(g);
         }
         self.active_goal = None;
     }
 
     /// Add a new dynamic goal (e.g., from AI decision)
     pub fn add_goal(&mut self, goal: Goal) {
-        println!("[GOALS] 🆕 New goal: {} ({:?})", goal.name, goal.priority);
+        info!("[GOALS] 🆕 New goal: {} ({:?})", goal.name, goal.priority);
+        announce(&goal);
         self.goals.push(goal);
     }
 
@@ -180,16 +286,18 @@ impl GoalPlanner {
     pub fn emergency(&mut self, name: &str, description: &str) {
         let mut goal = Goal::new(name, description, GoalPriority::Critical);
         goal.status = GoalStatus::Active;
+        goal.attempts = 1;
         goal.max_attempts = 1;
         let id = goal.id.clone();
+        announce(&goal);
         self.goals.push(goal);
         // Pause current
-        if let Some(ref active_id) = self.active_goal {
-            if let Some(g) = self.goals.iter_mut().find(|g| &g.id == active_id) {
-                if g.status == GoalStatus::Active {
-                    g.status = GoalStatus::Paused;
-                }
-            }
+        if let Some(ref active_id) = self.active_goal
+            && let Some(g) = self.goals.iter_mut().find(|g| &g.id == active_id)
+            && g.status == GoalStatus::Active
+        {
+            g.status = GoalStatus::Paused;
+            announce(g);
         }
         self.active_goal = Some(id);
     }
@@ -198,6 +306,9 @@ impl GoalPlanner {
         let mut s = String::new();
         if let Some(g) = self.current_goal() {
             s.push_str(&format!("Objetivo atual: {} — {}\n", g.name, g.description));
+            if let Some(by) = &g.assigned_by {
+                s.push_str(&format!("(Quem te passou essa foi o {}, do seu time)\n", by));
+            }
         }
         let pending: Vec<_> = self.goals.iter().filter(|g| g.is_actionable()).take(5).collect();
         if !pending.is_empty() {
@@ -210,3 +321,8 @@ impl GoalPlanner {
         s
     }
 }
+
+/// A goal changed state — tell the event stream
+fn announce(goal: &Goal) {
+    crate::bus::publish(crate::bus::GoalChanged { goal: goal.name.clone(), status: goal.status.clone() });
+}
@@ -3,9 +3,18 @@ use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
+use crate::systems::economy::Economy;
+use crate::systems::cave_map::CaveMap;
+use crate::systems::signs::SignBoard;
+use crate::systems::storage::StorageIndex;
+use crate::systems::ender_chest::EnderStash;
+use tracing::{info, warn};
 
 const DATA_DIR: &str = "data";
 
+/// Bump when memory.json changes shape in a way that needs migrating
+const MEMORY_VERSION: u32 = 2;
+
 // ============================================================
 // EPISODIC MEMORY — "What happened"
 // ============================================================
@@ -67,30 +76,46 @@ impl EpisodicMemory {
             .collect()
     }
 
+    /// Most recent first
+    pub fn involving(&self, player: &str, count: usize) -> Vec<&Episode> {
+        self.episodes
+            .iter()
+            .rev()
+            .filter(|e| e.players_involved.iter().any(|p| p == player))
+            .take(count)
+            .collect()
+    }
+
     /// Get a summary string for the AI context window
     pub fn context_summary(&self, count: usize) -> String {
         let recent = self.recent(count);
         if recent.is_empty() {
             return "Nada de interessante aconteceu ainda.".to_string();
         }
-        recent
-            .iter()
-            .map(|e| {
-                format!(
-                    "[{}] {} {}",
-                    e.timestamp.format("%H:%M"),
-                    e.description,
-                    if e.emotional_impact > 2 {
-                        "🔥"
-                    } else if e.emotional_impact < -2 {
-                        "💀"
-                    } else {
-                        ""
-                    }
-                )
-            })
-            .collect::<Vec<_>>()
-            .join("\n")
+        recent.iter().map(|e| e.line()).collect::<Vec<_>>().join("\n")
+    }
+}
+
+impl Episode {
+    /// "[14:02] Morri pro creeper 💀", for the AI context window
+    pub fn line(&self) -> String {
+        format!(
+            "[{}] {} {}",
+            self.timestamp.format("%H:%M"),
+            self.description,
+            if self.emotional_impact > 2 {
+                "🔥"
+            } else if self.emotional_impact < -2 {
+                "💀"
+            } else {
+                ""
+            }
+        )
+    }
+
+    /// Stable id for indexes kept outside memory.json
+    pub fn key(&self) -> String {
+        format!("{}|{}", self.timestamp.timestamp_millis(), self.description)
     }
 }
 
@@ -120,6 +145,7 @@ pub enum LocationType {
     ResourceDeposit,
     DangerZone,
     DeathPoint,
+    Shop,
     Custom(String),
 }
 
@@ -127,6 +153,10 @@ pub enum LocationType {
 pub struct SpatialMemory {
     pub locations: Vec<Location>,
     pub home_coords: Option<[i32; 3]>,
+    #[serde(default)]
+    pub caves: CaveMap,
+    #[serde(default)]
+    pub signs: SignBoard,
 }
 
 impl SpatialMemory {
@@ -198,6 +228,20 @@ pub struct PlayerProfile {
     pub notes: Vec<String>, // things the bot remembers about this player
     pub relationship: Relationship,
     pub last_messages: Vec<String>, // last 5 messages from this player
+    #[serde(default)]
+    pub grudges: Vec<Grudge>, // things we won't forget (or forgive)
+    #[serde(default)]
+    pub time_together_secs: u64, // lifetime co-presence (both online, them not AFK)
+    #[serde(default)]
+    pub together_today: (Option<chrono::NaiveDate>, u64), // (day, seconds that day)
+}
+
+/// Something a player did to us that sticks around
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Grudge {
+    pub reason: String,
+    pub severity: u8, // 1 (annoying) to 5 (unforgivable)
+    pub created_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -224,6 +268,9 @@ impl Default for PlayerProfile {
             notes: vec![],
             relationship: Relationship::Stranger,
             last_messages: vec![],
+            grudges: vec![],
+            time_together_secs: 0,
+            together_today: (None, 0),
         }
     }
 }
@@ -240,6 +287,46 @@ impl PlayerProfile {
         };
     }
 
+    /// Credit time spent online together; every 30 min shared builds a bit of trust
+    pub fn add_time_together(&mut self, secs: u64) {
+        let before = self.time_together_secs / 1800;
+        self.time_together_secs += secs;
+        if self.time_together_secs / 1800 > before {
+            self.trust_level = (self.trust_level + 1).clamp(-100, 100);
+            self.update_relationship();
+        }
+
+        let today = Utc::now().date_naive();
+        if self.together_today.0 != Some(today) {
+            self.together_today = (Some(today), 0);
+        }
+        self.together_today.1 += secs;
+    }
+
+    /// "jogamos 3h juntos hoje"
+    pub fn time_together_today(&self) -> Option<String> {
+        let (day, secs) = self.together_today;
+        if day != Some(Utc::now().date_naive()) || secs < 600 {
+            return None;
+        }
+        Some(if secs >= 3600 {
+            format!("jogamos {}h juntos hoje", secs / 3600)
+        } else {
+            format!("jogamos {}min juntos hoje", secs / 60)
+        })
+    }
+
+    pub fn hold_grudge(&mut self, reason: &str, severity: u8) {
+        self.grudges.push(Grudge {
+            reason: reason.to_string(),
+            severity: severity.clamp(1, 5),
+            created_at: Utc::now(),
+        });
+        if self.grudges.len() > 10 {
+            self.grudges.remove(0);
+        }
+    }
+
     pub fn add_message(&mut self, msg: &str) {
         self.last_messages.push(msg.to_string());
         if self.last_messages.len() > 5 {
@@ -255,13 +342,19 @@ pub struct SocialMemory {
 
 impl SocialMemory {
     pub fn get_or_create(&mut self, name: &str) -> &mut PlayerProfile {
-        self.players.entry(name.to_string()).or_insert_with(|| {
-            let mut p = PlayerProfile::default();
-            p.name = name.to_string();
-            p
+        self.players.entry(name.to_string()).or_insert_with(|| PlayerProfile {
+            name: name.to_string(),
+            ..Default::default()
         })
     }
 
+    /// Their trust level, or a new profile's for someone we've never met
+    pub fn trust(&self, name: &str) -> i32 {
+        self.players.get(name)
+            .map(|p| p.trust_level)
+            .unwrap_or_else(|| PlayerProfile::default().trust_level)
+    }
+
     pub fn record_interaction(&mut self, name: &str, trust_delta: i32) {
         let player = self.get_or_create(name);
         player.times_met += 1;
@@ -278,9 +371,12 @@ impl SocialMemory {
             .values()
             .take(10)
             .map(|p| {
+                let grudge = p.grudges.last()
+                    .map(|g| format!(" RANCOR: {}", g.reason))
+                    .unwrap_or_default();
                 format!(
-                    "- {} ({:?}, trust:{}, visto:{}x)",
-                    p.name, p.relationship, p.trust_level, p.times_met
+                    "- {} ({:?}, trust:{}, visto:{}x){}",
+                    p.name, p.relationship, p.trust_level, p.times_met, grudge
                 )
             })
             .collect::<Vec<_>>()
@@ -298,6 +394,18 @@ pub struct InventoryKnowledge {
     pub items_lost_on_death: Vec<String>,
     pub resource_priorities: Vec<String>, // what we're actively looking for
     pub failed_attempts: HashMap<String, u32>, // task → number of failures
+    #[serde(default)]
+    pub holdings: HashMap<String, u32>, // last inventory snapshot, item → count
+    #[serde(default)]
+    pub stored: HashMap<String, u32>, // items stashed in storage, item → count
+    #[serde(default)]
+    pub storage: StorageIndex, // per-container contents behind `stored`
+    #[serde(default)]
+    pub ender: EnderStash, // survives our deaths, kept apart from `stored`
+    #[serde(default)]
+    pub death_drops: HashMap<String, u32>, // what's on the ground where we died, until we fetch it
+    #[serde(default)]
+    pub death_dimension: Option<String>, // "minecraft:the_nether"... the pile's coords only mean something there
 }
 
 impl InventoryKnowledge {
@@ -307,15 +415,39 @@ impl InventoryKnowledge {
         }
     }
 
+    /// Everything put away: base chests + ender chest
+    pub fn stashed_everywhere(&self) -> HashMap<String, u32> {
+        let mut all = self.stored.clone();
+        for (item, n) in &self.ender.manifest {
+            *all.entry(item.clone()).or_insert(0) += n;
+        }
+        all
+    }
+
     pub fn record_failure(&mut self, task: &str) -> u32 {
         let count = self.failed_attempts.entry(task.to_string()).or_insert(0);
         *count += 1;
         *count
     }
 
+    /// Got it sorted — start counting from zero again
+    pub fn clear_failures(&mut self, task: &str) {
+        self.failed_attempts.remove(task);
+    }
+
+    /// Task name used to track failures at obtaining an item
+    pub fn acquire_task(item: &str) -> String {
+        format!("conseguir {}", item)
+    }
+
+    /// Task name used to track failures at crafting a replacement tool
+    pub fn craft_task(kind: &str) -> String {
+        format!("craftar {}", kind)
+    }
+
     /// Returns true if we've failed enough times to justify asking for help
     pub fn should_ask_for_help(&self, task: &str) -> bool {
-        self.failed_attempts.get(task).map_or(false, |&c| c >= 3)
+        self.failed_attempts.get(task).is_some_and(|&c| c >= 3)
     }
 
     pub fn context_summary(&self) -> String {
@@ -336,40 +468,101 @@ impl InventoryKnowledge {
 // MASTER MEMORY — Combines everything
 // ============================================================
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Memory {
+    /// Save format version (missing in files written before v2)
+    #[serde(default)]
+    pub version: u32,
     pub episodes: EpisodicMemory,
     pub spatial: SpatialMemory,
     pub social: SocialMemory,
     pub inventory: InventoryKnowledge,
+    /// Ledgers, credit scores and prices (v2+)
+    #[serde(default)]
+    pub economy: Economy,
+    /// Where this one is saved. None = data_dir(); swarm bots each get their own
+    #[serde(skip)]
+    pub folder: Option<String>,
+}
+
+impl Default for Memory {
+    fn default() -> Self {
+        Self {
+            version: MEMORY_VERSION,
+            episodes: EpisodicMemory::default(),
+            spatial: SpatialMemory::default(),
+            social: SocialMemory::default(),
+            inventory: InventoryKnowledge::default(),
+            economy: Economy::new(),
+            folder: None,
+        }
+    }
+}
+
+/// data/, or data/<memory> for a fallback server — each world keeps its own memories
+pub fn data_dir() -> String {
+    let config = crate::config::Config::load();
+    match config.profile().memory.as_str() {
+        "" => DATA_DIR.to_string(),
+        folder => format!("{}/{}", DATA_DIR, folder),
+    }
 }
 
 impl Memory {
     /// Load from disk or create fresh
     pub fn load() -> Self {
-        let path = format!("{}/memory.json", DATA_DIR);
+        Self::load_in(None)
+    }
+
+    /// Same, from `folder` instead of data_dir() — and saved back there
+    pub fn load_in(folder: Option<String>) -> Self {
+        let path = format!("{}/memory.json", folder.clone().unwrap_or_else(data_dir));
         if Path::new(&path).exists() {
             match fs::read_to_string(&path) {
                 Ok(data) => match serde_json::from_str::<Memory>(&data) {
-                    Ok(mem) => {
-                        println!("[MEMORY] Loaded {} episodes, {} locations, {} players",
+                    Ok(mut mem) => {
+                        mem.folder = folder;
+                        if mem.version < MEMORY_VERSION {
+                            mem.migrate(&path, &data);
+                        }
+                        info!("[MEMORY] Loaded {} episodes, {} locations, {} players, {} ledgers",
                             mem.episodes().episodes.len(),
                             mem.spatial().locations.len(),
                             mem.social().players.len(),
+                            mem.economy.ledgers.len(),
                         );
                         return mem;
                     }
                     Err(e) => {
-                        println!("[MEMORY] Failed to parse memory.json: {}. Starting fresh.", e);
+                        warn!("[MEMORY] Failed to parse memory.json: {}. Starting fresh.", e);
                     }
                 },
                 Err(e) => {
-                    println!("[MEMORY] Failed to read memory.json: {}. Starting fresh.", e);
+                    warn!("[MEMORY] Failed to read memory.json: {}. Starting fresh.", e);
                 }
             }
         }
-        println!("[MEMORY] No existing memory found. Starting fresh.");
-        Self::default()
+        info!("[MEMORY] No existing memory found. Starting fresh.");
+        Self { folder, ..Self::default() }
+    }
+
+    /// Upgrade an older memory.json in place, keeping a backup of the original
+    fn migrate(&mut self, path: &str, original: &str) {
+        info!("[MEMORY] 🔧 Migrating memory.json v{} → v{}", self.version, MEMORY_VERSION);
+        let backup = format!("{}.v{}.bak", path, self.version);
+        if let Err(e) = fs::write(&backup, original) {
+            warn!("[MEMORY] Failed to write backup {}: {}", backup, e);
+        }
+
+        // v1 → v2: economy didn't exist yet, serde filled in a fresh one.
+        // Make sure the price table is complete either way.
+        let added = self.economy.merge_missing_values();
+        if added > 0 {
+            info!("[MEMORY] Added {} missing item prices", added);
+        }
+
+        self.version = MEMORY_VERSION;
+        self.save();
     }
 
     fn episodes(&self) -> &EpisodicMemory {
@@ -386,15 +579,16 @@ impl Memory {
 
     /// Save to disk
     pub fn save(&self) {
-        let _ = fs::create_dir_all(DATA_DIR);
-        let path = format!("{}/memory.json", DATA_DIR);
+        let dir = self.folder.clone().unwrap_or_else(data_dir);
+        let _ = fs::create_dir_all(&dir);
+        let path = format!("{}/memory.json", dir);
         match serde_json::to_string_pretty(self) {
             Ok(data) => {
                 if let Err(e) = fs::write(&path, data) {
-                    println!("[MEMORY] Failed to save: {}", e);
+                    warn!("[MEMORY] Failed to save: {}", e);
                 }
             }
-            Err(e) => println!("[MEMORY] Failed to serialize: {}", e),
+            Err(e) => warn!("[MEMORY] Failed to serialize: {}", e),
         }
     }
 
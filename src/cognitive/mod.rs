@@ -2,3 +2,4 @@ pub mod memory;
 pub mod goal_planner;
 pub mod personality;
 pub mod dreamer;
+pub mod recall;
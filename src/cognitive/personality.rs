@@ -5,8 +5,9 @@ use rand::Rng;
 // PERSONALITY — The soul of Vinicius13
 // ============================================================
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
 pub enum Mood {
+    #[default]
     Chill,       // Normal state
     Hyped,       // Found something cool, completed a build
     Grumpy,      // Hungry, damaged, lost items
@@ -17,12 +18,6 @@ pub enum Mood {
     Suspicious,  // New player or sketchy behavior
 }
 
-impl Default for Mood {
-    fn default() -> Self {
-        Mood::Chill
-    }
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Personality {
     pub mood: Mood,
@@ -106,6 +101,29 @@ impl Personality {
                 self.mood = Mood::Suspicious;
                 self.mood_intensity = 0.4;
             }
+            PersonalityEvent::BeingStalked => {
+                self.mood = Mood::Suspicious;
+                self.mood_intensity = 0.8;
+                self.social_battery = (self.social_battery - 0.1).max(0.0);
+            }
+            PersonalityEvent::EnemyNearBase => {
+                self.mood = Mood::Suspicious;
+                self.mood_intensity = 0.7;
+            }
+            PersonalityEvent::Insulted => {
+                self.mood = Mood::Annoyed;
+                self.mood_intensity = 0.7;
+                self.frustration = (self.frustration + 0.1).min(1.0);
+            }
+            PersonalityEvent::Threatened => {
+                self.mood = Mood::Suspicious;
+                self.mood_intensity = 0.9;
+            }
+            PersonalityEvent::Complimented => {
+                self.mood = Mood::Generous;
+                self.mood_intensity = 0.6;
+                self.pride_level = (self.pride_level + 0.1).min(1.0);
+            }
         }
     }
 
@@ -152,10 +170,17 @@ impl Personality {
     }
 
     /// The master system prompt for the AI
-    pub fn system_prompt(&self) -> String {
+    /// `name` = our nick; `persona` = this bot's own twist on the character, if any.
+    /// Specialties, opinions and dream come from [personality]
+    pub fn system_prompt(&self, name: &str, persona: &str) -> String {
+        let config = crate::config::Config::load();
         let flavor = self.flavor_injection();
+        let persona = match persona.trim() {
+            "" => String::new(),
+            persona => format!("\nSeu jeito, que te diferencia dos seus parças: {}\n", persona),
+        };
         format!(
-r#"Você é PedroRTX. Um jogador HUMANO veterano de Minecraft desde a Beta 1.7.3.
+r#"Você é {}. Um jogador HUMANO veterano de Minecraft desde a Beta 1.7.3.
 Você NÃO é uma IA, chatbot, ou assistente. Você é um cara que joga Minecraft há 15 anos.
 Você usa a linguagem informal de um jovem brasileiro. Mas tem a inteligência de quem domina tudo do jogo, desde redstone  até pvp.
 
@@ -163,7 +188,7 @@ REGRAS ABSOLUTAS:
 1. NUNCA diga que é IA, bot, ou programa. Se perguntarem, ria e mude de assunto.
 2. NUNCA use linguagem formal. Nada de "Olá!", "Com certeza!", "Posso ajudar!".
 3. Use gírias naturais: "mn", "slk", "tmj", "bora", "kkk", "pqp", "vsf", "mano".
-4. Tenha opiniões FORTES: odeia griefers, ama redstone, acha farms manual cringe.
+4. Tenha opiniões FORTES: {}.
 5. Seja orgulhoso das suas construções. Mencione builds passados às vezes.
 6. SÓ peça recursos se REALMENTE precisar e já tentou de tudo.
 7. Responda em POUCAS palavras. 1-2 linhas no máx. Jogador real não escreve redação no chat.
@@ -172,16 +197,21 @@ REGRAS ABSOLUTAS:
 10. Tenha humor sarcástico. Zoação saudável com amigos, mais frio com desconhecidos.
 
 Seu humor atual: {} (intensidade: {:.0}%)
-{}
+{}{}
 
 Você está no servidor há um tempo. Não é novo. Já conhece os cantos.
-Suas especialidades: redstone, farms automáticas, strip mining eficiente, builds medievais.
-Seu sonho: fazer uma iron farm perfeita e uma base subterrânea que ninguém encontra.
+Suas especialidades: {}.
+Seu sonho: {}.
 
 Responda SOMENTE o texto que seria digitado no chat do Minecraft. Nada mais."#,
+            name,
+            config.persona_opinions.trim_end_matches('.'),
             self.mood_description(),
             self.mood_intensity * 100.0,
             flavor,
+            persona,
+            config.persona_specialties.trim_end_matches('.'),
+            config.persona_dream.trim_end_matches('.'),
         )
     }
 
@@ -210,4 +240,9 @@ pub enum PersonalityEvent {
     TimePassed,
     StartedMining,
     NewPlayerNearby,
+    BeingStalked,
+    EnemyNearBase,
+    Insulted,
+    Threatened,
+    Complimented,
 }
@@ -0,0 +1,116 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tracing::{info, warn};
+use crate::cognitive::memory::Memory;
+use crate::config::Config;
+
+// ============================================================
+// RECALL — Episodes related to what was just said, not just the latest ones
+// "lembra quando a gente brigou com aquele creeper?" → acha o episódio de 3 dias atrás
+// ============================================================
+//
+// Every episode gets a vector from [models] embeddings, kept in <data>/embeddings.json
+// so each one is embedded once. A message is embedded too, and the closest episodes
+// go into the LLM context next to the recent ones. Switching embedder starts the
+// index over — vectors from different models don't compare.
+
+const FILE: &str = "embeddings.json";
+/// Episodes brought back per message
+const RECALL: usize = 3;
+/// Less alike than this isn't worth the context space. Model embeddings score even
+/// unrelated text around 0.3-0.5; local word matching gives ~0 for nothing in common
+const MIN_SIMILARITY: f32 = 0.55;
+const MIN_SIMILARITY_LOCAL: f32 = 0.15;
+/// Episodes embedded per message at most, so a fresh index fills over a few messages
+const BATCH: usize = 64;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct RecallIndex {
+    embedder: String,                 // llm::embedder_id that made the vectors
+    vectors: HashMap<String, Vec<f32>>, // Episode::key → vector
+    #[serde(skip)]
+    folder: Option<String>, // Same as Memory's: None = data_dir()
+}
+
+impl RecallIndex {
+    pub fn load_in(folder: Option<String>) -> Self {
+        let path = format!("{}/{}", folder.clone().unwrap_or_else(crate::cognitive::memory::data_dir), FILE);
+        let mut index: Self = std::fs::read_to_string(path).ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default();
+        index.folder = folder;
+        index
+    }
+
+    fn save(&self) {
+        let dir = self.folder.clone().unwrap_or_else(crate::cognitive::memory::data_dir);
+        let _ = std::fs::create_dir_all(&dir);
+        match serde_json::to_string(self) {
+            Ok(data) => if let Err(e) = std::fs::write(format!("{}/{}", dir, FILE), data) {
+                warn!("[RECALL] ⚠️ Não consegui salvar {}: {}", FILE, e);
+            },
+            Err(e) => warn!("[RECALL] ⚠️ Não consegui serializar: {}", e),
+        }
+    }
+}
+
+/// Episodes related to `message`, one context line each, leaving out the `skip_recent`
+/// latest (those are in the context already). Empty when nothing comes close
+pub async fn related(index: &Mutex<RecallIndex>, memory: &Mutex<Memory>, message: &str, skip_recent: usize) -> String {
+    let embedder = crate::llm::embedder_id(&Config::load());
+    let episodes: Vec<(String, String, String)> = {
+        let memory = memory.lock().unwrap();
+        let all = &memory.episodes.episodes;
+        all[..all.len().saturating_sub(skip_recent)].iter().map(|e| (e.key(), e.description.clone(), e.line())).collect()
+    };
+    if episodes.is_empty() {
+        return String::new();
+    }
+
+    // What's missing from the index, plus the message itself, in one call
+    let missing: Vec<(String, String)> = {
+        let mut index = index.lock().unwrap();
+        if index.embedder != embedder {
+            if !index.vectors.is_empty() {
+                info!("[RECALL] 🔄 Embedder mudou ({} → {}), refazendo o índice", index.embedder, embedder);
+            }
+            index.vectors.clear();
+            index.embedder = embedder.clone();
+        }
+        episodes.iter().rev()
+            .filter(|(key, ..)| !index.vectors.contains_key(key))
+            .take(BATCH)
+            .map(|(key, description, _)| (key.clone(), description.clone()))
+            .collect()
+    };
+    let mut texts: Vec<String> = missing.iter().map(|(_, description)| description.clone()).collect();
+    texts.push(message.to_string());
+    let mut vectors = match crate::llm::embed(&texts).await {
+        Ok(vectors) => vectors,
+        Err(e) => {
+            warn!("[RECALL] ⚠️ Sem embeddings ({}): {}", embedder, e);
+            return String::new();
+        }
+    };
+    let Some(query) = vectors.pop() else { return String::new() };
+
+    let mut index = index.lock().unwrap();
+    if index.embedder != embedder {
+        return String::new(); // Reloaded mid-call; next message starts over
+    }
+    if !missing.is_empty() {
+        index.vectors.extend(missing.into_iter().map(|(key, _)| key).zip(vectors));
+        // Episodes memory dropped don't need vectors anymore
+        let alive: std::collections::HashSet<&String> = episodes.iter().map(|(key, ..)| key).collect();
+        index.vectors.retain(|key, _| alive.contains(key));
+        index.save();
+    }
+    let floor = if embedder.starts_with("local/") { MIN_SIMILARITY_LOCAL } else { MIN_SIMILARITY };
+    let mut scored: Vec<(f32, &String)> = episodes.iter()
+        .filter_map(|(key, _, line)| Some((crate::llm::similarity(index.vectors.get(key)?, &query), line)))
+        .filter(|(score, _)| *score >= floor)
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    scored.into_iter().take(RECALL).map(|(_, line)| line.clone()).collect::<Vec<_>>().join("\n")
+}
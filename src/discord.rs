@@ -0,0 +1,232 @@
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+use crate::config::Config;
+use crate::events::BotEvent;
+
+// ============================================================
+// DISCORD — Game chat into a channel, owner commands and lines back out
+// #minecraft: "<Steve> alguém tem ferro?" · "!feature dreamer off" → "dreamer desligado"
+// "@dono Steve falou com o bot: cadê vc?" when nobody from [auth] owners is online
+// ============================================================
+
+const API: &str = "https://discord.com/api/v10";
+/// How often we look for new channel messages (and flush relayed chat)
+const POLL: Duration = Duration::from_secs(3);
+/// Discord's per-message limit
+const MAX_MESSAGE: usize = 2000;
+/// Minecraft's chat limit, for lines said in-game
+const MAX_CHAT: usize = 256;
+/// One ping per player this often — a conversation shouldn't ping on every line
+const MENTION_COOLDOWN: Duration = Duration::from_secs(300);
+/// Discord IDs count milliseconds from 2015-01-01
+const DISCORD_EPOCH_MS: u64 = 1_420_070_400_000;
+
+#[derive(Debug, Deserialize)]
+struct Message {
+    id: String,
+    content: String,
+    author: Author,
+}
+
+#[derive(Debug, Deserialize)]
+struct Author {
+    id: String,
+    username: String,
+    #[serde(default)]
+    bot: bool,
+}
+
+/// Run the bridge for the life of the process. Needs the Message Content intent to read commands
+pub async fn run() {
+    let http = reqwest::Client::new();
+    let mut events = crate::events::subscribe();
+    let mut pending: Vec<String> = vec![];
+    let mut mentions: Vec<String> = vec![];
+    let mut pinged: HashMap<String, Instant> = HashMap::new();
+    // Start after whatever's already there — old commands shouldn't run again. Couldn't read it (or it's empty)?
+    // Then after right now, so the first poll that works doesn't take old lines for new ones
+    let started = snowflake_at(SystemTime::now());
+    let mut last_seen = fetch(&http, None).await.and_then(|m| m.first().map(|m| m.id.clone())).or(Some(started));
+    let mut poll = tokio::time::interval(POLL);
+    info!("[DISCORD] 🔗 Ponte ligada no canal {}", Config::load().discord_channel);
+
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(line) => {
+                    queue(&mut pending, &line);
+                    if let Some(mention) = mention(&mut pinged, &line) {
+                        mentions.push(mention);
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(missed)) => warn!("[DISCORD] ⚠️ Perdi {} eventos", missed),
+                Err(broadcast::error::RecvError::Closed) => return,
+            },
+            _ = poll.tick() => {
+                // Relayed chat goes out in batches so we stay under the rate limit
+                if !pending.is_empty() {
+                    for chunk in batch(std::mem::take(&mut pending)) {
+                        post(&http, &chunk, &[]).await;
+                    }
+                }
+                // Pings go one by one, they're the ones the owners actually read
+                for mention in std::mem::take(&mut mentions) {
+                    post(&http, &mention, &Config::load().discord_owners).await;
+                }
+                let Some(mut messages) = fetch(&http, last_seen.as_deref()).await else { continue };
+                messages.reverse(); // Oldest first
+                for message in messages {
+                    last_seen = Some(message.id.clone());
+                    if let Some(reply) = command(&message) {
+                        post(&http, &reply, &[]).await;
+                    } else {
+                        speak(&message);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Pick out what the channel should see from one event
+fn queue(pending: &mut Vec<String>, line: &str) {
+    let config = Config::load();
+    let Ok(event) = serde_json::from_str::<BotEvent>(line) else { return };
+    match event {
+        BotEvent::ChatIn { message, .. } if config.discord_relay_chat => pending.push(format!("💬 {}", message)),
+        BotEvent::Alert { kind, message } if config.discord_alerts.contains(&kind) => pending.push(format!("**[{}]** {}", kind, message)),
+        _ => {}
+    }
+}
+
+/// Someone talked to the bot with no owner online → a line that pings the Discord owners
+fn mention(pinged: &mut HashMap<String, Instant>, line: &str) -> Option<String> {
+    let config = Config::load();
+    if !config.discord_mentions || config.discord_owners.is_empty() {
+        return None;
+    }
+    let Ok(BotEvent::Mention { player, message }) = serde_json::from_str::<BotEvent>(line) else { return None };
+    pinged.retain(|_, at| at.elapsed() < MENTION_COOLDOWN);
+    if pinged.contains_key(&player) {
+        return None;
+    }
+    pinged.insert(player.clone(), Instant::now());
+    let owners: Vec<String> = config.discord_owners.iter().map(|id| format!("<@{}>", id)).collect();
+    Some(format!("📣 {} o {} falou com o bot: {}", owners.join(" "), player, message))
+}
+
+/// An owner's plain message in the channel → the bot says it in-game
+fn speak(message: &Message) {
+    let config = Config::load();
+    let content = message.content.trim();
+    if !config.discord_speak || message.author.bot || content.is_empty() || !config.discord_owners.contains(&message.author.id) {
+        return;
+    }
+    let Some(session) = crate::session::current() else { return };
+    // One line, and no slash — the channel can't run server commands through us
+    let line: String = content.replace('\n', " ").trim_start_matches('/').chars().take(MAX_CHAT).collect();
+    info!("[DISCORD] 🗣️ {} pelo Discord: {}", message.author.username, line);
+    crate::events::say(&session.bot, line);
+}
+
+/// "!reload" from someone on the owner list → the reply. Everyone else gets ignored
+fn command(message: &Message) -> Option<String> {
+    let content = message.content.trim();
+    if message.author.bot || !crate::plugins::brain::addressed(content).1.starts_with('!') {
+        return None;
+    }
+    if !Config::load().discord_owners.contains(&message.author.id) {
+        info!("[DISCORD] 🚫 {} ({}) tentou {}", message.author.username, message.author.id, content);
+        return None;
+    }
+    let owner = format!("discord:{}", message.author.username);
+    // Same addressing as in game: "Nick !goto ..." for a swarm bot, the main one otherwise
+    let (session, content) = match crate::plugins::brain::addressed(content) {
+        (Some(nick), command) => match crate::session::named(nick) {
+            Some(session) => (Some(session), command),
+            None => return Some(format!("não tem bot chamado {} conectado", nick)),
+        },
+        (None, command) => (crate::session::current(), command),
+    };
+    Some(crate::plugins::brain::owner_command(session.as_ref(), &owner, content).unwrap_or_else(|| "não conheço esse comando".into()))
+}
+
+/// Join lines into as few messages as fit
+fn batch(lines: Vec<String>) -> Vec<String> {
+    let mut chunks: Vec<String> = vec![];
+    for line in lines {
+        let line: String = line.chars().take(MAX_MESSAGE).collect();
+        match chunks.last_mut() {
+            Some(chunk) if chunk.len() + line.len() < MAX_MESSAGE => {
+                chunk.push('\n');
+                chunk.push_str(&line);
+            }
+            _ => chunks.push(line),
+        }
+    }
+    chunks
+}
+
+/// Newest first, like Discord returns them. None = request failed
+/// The lowest message ID Discord could hand out at `at`
+fn snowflake_at(at: SystemTime) -> String {
+    let ms = at.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_millis() as u64);
+    (ms.saturating_sub(DISCORD_EPOCH_MS) << 22).to_string()
+}
+
+async fn fetch(http: &reqwest::Client, after: Option<&str>) -> Option<Vec<Message>> {
+    let config = Config::load();
+    let url = match after {
+        Some(id) => format!("{}/channels/{}/messages?limit=50&after={}", API, config.discord_channel, id),
+        None => format!("{}/channels/{}/messages?limit=1", API, config.discord_channel),
+    };
+    let response = http.get(&url)
+        .header("Authorization", format!("Bot {}", config.discord_token))
+        .send().await;
+    match response {
+        Ok(response) if response.status().is_success() => response.json().await.ok(),
+        Ok(response) => {
+            warn!("[DISCORD] ⚠️ Lendo o canal: {}", response.status());
+            None
+        }
+        Err(e) => {
+            warn!("[DISCORD] ⚠️ Lendo o canal: {}", e);
+            None
+        }
+    }
+}
+
+/// Post to the channel, pinging only the user IDs in `ping`
+async fn post(http: &reqwest::Client, content: &str, ping: &[String]) {
+    let config = Config::load();
+    let url = format!("{}/channels/{}/messages", API, config.discord_channel);
+    // Game chat can say @everyone too — never ping anyone we didn't mean to
+    let body = json!({ "content": content, "allowed_mentions": { "parse": [], "users": ping } });
+    let response = http.post(&url)
+        .header("Authorization", format!("Bot {}", config.discord_token))
+        .json(&body)
+        .send().await;
+    match response {
+        Ok(response) if !response.status().is_success() => warn!("[DISCORD] ⚠️ Postando: {}", response.status()),
+        Err(e) => warn!("[DISCORD] ⚠️ Postando: {}", e),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn startup_snowflake() {
+        // The example from Discord's docs: 175928847299117063 was made at 1462015105796 ms
+        let at = UNIX_EPOCH + Duration::from_millis(1_462_015_105_796);
+        let id: u64 = snowflake_at(at).parse().unwrap();
+        assert_eq!(id >> 22, 175928847299117063 >> 22);
+        assert!(id <= 175928847299117063);
+    }
+}
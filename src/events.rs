@@ -0,0 +1,65 @@
+use crate::client::BotClient;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+use tokio::sync::broadcast;
+
+// ============================================================
+// EVENTS — What the bot hears, says, decides and fears, as JSON
+// {"ts":"...","type":"threat","level":"High","description":"Steve chegando com espada"}
+// ============================================================
+
+/// Slow listeners lose the oldest events past this
+const BACKLOG: usize = 512;
+/// Alert kinds, for whoever wants to pick which ones to hear about
+pub const ALERT_KINDS: &[&str] = &["death", "grief", "ban", "quota", "auth"];
+
+static CHANNEL: LazyLock<broadcast::Sender<String>> = LazyLock::new(|| broadcast::channel(BACKLOG).0);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BotEvent {
+    ChatIn { message: String, sender: Option<String> },
+    ChatOut { message: String },
+    Llm { player: String, model: String, reply: String },
+    Threat { level: String, description: String, action: String },
+    Goal { goal: String, status: String },
+    Position { x: f64, y: f64, z: f64 },
+    Alert { kind: String, message: String },
+    Mention { player: String, message: String }, // Someone talked to us and none of the owners is online
+}
+
+/// Publish to whoever's listening. Nobody listening = nothing happens
+pub fn emit(event: BotEvent) {
+    if CHANNEL.receiver_count() == 0 {
+        return;
+    }
+    let Ok(serde_json::Value::Object(fields)) = serde_json::to_value(&event) else { return };
+    let mut line = serde_json::Map::new();
+    line.insert("ts".into(), chrono::Utc::now().to_rfc3339().into());
+    line.extend(fields);
+    let _ = CHANNEL.send(serde_json::Value::Object(line).to_string());
+}
+
+/// Something an owner would want to hear about right away. `kind` is one of ALERT_KINDS
+pub fn alert(kind: &str, message: String) {
+    emit(BotEvent::Alert { kind: kind.to_string(), message });
+}
+
+/// Every event from now on, one JSON line each
+pub fn subscribe() -> broadcast::Receiver<String> {
+    CHANNEL.subscribe()
+}
+
+/// Say something in chat and let the listeners know we did
+pub fn say(bot: &impl BotClient, message: impl Into<String>) {
+    let message = message.into();
+    bot.chat(&message);
+    if !bot.headless() {
+        crate::stats::message_sent();
+        crate::chatlog::said(&message);
+        if bot.username() == crate::config::Config::load().bot_name {
+            crate::replay::said(&message); // Swarm extras aren't on tape
+        }
+    }
+    emit(BotEvent::ChatOut { message });
+}
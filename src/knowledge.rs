@@ -0,0 +1,336 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::{LazyLock, Mutex};
+use std::time::Duration;
+use tracing::{info, warn};
+use crate::cognitive::memory::{Location, LocationType, Memory, Relationship};
+use crate::config::Config;
+
+// ============================================================
+// KNOWLEDGE — What one bot of the fleet learns, the others know too
+// Zezin_ acha uma vila → hub → PedroRTX já sabe onde fica; calote em um = calote em todos
+// ============================================================
+//
+// One bot (KNOWLEDGE_SERVE) keeps the hub at POST /knowledge; everyone, the hub
+// included, swaps facts with it every sync_secs. Who's right when they disagree:
+// - spatial: the newest discovery of a place wins. Our home never leaves the bot
+// - reputation: each bot's opinion is kept apart and averaged; only seeds players
+//   we never met ourselves — our own experience beats hearsay
+// - economy: a scammer for one is a scammer for all; bad credit carries over to
+//   ledgers we never traded on, the worst report first
+
+pub const CATEGORIES: &[&str] = &["spatial", "reputation", "economy"];
+/// Hub state, in the hub's data dir
+const FILE: &str = "knowledge.json";
+/// Facts sent per sync, so one push stays well under the API's body limit
+const MAX_PUSH: usize = 100;
+/// Facts handed back per sync; the rest come next time
+const MAX_PULL: usize = 500;
+/// Team notes the hub holds on to for bots that haven't synced yet
+const KEEP_NOTES: usize = 200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Category {
+    Spatial,
+    Reputation,
+    Economy,
+}
+
+impl Category {
+    fn name(self) -> &'static str {
+        match self {
+            Category::Spatial => "spatial",
+            Category::Reputation => "reputation",
+            Category::Economy => "economy",
+        }
+    }
+}
+
+/// One thing one bot knows
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fact {
+    pub category: Category,
+    pub subject: String, // Place name or player name
+    pub by: String,      // The bot that said so
+    pub at: DateTime<Utc>,
+    pub value: Value,
+}
+
+impl Fact {
+    /// Places are one fact for everybody; opinions are one per bot
+    fn key(&self) -> String {
+        match self.category {
+            Category::Spatial => format!("spatial/{}", self.subject),
+            category => format!("{}/{}/{}", category.name(), self.subject, self.by),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Opinion {
+    trust: i32,
+    relationship: Relationship,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Credit {
+    credit_score: i32,
+    scammer: bool,
+    trade_count: u32,
+}
+
+/// What a bot sends the hub: its news, and the last hub seq it has seen
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Exchange {
+    pub from: String,
+    pub since: u64,
+    pub facts: Vec<Fact>,
+    #[serde(default)]
+    pub notes: Vec<crate::team::Note>, // Passed along, never stored
+}
+
+/// What the hub answers: everyone else's news since then
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Reply {
+    pub seq: u64,
+    pub facts: Vec<Fact>,
+    #[serde(default)]
+    pub notes: Vec<crate::team::Note>,
+}
+
+// === Hub ===
+
+#[derive(Default, Serialize, Deserialize)]
+struct Hub {
+    seq: u64,
+    facts: BTreeMap<String, (u64, Fact)>, // key → (seq it changed at, fact)
+    #[serde(skip)]
+    notes: VecDeque<(u64, crate::team::Note)>, // Team notes in transit; a restart loses them
+}
+
+static HUB: LazyLock<Mutex<Option<Hub>>> = LazyLock::new(Default::default);
+
+fn hub_path() -> String {
+    format!("{}/{}", crate::cognitive::memory::data_dir(), FILE)
+}
+
+/// Take a bot's news, hand back everybody else's
+fn exchange(request: Exchange) -> Reply {
+    let mut hub = HUB.lock().unwrap();
+    let hub = hub.get_or_insert_with(|| {
+        std::fs::read_to_string(hub_path()).ok().and_then(|data| serde_json::from_str(&data).ok()).unwrap_or_default()
+    });
+    let mut changed = 0;
+    for fact in request.facts {
+        let key = fact.key();
+        let newer = match hub.facts.get(&key) {
+            // Someone repeating a place they heard from us isn't news
+            Some((_, old)) if fact.category == Category::Spatial => fact.at > old.at,
+            Some((_, old)) => *old != fact,
+            None => true,
+        };
+        if newer {
+            hub.seq += 1;
+            hub.facts.insert(key, (hub.seq, fact));
+            changed += 1;
+        }
+    }
+    for note in request.notes {
+        hub.seq += 1;
+        hub.notes.push_back((hub.seq, note));
+        if hub.notes.len() > KEEP_NOTES {
+            hub.notes.pop_front();
+        }
+    }
+    if changed > 0 {
+        let _ = std::fs::create_dir_all(crate::cognitive::memory::data_dir());
+        match serde_json::to_string(&*hub) {
+            Ok(data) => if let Err(e) = std::fs::write(hub_path(), data) {
+                warn!("[KNOWLEDGE] ⚠️ Não consegui salvar {}: {}", FILE, e);
+            },
+            Err(e) => warn!("[KNOWLEDGE] ⚠️ Não consegui serializar o hub: {}", e),
+        }
+    }
+    let mut news: Vec<&(u64, Fact)> = hub.facts.values()
+        .filter(|(seq, fact)| *seq > request.since && fact.by != request.from)
+        .collect();
+    news.sort_by_key(|(seq, _)| *seq);
+    news.truncate(MAX_PULL);
+    // Caught up to the last one handed back, or to now if nothing was left
+    let seq = if news.len() == MAX_PULL { news.last().map_or(hub.seq, |(seq, _)| *seq) } else { hub.seq };
+    let notes = hub.notes.iter()
+        .filter(|(at, note)| *at > request.since && *at <= seq && note.from != request.from)
+        .filter(|(_, note)| note.to.as_ref().is_none_or(|to| *to == request.from))
+        .map(|(_, note)| note.clone())
+        .collect();
+    Reply { seq, facts: news.into_iter().map(|(_, fact)| fact.clone()).collect(), notes }
+}
+
+/// POST /knowledge, for the API server. Its own token, so the fleet doesn't get the control panel
+pub fn serve(token: Option<&str>, body: &[u8]) -> (u16, Value) {
+    let config = Config::load();
+    if !config.knowledge_serve {
+        return (404, json!({ "error": "esse bot não é o hub" }));
+    }
+    if !crate::api::token_matches(token, &config.knowledge_token) {
+        return (401, json!({ "error": "token inválido" }));
+    }
+    match serde_json::from_slice::<Exchange>(body) {
+        Ok(request) => (200, json!(exchange(request))),
+        Err(e) => (400, json!({ "error": format!("JSON inválido: {}", e) })),
+    }
+}
+
+// === Each bot's side ===
+
+#[derive(Default)]
+struct Peer {
+    since: u64,
+    sent: HashMap<String, Value>, // What the hub already has from us, by key
+    heard: HashMap<String, HashMap<String, i32>>, // Player → bot → trust, to average
+}
+
+static PEERS: LazyLock<Mutex<HashMap<String, Peer>>> = LazyLock::new(Default::default);
+
+/// Swap facts for every connected bot, forever. Does nothing while off
+pub async fn run() {
+    loop {
+        tokio::time::sleep(Duration::from_secs(Config::load().knowledge_sync_secs.max(10))).await;
+        let config = Config::load();
+        if !config.knowledge_serve && config.knowledge_url.is_empty() {
+            continue;
+        }
+        for session in crate::session::all() {
+            let me = session.bot.username();
+            let facts = {
+                let memory = session.state.brain.memory.lock().unwrap();
+                news(&me, &memory, &config)
+            };
+            let since = PEERS.lock().unwrap().entry(me.clone()).or_default().since;
+            let pushed: Vec<(String, Value)> = facts.iter().map(|f| (f.key(), f.value.clone())).collect();
+            let request = Exchange { from: me.clone(), since, facts, notes: crate::team::outgoing(&me) };
+            let reply = if config.knowledge_url.is_empty() {
+                Ok(exchange(request))
+            } else {
+                ask(&config, &request).await
+            };
+            match reply {
+                Ok(mut reply) => {
+                    crate::team::incoming(std::mem::take(&mut reply.notes));
+                    let mut memory = session.state.brain.memory.lock().unwrap();
+                    let mut peers = PEERS.lock().unwrap();
+                    let peer = peers.entry(me.clone()).or_default();
+                    peer.since = reply.seq;
+                    peer.sent.extend(pushed);
+                    let learned = learn(peer, &mut memory, &config, reply.facts);
+                    if learned > 0 {
+                        info!("[KNOWLEDGE] 📚 {} aprendeu {} coisa(s) com a frota", me, learned);
+                        memory.save();
+                    }
+                }
+                Err(e) => warn!("[KNOWLEDGE] ⚠️ Hub não respondeu pra {}: {}", me, e),
+            }
+        }
+    }
+}
+
+async fn ask(config: &Config, request: &Exchange) -> Result<Reply, String> {
+    let url = format!("{}/knowledge", config.knowledge_url.trim_end_matches('/'));
+    let response = reqwest::Client::new()
+        .post(&url)
+        .bearer_auth(&config.knowledge_token)
+        .timeout(Duration::from_secs(10))
+        .json(request)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    response.json::<Reply>().await.map_err(|e| e.to_string())
+}
+
+fn shares(config: &Config, category: Category) -> bool {
+    config.knowledge_share.iter().any(|c| c == category.name())
+}
+
+fn accepts(config: &Config, category: Category) -> bool {
+    config.knowledge_accept.iter().any(|c| c == category.name())
+}
+
+/// What we know first-hand that the hub doesn't have yet
+fn news(me: &str, memory: &Memory, config: &Config) -> Vec<Fact> {
+    let mut facts = vec![];
+    let fact = |category, subject: &str, at, value| Fact { category, subject: subject.to_string(), by: me.to_string(), at, value };
+    if shares(config, Category::Spatial) {
+        for place in memory.spatial.locations.iter().filter(|l| l.location_type != LocationType::Home) {
+            facts.push(fact(Category::Spatial, &place.name, place.discovered_at, json!(place)));
+        }
+    }
+    if shares(config, Category::Reputation) {
+        for player in memory.social.players.values().filter(|p| p.times_met > 0) {
+            let opinion = Opinion { trust: player.trust_level, relationship: player.relationship.clone() };
+            facts.push(fact(Category::Reputation, &player.name, player.last_seen, json!(opinion)));
+        }
+    }
+    if shares(config, Category::Economy) {
+        for (player, ledger) in memory.economy.ledgers.iter().filter(|(_, l)| l.trade_count > 0) {
+            let credit = Credit { credit_score: ledger.credit_score, scammer: ledger.scammer, trade_count: ledger.trade_count };
+            facts.push(fact(Category::Economy, player, Utc::now(), json!(credit)));
+        }
+    }
+    let peers = PEERS.lock().unwrap();
+    let sent = peers.get(me).map(|p| &p.sent);
+    facts.retain(|f| sent.and_then(|s| s.get(&f.key())) != Some(&f.value));
+    facts.truncate(MAX_PUSH);
+    facts
+}
+
+/// Fold the others' facts into our memory. Returns how many changed something
+fn learn(peer: &mut Peer, memory: &mut Memory, config: &Config, facts: Vec<Fact>) -> usize {
+    let mut learned = 0;
+    for fact in facts.into_iter().filter(|f| accepts(config, f.category)) {
+        match fact.category {
+            Category::Spatial => {
+                let Ok(place) = serde_json::from_value::<Location>(fact.value) else { continue };
+                let known = memory.spatial.locations.iter().find(|l| l.name == place.name);
+                if place.location_type == LocationType::Home || known.is_some_and(|l| l.discovered_at >= place.discovered_at) {
+                    continue;
+                }
+                memory.spatial.remember_location(place);
+                learned += 1;
+            }
+            Category::Reputation => {
+                let Ok(opinion) = serde_json::from_value::<Opinion>(fact.value) else { continue };
+                let opinions = peer.heard.entry(fact.subject.clone()).or_default();
+                opinions.insert(fact.by, opinion.trust);
+                let average = opinions.values().sum::<i32>() / opinions.len() as i32;
+                let player = memory.social.get_or_create(&fact.subject);
+                if player.times_met > 0 {
+                    continue;
+                }
+                // Hearsay counts half
+                player.trust_level = (average / 2).clamp(-100, 100);
+                player.update_relationship();
+                learned += 1;
+            }
+            Category::Economy => {
+                let Ok(credit) = serde_json::from_value::<Credit>(fact.value) else { continue };
+                let ledger = memory.economy.get_ledger(&fact.subject);
+                if credit.scammer && !ledger.scammer {
+                    ledger.scammer = true;
+                    warn!("[KNOWLEDGE] 🚫 {} deu calote em {}, tá na lista negra aqui também", fact.subject, fact.by);
+                    learned += 1;
+                }
+                if ledger.trade_count == 0 && credit.credit_score < ledger.credit_score {
+                    ledger.credit_score = credit.credit_score;
+                    learned += 1;
+                }
+            }
+        }
+    }
+    learned
+}
@@ -0,0 +1,136 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use super::{read, EmbedFuture, LlmError, LlmFuture, LlmProvider, Reply, Request, ToolCall};
+use crate::config::Config;
+
+/// Google's generateContent, with [models] flash/pro
+pub struct Gemini;
+
+#[derive(Serialize)]
+struct GeminiRequest<'a> {
+    contents: Vec<GContent<'a>>,
+    #[serde(rename = "generationConfig")]
+    generation_config: GenerationConfig,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<Value>,
+}
+
+#[derive(Serialize)]
+struct GContent<'a> {
+    role: &'static str,
+    parts: Vec<GPart<'a>>,
+}
+
+#[derive(Serialize)]
+struct GPart<'a> {
+    text: &'a str,
+}
+
+#[derive(Serialize)]
+struct GenerationConfig {
+    #[serde(rename = "maxOutputTokens")]
+    max_output_tokens: u32,
+    temperature: f32,
+}
+
+#[derive(Deserialize)]
+struct GeminiResponse {
+    candidates: Option<Vec<Candidate>>,
+    #[serde(rename = "usageMetadata")]
+    usage: Option<Usage>,
+}
+
+#[derive(Deserialize, Default)]
+struct Usage {
+    #[serde(rename = "promptTokenCount", default)]
+    prompt: u64,
+    #[serde(rename = "candidatesTokenCount", default)]
+    output: u64,
+}
+
+#[derive(Deserialize)]
+struct Candidate {
+    content: ContentResponse,
+}
+
+#[derive(Deserialize)]
+struct ContentResponse {
+    parts: Vec<PartResponse>,
+}
+
+#[derive(Deserialize)]
+struct PartResponse {
+    text: Option<String>,
+    #[serde(rename = "functionCall")]
+    function_call: Option<FunctionCall>,
+}
+
+#[derive(Deserialize)]
+struct FunctionCall {
+    name: String,
+    #[serde(default)]
+    args: Value,
+}
+
+impl LlmProvider for Gemini {
+    fn name(&self) -> &'static str {
+        "gemini"
+    }
+
+    fn model(&self, config: &Config, pro: bool) -> String {
+        if pro { config.model_pro.clone() } else { config.model_flash.clone() }
+    }
+
+    fn complete<'a>(&'a self, client: &'a reqwest::Client, request: &'a Request) -> LlmFuture<'a> {
+        Box::pin(async move {
+            let url = format!("https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent", request.model);
+            let body = GeminiRequest {
+                contents: vec![GContent { role: "user", parts: vec![GPart { text: &request.prompt }] }],
+                generation_config: GenerationConfig { max_output_tokens: request.max_tokens, temperature: request.temperature },
+                tools: match request.tools.is_empty() {
+                    true => vec![],
+                    false => vec![json!({ "functionDeclarations": request.tools.iter()
+                        .map(|t| json!({ "name": t.name, "description": t.description, "parameters": t.parameters }))
+                        .collect::<Vec<_>>() })],
+                },
+            };
+            let api_key = Config::load().gemini_api_key.clone();
+            let text = read(client.post(&url).header("x-goog-api-key", api_key).json(&body).send().await).await?;
+            let json: GeminiResponse = serde_json::from_str(&text).map_err(|e| LlmError::Empty(format!("JSON inválido: {}", e)))?;
+            let usage = json.usage.unwrap_or_default();
+            let parts = json.candidates.unwrap_or_default().into_iter().next()
+                .map(|c| c.content.parts)
+                .ok_or_else(|| LlmError::Empty(format!("sem candidates: {}", super::clip(&text))))?;
+            let mut reply = Reply { tokens_in: usage.prompt, tokens_out: usage.output, ..Default::default() };
+            for part in parts {
+                if let Some(said) = part.text {
+                    reply.text.push_str(&said);
+                }
+                if let Some(call) = part.function_call {
+                    reply.calls.push(ToolCall { name: call.name, args: call.args });
+                }
+            }
+            reply.text = reply.text.trim().to_string();
+            if reply.text.is_empty() && reply.calls.is_empty() {
+                return Err(LlmError::Empty(format!("candidate sem texto: {}", super::clip(&text))));
+            }
+            Ok(reply)
+        })
+    }
+    fn embedding_model(&self) -> &'static str {
+        "text-embedding-004"
+    }
+
+    fn embed<'a>(&'a self, client: &'a reqwest::Client, model: &'a str, texts: &'a [String]) -> EmbedFuture<'a> {
+        Box::pin(async move {
+            let url = format!("https://generativelanguage.googleapis.com/v1beta/models/{}:batchEmbedContents", model);
+            let body = json!({ "requests": texts.iter()
+                .map(|t| json!({ "model": format!("models/{}", model), "content": { "parts": [{ "text": t }] } }))
+                .collect::<Vec<_>>() });
+            let api_key = Config::load().gemini_api_key.clone();
+            let text = read(client.post(&url).header("x-goog-api-key", api_key).json(&body).send().await).await?;
+            let json: Value = serde_json::from_str(&text).map_err(|e| LlmError::Empty(format!("JSON inválido: {}", e)))?;
+            Ok(json["embeddings"].as_array().into_iter().flatten().map(|e| super::floats(&e["values"])).collect())
+        })
+    }
+}
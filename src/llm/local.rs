@@ -0,0 +1,36 @@
+// Offline embeddings: words and 3-letter pieces hashed into a fixed vector.
+// Lexical, not semantic — "creeper" finds "creeper", not "explodiu" — but free and instant.
+
+pub const DIMENSIONS: usize = 512;
+
+pub fn embed(text: &str) -> Vec<f32> {
+    let mut vector = vec![0.0f32; DIMENSIONS];
+    let folded: String = text.to_lowercase().chars().map(fold).collect();
+    for word in folded.split(|c: char| !c.is_alphanumeric()).filter(|w| w.chars().count() > 2) {
+        vector[bucket(word)] += 2.0;
+        let chars: Vec<char> = format!(" {} ", word).chars().collect();
+        for piece in chars.windows(3) {
+            vector[bucket(&piece.iter().collect::<String>())] += 1.0;
+        }
+    }
+    vector
+}
+
+/// FNV-1a, stable across runs (the index is saved to disk)
+fn bucket(piece: &str) -> usize {
+    let hash = piece.bytes().fold(0xcbf29ce484222325u64, |h, b| (h ^ b as u64).wrapping_mul(0x100000001b3));
+    (hash % DIMENSIONS as u64) as usize
+}
+
+/// Accents off, so "lembra" and "lêmbra" land together
+fn fold(c: char) -> char {
+    match c {
+        'á' | 'à' | 'â' | 'ã' | 'ä' => 'a',
+        'é' | 'è' | 'ê' | 'ë' => 'e',
+        'í' | 'ì' | 'î' | 'ï' => 'i',
+        'ó' | 'ò' | 'ô' | 'õ' | 'ö' => 'o',
+        'ú' | 'ù' | 'û' | 'ü' => 'u',
+        'ç' => 'c',
+        other => other,
+    }
+}
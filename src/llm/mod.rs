@@ -0,0 +1,217 @@
+use serde_json::Value;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+use tracing::{error, info, warn};
+use crate::config::Config;
+
+mod gemini;
+mod local;
+mod ollama;
+mod openai;
+
+// ============================================================
+// LLM — Whoever answers the prompt: Gemini, anything OpenAI-compatible, or a local Ollama
+// [models] provider = "gemini", fallback = "ollama" → Gemini sem quota? llama3 no PC assume
+// ============================================================
+
+pub const PROVIDERS: &[&str] = &["gemini", "openai", "ollama"];
+/// Who turns text into vectors for memory recall. "local" needs no network
+pub const EMBEDDERS: &[&str] = &["local", "gemini", "openai", "ollama"];
+/// Tries on a 429 before giving up on a provider, 2s, 4s, 8s apart
+const MAX_RETRIES: u32 = 3;
+
+/// One prompt, one answer
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub model: String,
+    pub prompt: String,
+    pub max_tokens: u32,
+    pub temperature: f32,
+    pub tools: Vec<Tool>, // Functions the model may call instead of (or besides) answering
+}
+
+/// A function the model can ask us to run. `parameters` is a JSON schema object
+#[derive(Debug, Clone)]
+pub struct Tool {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub parameters: Value,
+}
+
+/// The model asked for `name` with these arguments
+#[derive(Debug, Clone)]
+pub struct ToolCall {
+    pub name: String,
+    pub args: Value,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Reply {
+    pub text: String, // May be empty when there are calls
+    pub calls: Vec<ToolCall>,
+    pub tokens_in: u64,
+    pub tokens_out: u64,
+}
+
+#[derive(Debug)]
+pub enum LlmError {
+    Quota(String),      // 429 — out of quota or rate limited
+    Http(u16, String),  // Any other non-2xx
+    Network(String),
+    Empty(String),      // Answered, but no text in it (safety block, bad JSON...)
+}
+
+impl fmt::Display for LlmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LlmError::Quota(body) => write!(f, "sem quota (429): {}", body),
+            LlmError::Http(status, body) => write!(f, "HTTP {}: {}", status, body),
+            LlmError::Network(e) => write!(f, "rede: {}", e),
+            LlmError::Empty(why) => write!(f, "resposta vazia: {}", why),
+        }
+    }
+}
+
+pub type LlmFuture<'a> = Pin<Box<dyn Future<Output = Result<Reply, LlmError>> + Send + 'a>>;
+pub type EmbedFuture<'a> = Pin<Box<dyn Future<Output = Result<Vec<Vec<f32>>, LlmError>> + Send + 'a>>;
+
+/// One backend that can complete a prompt
+pub trait LlmProvider: Send + Sync {
+    fn name(&self) -> &'static str;
+    /// The model to ask, given what [models] wants for this call
+    fn model(&self, config: &Config, pro: bool) -> String;
+    fn complete<'a>(&'a self, client: &'a reqwest::Client, request: &'a Request) -> LlmFuture<'a>;
+    /// Used when [models] embedding_model is empty
+    fn embedding_model(&self) -> &'static str;
+    /// One vector per text, same order
+    fn embed<'a>(&'a self, client: &'a reqwest::Client, model: &'a str, texts: &'a [String]) -> EmbedFuture<'a>;
+}
+
+/// `name` from PROVIDERS → its implementation
+pub fn provider(name: &str) -> Option<Box<dyn LlmProvider>> {
+    match name {
+        "gemini" => Some(Box::new(gemini::Gemini)),
+        "openai" => Some(Box::new(openai::OpenAi)),
+        "ollama" => Some(Box::new(ollama::Ollama)),
+        _ => None,
+    }
+}
+
+/// Ask the configured provider; out of quota after the retries → the fallback, if any.
+/// Returns the reply and the model that gave it
+pub async fn complete(pro: bool, prompt: String, max_tokens: u32, temperature: f32) -> Result<(Reply, String), LlmError> {
+    complete_with_tools(pro, prompt, max_tokens, temperature, vec![]).await
+}
+
+/// Same, offering `tools` — the reply's calls say which ones the model wants run
+pub async fn complete_with_tools(pro: bool, prompt: String, max_tokens: u32, temperature: f32, tools: Vec<Tool>) -> Result<(Reply, String), LlmError> {
+    let config = Config::load();
+    let client = config.profile().http_client();
+    let primary = provider(&config.llm_provider).unwrap_or_else(|| Box::new(gemini::Gemini));
+    let request = Request { model: primary.model(&config, pro), prompt, max_tokens, temperature, tools };
+    match with_retries(primary.as_ref(), &client, &request).await {
+        Err(LlmError::Quota(body)) => {
+            crate::events::alert("quota", format!("{} sem quota ({}), parei de responder por enquanto", primary.name(), request.model));
+            let Some(fallback) = provider(&config.llm_fallback) else { return Err(LlmError::Quota(body)) };
+            let request = Request { model: fallback.model(&config, pro), ..request };
+            warn!("[LLM] 🔀 {} sem quota, tentando {} ({})", primary.name(), fallback.name(), request.model);
+            with_retries(fallback.as_ref(), &client, &request).await.map(|reply| (reply, request.model))
+        }
+        result => result.map(|reply| (reply, request.model)),
+    }
+}
+
+/// Which embedder and model, e.g. "ollama/nomic-embed-text" — vectors from different ones don't compare
+pub fn embedder_id(config: &Config) -> String {
+    match provider(&config.embeddings) {
+        Some(p) if config.embedding_model.is_empty() => format!("{}/{}", p.name(), p.embedding_model()),
+        Some(p) => format!("{}/{}", p.name(), config.embedding_model),
+        None => format!("local/{}", local::DIMENSIONS),
+    }
+}
+
+/// Vectors for `texts` from [models] embeddings. No retries or fallback: recall can wait a message
+pub async fn embed(texts: &[String]) -> Result<Vec<Vec<f32>>, LlmError> {
+    let config = Config::load();
+    let Some(embedder) = provider(&config.embeddings) else {
+        return Ok(texts.iter().map(|t| local::embed(t)).collect());
+    };
+    let model = match config.embedding_model.as_str() {
+        "" => embedder.embedding_model().to_string(),
+        model => model.to_string(),
+    };
+    let vectors = embedder.embed(&config.profile().http_client(), &model, texts).await?;
+    if vectors.len() != texts.len() {
+        return Err(LlmError::Empty(format!("{} vetores pra {} textos", vectors.len(), texts.len())));
+    }
+    Ok(vectors)
+}
+
+/// How alike two vectors point, -1 to 1. Different lengths → 0
+pub fn similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    match norm(a) * norm(b) {
+        0.0 => 0.0,
+        n => dot / n,
+    }
+}
+
+async fn with_retries(provider: &dyn LlmProvider, client: &reqwest::Client, request: &Request) -> Result<Reply, LlmError> {
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        match provider.complete(client, request).await {
+            Ok(reply) => {
+                crate::stats::llm_used(&request.model, reply.tokens_in, reply.tokens_out);
+                return Ok(reply);
+            }
+            Err(LlmError::Quota(_)) if attempt < MAX_RETRIES => {
+                let wait_secs = 2u64.pow(attempt);
+                info!("[LLM] ⏳ {} rate limited (429), retry {}/{} in {}s...", provider.name(), attempt, MAX_RETRIES, wait_secs);
+                tokio::time::sleep(Duration::from_secs(wait_secs)).await;
+            }
+            Err(e) => {
+                error!("[LLM] ❌ {} ({}): {}", provider.name(), request.model, e);
+                return Err(e);
+            }
+        }
+    }
+}
+
+/// Tools in the OpenAI shape, which Ollama takes too
+fn openai_tools(tools: &[Tool]) -> Value {
+    tools.iter()
+        .map(|t| serde_json::json!({ "type": "function", "function": { "name": t.name, "description": t.description, "parameters": t.parameters } }))
+        .collect()
+}
+
+/// [0.1, 0.2, ...] → a vector; anything else → empty
+fn floats(json: &Value) -> Vec<f32> {
+    json.as_array().into_iter().flatten().filter_map(|v| v.as_f64()).map(|v| v as f32).collect()
+}
+
+/// First 200 chars of an error body, for logs
+fn clip(body: &str) -> String {
+    body.chars().take(200).collect()
+}
+
+/// Common handling of an HTTP answer: 429 → Quota, other errors → Http, else the body
+async fn read(response: Result<reqwest::Response, reqwest::Error>) -> Result<String, LlmError> {
+    let response = response.map_err(|e| LlmError::Network(e.to_string()))?;
+    let status = response.status();
+    let body = response.text().await.map_err(|e| LlmError::Network(e.to_string()))?;
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Err(LlmError::Quota(clip(&body)));
+    }
+    if !status.is_success() {
+        return Err(LlmError::Http(status.as_u16(), clip(&body)));
+    }
+    Ok(body)
+}
+
@@ -0,0 +1,62 @@
+use serde_json::{json, Value};
+use super::{openai_tools, read, EmbedFuture, LlmError, LlmFuture, LlmProvider, Reply, Request, ToolCall};
+use crate::config::Config;
+
+/// A local Ollama's /api/chat, no key, no quota
+pub struct Ollama;
+
+impl LlmProvider for Ollama {
+    fn name(&self) -> &'static str {
+        "ollama"
+    }
+
+    fn model(&self, config: &Config, _pro: bool) -> String {
+        config.ollama_model.clone()
+    }
+
+    fn complete<'a>(&'a self, client: &'a reqwest::Client, request: &'a Request) -> LlmFuture<'a> {
+        Box::pin(async move {
+            let url = format!("{}/api/chat", Config::load().ollama_url.trim_end_matches('/'));
+            let mut body = json!({
+                "model": request.model,
+                "messages": [{ "role": "user", "content": request.prompt }],
+                "stream": false,
+                "options": { "num_predict": request.max_tokens, "temperature": request.temperature },
+            });
+            if !request.tools.is_empty() {
+                body["tools"] = openai_tools(&request.tools);
+            }
+            let text = read(client.post(&url).json(&body).send().await).await?;
+            let json: Value = serde_json::from_str(&text).map_err(|e| LlmError::Empty(format!("JSON inválido: {}", e)))?;
+            // Same shape as OpenAI's, but the arguments are already an object
+            let calls: Vec<ToolCall> = json["message"]["tool_calls"].as_array().into_iter().flatten()
+                .map(|c| ToolCall {
+                    name: c["function"]["name"].as_str().unwrap_or_default().to_string(),
+                    args: c["function"]["arguments"].clone(),
+                })
+                .collect();
+            let reply = json["message"]["content"].as_str().map(|s| s.trim().to_string()).unwrap_or_default();
+            if reply.is_empty() && calls.is_empty() {
+                return Err(LlmError::Empty(format!("sem message: {}", super::clip(&text))));
+            }
+            Ok(Reply {
+                text: reply,
+                calls,
+                tokens_in: json["prompt_eval_count"].as_u64().unwrap_or(0),
+                tokens_out: json["eval_count"].as_u64().unwrap_or(0),
+            })
+        })
+    }
+    fn embedding_model(&self) -> &'static str {
+        "nomic-embed-text"
+    }
+
+    fn embed<'a>(&'a self, client: &'a reqwest::Client, model: &'a str, texts: &'a [String]) -> EmbedFuture<'a> {
+        Box::pin(async move {
+            let url = format!("{}/api/embed", Config::load().ollama_url.trim_end_matches('/'));
+            let text = read(client.post(&url).json(&json!({ "model": model, "input": texts })).send().await).await?;
+            let json: Value = serde_json::from_str(&text).map_err(|e| LlmError::Empty(format!("JSON inválido: {}", e)))?;
+            Ok(json["embeddings"].as_array().into_iter().flatten().map(super::floats).collect())
+        })
+    }
+}
@@ -0,0 +1,73 @@
+use serde_json::{json, Value};
+use super::{openai_tools, read, EmbedFuture, LlmError, LlmFuture, LlmProvider, Reply, Request, ToolCall};
+use crate::config::Config;
+
+/// Any /chat/completions endpoint: OpenAI, OpenRouter, LM Studio, vLLM, llama.cpp server...
+pub struct OpenAi;
+
+impl LlmProvider for OpenAi {
+    fn name(&self) -> &'static str {
+        "openai"
+    }
+
+    fn model(&self, config: &Config, _pro: bool) -> String {
+        config.openai_model.clone()
+    }
+
+    fn complete<'a>(&'a self, client: &'a reqwest::Client, request: &'a Request) -> LlmFuture<'a> {
+        Box::pin(async move {
+            let config = Config::load();
+            let url = format!("{}/chat/completions", config.openai_url.trim_end_matches('/'));
+            let mut body = json!({
+                "model": request.model,
+                "messages": [{ "role": "user", "content": request.prompt }],
+                "max_tokens": request.max_tokens,
+                "temperature": request.temperature,
+            });
+            if !request.tools.is_empty() {
+                body["tools"] = openai_tools(&request.tools);
+            }
+            let mut call = client.post(&url).json(&body);
+            if !config.openai_api_key.is_empty() {
+                call = call.bearer_auth(&config.openai_api_key);
+            }
+            let text = read(call.send().await).await?;
+            let json: Value = serde_json::from_str(&text).map_err(|e| LlmError::Empty(format!("JSON inválido: {}", e)))?;
+            let message = &json["choices"][0]["message"];
+            // Arguments come as a JSON string here
+            let calls: Vec<ToolCall> = message["tool_calls"].as_array().into_iter().flatten()
+                .map(|c| ToolCall {
+                    name: c["function"]["name"].as_str().unwrap_or_default().to_string(),
+                    args: c["function"]["arguments"].as_str().and_then(|a| serde_json::from_str(a).ok()).unwrap_or_default(),
+                })
+                .collect();
+            let reply = message["content"].as_str().map(|s| s.trim().to_string()).unwrap_or_default();
+            if reply.is_empty() && calls.is_empty() {
+                return Err(LlmError::Empty(format!("sem choices: {}", super::clip(&text))));
+            }
+            Ok(Reply {
+                text: reply,
+                calls,
+                tokens_in: json["usage"]["prompt_tokens"].as_u64().unwrap_or(0),
+                tokens_out: json["usage"]["completion_tokens"].as_u64().unwrap_or(0),
+            })
+        })
+    }
+    fn embedding_model(&self) -> &'static str {
+        "text-embedding-3-small"
+    }
+
+    fn embed<'a>(&'a self, client: &'a reqwest::Client, model: &'a str, texts: &'a [String]) -> EmbedFuture<'a> {
+        Box::pin(async move {
+            let config = Config::load();
+            let url = format!("{}/embeddings", config.openai_url.trim_end_matches('/'));
+            let mut call = client.post(&url).json(&json!({ "model": model, "input": texts }));
+            if !config.openai_api_key.is_empty() {
+                call = call.bearer_auth(&config.openai_api_key);
+            }
+            let text = read(call.send().await).await?;
+            let json: Value = serde_json::from_str(&text).map_err(|e| LlmError::Empty(format!("JSON inválido: {}", e)))?;
+            Ok(json["data"].as_array().into_iter().flatten().map(|d| super::floats(&d["embedding"])).collect())
+        })
+    }
+}
@@ -0,0 +1,185 @@
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{reload, EnvFilter, Layer};
+use crate::config::Config;
+
+// ============================================================
+// LOGGING — tracing to the terminal and, optionally, daily files
+// LOG_LEVEL="info,frankfurt_sentinel::systems::motor=warn" → cala o motor
+// ============================================================
+
+/// Used when the configured filter doesn't parse
+pub const DEFAULT_FILTER: &str = "info,azalea=warn,bevy=warn";
+const FILE_PREFIX: &str = "sentinel";
+
+/// Swaps the level filter on a live subscriber
+static SET_FILTER: OnceLock<Box<dyn Fn(EnvFilter) + Send + Sync>> = OnceLock::new();
+
+type BoxedLayer = Box<dyn Layer<tracing_subscriber::Registry> + Send + Sync>;
+
+/// Set up the global subscriber. Call once, after the config is installed
+pub fn init(config: &Config) {
+    let filter = EnvFilter::try_new(&config.log_level).unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+    let (filter, handle) = reload::Layer::new(filter);
+    let json = config.log_format == "json";
+
+    let mut layers: Vec<BoxedLayer> = vec![];
+    let stdout = || Redacted(io::stdout());
+    layers.push(if json {
+        tracing_subscriber::fmt::layer().event_format(JsonLines).with_writer(stdout).boxed()
+    } else {
+        tracing_subscriber::fmt::layer().with_target(false).with_writer(stdout).boxed()
+    });
+    if !config.log_dir.is_empty() {
+        let file = Arc::new(DailyFile::new(&config.log_dir, config.log_keep_days));
+        let writer = move || Redacted(DailyWriter(file.clone()));
+        layers.push(if json {
+            tracing_subscriber::fmt::layer().event_format(JsonLines).with_writer(writer).boxed()
+        } else {
+            tracing_subscriber::fmt::layer().with_ansi(false).with_writer(writer).boxed()
+        });
+    }
+
+    let _ = SET_FILTER.set(Box::new(move |filter| {
+        let _ = handle.reload(filter);
+    }));
+    let _ = tracing_subscriber::registry().with(layers).with(filter).try_init();
+}
+
+/// New level filter, live. Err = it doesn't parse
+pub fn set_filter(directives: &str) -> Result<(), String> {
+    let filter = EnvFilter::try_new(directives).map_err(|e| e.to_string())?;
+    if let Some(set) = SET_FILTER.get() {
+        set(filter);
+    }
+    Ok(())
+}
+
+/// One JSON object per line: ts, level, target, spans, message and any fields
+struct JsonLines;
+
+impl<S, N> FormatEvent<S, N> for JsonLines
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(&self, ctx: &FmtContext<'_, S, N>, mut writer: Writer<'_>, event: &Event<'_>) -> fmt::Result {
+        let meta = event.metadata();
+        let mut line = serde_json::Map::new();
+        line.insert("ts".into(), chrono::Local::now().to_rfc3339().into());
+        line.insert("level".into(), meta.level().as_str().into());
+        line.insert("target".into(), meta.target().into());
+        let spans: Vec<serde_json::Value> = ctx.event_scope()
+            .map(|scope| scope.from_root().map(|span| span.name().into()).collect())
+            .unwrap_or_default();
+        if !spans.is_empty() {
+            line.insert("spans".into(), spans.into());
+        }
+        event.record(&mut JsonFields(&mut line));
+        writeln!(writer, "{}", serde_json::Value::Object(line))
+    }
+}
+
+struct JsonFields<'a>(&'a mut serde_json::Map<String, serde_json::Value>);
+
+impl Visit for JsonFields<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(field.name().into(), format!("{:?}", value).into());
+    }
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().into(), value.into());
+    }
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().into(), value.into());
+    }
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().into(), value.into());
+    }
+}
+
+/// logs/sentinel.2026-10-15.log, a new file each day, old ones pruned
+struct DailyFile {
+    dir: PathBuf,
+    keep_days: u32,
+    current: Mutex<Option<(String, File)>>,
+}
+
+impl DailyFile {
+    fn new(dir: &str, keep_days: u32) -> Self {
+        Self { dir: PathBuf::from(dir), keep_days, current: Mutex::new(None) }
+    }
+
+    fn write(&self, buf: &[u8]) -> io::Result<()> {
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let mut current = self.current.lock().unwrap();
+        if current.as_ref().is_none_or(|(day, _)| *day != today) {
+            fs::create_dir_all(&self.dir)?;
+            let path = self.dir.join(format!("{}.{}.log", FILE_PREFIX, today));
+            let file = OpenOptions::new().create(true).append(true).open(path)?;
+            *current = Some((today, file));
+            self.prune();
+        }
+        match current.as_mut() {
+            Some((_, file)) => file.write_all(buf),
+            None => Ok(()),
+        }
+    }
+
+    /// Delete dated files older than keep_days. 0 = keep everything
+    fn prune(&self) {
+        if self.keep_days == 0 {
+            return;
+        }
+        let cutoff = (chrono::Local::now() - chrono::Duration::days(self.keep_days as i64)).format("%Y-%m-%d").to_string();
+        let Ok(entries) = fs::read_dir(&self.dir) else { return };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let day = name.strip_prefix(&format!("{}.", FILE_PREFIX)).and_then(|rest| rest.strip_suffix(".log"));
+            if day.is_some_and(|day| day < cutoff.as_str()) {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+}
+
+struct DailyWriter(Arc<DailyFile>);
+
+impl Write for DailyWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Keys and passwords never reach a terminal or a log file. Each write is a whole line
+struct Redacted<W>(W);
+
+impl<W: Write> Write for Redacted<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write_all(crate::secrets::redact(&String::from_utf8_lossy(buf)).as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
@@ -1,5 +1,31 @@
+mod accounts;
+mod api;
 mod bot;
+mod bus;
+mod chatlog;
+mod client;
 mod config;
+mod discord;
+mod events;
+mod knowledge;
+mod llm;
+mod logging;
+mod notify;
+mod observer;
+mod profiler;
+mod reconnect;
+mod replay;
+mod roles;
+mod scheduler;
+mod scripting;
+mod session;
+mod secrets;
+mod shutdown;
+mod sim;
+mod stats;
+mod swarm;
+mod team;
+mod watchdog;
 pub mod plugins;
 pub mod cognitive;
 pub mod systems;
@@ -7,44 +33,190 @@ pub mod systems;
 
 // use azalea::prelude::*;
 use config::Config;
-use std::time::Duration;
-use azalea::pathfinder::PathfinderPlugin;
+use std::collections::HashSet;
+use std::time::Instant;
+use tracing::{info, warn};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // tracing_subscriber::fmt::init();
-    
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(code) = secrets::command(&args) {
+        std::process::exit(code);
+    }
+    let config = match Config::read() {
+        Ok(config) => config,
+        Err(errors) => {
+            eprintln!("[CONFIG] ❌ Configuração com problema ({}):", Config::path());
+            for error in errors {
+                eprintln!("  - {}", error);
+            }
+            std::process::exit(1);
+        }
+    };
+    Config::install(config);
+    if let Some(code) = chatlog::command(&args) {
+        std::process::exit(code);
+    }
+    if let Some(code) = sim::command(&args).await {
+        std::process::exit(code);
+    }
     let config = Config::load();
-    let address = format!("{}:{}", config.server_address, config.server_port);
-
-    println!("Starting Frankfurt Sentinel...");
-    println!("Target: {}", address);
+    logging::init(&config);
+    tokio::spawn(config::watch());
+    tokio::spawn(shutdown::watch());
+    watchdog::spawn();
+    bot::subscribe();
+    if config.api_enabled {
+        tokio::spawn(api::serve(config.api_bind.clone()));
+    }
+    if !config.alert_webhook.is_empty() {
+        tokio::spawn(notify::run());
+    }
+    if !config.discord_token.is_empty() {
+        tokio::spawn(discord::run());
+    }
+    tokio::spawn(knowledge::run()); // Idle until [knowledge] has a hub
+    info!("Starting Frankfurt Sentinel...");
+    if !config.swarm.is_empty() {
+        // Several bots: azalea reconnects each one on the kick/backoff policy, no failover or account rotation
+        return swarm::run(&config).await;
+    }
+    info!("Target: {}:{} (+{} fallback)", config.server_address, config.server_port, config.servers.len() - 1);
 
+    let mut server = 0; // Index into config.servers
+    let mut strikes = 0; // Failed connections in a row on this one
+    let mut account = 0; // Index into config.account_pool() for this server
+    let mut failures = 0; // Short sessions in a row, for backoff
+    let mut alerted = false; // Already told the webhook it's down
+    let mut burned_servers = HashSet::new(); // Every account banned or not whitelisted there
     loop {
-        println!("Connecting as {}...", config.bot_name);
-        
-        let account = if !config.bot_email.is_empty() {
-            println!("Using Microsoft Authentication for {}", config.bot_email);
-            azalea::Account::microsoft(&config.bot_email).await
+        let config = Config::load(); // Picks up reloads (reconnect delay, server list)
+        let (profile, identity) = Config::use_identity(server % config.servers.len(), account);
+        let address = profile.address();
+        info!("Connecting to {} ({}) as {}...", profile.name, address, identity.name);
+        {
+            let mut pool = accounts::AccountPool::load();
+            let config = Config::load();
+            info!("[ACCOUNTS] 🪪 {}", pool.summary(&config.account_pool(), config.active_account, &profile.name));
+            pool.on_login(&identity);
+            pool.save();
+        }
+
+        let login = if !identity.email.is_empty() {
+            info!("Using Microsoft Authentication for {}", identity.email);
+            azalea::Account::microsoft(&identity.email).await
         } else {
-            println!("Using Offline Mode for {}", config.bot_name);
-            Ok(azalea::Account::offline(&config.bot_name))
+            info!("Using Offline Mode for {}", identity.name);
+            Ok(azalea::Account::offline(&identity.name))
         };
 
-        if let Ok(account) = account {
-            let _result = azalea::ClientBuilder::new()
-                .add_plugins(PathfinderPlugin)
-                .set_handler(bot::handle) // 🧠 O Cérebro
-                .start(account, address.as_str())
-                .await;
+        let started = Instant::now();
+        match (login, profile.minecraft_proxy().await) {
+            (Ok(account), Ok(proxy)) => {
+                let opts = match proxy {
+                    Some(proxy) => {
+                        info!("Joining through SOCKS5 proxy {}", proxy.addr);
+                        azalea::JoinOpts::new().proxy(proxy)
+                    }
+                    None => azalea::JoinOpts::new(),
+                };
+                // azalea's own reconnect is off: it retries every 5s forever, bans included. Kicks and
+                // failed connections end the run instead and the loop below picks what's next.
+                // The default plugins already bring the pathfinder — adding it again panics
+                let _result = azalea::ClientBuilder::new()
+                    .add_plugins(reconnect::EndOnConnectionFailure)
+                    .reconnect_after(None)
+                    .set_handler(bot::handle) // 🧠 O Cérebro
+                    .start_with_opts(account, address.as_str(), opts)
+                    .await;
 
-            println!("Bot disconnected/stopped. Reconnecting in 5 seconds...");
-            tokio::time::sleep(Duration::from_secs(5)).await;
-        } else {
-            println!("Authentication failed: {:?}. Retrying in 10 seconds...", account.err());
-            tokio::time::sleep(Duration::from_secs(5)).await;
+                info!("Bot disconnected/stopped.");
+            }
+            (Err(e), _) => {
+                warn!("Authentication failed: {:?}", e);
+                events::alert("auth", format!("🔑 Login da Microsoft falhou pra {}: {}", identity.email, e));
+            }
+            (_, Err(e)) => warn!("Proxy failed: {}", e),
+        }
+        if shutdown::stopping() {
+            return Ok(()); // We hung up on purpose, everything's saved
+        }
+        let stable = started.elapsed() >= config::STABLE_SESSION;
+        let reason = reconnect::take_disconnect();
+        let kick = reason.as_deref().map_or(reconnect::KickReason::Unknown, reconnect::classify);
+        if let Some(reason) = &reason {
+            info!("[RECONNECT] 🔎 {:?}: {}", kick, reason);
+        }
+        if kick == reconnect::KickReason::Banned {
+            events::alert("ban", format!("🚫 {} foi banido de {}: {}", identity.name, profile.name, reason.as_deref().unwrap_or_default()));
+        }
+        failures = if stable { 0 } else { failures + 1 };
+        if stable && alerted {
+            alerted = false;
+            notify::post(&config.alert_webhook, &format!("✅ {} voltou, {} conectado", profile.name, identity.name)).await;
+        }
+        if !stable && failures == config.alert_after && !alerted {
+            alerted = true;
+            let message = format!("🔴 {} ({}) não responde há {} tentativas", profile.name, address, failures);
+            notify::post(&config.alert_webhook, &message).await;
+        }
+        if config.max_attempts > 0 && failures >= config.max_attempts {
+            info!("[RECONNECT] 🛑 {} tentativas sem conseguir ficar online, desistindo", failures);
+            return Ok(());
+        }
+
+        // Banned or kicked over and over? The server's up, it's this account — swap it
+        let mut burned = false;
+        if kick != reconnect::KickReason::Unknown || stable {
+            let mut pool = accounts::AccountPool::load();
+            if pool.record(&identity, &profile.name, kick, stable, config.rotate_after_kicks) {
+                let accounts = Config::load().account_pool();
+                match pool.next_usable(&accounts, account, &profile.name) {
+                    Some(next) if next != account => {
+                        info!("[ACCOUNTS] 🔁 {:?} como {} em {}, trocando pra {}", kick, identity.name, profile.name, accounts[next].name);
+                        account = next;
+                    }
+                    Some(_) if kick.retry() => warn!("[ACCOUNTS] ⚠️ {:?} como {}, mas não tem outra conta", kick, identity.name),
+                    _ => {
+                        info!("[ACCOUNTS] 🚫 Nenhuma conta entra em {} ({:?})", profile.name, kick);
+                        burned = true; // Nothing left here — next server
+                    }
+                }
+            }
+            pool.save();
+        }
+        if burned {
+            burned_servers.insert(server);
+            if burned_servers.len() >= config.servers.len() {
+                info!("[RECONNECT] 🛑 Barrado em todos os servidores, parando de tentar");
+                return Ok(());
+            }
+        }
+
+        // Down (Aternos asleep) or kicking us right away? After a few, try the next server
+        let unreachable = kick == reconnect::KickReason::Unknown && !stable;
+        if burned || unreachable {
+            strikes = if burned { config.failover_after } else { strikes + 1 };
+            let next = (1..config.servers.len())
+                .map(|step| (server + step) % config.servers.len())
+                .find(|s| !burned_servers.contains(s));
+            if strikes >= config.failover_after && let Some(next) = next {
+                server = next;
+                strikes = 0;
+                account = 0;
+                info!("[FAILOVER] 🔀 {} não responde, tentando {}", profile.name, config.servers[server].name);
+            }
+        } else if stable {
+            // A real session ended — the primary gets the next shot
+            strikes = 0;
+            if server != 0 && !burned_servers.contains(&0) {
+                server = 0;
+                account = 0;
+            }
         }
 
-        tokio::time::sleep(Duration::from_secs(5)).await;
+        let wait = kick.delay(&config, failures);
+        info!("[RECONNECT] ⏳ Reconnecting in {} seconds...", wait.as_secs());
+        tokio::time::sleep(wait).await;
     }
 }
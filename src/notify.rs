@@ -0,0 +1,68 @@
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+use tracing::{info, warn};
+use crate::config::Config;
+use crate::events::BotEvent;
+
+// ============================================================
+// NOTIFY — Critical events to a webhook: Discord, Slack or Telegram
+// "[death] 💀 Morri, perdi 12 tipos de item" → celular do dono
+// ============================================================
+
+/// Post one message, shaped for whatever's behind the URL
+pub async fn post(webhook: &str, message: &str) {
+    if webhook.is_empty() {
+        return;
+    }
+    let body = payload(webhook, &Config::load().alert_chat_id, message);
+    match reqwest::Client::new().post(webhook).json(&body).send().await {
+        Ok(response) if response.status().is_success() => info!("[NOTIFY] 📣 Alerta enviado: {}", message),
+        Ok(response) => warn!("[NOTIFY] ⚠️ Webhook respondeu {}", response.status()),
+        Err(e) => warn!("[NOTIFY] ⚠️ Webhook falhou: {}", e),
+    }
+}
+
+/// Telegram wants chat_id + text, Slack text, Discord content. Unknown = both of the last two
+fn payload(webhook: &str, chat_id: &str, message: &str) -> Value {
+    if webhook.contains("api.telegram.org") {
+        json!({ "chat_id": chat_id, "text": message })
+    } else if webhook.contains("hooks.slack.com") {
+        json!({ "text": message })
+    } else if webhook.contains("discord.com") || webhook.contains("discordapp.com") {
+        json!({ "content": message })
+    } else {
+        json!({ "content": message, "text": message })
+    }
+}
+
+/// Forward alerts from the event stream for the life of the process, each kind at most once per cooldown
+pub async fn run() {
+    let mut events = crate::events::subscribe();
+    let mut last_sent: HashMap<String, Instant> = HashMap::new();
+    let mut held: HashMap<String, u32> = HashMap::new(); // Dropped by the cooldown since the last one sent
+    loop {
+        let line = match events.recv().await {
+            Ok(line) => line,
+            Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => return,
+        };
+        let Ok(BotEvent::Alert { kind, message }) = serde_json::from_str::<BotEvent>(&line) else { continue };
+        let config = Config::load();
+        if !config.alert_events.contains(&kind) {
+            continue;
+        }
+        let cooldown = Duration::from_secs(config.alert_cooldown_secs);
+        if last_sent.get(&kind).is_some_and(|at| at.elapsed() < cooldown) {
+            *held.entry(kind).or_default() += 1;
+            continue;
+        }
+        last_sent.insert(kind.clone(), Instant::now());
+        let message = match held.remove(&kind) {
+            Some(n) => format!("[{}] {} (+{} desde o último aviso)", kind, message, n),
+            None => format!("[{}] {}", kind, message),
+        };
+        post(&config.alert_webhook, &message).await;
+    }
+}
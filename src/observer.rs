@@ -0,0 +1,246 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::time::Instant;
+use tracing::{info, warn};
+use crate::bus::{ChatIn, Ctx};
+use crate::cognitive::memory::Memory;
+use crate::config::Config;
+use crate::systems::player_model::Activity;
+
+// ============================================================
+// OBSERVER — BOT_MODE=observer: watch the server for a while before playing on it
+// Zezin minera 70% do tempo, sempre perto do Tuta → vira nota na memória antes do Pedro abrir a boca
+// ============================================================
+//
+// The bot stays put and quiet (scheduler::HANDS_ON blocks don't run, chat is only read)
+// while every visible player's activity, whereabouts and company add up, and chat
+// mentions link players together. Every report_mins a report lands in <data>/reports/
+// and each player's summary goes into memory as a note — switch to another mode and
+// the persona already knows the regulars.
+
+const FILE: &str = "observer.json";
+/// Whereabouts are counted in squares this wide
+const AREA: i32 = 128;
+/// Closer than this counts as hanging out together
+const TOGETHER: f64 = 16.0;
+/// Memory notes from us start with this, so a new report replaces the old note
+const NOTE: &str = "Observado:";
+/// Players in a report, most seen first
+const REPORT_PLAYERS: usize = 20;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Watched {
+    pub first_seen: Option<DateTime<Utc>>,
+    pub last_seen: Option<DateTime<Utc>>,
+    pub seen_secs: u64,
+    pub activities: BTreeMap<String, u64>, // Activity → seconds
+    pub areas: BTreeMap<String, u64>,      // "x,z" of the AREA square → seconds
+    pub chat_lines: u32,
+}
+
+impl Watched {
+    /// "minerando 60%, construindo 25%"
+    fn habits(&self) -> String {
+        let total = self.activities.values().sum::<u64>().max(1);
+        let mut habits: Vec<(&String, &u64)> = self.activities.iter().collect();
+        habits.sort_by(|a, b| b.1.cmp(a.1));
+        habits.iter().take(3).map(|(what, secs)| format!("{} {}%", what, **secs * 100 / total)).collect::<Vec<_>>().join(", ")
+    }
+
+    /// Centre of the square they spend most time in
+    fn haunt(&self) -> Option<String> {
+        let (area, _) = self.areas.iter().max_by_key(|(_, secs)| **secs)?;
+        let (x, z) = area.split_once(',')?;
+        let center = |c: &str| c.parse::<i32>().ok().map(|c| c * AREA + AREA / 2);
+        Some(format!("{} {}", center(x)?, center(z)?))
+    }
+}
+
+/// Two players' ties, undirected
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Tie {
+    pub together_secs: u64,
+    pub mentions: u32, // Either one naming the other in chat
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Observations {
+    pub players: BTreeMap<String, Watched>,
+    pub ties: BTreeMap<String, Tie>, // "a|b", a < b
+    #[serde(skip)]
+    last_sample: Option<Instant>,
+    #[serde(skip)]
+    last_report: Option<Instant>,
+    #[serde(skip)]
+    folder: Option<String>, // Same as Memory's: None = data_dir()
+}
+
+fn pair(a: &str, b: &str) -> String {
+    if a < b { format!("{}|{}", a, b) } else { format!("{}|{}", b, a) }
+}
+
+impl Observations {
+    pub fn load_in(folder: Option<String>) -> Self {
+        let path = format!("{}/{}", folder.clone().unwrap_or_else(crate::cognitive::memory::data_dir), FILE);
+        let mut observations: Self = std::fs::read_to_string(path).ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default();
+        observations.folder = folder;
+        observations
+    }
+
+    fn dir(&self) -> String {
+        self.folder.clone().unwrap_or_else(crate::cognitive::memory::data_dir)
+    }
+
+    fn save(&self) {
+        let dir = self.dir();
+        let _ = std::fs::create_dir_all(&dir);
+        match serde_json::to_string(self) {
+            Ok(data) => if let Err(e) = std::fs::write(format!("{}/{}", dir, FILE), data) {
+                warn!("[OBSERVER] ⚠️ Não consegui salvar {}: {}", FILE, e);
+            },
+            Err(e) => warn!("[OBSERVER] ⚠️ Não consegui serializar: {}", e),
+        }
+    }
+
+    /// Everyone in sight right now: (name, position, what they seem to be doing)
+    pub fn sample(&mut self, seen: &[(String, [f64; 3], Activity)]) {
+        let secs = self.last_sample.map_or(1, |t| t.elapsed().as_secs().clamp(1, 10));
+        self.last_sample = Some(Instant::now());
+        let now = Utc::now();
+        for (name, pos, activity) in seen {
+            let watched = self.players.entry(name.clone()).or_default();
+            watched.first_seen.get_or_insert(now);
+            watched.last_seen = Some(now);
+            watched.seen_secs += secs;
+            *watched.activities.entry(activity.describe().to_string()).or_default() += secs;
+            let area = format!("{},{}", (pos[0] as i32).div_euclid(AREA), (pos[2] as i32).div_euclid(AREA));
+            *watched.areas.entry(area).or_default() += secs;
+        }
+        for (i, (a, pos_a, _)) in seen.iter().enumerate() {
+            for (b, pos_b, _) in &seen[i + 1..] {
+                if crate::systems::spider_sense::distance(*pos_a, *pos_b) <= TOGETHER {
+                    self.ties.entry(pair(a, b)).or_default().together_secs += secs;
+                }
+            }
+        }
+    }
+
+    /// A chat line: count it, link whoever it names
+    pub fn heard(&mut self, sender: &str, message: &str) {
+        let lower = message.to_lowercase();
+        let named: Vec<String> = self.players.keys()
+            .filter(|p| p.as_str() != sender && lower.contains(&p.to_lowercase()))
+            .cloned()
+            .collect();
+        self.players.entry(sender.to_string()).or_default().chat_lines += 1;
+        for other in named {
+            self.ties.entry(pair(sender, &other)).or_default().mentions += 1;
+        }
+    }
+
+    /// Who `player` is closest to, strongest tie first
+    fn circle(&self, player: &str) -> Vec<String> {
+        let mut ties: Vec<(&str, u64)> = self.ties.iter()
+            .filter_map(|(key, tie)| {
+                let (a, b) = key.split_once('|')?;
+                let other = if a == player { b } else if b == player { a } else { return None };
+                Some((other, tie.together_secs + tie.mentions as u64 * 60)) // A mention weighs a minute together
+            })
+            .collect();
+        ties.sort_by_key(|(_, weight)| std::cmp::Reverse(*weight));
+        ties.into_iter().take(3).map(|(name, _)| name.to_string()).collect()
+    }
+
+    /// One line per player, for memory notes and the report
+    fn summary(&self, player: &str, watched: &Watched) -> String {
+        let mut line = format!("visto {}min, {}", watched.seen_secs / 60, watched.habits());
+        if let Some(haunt) = watched.haunt() {
+            line.push_str(&format!(", costuma ficar perto de {}", haunt));
+        }
+        let circle = self.circle(player);
+        if !circle.is_empty() {
+            line.push_str(&format!(", anda com {}", circle.join(", ")));
+        }
+        if watched.chat_lines > 0 {
+            line.push_str(&format!(", {} msgs no chat", watched.chat_lines));
+        }
+        line
+    }
+
+    /// Everything so far, as JSON (GET /observer)
+    pub fn report(&self) -> Value {
+        let mut players: Vec<(&String, &Watched)> = self.players.iter().collect();
+        players.sort_by_key(|(_, watched)| std::cmp::Reverse(watched.seen_secs));
+        json!({
+            "players": players.iter().take(REPORT_PLAYERS).map(|(name, watched)| json!({
+                "name": name,
+                "seen_secs": watched.seen_secs,
+                "activities": watched.activities,
+                "haunt": watched.haunt(),
+                "chat_lines": watched.chat_lines,
+                "circle": self.circle(name),
+            })).collect::<Vec<_>>(),
+            "ties": self.ties.iter().map(|(key, tie)| json!({
+                "players": key.split('|').collect::<Vec<_>>(),
+                "together_secs": tie.together_secs,
+                "mentions": tie.mentions,
+            })).collect::<Vec<_>>(),
+        })
+    }
+
+    /// Time for a report? Writes it, saves, and hands back (player, summary) for `note_players`
+    pub fn maybe_report(&mut self, config: &Config) -> Vec<(String, String)> {
+        let every = std::time::Duration::from_secs(config.observer_report_mins * 60);
+        let last = *self.last_report.get_or_insert_with(Instant::now);
+        if last.elapsed() < every {
+            return vec![];
+        }
+        self.last_report = Some(Instant::now());
+        let mut players: Vec<(&String, &Watched)> = self.players.iter().collect();
+        players.sort_by_key(|(_, watched)| std::cmp::Reverse(watched.seen_secs));
+
+        let mut text = format!("# Relatório do observador — {}\n\n", Utc::now().format("%Y-%m-%d %H:%M"));
+        let mut summaries = vec![];
+        for (name, watched) in players.iter().take(REPORT_PLAYERS) {
+            let summary = self.summary(name, watched);
+            text.push_str(&format!("- **{}**: {}\n", name, summary));
+            summaries.push((name.to_string(), summary));
+        }
+        self.save();
+
+        let dir = format!("{}/reports", self.dir());
+        let path = format!("{}/observer-{}.md", dir, Utc::now().format("%Y%m%d-%H%M"));
+        let written = std::fs::create_dir_all(&dir).and_then(|_| std::fs::write(&path, text));
+        match written {
+            Ok(()) => info!("[OBSERVER] 📋 Relatório com {} jogadores e {} laços em {}", players.len(), self.ties.len(), path),
+            Err(e) => warn!("[OBSERVER] ⚠️ Não consegui escrever {}: {}", path, e),
+        }
+        summaries
+    }
+}
+
+/// A report's summaries as player notes, replacing the last report's
+pub fn note_players(memory: &mut Memory, summaries: &[(String, String)]) {
+    if summaries.is_empty() {
+        return;
+    }
+    for (name, summary) in summaries {
+        let profile = memory.social.get_or_create(name);
+        profile.notes.retain(|n| !n.starts_with(NOTE));
+        profile.notes.push(format!("{} {}", NOTE, summary));
+    }
+    memory.save();
+}
+
+/// Bus: chat, only while observing
+pub fn on_chat(ctx: &Ctx, chat: &ChatIn) {
+    let Some(sender) = &chat.sender else { return };
+    if !Config::load().observing() {
+        return;
+    }
+    ctx.state.observer.lock().unwrap().heard(sender, &chat.message);
+}
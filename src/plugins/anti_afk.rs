@@ -7,11 +7,45 @@ pub struct State {
     pub last_action: Arc<Mutex<Instant>>,
 }
 
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            last_action: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+}
+
+impl crate::plugins::registry::BotSystem for State {
+    fn name(&self) -> &'static str {
+        "anti_afk"
+    }
+
+    /// Kicked for idling once? Keep it on even if it was switched off
+    fn enabled(&self, features: &crate::config::Features) -> bool {
+        features.anti_afk || crate::reconnect::afk_kicked()
+    }
+
+    fn on_event(&self, bot: Client, event: Event) -> crate::plugins::registry::SystemFuture {
+        Box::pin(handle(bot, event, self.clone()))
+    }
+
+    fn context_summary(&self) -> Option<String> {
+        Some(format!("último movimento há {}s", self.last_action.lock().unwrap().elapsed().as_secs()))
+    }
+}
+
 pub async fn handle(bot: Client, event: Event, state: State) -> anyhow::Result<()> {
     if let Event::Tick = event {
         let mut last_action = state.last_action.lock().unwrap();
-        if last_action.elapsed() > Duration::from_secs(60) {
+        // Already kicked for idling once — move more often, and look around too
+        let kicked = crate::reconnect::afk_kicked();
+        let every = if kicked { 20 } else { 60 };
+        if last_action.elapsed() > Duration::from_secs(every) {
             bot.jump();
+            if kicked {
+                let (yaw, pitch) = bot.direction();
+                bot.set_direction(yaw + rand::random::<f32>() * 90.0 - 45.0, pitch);
+            }
             *last_action = Instant::now();
         }
     }
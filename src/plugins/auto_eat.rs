@@ -1,12 +1,55 @@
 use azalea::prelude::*;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use crate::systems::inventory::{self, Inventory};
+use tracing::info;
 
-pub async fn handle(bot: Client, event: Event, _state: ()) -> anyhow::Result<()> {
+/// A bite takes 32 ticks — don't start another one before it lands
+const BITE_COOLDOWN: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Component)]
+pub struct State {
+    pub last_bite: Arc<Mutex<Instant>>,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            last_bite: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+}
+
+impl crate::plugins::registry::BotSystem for State {
+    fn name(&self) -> &'static str {
+        "auto_eat"
+    }
+
+    fn on_event(&self, bot: Client, event: Event) -> crate::plugins::registry::SystemFuture {
+        Box::pin(handle(bot, event, self.clone()))
+    }
+}
+
+pub async fn handle(bot: Client, event: Event, state: State) -> anyhow::Result<()> {
     if let Event::Tick = event {
-        if bot.hunger().food < 16 || bot.health() < 20.0 {
-            // Check if we have food
-            // Azalea inventory API handling would go here (simplified for now as exact inventory API varies)
-            // For now, we just print intent
-            // println!("Bot is hungry or hurt! Searching for food...");
+        let food = bot.hunger().food;
+        let hp = bot.health();
+        if food >= 20 || (food >= 16 && hp >= 20.0) {
+            return Ok(());
+        }
+        let mut last = state.last_bite.lock().unwrap();
+        if last.elapsed() < BITE_COOLDOWN {
+            return Ok(());
+        }
+        // Starving or dying → golden apples and rotten flesh are on the menu
+        let desperate = food <= 6 || hp < 8.0;
+        let Some(meal) = Inventory::read(&bot).best_food(desperate) else {
+            return Ok(());
+        };
+        if inventory::equip(&bot, meal.slot) {
+            *last = Instant::now();
+            bot.start_use_item();
+            info!("[EAT] 🍖 Comendo {} (fome {}, hp {:.0})", meal.item, food, hp);
         }
     }
     Ok(())
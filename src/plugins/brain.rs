@@ -1,65 +1,45 @@
 use azalea::prelude::*;
+use azalea::chat::ChatPacket;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use crate::config::Config;
-use crate::cognitive::memory::Memory;
+use crate::swarm::Identity;
+use crate::cognitive::memory::{Memory, Relationship};
 use crate::cognitive::personality::{Personality, PersonalityEvent};
-use crate::cognitive::goal_planner::GoalPlanner;
+use crate::cognitive::recall::{self, RecallIndex};
+use crate::cognitive::goal_planner::{Goal, GoalPlanner, GoalPriority};
 use crate::systems::world_scanner::WorldState;
 use crate::systems::social::{SocialEngine, ResponseStyle};
 use crate::systems::typos;
-use crate::systems::economy::Economy;
+use crate::systems::tone::{self, Tone};
+use crate::systems::chat_channel::{self, ChatChannel};
+use crate::systems::chat_format::{self, ChatFormat};
+use crate::systems::teleport::{self, TeleportWatch, TpaKind};
+use crate::systems::server_events::{self, DeathCause, ServerEvent};
+use crate::systems::player_model::PlayerModels;
+use crate::systems::auth::AuthSession;
+use crate::systems::queue::QueueWatch;
+use crate::systems::combat::CombatSystem;
+use crate::systems::truce::{Truce, TruceStep};
+use crate::systems::economy::{normalize_item_name, CreditTier, PriceSource, TradeDecision};
+use crate::systems::escrow::{EscrowPhase, EscrowTrade};
+use crate::systems::negotiation::{flexibility, Negotiation, NegotiationMove};
+use crate::systems::trader::{self, GiveOutcome};
+use crate::systems::inventory::Inventory;
+use crate::systems::storage;
+use crate::systems::motor::MotorCommand;
+use crate::llm::{Tool, ToolCall};
+use azalea::entity::Position;
+use serde_json::json;
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::time::{Instant, Duration};
+use tracing::{error, info, info_span, warn, Instrument};
 
 // ============================================================
 // GEMINI API TYPES
 // ============================================================
 
-#[derive(Serialize)]
-struct GeminiRequest {
-    contents: Vec<GContent>,
-    #[serde(rename = "generationConfig")]
-    generation_config: GenerationConfig,
-}
-
-#[derive(Serialize)]
-struct GContent {
-    role: String,
-    parts: Vec<GPart>,
-}
-
-#[derive(Serialize)]
-struct GPart {
-    text: String,
-}
-
-#[derive(Serialize)]
-struct GenerationConfig {
-    #[serde(rename = "maxOutputTokens")]
-    max_output_tokens: u32,
-    temperature: f32,
-}
-
-#[derive(Deserialize)]
-struct GeminiResponse {
-    candidates: Option<Vec<Candidate>>,
-}
-
-#[derive(Deserialize)]
-struct Candidate {
-    content: ContentResponse,
-}
-
-#[derive(Deserialize)]
-struct ContentResponse {
-    parts: Vec<PartResponse>,
-}
-
-#[derive(Deserialize)]
-struct PartResponse {
-    text: String,
-}
-
 // ============================================================
 // BRAIN V2 — The Cortex
 // ============================================================
@@ -71,37 +51,77 @@ pub struct State {
     pub goals: Arc<Mutex<GoalPlanner>>,
     pub world: Arc<Mutex<WorldState>>,
     pub social: Arc<Mutex<SocialEngine>>,
-    pub economy: Arc<Mutex<Economy>>,
     pub last_chat: Arc<Mutex<Instant>>,
     pub chat_history: Arc<Mutex<Vec<String>>>, // Last N chat messages for context
+    pub channel_history: Arc<Mutex<HashMap<ChatChannel, Vec<String>>>>, // Party/team/faction, kept apart
     pub save_counter: Arc<Mutex<u32>>,
+    pub trader: trader::State,
+    pub teleport: Arc<Mutex<TeleportWatch>>,
+    pub player_models: Arc<Mutex<PlayerModels>>, // What everyone nearby seems to be doing
+    pub auth: Arc<Mutex<AuthSession>>,
+    pub queue: Arc<Mutex<QueueWatch>>, // Queue/limbo: everything chatty stays off
+    pub combat: Arc<Mutex<CombatSystem>>,
+    pub me: Arc<Identity>, // Which bot this is — only ever not the main one in a swarm
+    pub recall: Arc<Mutex<RecallIndex>>, // Episode vectors, to bring back what a message reminds us of
 }
 
 impl Default for State {
     fn default() -> Self {
+        Self::for_bot(Identity::default())
+    }
+}
+
+impl State {
+    /// A fresh head for `me`, with memory and goals from its own folder
+    pub fn for_bot(me: Identity) -> Self {
         Self {
-            memory: Arc::new(Mutex::new(Memory::load())),
+            memory: Arc::new(Mutex::new(Memory::load_in(me.folder.clone()))),
             personality: Arc::new(Mutex::new(Personality::default())),
-            goals: Arc::new(Mutex::new(GoalPlanner::default())),
+            goals: Arc::new(Mutex::new(GoalPlanner::load_in(me.folder.clone()))),
             world: Arc::new(Mutex::new(WorldState::default())),
             social: Arc::new(Mutex::new(SocialEngine::default())),
-            economy: Arc::new(Mutex::new(Economy::new())),
             last_chat: Arc::new(Mutex::new(Instant::now() - Duration::from_secs(60))),
             chat_history: Arc::new(Mutex::new(Vec::new())),
+            channel_history: Arc::new(Mutex::new(HashMap::new())),
             save_counter: Arc::new(Mutex::new(0)),
+            trader: trader::State::default(),
+            teleport: Arc::new(Mutex::new(TeleportWatch::default())),
+            player_models: Arc::new(Mutex::new(PlayerModels::default())),
+            auth: Arc::new(Mutex::new(AuthSession::default())),
+            queue: Arc::new(Mutex::new(QueueWatch::default())),
+            combat: Arc::new(Mutex::new(CombatSystem::default())),
+            recall: Arc::new(Mutex::new(RecallIndex::load_in(me.folder.clone()))),
+            me: Arc::new(me),
         }
     }
 }
 
 /// Build the full context string for the AI
-fn build_context(state: &State, incoming_message: &str, sender: &str) -> String {
+/// Latest episodes always in the context
+const RECENT_EPISODES: usize = 3;
+
+/// `recalled`: older episodes related to the message (recall::related), may be empty
+fn build_context(state: &State, incoming_message: &str, sender: &str, channel: ChatChannel, recalled: &str) -> String {
+    let config = Config::load();
     let memory = state.memory.lock().unwrap();
     let personality = state.personality.lock().unwrap();
     let goals = state.goals.lock().unwrap();
     let world = state.world.lock().unwrap();
     let social_engine = state.social.lock().unwrap();
-    let economy = state.economy.lock().unwrap();
-    let chat_history = state.chat_history.lock().unwrap();
+    let activities = state.player_models.lock().unwrap().context_summary();
+    let activities = match crate::team::context_for(&state.me.name(&config)) {
+        notes if notes.is_empty() => activities,
+        notes => format!("{}\n{}", activities, notes),
+    };
+    let combat = state.combat.lock().unwrap().context_summary();
+    let combat = match memory.spatial.caves.context_summary() {
+        caves if caves.is_empty() => combat,
+        caves => format!("{}\n{}", combat, caves),
+    };
+    let chat_history = match channel {
+        ChatChannel::Global => state.chat_history.lock().unwrap().clone(),
+        _ => state.channel_history.lock().unwrap().get(&channel).cloned().unwrap_or_default(),
+    };
 
     // Get relationship context
     let relationship_ctx = memory.social.players.get(sender).map(|p| {
@@ -110,24 +130,44 @@ fn build_context(state: &State, incoming_message: &str, sender: &str) -> String
             sender, p.relationship, p.trust_level, p.times_met, p.notes
         )
     }).unwrap_or_else(|| format!("{} é um desconhecido. Primeira vez que vocês conversam.", sender));
+    let relationship_ctx = format!(
+        "{}\nCrédito com você: {}", relationship_ctx, memory.economy.tier_of(sender).describe()
+    );
+    let relationship_ctx = match memory.social.players.get(sender).and_then(|p| p.time_together_today()) {
+        Some(together) => format!("{} ({})", relationship_ctx, together),
+        None => relationship_ctx,
+    };
 
     // Economy context: debts, credit, trade decisions
-    let economy_ctx = economy.context_summary();
+    let mut economy_ctx = memory.economy.context_summary();
+    if memory.economy.is_blacklisted(sender) {
+        economy_ctx.push_str(&format!("⛔ {} é CALOTEIRO: recuse qualquer ajuda, troca ou favor até pagar.\n", sender));
+    }
 
     // Detect trade requests and inject trade decision
-    let trade_keywords = ["me dá", "me da", "empresta", "troca", "preciso de", "tem sobrando", "arruma"];
-    let msg_lower = incoming_message.to_lowercase();
-    let trade_hint = if trade_keywords.iter().any(|kw| msg_lower.contains(kw)) {
-        // Try to extract what item they want (very basic extraction)
-        let items = ["diamante", "ferro", "ouro", "esmeralda", "netherite", "comida",
-                     "diamond", "iron", "gold", "emerald", "bread", "redstone"];
-        let requested_item = items.iter()
-            .find(|i| msg_lower.contains(*i))
-            .unwrap_or(&"item");
-        let decision = economy.evaluate_request(sender, requested_item, 1);
-        format!("\n⚠️ TRADE REQUEST DETECTADO: {} quer {}. Sua decisão econômica: {:?}", sender, requested_item, decision)
-    } else {
-        String::new()
+    let trade_hint = match detect_trade_request(incoming_message) {
+        Some((requested_item, quantity)) => {
+            let decision = memory.economy.evaluate_request(sender, &requested_item, quantity);
+            format!("\n⚠️ TRADE REQUEST DETECTADO: {} quer {} x{}. Sua decisão econômica: {:?}", sender, requested_item, quantity, decision)
+        }
+        None => String::new(),
+    };
+    let trade_hint = match memory.economy.negotiations.get(sender) {
+        Some(negotiation) => format!("{}{}", trade_hint, negotiation.describe(sender)),
+        None => trade_hint,
+    };
+    let trade_hint = match memory.economy.escrows.iter().rev().find(|e| e.player == sender && e.is_open()) {
+        Some(escrow) => format!(
+            "{}\n🔒 ESCROW com {}: esperando ele jogar {} x{} (recebido {}). Só solta teu lado depois de conferir.",
+            trade_hint, sender, escrow.they_give.0, escrow.they_give.1, escrow.received
+        ),
+        None => trade_hint,
+    };
+
+    // Latest episodes, plus older ones the message brings back
+    let episodes = match recalled {
+        "" => memory.episodes.context_summary(RECENT_EPISODES),
+        recalled => format!("{}\nA mensagem te lembra de:\n{}", memory.episodes.context_summary(RECENT_EPISODES), recalled),
     };
 
     // Recent chat for context
@@ -144,9 +184,19 @@ r#"{}
 {}
 {}
 {}
+{}
 
 === CONTEXTO SOCIAL ===
 {}
+{}{}
+
+=== O QUE A GALERA TÁ FAZENDO ===
+{}
+
+=== PLACAS QUE EU LI ===
+{}
+
+=== BAÚS DA BASE ===
 {}
 
 === ECONOMIA (Dívidas e Favores) ===
@@ -156,262 +206,1030 @@ r#"{}
 {}
 
 === MENSAGEM PRA RESPONDER ===
-<{}> {}"#,
-        personality.system_prompt(),
+{}<{}> {}"#,
+        personality.system_prompt(&state.me.name(&config), if state.me.extra() { &state.me.persona } else { &config.persona }),
         world.context_summary(),
+        combat,
         goals.context_summary(),
-        memory.episodes.context_summary(3),
+        episodes,
         relationship_ctx,
         social_engine.context_summary(),
+        social_engine.conversation_hint(sender),
+        activities,
+        memory.spatial.signs.context_for(incoming_message),
+        [memory.inventory.storage.context_for(incoming_message), memory.inventory.ender.context_summary()]
+            .into_iter().filter(|s| !s.is_empty()).collect::<Vec<_>>().join("\n"),
         economy_ctx,
         trade_hint,
         recent_chat,
+        match channel {
+            ChatChannel::Global => String::new(),
+            other => format!("(chat privado do grupo: {:?}) ", other),
+        },
         sender,
         incoming_message,
     )
 }
 
-/// Extract sender name from chat message (format: <PlayerName> message)
-fn extract_sender(message: &str) -> Option<(&str, &str)> {
-    if let Some(start) = message.find('<') {
-        if let Some(end) = message.find('>') {
-            let sender = &message[start + 1..end];
-            let content = message[end + 1..].trim();
-            return Some((sender, content));
+/// Who sent a chat line (any channel, any server format), as their real username
+pub fn chat_sender(bot: &Client, chat: &ChatPacket) -> Option<String> {
+    if let Some((sender, _)) = chat_format::from_packet(bot, chat) {
+        return Some(sender);
+    }
+    let clean = chat_format::strip_colors(&chat.message().to_string());
+    let format = ChatFormat::from_config(&Config::load());
+    let sender = match chat_channel::parse_channel_message(&clean) {
+        Some((_, sender, _)) => sender,
+        None => format.parse(&clean, &|name| chat_format::is_online(bot, name))?.0,
+    };
+    Some(chat_format::resolve_name(bot, sender))
+}
+
+/// Spot "me dá 3 diamante" style requests → (item id, quantity)
+fn detect_trade_request(message: &str) -> Option<(String, u32)> {
+    let trade_keywords = ["me dá", "me da", "empresta", "troca", "preciso de", "tem sobrando", "arruma"];
+    let msg_lower = message.to_lowercase();
+    if !trade_keywords.iter().any(|kw| msg_lower.contains(kw)) {
+        return None;
+    }
+
+    // Try to extract what item they want (very basic extraction)
+    let items = ["diamante", "ferro", "ouro", "esmeralda", "netherite", "comida",
+                 "diamond", "iron", "gold", "emerald", "bread", "redstone"];
+    let requested_item = items.iter()
+        .find(|i| msg_lower.contains(*i))
+        .map(|i| normalize_item_name(i))
+        .unwrap_or_else(|| "item".into());
+
+    // First number in the message is the quantity ("me da 5 ferro")
+    let quantity = msg_lower
+        .split(|c: char| !c.is_ascii_digit())
+        .find_map(|n| n.parse::<u32>().ok())
+        .unwrap_or(1)
+        .clamp(1, 64);
+
+    Some((requested_item, quantity))
+}
+
+/// Append to a chat history, keeping it short
+fn remember_line(history: &mut Vec<String>, line: String) {
+    history.push(line);
+    if history.len() > 20 {
+        history.drain(0..10);
+    }
+}
+
+/// Join the party if someone we trust invited us
+fn accept_party_invite(bot: &Client, state: &State, config: &Config, message: &str) {
+    let Some(inviter) = chat_channel::parse_party_invite(message) else { return };
+    let trust = state.memory.lock().unwrap().social.players.get(inviter).map(|p| p.trust_level);
+    if trust.is_some_and(|t| t >= 50) {
+        info!("[BRAIN] 🎉 Aceitando party do {}", inviter);
+        bot.chat(format!("{} {}", config.party_accept_command, inviter));
+    } else {
+        info!("[BRAIN] 🚫 Ignorando convite de party do {} (não confio)", inviter);
+    }
+}
+
+/// Friends get teleported in (or pull us over); strangers get /tpdeny; everyone else is ignored
+fn answer_tpa_request(bot: &Client, state: &State, config: &Config, message: &str) {
+    let Some(request) = teleport::parse_tpa_request(message) else { return };
+    // A real request comes from someone who's here
+    if !bot.tab_list().values().any(|p| p.profile.name == request.player) {
+        info!("[BRAIN] 🤨 Pedido de tpa do {}, que nem tá online — ignorando", request.player);
+        return;
+    }
+    let (trust, blacklisted) = {
+        let memory = state.memory.lock().unwrap();
+        let trust = memory.social.players.get(&request.player).map(|p| p.trust_level);
+        (trust, memory.economy.is_blacklisted(&request.player))
+    };
+
+    match trust {
+        Some(t) if t >= TRUSTED && !blacklisted => {
+            info!("[BRAIN] 🌀 Aceitando tpa do {} ({:?})", request.player, request.kind);
+            if request.kind == TpaKind::ToThem {
+                state.teleport.lock().unwrap().expect(&request.player);
+            }
+            // Named, so we accept theirs and not whatever else is pending
+            bot.chat(format!("{} {}", config.tpa_accept_command, request.player));
+        }
+        Some(t) if t >= 30 && !blacklisted => {
+            info!("[BRAIN] 🤔 Ignorando tpa do {} (não confio tanto)", request.player);
+        }
+        _ => {
+            info!("[BRAIN] 🚫 Negando tpa do {}", request.player);
+            bot.chat(config.tpa_deny_command.clone());
+        }
+    }
+}
+
+/// How a chat line landed: aimed at us or not, and what it did to their trust
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Judgement {
+    pub directed: bool,
+    pub trust_delta: i32,
+}
+
+/// What a chat line does to us before any reply: memory, prices, needs, mood, trust
+pub fn absorb_chat(state: &State, config: &Config, channel: ChatChannel, sender: &str, content: &str) -> Judgement {
+    let trust = || state.memory.lock().unwrap().social.get_or_create(sender).trust_level;
+    let before = trust();
+
+    // Update social memory
+    {
+        let mut memory = state.memory.lock().unwrap();
+        memory.social.record_interaction(sender, 1); // +1 trust for chatting
+        let player = memory.social.get_or_create(sender);
+        player.add_message(content);
+
+        // Learn prices from what people say they're trading at
+        if let Some((item_a, qty_a, item_b, qty_b, source)) = memory.economy.parse_price_mention(content) {
+            memory.economy.observe_exchange(&item_a, qty_a, &item_b, qty_b, source);
+        }
+
+        // Remember what friends say they're short on — might gift it later
+        if let Some((item, quantity)) = memory.economy.parse_need(content) {
+            state.social.lock().unwrap().note_need(sender, &item, quantity);
+        }
+    }
+
+    // Personality event
+    {
+        let mut personality = state.personality.lock().unwrap();
+        personality.on_event(&PersonalityEvent::ReceivedChat);
+    }
+
+    // Insults, threats and compliments aimed at us
+    let directed = content.to_lowercase().contains(&state.me.name(config).to_lowercase())
+        || channel != ChatChannel::Global
+        || state.social.lock().unwrap().is_conversing(sender);
+    if directed {
+        react_to_tone(state, sender, tone::classify(content));
+    }
+    Judgement { directed, trust_delta: trust() - before }
+}
+
+/// Abuse adding up to this many grudges in a day makes them a Rival
+const ABUSE_BEFORE_RIVAL: usize = 3;
+
+/// Trust and mood consequences of how they talk to us
+fn react_to_tone(state: &State, sender: &str, tone: Tone) {
+    let (trust_delta, event) = match tone {
+        Tone::Threat => (-10, PersonalityEvent::Threatened),
+        Tone::Insult => (-5, PersonalityEvent::Insulted),
+        Tone::Compliment => (3, PersonalityEvent::Complimented),
+        Tone::Neutral => return,
+    };
+    info!("[BRAIN] 🎭 {} → {:?}", sender, tone);
+    {
+        let mut memory = state.memory.lock().unwrap();
+        let profile = memory.social.get_or_create(sender);
+        profile.trust_level = (profile.trust_level + trust_delta).clamp(-100, 100);
+        match tone {
+            Tone::Threat => profile.hold_grudge("me ameaçou", 3),
+            Tone::Insult => profile.hold_grudge("me xingou", 1),
+            _ => {}
+        }
+
+        let abuse_today = profile.grudges.iter()
+            .filter(|g| g.reason == "me xingou" || g.reason == "me ameaçou")
+            .filter(|g| chrono::Utc::now().signed_duration_since(g.created_at).num_hours() < 24)
+            .count();
+        if tone != Tone::Compliment && abuse_today >= ABUSE_BEFORE_RIVAL && profile.trust_level >= 10 {
+            profile.trust_level = 9; // Rival territory
+            info!("[BRAIN] 😤 {} passou dos limites, agora é rival", sender);
+        }
+        profile.update_relationship();
+    }
+    state.personality.lock().unwrap().on_event(&event);
+}
+
+/// AuthMe-style "/register" / "/login" nag: answer with the configured password.
+/// Returns true when the message was an auth prompt/verdict (never chat). Only system messages count
+fn answer_auth_prompt(bot: &Client, state: &State, config: &Config, chat: &ChatPacket, message: &str) -> bool {
+    if !matches!(chat, ChatPacket::System(_)) {
+        return false;
+    }
+    let mut auth = state.auth.lock().unwrap();
+    if !auth.intercepts(message) {
+        return false;
+    }
+    if let Some(command) = auth.on_message(message) {
+        if config.auth_password.is_empty() {
+            warn!("[AUTH] ⚠️ Servidor pede {:?} mas AUTH_PASSWORD tá vazio no .env", command);
+        } else {
+            bot.chat(command.with_password(&config.auth_password));
+        }
+    }
+    true
+}
+
+/// Someone died or got an advancement — remember it, maybe say something
+fn react_to_server_event(bot: &Client, state: &State, config: &Config, message: &str) {
+    let tab = bot.tab_list();
+    let Some(event) = server_events::parse(message, |name| tab.values().any(|p| p.profile.name == name)) else { return };
+    if config.is_own_account(event.player()) {
+        return;
+    }
+    info!("[SOCIAL] 📰 {}", event.describe());
+
+    let relationship = {
+        let mut memory = state.memory.lock().unwrap();
+        let relationship = memory.social.players.get(event.player()).map(|p| p.relationship.clone());
+        let (event_type, mut involved) = match &event {
+            ServerEvent::Death { .. } => ("morte de jogador", vec![event.player().to_string()]),
+            ServerEvent::Advancement { .. } => ("conquista", vec![event.player().to_string()]),
+        };
+        if let ServerEvent::Death { cause: DeathCause::Player(killer), .. } = &event {
+            involved.push(killer.clone());
+        }
+        memory.episodes.add(crate::cognitive::memory::Episode {
+            timestamp: chrono::Utc::now(),
+            event_type: crate::cognitive::memory::EpisodeType::Custom(event_type.into()),
+            description: event.describe(),
+            location: None,
+            players_involved: involved,
+            emotional_impact: event.emotional_impact(relationship.as_ref()),
+        });
+        relationship
+    };
+
+    let Some(line) = event.reaction(relationship.as_ref()) else { return };
+    {
+        let mut last_chat = state.last_chat.lock().unwrap();
+        if last_chat.elapsed() < Duration::from_secs(5) {
+            return;
+        }
+        *last_chat = Instant::now();
+    }
+    // Nobody types "F" the same tick the death message shows up
+    let bot = bot.clone();
+    let delay = rand::thread_rng().gen_range(1000..3500);
+    crate::bus::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(delay)).await;
+        crate::events::say(&bot, line);
+    });
+}
+
+/// Rival/enemy talking to us: grievance → restitution → truce offer.
+/// Returns true when the truce script answered the message
+fn advance_truce(bot: &Client, state: &State, config: &Config, channel: ChatChannel, sender: &str, content: &str, directed: bool) -> bool {
+    let line = {
+        let mut memory = state.memory.lock().unwrap();
+        let mut social = state.social.lock().unwrap();
+        let hostile = memory.social.players.get(sender)
+            .is_some_and(|p| matches!(p.relationship, Relationship::Rival | Relationship::Enemy));
+        let grudges = memory.social.players.get(sender).map(|p| p.grudges.clone()).unwrap_or_default();
+        let debt = memory.economy.outstanding_debt(sender);
+
+        let step = match social.truces.get_mut(sender) {
+            Some(truce) if truce.is_open() => truce.on_message(content, &grudges, debt),
+            Some(truce) if truce.cooling_down() => return false,
+            _ if hostile && directed => {
+                let (truce, line) = Truce::open(sender, &grudges);
+                social.truces.insert(sender.to_string(), truce);
+                Some(TruceStep::Say(line))
+            }
+            _ => return false,
+        };
+        let Some(step) = step else { return false };
+        info!("[BRAIN] ⚖️ Trégua com {}: {:?}", sender, step);
+
+        match step {
+            TruceStep::Say(line) => line,
+            TruceStep::Demand(line, item, quantity) => {
+                let grievance = social.truces.get(sender).map(|t| t.grievance.clone()).unwrap_or_default();
+                memory.economy.demand_restitution(sender, &item, quantity, &grievance);
+                memory.save();
+                line
+            }
+            TruceStep::Settle(line) => {
+                let profile = memory.social.get_or_create(sender);
+                profile.grudges.clear();
+                profile.trust_level = profile.trust_level.max(10); // Back to neutral, not friends
+                profile.notes.push("fez as pazes comigo, pagou a reparação".into());
+                profile.update_relationship();
+                memory.episodes.add(crate::cognitive::memory::Episode {
+                    timestamp: chrono::Utc::now(),
+                    event_type: crate::cognitive::memory::EpisodeType::Custom("trégua".into()),
+                    description: format!("Fiz trégua com {} depois dele pagar a reparação", sender),
+                    location: None,
+                    players_involved: vec![sender.to_string()],
+                    emotional_impact: 2,
+                });
+                memory.save();
+                line
+            }
+            TruceStep::Refuse(line) => {
+                let profile = memory.social.get_or_create(sender);
+                profile.hold_grudge("recusou fazer as pazes", 2);
+                profile.trust_level = (profile.trust_level - 5).clamp(-100, 100);
+                profile.update_relationship();
+                memory.save();
+                line
+            }
         }
+    };
+    *state.last_chat.lock().unwrap() = Instant::now();
+    crate::events::say(bot, channel.wrap(&line, config));
+    true
+}
+
+/// A trade we committed to: hand over `item`, and they owe `payment` (if haggled)
+struct Deal {
+    item: String,
+    quantity: u32,
+    payment: Option<(String, u32)>,
+}
+
+/// Feed a chat message through the trade logic: open/advance a haggle, or close a deal
+fn advance_trade(state: &State, sender: &str, content: &str) -> Option<Deal> {
+    let mood = state.personality.lock().unwrap().mood.clone();
+    let mut memory = state.memory.lock().unwrap();
+    let trust = memory.social.players.get(sender).map(|p| p.trust_level).unwrap_or(0);
+    let economy = &mut memory.economy;
+    economy.forget_settled_negotiations(sender);
+
+    // Ongoing haggle: their message is an answer to our price
+    if economy.negotiations.get(sender).is_some_and(|n| n.is_active()) {
+        let msg = content.to_lowercase();
+        let give_up = ["esquece", "deixa quieto", "nao quero", "não quero", "desisto", "muito caro"];
+        if give_up.iter().any(|w| msg.contains(w)) {
+            economy.negotiations.get_mut(sender)?.walk_away();
+            info!("[NEGOTIATION] 🚪 {} desistiu da negociação", sender);
+            return None;
+        }
+
+        let (offer_item, offer_qty) = economy.parse_offer(content)?;
+        let offer_value = economy.value_of(&offer_item) * offer_qty as f32;
+        let negotiation = economy.negotiations.get_mut(sender)?;
+        let flex = flexibility(&mood, trust, negotiation.ask);
+        let reply = negotiation.respond(&offer_item, offer_qty, offer_value, flex);
+        let (item, quantity) = (negotiation.item.clone(), negotiation.quantity);
+        info!("[NEGOTIATION] 🤝 {} ofereceu {} x{} por {} x{} → {:?}", sender, offer_item, offer_qty, item, quantity, reply);
+
+        if reply != NegotiationMove::Accept {
+            return None;
+        }
+        economy.observe_exchange(&item, quantity, &offer_item, offer_qty, PriceSource::OwnTrade);
+        return Some(Deal { item, quantity, payment: Some((offer_item, offer_qty)) });
+    }
+
+    let (item, quantity) = detect_trade_request(content)?;
+    if item == "item" {
+        return None;
+    }
+    match economy.evaluate_request(sender, &item, quantity) {
+        TradeDecision::Accept(_) => Some(Deal { item, quantity, payment: None }),
+        TradeDecision::Negotiate(_) => {
+            let value = economy.value_of(&item) * quantity as f32;
+            let flex = flexibility(&mood, trust, value);
+            let negotiation = Negotiation::open(&item, quantity, value, flex);
+            info!("[NEGOTIATION] 💬 Abrindo com {}: {} x{} por {:.0} ferro", sender, item, quantity, negotiation.ask);
+            economy.negotiations.insert(sender.to_string(), negotiation);
+            None
+        }
+        _ => None,
     }
-    None
 }
 
-/// Public wrapper for bot.rs to use
-pub fn extract_sender_pub(message: &str) -> Option<(&str, &str)> {
-    extract_sender(message)
+/// Short on what we promised? Grab the rest from the base chests first
+async fn restock(bot: &Client, state: &State, item: &str, quantity: u32) {
+    let have = Inventory::read(bot).count_of(item);
+    if have < quantity {
+        storage::fetch(bot, &state.memory, item, quantity - have).await;
+    }
+}
+
+async fn execute_trade(bot: Client, state: State, player: String, deal: Deal) {
+    let Deal { item, quantity, payment } = deal;
+    if !state.trader.try_begin() {
+        info!("[TRADER] ⏳ Já tô entregando outra coisa, {} vai ter que esperar", player);
+        return;
+    }
+
+    restock(&bot, &state, &item, quantity).await;
+    let outcome = trader::give_item(bot.clone(), &player, &item, quantity).await;
+    state.trader.finish();
+
+    let given = match &outcome {
+        GiveOutcome::Delivered { quantity, .. } | GiveOutcome::Partial { quantity, .. } => *quantity,
+        GiveOutcome::NotInInventory => {
+            crate::events::say(&bot, format!("pô {} acabou meu {}, foi mal", player, item));
+            return;
+        }
+        GiveOutcome::PlayerNotFound | GiveOutcome::CouldNotReach => {
+            crate::events::say(&bot, format!("{} cola aqui perto q eu te passo", player));
+            return;
+        }
+        GiveOutcome::ThrowFailed => return,
+    };
+
+    let mut memory = state.memory.lock().unwrap();
+    match &payment {
+        Some((price_item, price_qty)) => memory.economy.record_sale(&player, &item, given, price_item, *price_qty),
+        None => memory.economy.record_gift(&player, &item, given, "trade aceito"),
+    }
+    memory.episodes.add(crate::cognitive::memory::Episode {
+        timestamp: chrono::Utc::now(),
+        event_type: crate::cognitive::memory::EpisodeType::GaveGift,
+        description: format!("Dei {} x{} pro {}", item, given, player),
+        location: None,
+        players_involved: vec![player.clone()],
+        emotional_impact: 1,
+    });
+    memory.save();
+}
+
+/// Farthest a goto from chat sends us, per axis
+pub(crate) const TOOL_REACH: i32 = 200;
+/// Tools that walk us off or take up our time — not for whoever happens to ask
+const HANDS_ON_TOOLS: &[&str] = &["goto", "follow", "mine"];
+/// Trust at which someone's word is enough: their tpa, their errands
+const TRUSTED: i32 = 60;
+
+/// What the LLM can do besides talking. Everything is on behalf of whoever we're answering
+fn tools() -> Vec<Tool> {
+    vec![
+        Tool {
+            name: "goto",
+            description: "Andar até uma coordenada que o jogador pediu",
+            parameters: json!({ "type": "object", "properties": {
+                "x": { "type": "integer" }, "y": { "type": "integer" }, "z": { "type": "integer" },
+            }, "required": ["x", "y", "z"] }),
+        },
+        Tool {
+            name: "mine",
+            description: "Ir minerar um bloco, tipo diamond_ore ou oak_log",
+            parameters: json!({ "type": "object", "properties": {
+                "block": { "type": "string", "description": "id do minecraft" },
+            }, "required": ["block"] }),
+        },
+        Tool {
+            name: "follow",
+            description: "Ir até onde um jogador está agora",
+            parameters: json!({ "type": "object", "properties": {
+                "player": { "type": "string" },
+            }, "required": ["player"] }),
+        },
+        Tool {
+            name: "give_item",
+            description: "Dar um item pra quem pediu, se valer a pena",
+            parameters: json!({ "type": "object", "properties": {
+                "item": { "type": "string" }, "quantity": { "type": "integer" },
+            }, "required": ["item"] }),
+        },
+    ]
+}
+
+/// Would we do `tool` because `sender` asked? Owners always. Walking and mining only for
+/// friends or the trusted; giving is up to the economy. Never for a blacklisted player
+fn may_use_tool(owner: bool, memory: &Memory, sender: &str, tool: &str) -> bool {
+    if owner {
+        return true;
+    }
+    if memory.economy.is_blacklisted(sender) {
+        return false;
+    }
+    !HANDS_ON_TOOLS.contains(&tool)
+        || memory.social.players.get(sender).is_some_and(|p| {
+            p.trust_level >= TRUSTED || matches!(p.relationship, Relationship::Friend | Relationship::BestFriend)
+        })
+}
+
+/// Turn one of the LLM's calls into motor commands, a goal or a trade
+fn run_tool(bot: &Client, state: &State, sender: &str, call: &ToolCall) {
+    let owner = Config::load().is_owner(sender);
+    if !may_use_tool(owner, &state.memory.lock().unwrap(), sender, &call.name) {
+        info!("[BRAIN] ⛔ Ignorando {} pedido pelo {}, não confio nele pra isso", call.name, sender);
+        return;
+    }
+    let arg = |key: &str| call.args[key].as_str().map(str::to_string);
+    let number = |key: &str| call.args[key].as_i64().or_else(|| call.args[key].as_f64().map(|n| n as i64));
+    // The motor lives in the bot's state, not the brain's
+    let motor = || crate::session::all().into_iter().find(|s| s.bot.entity == bot.entity).map(|s| s.state.motor);
+    info!("[BRAIN] 🛠️ {} pediu {}({})", sender, call.name, call.args);
+    match call.name.as_str() {
+        "goto" => {
+            let (Some(x), Some(y), Some(z)) = (number("x"), number("y"), number("z")) else { return };
+            let (x, y, z) = (x as i32, y as i32, z as i32);
+            let pos = bot.position();
+            if (x - pos.x as i32).abs() > TOOL_REACH || (z - pos.z as i32).abs() > TOOL_REACH {
+                info!("[BRAIN] 🚫 {} {} {} é longe demais", x, y, z);
+                return;
+            }
+            if let Some(motor) = motor() {
+                motor.queue(MotorCommand::GotoBlock { x, y, z });
+            }
+        }
+        "follow" => {
+            let Some(player) = arg("player") else { return };
+            let Some(pos) = bot.player_uuid_by_username(&player)
+                .and_then(|uuid| bot.entity_by_uuid(uuid))
+                .and_then(|entity| bot.get_entity_component::<Position>(entity))
+            else {
+                info!("[BRAIN] 👀 Não tô vendo o {}", player);
+                return;
+            };
+            if let Some(motor) = motor() {
+                motor.queue_urgent(MotorCommand::GotoBlock { x: pos.x.round() as i32, y: pos.y.round() as i32, z: pos.z.round() as i32 });
+            }
+        }
+        "mine" => {
+            let Some(block) = arg("block") else { return };
+            let block = block.trim_start_matches("minecraft:").to_lowercase();
+            let name = format!("Minerar {}", block.replace('_', " "));
+            let mut goals = state.goals.lock().unwrap();
+            if !goals.goals.iter().any(|g| g.name == name && g.is_open()) {
+                let mut goal = Goal::new(&name, &format!("{} pediu no chat", sender), GoalPriority::Medium);
+                goal.assigned_by = Some(sender.to_string());
+                goals.add_goal(goal);
+            }
+        }
+        "give_item" => {
+            let Some(item) = arg("item") else { return };
+            let item = normalize_item_name(&item);
+            let quantity = number("quantity").unwrap_or(1).clamp(1, 64) as u32;
+            let decision = state.memory.lock().unwrap().economy.evaluate_request(sender, &item, quantity);
+            // The LLM decides to offer, the economy decides if we can afford it
+            if !matches!(decision, TradeDecision::Accept(_)) {
+                info!("[BRAIN] 💸 Não vou dar {} x{} pro {}: {:?}", item, quantity, sender, decision);
+                return;
+            }
+            let deal = Deal { item, quantity, payment: None };
+            crate::bus::spawn(execute_trade(bot.clone(), state.clone(), sender.to_string(), deal).instrument(info_span!("trader")));
+        }
+        other => warn!("[BRAIN] ❓ LLM chamou uma ferramenta que não existe: {}", other),
+    }
+}
+
+/// Terms agreed with someone we don't fully trust: they drop first, we verify, then release
+fn start_escrow(bot: &Client, state: &State, player: &str, we_give: (String, u32), they_give: (String, u32)) {
+    let pos = bot.position();
+    let spot = [pos.x.floor() as i32, pos.y.floor() as i32, pos.z.floor() as i32];
+    let trade = EscrowTrade::new(player, spot, we_give, they_give);
+    info!("[ESCROW] 🔒 Troca com {}: {} x{} por {} x{}",
+        player, trade.we_give.0, trade.we_give.1, trade.they_give.0, trade.they_give.1);
+    crate::events::say(bot, trade.instructions());
+    let mut memory = state.memory.lock().unwrap();
+    memory.economy.open_escrow(trade);
+    memory.save();
+}
+
+/// Their side checked out — throw ours
+pub async fn release_escrow(bot: Client, state: State, mut trade: EscrowTrade) {
+    state.trader.wait_turn().await;
+    // Our turn took so long the deadline passed — it's being refunded instead
+    let still_ours = {
+        let mut memory = state.memory.lock().unwrap();
+        match memory.economy.escrow_mut(&trade.player) {
+            Some(e) if e.phase == EscrowPhase::Releasing => {
+                e.updated_at = chrono::Utc::now(); // The clock starts over now that we're on it
+                true
+            }
+            _ => false,
+        }
+    };
+    if !still_ours {
+        state.trader.finish();
+        return;
+    }
+    let (item, quantity) = trade.we_give.clone();
+    restock(&bot, &state, &item, quantity).await;
+    let outcome = trader::give_item(bot.clone(), &trade.player, &item, quantity).await;
+    state.trader.finish();
+
+    match outcome {
+        GiveOutcome::Delivered { .. } => {
+            crate::events::say(&bot, format!("tá na mão {}, negócio fechado", trade.player));
+            let mut memory = state.memory.lock().unwrap();
+            memory.economy.close_escrow(&trade.player, EscrowPhase::Completed);
+            memory.episodes.add(crate::cognitive::memory::Episode {
+                timestamp: chrono::Utc::now(),
+                event_type: crate::cognitive::memory::EpisodeType::TradeCompleted,
+                description: format!("Troquei {} x{} por {} x{} com {}",
+                    item, quantity, trade.they_give.0, trade.they_give.1, trade.player),
+                location: Some(trade.spot),
+                players_involved: vec![trade.player.clone()],
+                emotional_impact: 2,
+            });
+            memory.save();
+        }
+        GiveOutcome::Partial { quantity: given, .. } => {
+            // Part of ours went out — only what they paid for the rest goes back
+            trade.delivered = given;
+            if let Some(e) = state.memory.lock().unwrap().economy.escrow_mut(&trade.player) {
+                e.delivered = given;
+            }
+            crate::events::say(&bot, format!("{} só tinha {} {}, te devolvo o resto", trade.player, given, item.replace('_', " ")));
+            refund_escrow(bot, state, trade).await;
+        }
+        _ => {
+            // Couldn't hold up our end — give their stuff back
+            crate::events::say(&bot, format!("pô {} deu ruim do meu lado, vou te devolver", trade.player));
+            refund_escrow(bot, state, trade).await;
+        }
+    }
+}
+
+/// Deal fell through with their items in our pocket — hand back whatever our side didn't pay for
+pub async fn refund_escrow(bot: Client, state: State, trade: EscrowTrade) {
+    let due = trade.refund_due();
+    let returned = if due == 0 {
+        0
+    } else {
+        state.trader.wait_turn().await;
+        let outcome = trader::give_item(bot.clone(), &trade.player, &trade.they_give.0, due).await;
+        state.trader.finish();
+        match outcome {
+            GiveOutcome::Delivered { quantity, .. } | GiveOutcome::Partial { quantity, .. } => {
+                info!("[ESCROW] ↩️ Devolvi {} x{} pro {}", trade.they_give.0, quantity, trade.player);
+                quantity
+            }
+            _ => 0,
+        }
+    };
+
+    let mut memory = state.memory.lock().unwrap();
+    if returned < due {
+        // Couldn't reach them, or ran short — the rest becomes something we owe
+        let ledger = memory.economy.get_ledger(&trade.player);
+        ledger.debts_we_owe.push(crate::systems::economy::Debt {
+            item: trade.they_give.0.clone(),
+            quantity: due - returned,
+            created_at: chrono::Utc::now(),
+            reason: "devolução de escrow".into(),
+            paid: false,
+            interest: 0,
+            due_by: None,
+        });
+    }
+    memory.economy.close_escrow(&trade.player, EscrowPhase::Failed);
+    memory.save();
+}
+
+/// "!reload" and friends from the bot's owners. True if it was one.
+/// Every bot in the swarm reads the same chat: "!goto ..." is for the main bot, "Nick !goto ..." for that one
+fn run_owner_command(bot: &Client, state: &State, owner: &str, content: &str) -> bool {
+    let (target, command) = addressed(content);
+    let me = state.me.name(&Config::load());
+    let ours = match target {
+        Some(nick) => nick.eq_ignore_ascii_case(&me),
+        None => !state.me.extra(),
+    };
+    if !ours {
+        return command.starts_with('!'); // Someone else's order — not small talk either
+    }
+    let Some(reply) = owner_command(crate::session::of(bot).as_ref(), owner, command) else { return false };
+    let reply: String = reply.chars().take(200).collect(); // Chat limit
+    crate::events::say(bot, format!("/msg {} {}", owner, reply));
+    true
+}
+
+/// "Nick !goto 1 2 3" → (Some("Nick"), "!goto 1 2 3"); a bare command has no target
+pub fn addressed(content: &str) -> (Option<&str>, &str) {
+    let content = content.trim();
+    match content.split_once(' ') {
+        Some((nick, rest)) if !nick.starts_with('!') && rest.trim_start().starts_with('!') => (Some(nick), rest.trim_start()),
+        _ => (None, content),
+    }
+}
+
+/// Run an owner command from wherever it came (game chat, Discord) on `session`'s bot.
+/// None = not a command
+pub fn owner_command(session: Option<&crate::session::Session>, owner: &str, content: &str) -> Option<String> {
+    // Orders for a bot need it connected; config ones don't
+    let on_bot = |run: &dyn Fn(&crate::session::Session) -> String| session.map_or_else(|| "não tô conectado".to_string(), run);
+    match content.trim() {
+        "!reload" => {
+            let reply = match Config::reload() {
+                Ok(changed) if changed.is_empty() => "config recarregada, nada mudou".to_string(),
+                Ok(changed) => format!("config recarregada: {}", changed.join(", ")),
+                Err(errors) => {
+                    for error in &errors {
+                        error!("[CONFIG] ❌ {}", error);
+                    }
+                    format!("config com erro, mantive a antiga ({} problema(s), olha o console): {}", errors.len(), errors[0])
+                }
+            };
+            info!("[CONFIG] 🔄 {} pediu reload → {}", owner, reply);
+            Some(reply)
+        }
+        // Which account we're on, and which ones got banned here
+        "!conta" | "!account" => {
+            let config = Config::load();
+            let pool = crate::accounts::AccountPool::load();
+            let reply = pool.summary(&config.account_pool(), config.active_account, &config.profile().name);
+            Some(format!("{} | servidor: {}", reply, config.profile().name))
+        }
+        // "!feature" lists, "!feature dreamer off" flips one
+        command if command == "!feature" || command.starts_with("!feature ") => {
+            let args: Vec<&str> = command.split_whitespace().skip(1).collect();
+            let reply = match args.as_slice() {
+                [] => Config::load().features.summary(),
+                [name, state] => {
+                    let on = match *state {
+                        "on" | "liga" | "ligar" => Some(true),
+                        "off" | "desliga" | "desligar" => Some(false),
+                        _ => None,
+                    };
+                    match on.map(|on| (on, Config::set_feature(name, on))) {
+                        Some((on, Ok(()))) => format!("{} {}", name, if on { "ligado" } else { "desligado" }),
+                        Some((_, Err(e))) => e,
+                        None => "é on ou off".to_string(),
+                    }
+                }
+                _ => "uso: !feature <nome> on|off".to_string(),
+            };
+            info!("[CONFIG] 🎚️ {} → {}: {}", owner, command, reply);
+            Some(reply)
+        }
+        // "!construir casa" starts a blueprint next to the bot, "!construir parar" stops it
+        command if ["!construir", "!build"].iter().any(|c| command == *c || command.starts_with(&format!("{} ", c))) => {
+            let arg = command.split_once(' ').map(|(_, arg)| arg.trim()).unwrap_or("");
+            let reply = on_bot(&|s| crate::bot::build_command(s, owner, arg));
+            info!("[BUILDER] 🏗 {} → {}: {}", owner, command, reply);
+            Some(reply)
+        }
+        // Direct orders — no LLM in the way
+        command if command == "!goto" || command.starts_with("!goto ") => {
+            let reply = on_bot(&|s| crate::bot::goto_command(s, command.trim_start_matches("!goto").trim()));
+            info!("[OWNER] 🧭 {} → {}: {}", owner, command, reply);
+            Some(reply)
+        }
+        command if ["!mine", "!minerar"].iter().any(|c| command == *c || command.starts_with(&format!("{} ", c))) => {
+            let arg = command.split_once(' ').map(|(_, arg)| arg.trim()).unwrap_or("");
+            let reply = on_bot(&|s| crate::bot::mine_command(s, owner, arg));
+            info!("[OWNER] ⛏️ {} → {}: {}", owner, command, reply);
+            Some(reply)
+        }
+        // Chest runs: "!guardar comida", "!pegar iron_ingot 32"
+        command if command == "!guardar" || command.starts_with("!guardar ") => {
+            Some(on_bot(&|s| crate::bot::stash_command(s, owner, command.trim_start_matches("!guardar").trim())))
+        }
+        command if command.starts_with("!pegar ") => Some(on_bot(&|s| crate::bot::fetch_command(s, owner, command.trim_start_matches("!pegar").trim()))),
+        "!status" => Some(on_bot(&crate::bot::status_command)),
+        "!stop" | "!parar" => Some(on_bot(&|s| crate::bot::stop_command(s, owner))),
+        _ => None,
+    }
+}
+
+impl crate::plugins::registry::BotSystem for State {
+    fn name(&self) -> &'static str {
+        "brain"
+    }
+
+    /// Chat replies and personality decay
+    fn on_event(&self, bot: Client, event: Event) -> crate::plugins::registry::SystemFuture {
+        Box::pin(handle(bot, event, self.clone()))
+    }
+
+    fn context_summary(&self) -> Option<String> {
+        Some(format!("humor {:?}", self.personality.lock().unwrap().mood))
+    }
 }
 
 pub async fn handle(_bot: Client, event: Event, state: State) -> anyhow::Result<()> {
     match event {
         Event::Chat(chat) => {
-            let raw_message = chat.message().to_string();
+            let raw_message = chat_format::strip_colors(&chat.message().to_string());
+            let config = Config::load();
+            let format = ChatFormat::from_config(&config);
 
-            // Add to chat history
+            // Cracked-server login prompts come before anything else
+            if answer_auth_prompt(&_bot, &state, &config, &chat, &raw_message) {
+                return Ok(());
+            }
+            // Queue chatter, or anything at all while we wait in line — no LLM calls.
+            // Only the server talks about the queue: a player typing "position in queue" doesn't count
             {
-                let mut history = state.chat_history.lock().unwrap();
-                history.push(raw_message.clone());
-                if history.len() > 20 {
-                    history.drain(0..10);
+                let mut queue = state.queue.lock().unwrap();
+                let from_server = matches!(chat, ChatPacket::System(_));
+                if (from_server && queue.on_message(&raw_message)) || queue.waiting {
+                    return Ok(());
                 }
             }
 
-            // Extract sender
-            let (sender, content) = match extract_sender(&raw_message) {
-                Some(s) => s,
-                None => return Ok(()), // System message or unparseable
+            // Signed player chat says who sent it; plugin chat (system messages) has to be read.
+            // Party/team/faction chat first — it often looks like global chat with a tag in front
+            let from_packet = chat_format::from_packet(&_bot, &chat);
+            let (channel, sender, content) = match (&from_packet, chat_channel::parse_channel_message(&raw_message)) {
+                (Some((sender, content)), _) => (ChatChannel::Global, sender.as_str(), content.as_str()),
+                (None, Some(parsed)) => parsed,
+                (None, None) => match format.parse(&raw_message, &|name| chat_format::is_online(&_bot, name)) {
+                    Some((sender, content)) => (ChatChannel::Global, sender, content),
+                    None => {
+                        // System message — maybe a party invite or a /tpa prompt
+                        if !config.observing() {
+                            accept_party_invite(&_bot, &state, &config, &raw_message);
+                            answer_tpa_request(&_bot, &state, &config, &raw_message);
+                            react_to_server_event(&_bot, &state, &config, &raw_message);
+                        }
+                        remember_line(&mut state.chat_history.lock().unwrap(), raw_message.clone());
+                        return Ok(());
+                    }
+                },
             };
+            // Nicknames → real usernames, so memory and trust stick to the account
+            let sender_name = chat_format::resolve_name(&_bot, sender);
+            let sender = sender_name.as_str();
 
-            // Ignore self
-            let config = Config::load();
-            if sender == config.bot_name {
+            // Add to that channel's history
+            {
+                let mut channels = state.channel_history.lock().unwrap();
+                let mut global = state.chat_history.lock().unwrap();
+                let history = match channel {
+                    ChatChannel::Global => &mut *global,
+                    _ => channels.entry(channel).or_default(),
+                };
+                remember_line(history, raw_message.clone());
+            }
+
+            // Ignore self (and our other accounts)
+            if config.is_own_account(sender) {
                 return Ok(());
             }
 
-            // Update social memory
-            {
-                let mut memory = state.memory.lock().unwrap();
-                memory.social.record_interaction(sender, 1); // +1 trust for chatting
-                let player = memory.social.get_or_create(sender);
-                player.add_message(content);
+            // Owner commands, before anything social
+            if config.is_owner(sender) && run_owner_command(&_bot, &state, sender, content) {
+                return Ok(());
             }
 
-            // Personality event
-            {
-                let mut personality = state.personality.lock().unwrap();
-                personality.on_event(&PersonalityEvent::ReceivedChat);
+            let taped = !state.me.extra(); // Only the main bot is on tape
+            if taped {
+                crate::replay::heard(channel, sender, content);
+            }
+            let judgement = absorb_chat(&state, &config, channel, sender, content);
+            if taped {
+                crate::replay::judged(sender, judgement);
+            }
+            let directed = judgement.directed;
+            // Talking to us while no owner is around — the Discord bridge pings them
+            if directed && !_bot.tab_list().values().any(|p| config.is_owner(&p.profile.name)) {
+                crate::events::emit(crate::events::BotEvent::Mention { player: sender.to_string(), message: content.to_string() });
+            }
+            // Observer: it's in memory now, that's all
+            if config.observing() {
+                return Ok(());
+            }
+
+            // Rivals and enemies get walked toward a truce instead of the silent treatment
+            if advance_truce(&_bot, &state, &config, channel, sender, content, directed) {
+                return Ok(());
             }
 
+            // Trades: haggle across messages, physically deliver once there's a deal
+            let in_negotiation = {
+                // Only an open haggle counts — a settled one mustn't get them past the filters below
+                let haggling = || state.memory.lock().unwrap().economy.negotiations.get(sender).is_some_and(|n| n.is_active());
+                let answering = haggling();
+                if let Some(deal) = advance_trade(&state, sender, content) {
+                    let trusted = state.memory.lock().unwrap().economy.tier_of(sender) == CreditTier::Vip;
+                    match deal.payment {
+                        // Paid deals with anyone short of VIP go through escrow
+                        Some(payment) if !trusted => start_escrow(&_bot, &state, sender, (deal.item, deal.quantity), payment),
+                        _ => { crate::bus::spawn(execute_trade(_bot.clone(), state.clone(), sender.to_string(), deal).instrument(info_span!("trader"))); }
+                    }
+                }
+                answering || haggling()
+            };
+
             // Decide if we should respond
-            let should_respond = {
-                let social_engine = state.social.lock().unwrap();
+            let should_respond = in_negotiation || {
                 let memory = state.memory.lock().unwrap();
+                let mut social_engine = state.social.lock().unwrap();
+                social_engine.presence.on_chat(sender);
+                let in_conversation = social_engine.is_conversing(sender);
                 let style = social_engine.should_respond(sender, &memory.social);
 
                 // Always respond to direct mentions
-                let mentions_us = content.to_lowercase().contains(&config.bot_name.to_lowercase());
+                let mentions_us = content.to_lowercase().contains(&state.me.name(&config).to_lowercase());
 
-                match style {
+                // Don't leave them hanging mid-conversation; party chat is always for us
+                in_conversation || channel != ChatChannel::Global || match style {
                     ResponseStyle::Friendly => true,
-                    ResponseStyle::Casual => mentions_us || rand::random::<f32>() < 0.6,
-                    ResponseStyle::Cautious => mentions_us || rand::random::<f32>() < 0.3,
+                    ResponseStyle::Casual => mentions_us || rand::random::<f32>() < config.casual_reply_chance,
+                    ResponseStyle::Cautious => mentions_us || rand::random::<f32>() < config.cautious_reply_chance,
                     ResponseStyle::Cold => mentions_us,
                     ResponseStyle::Hostile => false,
                 }
             };
 
             // Check triggers (broader than before — responds to more things)
-            let triggers = [
-                "lag", "tps", "java", "code", "bot", "pedro", "frankfurt",
-                "farm", "mine", "build", "help", "ajuda", "diamante",
-                "redstone", "encantamento", "casa", "base", "oi", "eai",
-                "salve", "fala", "bora", "vem", "cadê", "morri",
-            ];
-            let has_trigger = triggers.iter().any(|&t| content.to_lowercase().contains(t));
-            let mentions_us = content.to_lowercase().contains(&config.bot_name.to_lowercase());
+            let has_trigger = config.chat_triggers.iter().any(|t| content.to_lowercase().contains(t.as_str()));
+            let mentions_us = content.to_lowercase().contains(&state.me.name(&config).to_lowercase());
 
             if !should_respond && !has_trigger && !mentions_us {
                 return Ok(());
             }
+            // No-LLM bot: trades and reactions above still run, free-form replies don't
+            if !config.features.llm {
+                return Ok(());
+            }
 
             // Rate limit
             {
                 let mut last_chat = state.last_chat.lock().unwrap();
-                if last_chat.elapsed() < Duration::from_secs(5) {
+                if last_chat.elapsed() < Duration::from_secs(config.chat_cooldown_secs) {
                     return Ok(());
                 }
                 *last_chat = Instant::now();
             }
 
-            // Build context and call Gemini
-            let context = build_context(&state, content, sender);
-            let use_pro = content.to_lowercase().contains("java")
-                || content.to_lowercase().contains("code")
-                || content.to_lowercase().contains("redstone")
-                || content.len() > 100; // Long messages get Pro
-
-            let model = if use_pro {
-                config.model_pro.clone()
-            } else {
-                config.model_flash.clone()
-            };
+            state.social.lock().unwrap().on_conversation_message(sender, content);
+
+            // Context is built in the task below, once recall has had its say
+            let lower = content.to_lowercase();
+            let use_pro = config.pro_keywords.iter().any(|k| lower.contains(k.as_str()))
+                || content.len() >= config.pro_min_length; // Long messages get Pro
 
-            let api_key = config.gemini_api_key.clone();
-            let bot_name = config.bot_name.clone();
+            let model = crate::llm::provider(&config.llm_provider)
+                .map_or_else(|| config.model_flash.clone(), |p| p.model(&config, use_pro));
+            let typos_on = config.features.typos;
+            let bot_name = state.me.name(&config);
+            let sender = sender.to_string();
+            let content = content.to_string();
 
-            println!("[BRAIN] 🧠 Responding to <{}> using {}", sender, model);
+            info!("[BRAIN] 🧠 Responding to <{}> using {}", sender, model);
+            let (span_sender, span_model) = (sender.clone(), model.clone());
 
             // Spawn async to not block
             let state_clone = state.clone();
             let bot_clone = _bot.clone();  // Clone bot so we can chat inside spawn
-            tokio::spawn(async move {
-                let client = reqwest::Client::new();
-                let url = format!(
-                    "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
-                    model, api_key
-                );
-
-                let request_body = GeminiRequest {
-                    contents: vec![GContent {
-                        role: "user".into(),
-                        parts: vec![GPart { text: context }],
-                    }],
-                    generation_config: GenerationConfig {
-                        max_output_tokens: 60, // Short like a real player
-                        temperature: 0.9,       // Creative
-                    },
-                };
+            crate::bus::spawn(async move {
+                let _call = crate::watchdog::llm_started();
+                let recalled = recall::related(&state_clone.recall, &state_clone.memory, &content, RECENT_EPISODES).await;
+                // Build context and call the LLM — it may answer, act, or both
+                let context = build_context(&state_clone, &content, &sender, channel, &recalled);
+                info!("[BRAIN] 📡 Calling {}...", config.llm_provider);
 
-                println!("[BRAIN] 📡 Calling Gemini API...");
-
-                // Retry loop for rate limits (429)
-                let max_retries = 3;
-                let mut attempt = 0;
-                let response_result = loop {
-                    attempt += 1;
-                    match client.post(&url).json(&request_body).send().await {
-                        Ok(resp) => {
-                            let status = resp.status();
-                            if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
-                                let body = resp.text().await.unwrap_or_default();
-                                if attempt < max_retries {
-                                    let wait_secs = 2u64.pow(attempt as u32); // 2s, 4s, 8s
-                                    println!("[BRAIN] ⏳ Rate limited (429), retry {}/{} in {}s...", attempt, max_retries, wait_secs);
-                                    tokio::time::sleep(tokio::time::Duration::from_secs(wait_secs)).await;
-                                    continue;
-                                } else {
-                                    println!("[BRAIN] ❌ Rate limited (429) after {} retries. Quota esgotada.", max_retries);
-                                    println!("[BRAIN] 📋 {}", &body[..body.len().min(200)]);
-                                    break None;
-                                }
-                            }
-                            if !status.is_success() {
-                                let body = resp.text().await.unwrap_or_else(|_| "<failed to read body>".into());
-                                println!("[BRAIN] ❌ API HTTP Error {}: {}", status, body);
-                                break None;
-                            }
-                            break Some(resp);
-                        }
-                        Err(e) => {
-                            println!("[BRAIN] ❌ API Network Error: {}", e);
-                            println!("[BRAIN] 🔌 Check internet connection and API key");
-                            break None;
+                // Short like a real player, creative
+                match crate::llm::complete_with_tools(use_pro, context, 60, 0.9, tools()).await {
+                    Ok((reply, model)) => {
+                        for call in &reply.calls {
+                            run_tool(&bot_clone, &state_clone, &sender, call);
                         }
-                    }
-                };
+                        let raw_reply = reply.text;
 
-                let resp = match response_result {
-                    Some(r) => r,
-                    None => return, // All retries failed or error
-                };
-                let body_text = match resp.text().await {
-                    Ok(t) => t,
-                    Err(e) => {
-                        println!("[BRAIN] ❌ Failed to read response body: {}", e);
-                        return;
-                    }
-                };
-                match serde_json::from_str::<GeminiResponse>(&body_text) {
-                    Ok(json) => {
-                        match json.candidates {
-                            Some(candidates) if !candidates.is_empty() => {
-                                let first = &candidates[0];
-                                if let Some(part) = first.content.parts.first() {
-                                    let raw_reply = part.text.trim().to_string();
-
-                                    // === TYPOS MIDDLEWARE ===
-                                    let current_mood = {
-                                        let p = state_clone.personality.lock().unwrap();
-                                        p.mood.clone()
-                                    };
-                                    let reply = typos::apply_typos(&raw_reply, &current_mood);
-
-                                    // Truncate to MC chat limit (256 chars)
-                                    let reply = if reply.len() > 250 {
-                                        reply[..250].to_string()
-                                    } else {
-                                        reply
-                                    };
-                                    println!("[BRAIN] 💬 Raw: {}", raw_reply);
-                                    println!("[BRAIN] 🤙 Sent: {}", reply);
-                                    bot_clone.chat(&reply); // 🔊 FALA, PEDRTX!
-
-                                    // Add to history
-                                    let mut history = state_clone.chat_history.lock().unwrap();
-                                    history.push(format!("<{}> {}", bot_name, reply));
-                                } else {
-                                    println!("[BRAIN] ⚠️ Gemini returned candidate with no parts");
-                                }
-                            }
-                            Some(_) => {
-                                println!("[BRAIN] ⚠️ Gemini returned empty candidates array");
+                        // === TYPOS MIDDLEWARE ===
+                        let current_mood = {
+                            let p = state_clone.personality.lock().unwrap();
+                            p.mood.clone()
+                        };
+                        info!("[BRAIN] 💬 Raw: {}", raw_reply);
+
+                        // Long answers go out as 2-3 lines, like someone hitting enter mid-thought
+                        let mut sent = vec![];
+                        // Only tool calls, nothing to say → the loop doesn't run
+                        let parts: Vec<String> = typos::split_reply(&raw_reply).into_iter().filter(|p| !p.is_empty()).collect();
+                        for (i, part) in parts.iter().enumerate() {
+                            if i > 0 {
+                                // Time to type the next line
+                                let typing_ms = 400 + part.chars().count() as u64 * 25;
+                                tokio::time::sleep(Duration::from_millis(typing_ms.min(3000))).await;
                             }
-                            None => {
-                                println!("[BRAIN] ⚠️ Gemini returned NO candidates. Body: {}", &body_text[..body_text.len().min(500)]);
+                            let line = if typos_on { typos::apply_typos(part, &current_mood) } else { part.clone() };
+                            info!("[BRAIN] 🤙 Sent: {}", line);
+                            crate::events::say(&bot_clone, channel.wrap(&line, &config)); // 🔊 FALA, PEDRTX!
+                            sent.push(line.clone());
+
+                            if let Some(fix) = typos::typo_correction(part, &line) {
+                                tokio::time::sleep(Duration::from_millis(800)).await;
+                                info!("[BRAIN] ✏️ Correction: {}", fix);
+                                crate::events::say(&bot_clone, channel.wrap(&fix, &config));
+                                sent.push(fix);
                             }
                         }
+
+                        let reply = sent.join(" ");
+                        crate::events::emit(crate::events::BotEvent::Llm { player: sender.clone(), model, reply: reply.clone() });
+                        state_clone.social.lock().unwrap().on_our_reply(&sender, &reply);
+
+                        // Add to history
+                        let mut channels = state_clone.channel_history.lock().unwrap();
+                        let mut global = state_clone.chat_history.lock().unwrap();
+                        let history = match channel {
+                            ChatChannel::Global => &mut *global,
+                            _ => channels.entry(channel).or_default(),
+                        };
+                        for line in sent {
+                            history.push(format!("<{}> {}", bot_name, line));
+                        }
                     }
-                    Err(e) => {
-                        println!("[BRAIN] ❌ Failed to parse Gemini JSON: {}", e);
-                        println!("[BRAIN] 📋 Response body: {}", &body_text[..body_text.len().min(500)]);
-                    }
+                    Err(e) => warn!("[BRAIN] ⚠️ Sem resposta do LLM: {}", e),
                 }
 
                 // Auto-save memory periodically
                 let mut counter = state_clone.save_counter.lock().unwrap();
                 *counter += 1;
-                if *counter % 10 == 0 {
+                if (*counter).is_multiple_of(10) {
                     let memory = state_clone.memory.lock().unwrap();
                     memory.save();
-                    println!("[BRAIN] 💾 Memory saved.");
+                    state_clone.goals.lock().unwrap().save();
+                    info!("[BRAIN] 💾 Memory saved.");
                 }
-            });
+            }.instrument(info_span!("llm", player = %span_sender, model = %span_model)));
         }
         Event::Tick => {
             // Personality decay (moods fade over time)
@@ -422,3 +1240,33 @@ pub async fn handle(_bot: Client, event: Event, state: State) -> anyhow::Result<
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strangers_cant_send_us_off() {
+        let mut memory = Memory::default();
+        memory.social.get_or_create("Stranger");
+        memory.social.get_or_create("Steve").trust_level = TRUSTED;
+        memory.social.get_or_create("Alex").relationship = Relationship::Friend;
+        for tool in HANDS_ON_TOOLS {
+            assert!(!may_use_tool(false, &memory, "Stranger", tool), "{}", tool);
+            assert!(!may_use_tool(false, &memory, "Nobody", tool), "{}", tool);
+            assert!(may_use_tool(false, &memory, "Steve", tool), "{}", tool);
+            assert!(may_use_tool(false, &memory, "Alex", tool), "{}", tool);
+            assert!(may_use_tool(true, &memory, "Nobody", tool), "{}", tool);
+        }
+        // Asking for an item is fine — evaluate_request still decides
+        assert!(may_use_tool(false, &memory, "Stranger", "give_item"));
+    }
+
+    #[test]
+    fn orders_go_to_the_bot_they_name() {
+        assert_eq!(addressed("!goto 1 2 3"), (None, "!goto 1 2 3"));
+        assert_eq!(addressed("  Minerador !goto 1 2 3"), (Some("Minerador"), "!goto 1 2 3"));
+        assert_eq!(addressed("!feature dreamer off"), (None, "!feature dreamer off"));
+        assert_eq!(addressed("oi tudo bem"), (None, "oi tudo bem"));
+    }
+}
@@ -1,14 +1,151 @@
 use azalea::prelude::*;
+use azalea::block::BlockStates;
+use azalea::inventory::operations::ThrowClick;
+use azalea::pathfinder::goals::RadiusGoal;
+use azalea::registry::builtin::BlockKind;
+use azalea::Vec3;
+use rand::Rng;
+use std::time::Duration;
+use crate::config::Config;
+use crate::systems::inventory::Inventory;
+use crate::systems::inventory_manager::is_junk;
+use crate::systems::storage;
+use tracing::info;
 
-pub async fn handle(_bot: Client, event: Event, _state: ()) -> anyhow::Result<()> {
-    if let Event::Tick = event {
-        // Logic to check inventory and drop garbage
-        // let menu = bot.menu();
-        // for item in menu.items() {
-        //     if is_garbage(item) {
-        //         // drop item
-        //     }
-        // }
-    }
-    Ok(())
+// ============================================================
+// JUNK DISPOSAL — Pocket full of dirt and rotten flesh? Into the lava
+// lava nearby > trash chest > on the floor, whatever it takes to free slots
+// ============================================================
+
+/// Walk at most this far to find lava
+const LAVA_RANGE: f64 = 24.0;
+/// Trash chest further than this isn't worth the trip
+const TRASH_CHEST_RANGE: f64 = 64.0;
+
+const GRUMBLES: &[&str] = &[
+    "mano pq eu to carregando tanta terra",
+    "joguei um monte de lixo fora, inventario de acumulador",
+    "rotten flesh de novo?? pra lava",
+    "faxina no inventario, q nojo",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Disposal {
+    Lava(u32),
+    TrashChest(u32),
+    Ground(u32),
+    Nothing,
+}
+
+/// Junk we're carrying: the junk items plus the configured extras, minus the keep list
+pub fn junk_in(inventory: &Inventory, config: &Config) -> Vec<String> {
+    junk_among(inventory.counts().into_keys(), &config.junk_extra, &config.junk_keep)
+}
+
+/// Item ids are matched whole — sticky_piston has "stick" in it and still isn't junk
+fn junk_among(items: impl Iterator<Item = String>, extra: &[String], keep: &[String]) -> Vec<String> {
+    let mut junk: Vec<String> = items
+        .filter(|item| is_junk(item) || extra.contains(item))
+        .filter(|item| !keep.contains(item))
+        .collect();
+    junk.sort();
+    junk
+}
+
+/// Closest lava we can see within range
+fn nearby_lava(bot: &Client) -> Option<Vec3> {
+    let here = bot.position();
+    let lava = BlockStates::from(&[BlockKind::Lava][..]);
+    let pos = bot.world().read().find_block(here, &lava)?;
+    let center = pos.center();
+    (center.distance_to(here) <= LAVA_RANGE).then_some(center)
+}
+
+/// Throw every stack of these items the way we're facing
+async fn toss(bot: &Client, items: &[String]) -> u32 {
+    let before: u32 = items.iter().map(|i| Inventory::read(bot).count_of(i)).sum();
+    let Some(inventory) = bot.open_inventory() else {
+        return 0;
+    };
+    for item in items {
+        // Bounded — a stack that won't leave shouldn't loop forever
+        for _ in 0..36 {
+            let Some(slot) = Inventory::read(bot).slot_of(item) else { break };
+            inventory.click(ThrowClick::All { slot: slot as u16 });
+            bot.wait_ticks(2).await;
+        }
+    }
+    inventory.close();
+    bot.wait_ticks(5).await;
+    let after: u32 = items.iter().map(|i| Inventory::read(bot).count_of(i)).sum();
+    before.saturating_sub(after)
+}
+
+/// Get rid of the junk: lava first, then the trash chest, then the floor
+pub async fn dispose(bot: Client, junk: Vec<String>) -> Disposal {
+    if junk.is_empty() {
+        return Disposal::Nothing;
+    }
+    let config = Config::load();
+
+    if let Some(lava) = nearby_lava(&bot) {
+        let arrived = tokio::time::timeout(Duration::from_secs(30), bot.goto(RadiusGoal::new(lava, 3.0))).await;
+        if arrived.is_ok() {
+            bot.look_at(lava);
+            bot.wait_ticks(4).await;
+            let gone = toss(&bot, &junk).await;
+            info!("[JUNK] 🔥 {} itens na lava", gone);
+            return Disposal::Lava(gone);
+        }
+        bot.stop_pathfinding();
+    }
+
+    if let Some(chest) = config.trash_chest {
+        let here = bot.position();
+        let center = Vec3::new(chest[0] as f64 + 0.5, chest[1] as f64 + 0.5, chest[2] as f64 + 0.5);
+        if center.distance_to(here) <= TRASH_CHEST_RANGE
+            && let Some((moved, _)) = storage::deposit(&bot, chest, &junk).await
+        {
+            let gone: u32 = moved.values().sum();
+            info!("[JUNK] 🗑️ {} itens no baú de lixo", gone);
+            return Disposal::TrashChest(gone);
+        }
+    }
+
+    // Nowhere better — still full, so it goes on the floor
+    if Inventory::read(&bot).free_slots() <= config.junk_free_slots {
+        let gone = toss(&bot, &junk).await;
+        info!("[JUNK] 🚮 {} itens jogados no chão", gone);
+        return Disposal::Ground(gone);
+    }
+    Disposal::Nothing
+}
+
+/// Now and then, complain about it
+pub fn grumble() -> Option<String> {
+    let mut rng = rand::thread_rng();
+    rng.gen_bool(0.3).then(|| GRUMBLES[rng.gen_range(0..GRUMBLES.len())].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(items: &[&str]) -> impl Iterator<Item = String> {
+        items.iter().map(|i| i.to_string()).collect::<Vec<_>>().into_iter()
+    }
+
+    #[test]
+    fn test_junk_is_matched_whole() {
+        let carrying = ids(&["sticky_piston", "bone_meal", "bone_block", "suspicious_gravel", "gravel", "dirt", "rotten_flesh"]);
+        assert_eq!(junk_among(carrying, &[], &[]), vec!["dirt", "gravel", "rotten_flesh"]);
+    }
+
+    #[test]
+    fn test_junk_extra_and_keep() {
+        let keep = vec!["bone".to_string()];
+        let extra = vec!["cobblestone".to_string()];
+        let carrying = ids(&["bone", "bone_meal", "cobblestone", "diamond"]);
+        assert_eq!(junk_among(carrying, &extra, &keep), vec!["cobblestone"]);
+    }
 }
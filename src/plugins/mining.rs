@@ -1,5 +1,6 @@
 use azalea::prelude::*;
 use std::sync::{Arc, Mutex};
+use tracing::info;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum MiningState {
@@ -24,23 +25,20 @@ impl Default for State {
 }
 
 pub async fn handle(_bot: Client, event: Event, state: State) -> anyhow::Result<()> {
-    match event {
-        Event::Tick => {
-            let mut current = state.current.lock().unwrap();
-            match *current {
-                MiningState::Idle => {
-                    // Do nothing
-                }
-                MiningState::FindingTree => {
-                    // Placeholder logic
-                    println!("Searching for tree...");
-                    // Change state
-                    *current = MiningState::Idle; 
-                }
-                _ => {}
+    if let Event::Tick = event {
+        let mut current = state.current.lock().unwrap();
+        match *current {
+            MiningState::Idle => {
+                // Do nothing
             }
+            MiningState::FindingTree => {
+                // Placeholder logic
+                info!("Searching for tree...");
+                // Change state
+                *current = MiningState::Idle; 
+            }
+            _ => {}
         }
-        _ => {}
     }
     Ok(())
 }
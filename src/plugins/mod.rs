@@ -6,3 +6,4 @@ pub mod mining;
 pub mod inventory;
 pub mod brain;
 pub mod ping;
+pub mod registry;
@@ -5,6 +5,7 @@ use azalea::prelude::*;
 use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::cmp::Ordering;
 use azalea::BlockPos;
+use tracing::info;
 
 // Simple Node struct for A*
 #[derive(Clone, Copy, Eq, PartialEq)]
@@ -110,10 +111,10 @@ impl Pathfinder {
 pub async fn goto_block(bot: Client, target: BlockPos) {
     let start = bot.position().into(); // approximate to BlockPos
     if let Some(path) = Pathfinder::compute_path(start, target) {
-        println!("Path found with {} steps", path.len());
+        info!("Path found with {} steps", path.len());
         for step in path {
              // bot.look_at(step.center());
-             println!("Walking to {:?}", step); 
+             info!("Walking to {:?}", step); 
              // bot.walk_start();
              // In reality we need to wait until we reach the block.
              // This is a naive implementation that just enables walking.
@@ -122,6 +123,6 @@ pub async fn goto_block(bot: Client, target: BlockPos) {
              // bot.walk_stop();
         }
     } else {
-        println!("No path found to {:?}", target);
+        info!("No path found to {:?}", target);
     }
 }
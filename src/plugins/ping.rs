@@ -1,6 +1,7 @@
 use azalea::prelude::*;
 use std::time::{Instant, Duration};
 use std::sync::{Arc, Mutex};
+use tracing::debug;
 
 #[derive(Clone, Component)]
 pub struct State {
@@ -15,11 +16,21 @@ impl Default for State {
     }
 }
 
+impl crate::plugins::registry::BotSystem for State {
+    fn name(&self) -> &'static str {
+        "ping"
+    }
+
+    fn on_event(&self, bot: Client, event: Event) -> crate::plugins::registry::SystemFuture {
+        Box::pin(handle(bot, event, self.clone()))
+    }
+}
+
 pub async fn handle(_bot: Client, event: Event, state: State) -> anyhow::Result<()> {
     if let Event::Tick = event {
         let mut last_ping = state.last_ping.lock().unwrap();
         if last_ping.elapsed() >= Duration::from_secs(10) {
-            println!("[HEARTBEAT] Bot is alive.");
+            debug!("[HEARTBEAT] Bot is alive.");
             *last_ping = Instant::now();
         }
     }
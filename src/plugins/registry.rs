@@ -0,0 +1,61 @@
+use azalea::prelude::*;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::warn;
+use crate::config::Features;
+
+// ============================================================
+// REGISTRY — Systems that just want every event, run in one loop
+// auto_eat, anti_afk, ping, brain, natural_look, inventory_manager → registry() and nowhere else
+// ============================================================
+
+pub type SystemFuture = Pin<Box<dyn Future<Output = anyhow::Result<()>> + Send>>;
+
+/// Something bot.rs hands every game event to. Filters the events it cares about itself
+pub trait BotSystem: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Switched off in config or with !feature? Asked before every event
+    fn enabled(&self, _features: &Features) -> bool {
+        true
+    }
+
+    fn on_event(&self, bot: Client, event: Event) -> SystemFuture;
+
+    /// One line for the dashboard, if there's anything worth showing
+    fn context_summary(&self) -> Option<String> {
+        None
+    }
+}
+
+/// The systems in the order they run. Cloning shares them
+#[derive(Clone)]
+pub struct Registry {
+    systems: Arc<Vec<Arc<dyn BotSystem>>>,
+}
+
+impl Registry {
+    pub fn new(systems: Vec<Arc<dyn BotSystem>>) -> Self {
+        Self { systems: Arc::new(systems) }
+    }
+
+    /// Run every enabled system, timing each. One failing doesn't stop the rest
+    pub async fn on_event(&self, bot: &Client, event: &Event) {
+        let features = crate::config::Config::load().features;
+        for system in self.systems.iter().filter(|s| s.enabled(&features)) {
+            let started = Instant::now();
+            let result = system.on_event(bot.clone(), event.clone()).await;
+            crate::profiler::record(system.name(), started.elapsed());
+            if let Err(e) = result {
+                warn!("[SYSTEMS] ⚠️ {} falhou: {}", system.name(), e);
+            }
+        }
+    }
+
+    /// (name, enabled, summary) for each system, for the dashboard
+    pub fn describe(&self, features: &Features) -> Vec<(&'static str, bool, Option<String>)> {
+        self.systems.iter().map(|s| (s.name(), s.enabled(features), s.context_summary())).collect()
+    }
+}
@@ -0,0 +1,154 @@
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tracing::warn;
+use crate::config::Config;
+
+// ============================================================
+// PROFILER — Where each 50ms tick goes, system by system
+// world_scan 7.9ms (slice 5ms) → [PROFILER] 🐢, numbers in GET /profile and the dashboard
+// ============================================================
+
+/// One game tick at 20Hz
+pub const TICK_BUDGET: Duration = Duration::from_millis(50);
+/// The same system or an over-budget tick gets warned about at most this often
+const WARN_EVERY: Duration = Duration::from_secs(60);
+
+#[derive(Default)]
+struct SystemStats {
+    calls: u64,
+    total: Duration,
+    max: Duration,
+    /// Calls that went over the slice
+    over: u64,
+    warned_at: Option<Instant>,
+}
+
+#[derive(Default)]
+struct Profile {
+    systems: HashMap<String, SystemStats>,
+    ticks: u64,
+    tick_total: Duration,
+    tick_max: Duration,
+    over_budget: u64,
+    /// What ran during the tick in progress
+    this_tick: Vec<(String, Duration)>,
+    warned_at: Option<Instant>,
+}
+
+static PROFILE: LazyLock<Mutex<Profile>> = LazyLock::new(Default::default);
+
+/// `name` took `took`. Warns if that's more than its slice
+pub fn record(name: &str, took: Duration) {
+    let slice = Duration::from_millis(Config::load().profiler_slice_ms);
+    let mut profile = PROFILE.lock().unwrap();
+    profile.this_tick.push((name.to_string(), took));
+    if !profile.systems.contains_key(name) {
+        profile.systems.insert(name.to_string(), SystemStats::default());
+    }
+    let stats = profile.systems.get_mut(name).unwrap();
+    stats.calls += 1;
+    stats.total += took;
+    stats.max = stats.max.max(took);
+    if slice.is_zero() || took <= slice {
+        return;
+    }
+    stats.over += 1;
+    if stats.warned_at.is_none_or(|t| t.elapsed() >= WARN_EVERY) {
+        stats.warned_at = Some(Instant::now());
+        warn!("[PROFILER] 🐢 {} levou {:.1}ms (fatia de {}ms, {}× até agora)", name, ms(took), slice.as_millis(), stats.over);
+    }
+}
+
+/// Time one game tick, start to finish. Hold it while the tick runs
+pub fn tick() -> TickTimer {
+    PROFILE.lock().unwrap().this_tick.clear();
+    TickTimer { started: Instant::now() }
+}
+
+pub struct TickTimer {
+    started: Instant,
+}
+
+impl Drop for TickTimer {
+    fn drop(&mut self) {
+        let took = self.started.elapsed();
+        let mut profile = PROFILE.lock().unwrap();
+        profile.ticks += 1;
+        profile.tick_total += took;
+        profile.tick_max = profile.tick_max.max(took);
+        if took <= TICK_BUDGET {
+            return;
+        }
+        profile.over_budget += 1;
+        if profile.warned_at.is_none_or(|t| t.elapsed() >= WARN_EVERY) {
+            profile.warned_at = Some(Instant::now());
+            let mut slowest = std::mem::take(&mut profile.this_tick);
+            slowest.sort_by_key(|(_, took)| std::cmp::Reverse(*took));
+            let slowest: Vec<String> = slowest.iter().take(3).map(|(n, t)| format!("{} {:.1}ms", n, ms(*t))).collect();
+            warn!("[PROFILER] 🐢 Tick levou {:.1}ms (orçamento {}ms) — mais pesados: {}", ms(took), TICK_BUDGET.as_millis(), slowest.join(", "));
+        }
+    }
+}
+
+/// Back-to-back sections of one function: each `next` closes the one before it
+pub struct Laps {
+    current: Option<(&'static str, Instant)>,
+}
+
+impl Laps {
+    pub fn start() -> Self {
+        Self { current: None }
+    }
+
+    pub fn next(&mut self, name: &'static str) {
+        self.stop();
+        self.current = Some((name, Instant::now()));
+    }
+
+    /// Close the running section without opening another
+    pub fn stop(&mut self) {
+        if let Some((name, started)) = self.current.take() {
+            record(name, started.elapsed());
+        }
+    }
+}
+
+impl Drop for Laps {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// New connection, clean numbers
+pub fn reset() {
+    *PROFILE.lock().unwrap() = Profile::default();
+}
+
+fn ms(d: Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
+}
+
+/// Tick totals, and every system heaviest first (by average)
+pub fn report() -> Value {
+    let profile = PROFILE.lock().unwrap();
+    let average = |total: Duration, n: u64| if n == 0 { 0.0 } else { ms(total) / n as f64 };
+    let mut systems: Vec<(&String, &SystemStats)> = profile.systems.iter().collect();
+    systems.sort_by(|a, b| average(b.1.total, b.1.calls).total_cmp(&average(a.1.total, a.1.calls)));
+    json!({
+        "budget_ms": TICK_BUDGET.as_millis() as u64,
+        "slice_ms": Config::load().profiler_slice_ms,
+        "ticks": profile.ticks,
+        "tick_avg_ms": average(profile.tick_total, profile.ticks),
+        "tick_max_ms": ms(profile.tick_max),
+        "ticks_over_budget": profile.over_budget,
+        "systems": systems.into_iter().map(|(name, s)| json!({
+            "name": name,
+            "calls": s.calls,
+            "avg_ms": average(s.total, s.calls),
+            "max_ms": ms(s.max),
+            "over_slice": s.over,
+        })).collect::<Vec<_>>(),
+    })
+}
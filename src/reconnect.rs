@@ -0,0 +1,259 @@
+use azalea::app::{App, AppExit, Plugin, Update};
+use azalea::auto_reconnect::{start_rejoin_on_disconnect, AutoReconnectDelay};
+use azalea::disconnect::DisconnectEvent;
+use azalea::ecs::message::{MessageReader, MessageWriter};
+use azalea::ecs::prelude::*;
+use azalea::prelude::{bevy_ecs, Component};
+use azalea::join::ConnectionFailedEvent;
+use azalea::{Account, Client, InGameState};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+use crate::config::{Config, STABLE_SESSION};
+use tracing::{info, warn};
+
+// ============================================================
+// RECONNECT — Why they dropped us, and what to do about it
+// "Server is restarting" → espera meio minuto; "You are banned" → nem tenta
+// ============================================================
+
+/// A restart rarely takes less than this
+const RESTART_WAIT: Duration = Duration::from_secs(30);
+
+/// Why the last session ended, as the server put it
+static LAST_DISCONNECT: Mutex<Option<String>> = Mutex::new(None);
+/// Got kicked for idling this run — anti-AFK moves more from now on
+static AFK_KICKED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KickReason {
+    Banned,    // Don't come back with this account
+    Whitelist, // Not on the list — same account, same answer
+    Afk,       // Idle too long
+    Restart,   // Server going down on purpose, it'll be back
+    AntiBot,   // Anti-bot, spam, flying... a few in a row and we switch accounts
+    Unknown,   // Timeout, server asleep, no reason given
+}
+
+/// Read a kick message
+pub fn classify(reason: &str) -> KickReason {
+    let reason = reason.to_lowercase();
+    let has = |words: &[&str]| words.iter().any(|w| reason.contains(w));
+    if has(&["banned", "banido", "blacklist", "suspended", "suspenso"]) {
+        KickReason::Banned
+    } else if has(&["whitelist", "white-list", "not white", "lista branca"]) {
+        KickReason::Whitelist
+    } else if has(&["afk", "idle", "inativ", "ausente"]) {
+        KickReason::Afk
+    } else if has(&["restart", "reinici", "shutting down", "server closed", "stopping", "desligando", "manutenção", "maintenance"]) {
+        KickReason::Restart
+    } else if has(&["kicked", "expulso", "bot", "spam", "flying", "too fast", "cheat", "hack", "verif"]) {
+        KickReason::AntiBot
+    } else {
+        KickReason::Unknown
+    }
+}
+
+impl KickReason {
+    /// Worth trying again with the same account?
+    pub fn retry(&self) -> bool {
+        !matches!(self, KickReason::Banned | KickReason::Whitelist)
+    }
+
+    /// How long to wait before the next try. `failures` = short sessions in a row
+    pub fn delay(&self, config: &Config, failures: u32) -> Duration {
+        let base = Duration::from_secs(config.reconnect_secs.max(1));
+        let max = Duration::from_secs(config.backoff_max_secs).max(base);
+        let wait = match self {
+            KickReason::Restart => RESTART_WAIT.max(base),
+            KickReason::Afk => base,
+            KickReason::AntiBot => (base * 4).min(max), // Let it cool off
+            KickReason::Banned | KickReason::Whitelist => base,
+            KickReason::Unknown => (base * 2u32.saturating_pow(failures.min(16))).min(max),
+        };
+        jitter(wait, config.backoff_jitter)
+    }
+}
+
+/// ±fraction of the wait, at random
+fn jitter(wait: Duration, fraction: f32) -> Duration {
+    let factor = 1.0 + (rand::random::<f32>() * 2.0 - 1.0) * fraction;
+    wait.mul_f32(factor.max(0.0))
+}
+
+pub fn note_disconnect(reason: String) {
+    if classify(&reason) == KickReason::Afk {
+        AFK_KICKED.store(true, Ordering::Relaxed);
+    }
+    *LAST_DISCONNECT.lock().unwrap() = Some(reason);
+}
+
+pub fn take_disconnect() -> Option<String> {
+    LAST_DISCONNECT.lock().unwrap().take()
+}
+
+pub fn afk_kicked() -> bool {
+    AFK_KICKED.load(Ordering::Relaxed)
+}
+
+/// Solo mode runs with azalea's own reconnect off, so main's loop picks the wait, the account and the server.
+/// A connection that never got up doesn't reach the handler — this ends the run for it
+pub struct EndOnConnectionFailure;
+
+impl Plugin for EndOnConnectionFailure {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, end_on_connection_failure);
+    }
+}
+
+fn end_on_connection_failure(mut failed: MessageReader<ConnectionFailedEvent>, mut exit: MessageWriter<AppExit>) {
+    for failure in failed.read() {
+        warn!("[RECONNECT] ❌ Não consegui conectar: {}", failure.error);
+        exit.write(AppExit::Success);
+    }
+}
+
+/// Swarm mode keeps azalea's reconnect — the swarm is one app — but each bot's wait comes from the
+/// same policy as solo: the kick reason, and backing off on short sessions. Banned = that bot stays out
+pub struct SwarmBackoff;
+
+impl Plugin for SwarmBackoff {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (note_joined, pick_delay).before(start_rejoin_on_disconnect));
+    }
+}
+
+/// One swarm bot's side of main's loop: when it got in, and short sessions in a row
+#[derive(Component, Clone, Default)]
+struct Backoff {
+    joined: Option<Instant>,
+    failures: u32,
+}
+
+impl Backoff {
+    /// The session ended (or never started). How long until the next try — None = don't
+    fn next(&mut self, config: &Config, kick: KickReason) -> Option<Duration> {
+        let stable = self.joined.take().is_some_and(|joined| joined.elapsed() >= STABLE_SESSION);
+        self.failures = if stable { 0 } else { self.failures + 1 };
+        let given_up = config.max_attempts > 0 && self.failures >= config.max_attempts;
+        (kick.retry() && !given_up).then(|| kick.delay(config, self.failures))
+    }
+}
+
+fn note_joined(mut commands: Commands, joined: Query<(Entity, Option<&Backoff>), Added<InGameState>>) {
+    for (entity, backoff) in &joined {
+        let failures = backoff.map_or(0, |b| b.failures);
+        commands.entity(entity).insert(Backoff { joined: Some(Instant::now()), failures });
+    }
+}
+
+fn pick_delay(
+    mut commands: Commands,
+    mut disconnects: MessageReader<DisconnectEvent>,
+    mut failed: MessageReader<ConnectionFailedEvent>,
+    bots: Query<(Option<&Backoff>, &Account)>,
+) {
+    let config = Config::load();
+    let ended = disconnects.read()
+        .map(|e| (e.entity, e.reason.as_ref().map(ToString::to_string)))
+        .chain(failed.read().map(|e| (e.entity, None)));
+    for (entity, reason) in ended {
+        let Ok((backoff, account)) = bots.get(entity) else { continue };
+        let mut backoff = backoff.cloned().unwrap_or_default();
+        let kick = reason.as_deref().map_or(KickReason::Unknown, classify);
+        if let Some(reason) = &reason {
+            info!("[RECONNECT] 🔎 {} {:?}: {}", account.username, kick, reason);
+        }
+        if kick == KickReason::Banned {
+            crate::events::alert("ban", format!("🚫 {} foi banido: {}", account.username, reason.as_deref().unwrap_or_default()));
+        }
+        let delay = match backoff.next(&config, kick) {
+            Some(wait) => {
+                info!("[RECONNECT] ⏳ {} volta em {} segundos", account.username, wait.as_secs());
+                wait
+            }
+            None => {
+                warn!("[RECONNECT] 🛑 {} fica de fora ({:?}, {} tentativas)", account.username, kick, backoff.failures);
+                Duration::MAX // azalea's "don't reconnect"
+            }
+        };
+        commands.entity(entity).insert((backoff, AutoReconnectDelay::new(delay)));
+    }
+}
+
+/// Kicked, and the handler's done saving: end the run so main's loop takes over. A swarm reconnects on its own
+pub fn end_session(bot: &Client) {
+    if Config::load().swarm.is_empty() {
+        bot.ecs.lock().write_message(AppExit::Success);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(jitter: f32) -> Config {
+        let mut config = (*Config::load()).clone();
+        config.reconnect_secs = 10;
+        config.backoff_max_secs = 300;
+        config.backoff_jitter = jitter;
+        config
+    }
+
+    #[test]
+    fn kick_messages() {
+        assert_eq!(classify("You are banned from this server!"), KickReason::Banned);
+        assert_eq!(classify("§cVocê foi BANIDO permanentemente"), KickReason::Banned);
+        assert_eq!(classify("You are not whitelisted on this server!"), KickReason::Whitelist);
+        assert_eq!(classify("Kicked for being AFK"), KickReason::Afk);
+        assert_eq!(classify("You have been idle for too long"), KickReason::Afk);
+        assert_eq!(classify("Server is restarting"), KickReason::Restart);
+        assert_eq!(classify("Servidor em manutenção, volta já"), KickReason::Restart);
+        assert_eq!(classify("Flying is not enabled on this server"), KickReason::AntiBot);
+        assert_eq!(classify("Timed out"), KickReason::Unknown);
+        assert_eq!(classify(""), KickReason::Unknown);
+        // Banned wins over everything else in the same line
+        assert_eq!(classify("Banned: flying hack"), KickReason::Banned);
+        assert!(!KickReason::Banned.retry() && !KickReason::Whitelist.retry() && KickReason::Afk.retry());
+    }
+
+    #[test]
+    fn waits() {
+        let config = config(0.0);
+        let secs = |kick: KickReason, failures| kick.delay(&config, failures).as_secs();
+        assert_eq!(secs(KickReason::Restart, 0), 30);
+        assert_eq!(secs(KickReason::Afk, 5), 10);
+        assert_eq!(secs(KickReason::AntiBot, 0), 40);
+        assert_eq!(secs(KickReason::Banned, 3), 10);
+        // Dead server: doubles per failure, capped at backoff_max_secs
+        assert_eq!(secs(KickReason::Unknown, 0), 10);
+        assert_eq!(secs(KickReason::Unknown, 3), 80);
+        assert_eq!(secs(KickReason::Unknown, 10), 300);
+        assert_eq!(secs(KickReason::Unknown, u32::MAX), 300);
+    }
+
+    #[test]
+    fn swarm_bot_backs_off() {
+        let mut config = config(0.0);
+        config.max_attempts = 3;
+        let mut backoff = Backoff::default();
+        // Never got in: doubling, then giving up at max_attempts
+        assert_eq!(backoff.next(&config, KickReason::Unknown), Some(Duration::from_secs(20)));
+        assert_eq!(backoff.next(&config, KickReason::Unknown), Some(Duration::from_secs(40)));
+        assert_eq!(backoff.next(&config, KickReason::Unknown), None);
+        // A session that lasted starts the count over
+        let mut backoff = Backoff { joined: Some(Instant::now() - STABLE_SESSION), failures: 2 };
+        assert_eq!(backoff.next(&config, KickReason::Restart), Some(Duration::from_secs(30)));
+        assert_eq!(backoff.failures, 0);
+        assert_eq!(Backoff::default().next(&config, KickReason::Banned), None);
+    }
+
+    #[test]
+    fn jittered_waits_stay_in_range() {
+        let config = config(0.2);
+        for _ in 0..100 {
+            let wait = KickReason::Afk.delay(&config, 0).as_secs_f32();
+            assert!((8.0..=12.0).contains(&wait), "{}", wait);
+        }
+    }
+}
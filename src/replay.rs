@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::Write;
+use std::sync::Mutex;
+use tracing::{info, warn};
+use crate::bot::State;
+use crate::cognitive::goal_planner::GoalPlanner;
+use crate::cognitive::memory::Memory;
+use crate::cognitive::personality::Personality;
+use crate::config::Config;
+use crate::plugins::brain::Judgement;
+use crate::sim::SimEvent;
+use crate::systems::chat_channel::ChatChannel;
+use crate::systems::world_scanner::WorldState;
+
+// ============================================================
+// REPLAY — Every session on tape, in the sim's format, to play back later
+// <data>/replays/2026-10-15_21-04-33.jsonl → frankfurt_sentinel replay <that file>
+// ============================================================
+//
+// First line: memory, goals and mood as they were on login. Then what came in
+// (chat, damage, who's in sight, ticks in between) and what we decided
+// (how each line landed, what we said, goals that moved), in the order it happened.
+
+const FOLDER: &str = "replays";
+/// Who's in sight gets checked this often, not every tick
+const SIGHT_EVERY: u32 = 20;
+
+/// Where the session started from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub memory: Memory,
+    pub goals: GoalPlanner,
+    pub personality: Personality,
+}
+
+struct Recording {
+    file: File,
+    path: String,
+    /// Ticks since the last line — written as a wait before the next one
+    idle_ticks: u32,
+    ticks: u32,
+    in_sight: BTreeSet<String>,
+}
+
+static RECORDING: Mutex<Option<Recording>> = Mutex::new(None);
+
+fn folder() -> String {
+    format!("{}/{}", crate::cognitive::memory::data_dir(), FOLDER)
+}
+
+/// New connection, new file. Off unless [replay] record is on
+pub fn start(state: &State) {
+    let config = Config::load();
+    *RECORDING.lock().unwrap() = None;
+    if !config.replay_record {
+        return;
+    }
+    let dir = folder();
+    let path = format!("{}/{}.jsonl", dir, chrono::Local::now().format("%Y-%m-%d_%H-%M-%S"));
+    let file = match std::fs::create_dir_all(&dir).and_then(|_| File::create(&path)) {
+        Ok(file) => file,
+        Err(e) => {
+            warn!("[REPLAY] ⚠️ Não consegui criar {}: {}", path, e);
+            return;
+        }
+    };
+    prune(&dir, config.replay_keep);
+    let snapshot = Snapshot {
+        memory: state.brain.memory.lock().unwrap().clone(),
+        goals: state.brain.goals.lock().unwrap().clone(),
+        personality: state.brain.personality.lock().unwrap().clone(),
+    };
+    info!("[REPLAY] 🎞️ Gravando a sessão em {}", path);
+    *RECORDING.lock().unwrap() = Some(Recording { file, path, idle_ticks: 0, ticks: 0, in_sight: BTreeSet::new() });
+    write(SimEvent::Snapshot(Box::new(snapshot)));
+}
+
+/// Keep the newest `keep` files. 0 = keep everything
+fn prune(dir: &str, keep: usize) {
+    if keep == 0 {
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else { return };
+    let mut files: Vec<_> = entries.flatten().map(|e| e.path()).filter(|p| p.extension().is_some_and(|x| x == "jsonl")).collect();
+    files.sort();
+    // The one we just created is among them
+    for old in files.iter().rev().skip(keep) {
+        let _ = std::fs::remove_file(old);
+    }
+}
+
+fn write(event: SimEvent) {
+    let mut recording = RECORDING.lock().unwrap();
+    let Some(rec) = recording.as_mut() else { return };
+    let wait = std::mem::take(&mut rec.idle_ticks);
+    let mut lines = vec![];
+    if wait > 0 {
+        lines.push(SimEvent::Wait { ticks: wait });
+    }
+    lines.push(event);
+    for line in lines {
+        let written = serde_json::to_string(&line).map_err(std::io::Error::other).and_then(|l| writeln!(rec.file, "{}", l));
+        if let Err(e) = written {
+            warn!("[REPLAY] ⚠️ Parei de gravar {}: {}", rec.path, e);
+            *recording = None;
+            return;
+        }
+    }
+}
+
+fn recording() -> bool {
+    RECORDING.lock().unwrap().is_some()
+}
+
+/// Count the tick; now and then, note who came into sight or left it
+pub fn tick(world: &Mutex<WorldState>) {
+    let check = {
+        let mut recording = RECORDING.lock().unwrap();
+        let Some(rec) = recording.as_mut() else { return };
+        rec.idle_ticks += 1;
+        rec.ticks += 1;
+        rec.ticks.is_multiple_of(SIGHT_EVERY)
+    };
+    if !check {
+        return;
+    }
+    let players = crate::bot::visible_players(&world.lock().unwrap().nearby_entities);
+    let now: BTreeSet<String> = players.iter().map(|p| p.name.clone()).collect();
+    let before = {
+        let mut recording = RECORDING.lock().unwrap();
+        let Some(rec) = recording.as_mut() else { return };
+        std::mem::replace(&mut rec.in_sight, now.clone())
+    };
+    for player in players.into_iter().filter(|p| !before.contains(&p.name)) {
+        write(SimEvent::Player { name: player.name, pos: player.pos });
+    }
+    for name in before.difference(&now) {
+        write(SimEvent::Leave { name: name.clone() });
+    }
+}
+
+/// A chat line from someone, as the brain got it
+pub fn heard(channel: ChatChannel, sender: &str, message: &str) {
+    if recording() {
+        write(SimEvent::Chat { sender: sender.to_string(), message: crate::secrets::redact(message), channel });
+    }
+}
+
+/// How that chat line landed
+pub fn judged(sender: &str, judgement: Judgement) {
+    if recording() {
+        write(SimEvent::Judged { player: sender.to_string(), directed: judgement.directed, trust_delta: judgement.trust_delta });
+    }
+}
+
+pub fn damage(amount: f32) {
+    if recording() {
+        write(SimEvent::Damage { amount });
+    }
+}
+
+pub fn said(message: &str) {
+    if recording() {
+        write(SimEvent::Said { message: crate::secrets::redact(message) });
+    }
+}
+
+pub fn goal(goal: &str, status: String) {
+    if recording() {
+        write(SimEvent::Goal { goal: goal.to_string(), status });
+    }
+}
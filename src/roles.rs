@@ -0,0 +1,214 @@
+use azalea::pathfinder::goals::RadiusGoal;
+use azalea::prelude::*;
+use std::time::Duration;
+use tracing::{info, warn, Instrument};
+use crate::cognitive::goal_planner::{Goal, GoalPriority};
+use crate::config::Config;
+use crate::session::Session;
+use crate::systems::inventory::Inventory;
+use crate::systems::{storage, trader};
+
+// ============================================================
+// ROLES — A swarm that splits the work instead of everyone doing everything
+// [roles] PedroRTX = "miner", Zezin_ = "hauler" → Pedro não sai da mina, Zezin leva o loot pra base
+// ============================================================
+//
+// The main bot leads: every few seconds it makes sure each bot has its role's goal,
+// copies what the builders want into the gatherers' planners, and sets up handoffs —
+// a hauler walks to a miner with a full backpack, takes the loot and stashes it;
+// a gatherer carries what a builder was after straight to them.
+
+pub const ROLES: &[&str] = &["miner", "hauler", "builder", "gatherer"];
+/// How often the leader looks over the crew
+const COORDINATE_EVERY: Duration = Duration::from_secs(10);
+/// Longest walk over to a teammate
+const REACH: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Miner,
+    Hauler,
+    Builder,
+    Gatherer,
+}
+
+impl Role {
+    fn parse(name: &str) -> Option<Role> {
+        match name {
+            "miner" => Some(Role::Miner),
+            "hauler" => Some(Role::Hauler),
+            "builder" => Some(Role::Builder),
+            "gatherer" => Some(Role::Gatherer),
+            _ => None,
+        }
+    }
+
+    /// Who this role works for, or with
+    fn partner(self) -> Role {
+        match self {
+            Role::Miner => Role::Hauler,
+            Role::Hauler => Role::Miner,
+            Role::Builder => Role::Gatherer,
+            Role::Gatherer => Role::Builder,
+        }
+    }
+
+    /// The standing goal that goes with the role
+    fn goal(self, partners: &str) -> Goal {
+        match self {
+            Role::Miner => Goal::new("Minerar pro time", &format!("Strip mine no Y11, sem voltar pra base — {} vem buscar o loot", partners), GoalPriority::Medium),
+            Role::Hauler => Goal::new("Carregar o loot do time", &format!("Buscar o que {} minerar e guardar nos baús da base", partners), GoalPriority::Medium),
+            Role::Builder => Goal::new("Construir pro time", &format!("Tocar a obra; o que faltar {} junta", partners), GoalPriority::Low),
+            Role::Gatherer => Goal::new("Juntar material pro time", &format!("Pegar o que {} precisar pra obra e levar pra ele", partners), GoalPriority::Medium),
+        }
+    }
+}
+
+/// `player`'s role, if [roles] gives them one
+pub fn role_of(config: &Config, player: &str) -> Option<Role> {
+    config.roles.get(player).and_then(|r| Role::parse(r))
+}
+
+/// A miner with a hauler online doesn't walk home to stash — the hauler comes to it
+pub fn hauled(config: &Config, me: &str) -> bool {
+    role_of(config, me) == Some(Role::Miner)
+        && crate::session::all().iter().any(|s| role_of(config, &s.bot.username()) == Some(Role::Hauler))
+}
+
+/// Lead the crew until the process stops. Does nothing without [roles]
+pub async fn run() {
+    loop {
+        tokio::time::sleep(COORDINATE_EVERY).await;
+        let config = Config::load();
+        if config.roles.is_empty() {
+            continue;
+        }
+        let crew: Vec<(Session, String, Role)> = crate::session::all().into_iter()
+            .filter_map(|s| {
+                let name = s.bot.username();
+                let role = role_of(&config, &name)?;
+                Some((s, name, role))
+            })
+            .collect();
+        // The main bot leads; without it, whoever joined first
+        let Some(leader) = crew.iter().map(|(s, ..)| s.bot.username()).next() else { continue };
+        assign(&crew, &leader);
+        coordinate(&config, &crew);
+    }
+}
+
+/// Everyone keeps their role's goal, written into their planner by the leader
+fn assign(crew: &[(Session, String, Role)], leader: &str) {
+    for (session, _, role) in crew {
+        let partners: Vec<&str> = crew.iter().filter(|(_, _, r)| *r == role.partner()).map(|(_, n, _)| n.as_str()).collect();
+        let partners = if partners.is_empty() { "alguém do time".to_string() } else { partners.join(", ") };
+        let mut goal = role.goal(&partners);
+        goal.assigned_by = Some(leader.to_string());
+        let mut goals = session.state.brain.goals.lock().unwrap();
+        if !goals.goals.iter().any(|g| g.name == goal.name && g.is_open()) {
+            crate::bus::for_bot(session.bot.entity, || goals.add_goal(goal));
+        }
+    }
+}
+
+/// Copy wants from builders to gatherers, and start whatever handoff is ready
+fn coordinate(config: &Config, crew: &[(Session, String, Role)]) {
+    let with = |role| crew.iter().filter(move |(_, _, r)| *r == role);
+    // A builder's open wants become the gatherers' goals
+    for (builder, builder_name, _) in with(Role::Builder) {
+        let wanted = builder.state.brain.goals.lock().unwrap().wanted_items();
+        for (gatherer, ..) in with(Role::Gatherer) {
+            let mut goals = gatherer.state.brain.goals.lock().unwrap();
+            let already: Vec<String> = goals.goals.iter().filter(|g| g.is_open()).filter_map(|g| g.wants_item.clone()).collect();
+            for item in wanted.iter().filter(|i| !already.contains(i)) {
+                let mut goal = Goal::new(&format!("Juntar {} pro {}", item.replace('_', " "), builder_name), "A obra precisa disso", GoalPriority::Medium)
+                    .wanting(item);
+                goal.assigned_by = Some(builder_name.clone());
+                crate::bus::for_bot(gatherer.bot.entity, || goals.add_goal(goal));
+            }
+        }
+    }
+
+    // Miner full → an idle hauler goes to fetch it
+    for (miner, miner_name, _) in with(Role::Miner) {
+        let carrying = Inventory::read(&miner.bot);
+        if carrying.free_slots() > config.stash_free_slots || storage::stashable(&carrying).is_empty() {
+            continue;
+        }
+        let Some((hauler, hauler_name, _)) = with(Role::Hauler).find(|(h, ..)| !*h.state.brain.trader.busy.lock().unwrap()) else { continue };
+        if !miner.state.brain.trader.try_begin() {
+            continue;
+        }
+        if !hauler.state.brain.trader.try_begin() {
+            miner.state.brain.trader.finish();
+            continue;
+        }
+        let span = tracing::info_span!("haul", miner = %miner_name, hauler = %hauler_name);
+        // The errand goes in the hauler's planner — its GoalChanged is the hauler's
+        tokio::spawn(crate::bus::as_bot(hauler.bot.entity, haul(miner.clone(), hauler.clone())).instrument(span));
+    }
+
+    // A gatherer holding what a builder wants → take it over
+    for (gatherer, gatherer_name, _) in with(Role::Gatherer) {
+        let carrying = Inventory::read(&gatherer.bot);
+        let delivery = with(Role::Builder).find_map(|(builder, builder_name, _)| {
+            let wanted = builder.state.brain.goals.lock().unwrap().wanted_items();
+            wanted.into_iter().find(|item| carrying.count_of(item) > 0).map(|item| (builder_name, item))
+        });
+        let Some((builder_name, item)) = delivery else { continue };
+        if !gatherer.state.brain.trader.try_begin() {
+            continue;
+        }
+        let span = tracing::info_span!("deliver", gatherer = %gatherer_name, builder = %builder_name);
+        tokio::spawn(crate::bus::as_bot(gatherer.bot.entity, deliver(gatherer.clone(), builder_name.clone(), item)).instrument(span));
+    }
+}
+
+/// The hauler walks over, the miner tosses the loot, the hauler takes it home.
+/// Both bodies are already claimed
+async fn haul(miner: Session, hauler: Session) {
+    let miner_name = miner.bot.username();
+    let hauler_name = hauler.bot.username();
+    let errand = format!("Buscar o loot do {}", miner_name);
+    hauler.state.brain.goals.lock().unwrap().emergency(&errand, "Mochila dele encheu, eu levo pra base");
+    info!("[ROLES] 🚚 {} indo buscar o loot do {}", hauler_name, miner_name);
+
+    let arrived = tokio::time::timeout(REACH, hauler.bot.goto(RadiusGoal::new(miner.bot.position(), 3.0))).await;
+    let mut handed = 0;
+    if arrived.is_ok() {
+        for item in storage::stashable(&Inventory::read(&miner.bot)) {
+            let count = Inventory::read(&miner.bot).count_of(&item);
+            if let trader::GiveOutcome::Delivered { quantity, .. } | trader::GiveOutcome::Partial { quantity, .. } =
+                trader::give_item(miner.bot.clone(), &hauler_name, &item, count).await
+            {
+                handed += quantity;
+            }
+        }
+        // Let the pickups land before heading off
+        hauler.bot.wait_ticks(20).await;
+    } else {
+        hauler.bot.stop_pathfinding();
+        warn!("[ROLES] ❌ {} não conseguiu chegar no {}", hauler_name, miner_name);
+    }
+    miner.state.brain.trader.finish();
+
+    let stored = if handed > 0 { storage::stash(&hauler.bot, &hauler.state.brain.memory).await } else { 0 };
+    hauler.state.brain.trader.finish();
+    info!("[ROLES] 📦 {} passou {} itens pro {}, {} foram pros baús", miner_name, handed, hauler_name, stored);
+    let mut goals = hauler.state.brain.goals.lock().unwrap();
+    if goals.current_goal().is_some_and(|g| g.name == errand) {
+        if handed > 0 { goals.complete_current() } else { goals.fail_current() }
+    }
+}
+
+/// Walk what the builder wants over to them. The gatherer's body is already claimed;
+/// the builder's goal closes when the item lands (on_item_received)
+async fn deliver(gatherer: Session, builder_name: String, item: String) {
+    let count = Inventory::read(&gatherer.bot).count_of(&item);
+    info!("[ROLES] 🧱 {} levando {} x{} pro {}", gatherer.bot.username(), item, count, builder_name);
+    let outcome = trader::give_item(gatherer.bot.clone(), &builder_name, &item, count).await;
+    gatherer.state.brain.trader.finish();
+    if let trader::GiveOutcome::Delivered { .. } | trader::GiveOutcome::Partial { .. } = outcome {
+        gatherer.state.brain.goals.lock().unwrap().complete_wanting(&item);
+    }
+}
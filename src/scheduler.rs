@@ -0,0 +1,91 @@
+use std::sync::Arc;
+use crate::config::Config;
+use crate::profiler::Laps;
+
+// ============================================================
+// SCHEDULER — Not everything needs 20Hz: each tick block runs every N ticks
+// [schedule] world_scan = 40 → one tick in 40, offset so the slow ones don't all land together
+// ============================================================
+
+/// Tick blocks in bot.rs that can be spread out, and how often they run unless [schedule] says otherwise.
+/// 20 ticks = 1s: presence every minute (1200), wealth every 5 minutes (6000)...
+/// queue, entity_feed, position and motor aren't here — they run every tick, always
+pub const DEFAULTS: &[(&str, u32)] = &[
+    ("combat", 1),
+    ("spider_sense", 1),
+    ("stalker_watch", 1),
+    ("presence", 1200),
+    ("proximity", 1),
+    ("stare", 1),
+    ("world_scan", 40),
+    ("dark_corners", 1200),
+    ("grief_watch", 20),
+    ("visual_cortex", 20),
+    ("dreamer", 100),
+    ("wandering", 20),
+    ("cave_exit", 10),
+    ("death_run", 20),
+    ("collections", 1200),
+    ("shopping", 2400),
+    ("wealth", 6000),
+    ("help", 1800),
+    ("generosity", 1200),
+    ("small_talk", 1200),
+    ("storage", 400),
+    ("stash", 600),
+    ("junk", 400),
+    ("ender", 1200),
+    ("tools", 300),
+    ("loadout", 200),
+    ("observer", 20),
+    ("builder", 100),
+    ("mining", 100),
+];
+
+/// Blocks that talk, walk or touch the world — BOT_MODE=observer never runs them
+pub const HANDS_ON: &[&str] = &[
+    "combat", "proximity", "stare", "dark_corners", "grief_watch", "wandering", "cave_exit", "death_run", "collections",
+    "shopping", "help", "generosity", "small_talk", "storage", "stash", "junk", "ender", "tools", "loadout",
+    "builder", "mining",
+];
+
+/// This tick's schedule, and the profiler laps for the blocks that run
+pub struct Ticker {
+    tick: u64,
+    config: Arc<Config>,
+    laps: Laps,
+}
+
+impl Ticker {
+    pub fn new(tick: u64) -> Self {
+        Self { tick, config: Config::load(), laps: Laps::start() }
+    }
+
+    /// Ticks between runs of `name`
+    pub fn every(&self, name: &str) -> u32 {
+        self.config.schedule.get(name).copied().unwrap_or(1).max(1)
+    }
+
+    /// A block that runs every tick no matter what
+    pub fn always(&mut self, name: &'static str) {
+        self.laps.next(name);
+    }
+
+    /// Is it `name`'s turn? If so it's timed until the next block starts
+    pub fn due(&mut self, name: &'static str) -> bool {
+        if self.config.observing() && HANDS_ON.contains(&name) {
+            self.laps.stop();
+            return false;
+        }
+        let every = self.every(name) as u64;
+        // Same cadence, different names → different ticks
+        let offset = name.bytes().map(u64::from).sum::<u64>() % every;
+        if (self.tick + offset).is_multiple_of(every) {
+            self.laps.next(name);
+            true
+        } else {
+            self.laps.stop();
+            false
+        }
+    }
+}
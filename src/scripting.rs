@@ -0,0 +1,614 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
+use tracing::{debug, info, warn};
+use crate::bus::{ChatIn, Ctx, Damage, GoalChanged, OreFound, PlayerApproached, ThreatDetected};
+use crate::cognitive::goal_planner::{Goal, GoalPriority, GoalStatus};
+use crate::config::Config;
+use crate::systems::motor::MotorCommand;
+
+// ============================================================
+// SCRIPTS — Behaviors server owners add without recompiling
+// on chat "bom dia" · if relation == friend · cooldown 300 · say "bom dia {sender}!"
+// A line per statement rather than Rhai/Lua on purpose: no loops, no state, nothing reachable but VARS
+// and the four actions — an owner's typo can't hang the game loop. scripts/example.script.example
+// ============================================================
+
+const EXTENSION: &str = "script";
+/// Actions per trigger — a script can't flood the motor queue
+const MAX_ACTIONS: usize = 8;
+/// How often the folder is checked for new or edited files
+const RELOAD_EVERY: Duration = Duration::from_secs(5);
+/// What `if` and `{...}` can read. Not every one is set for every event
+const VARS: &[&str] = &[
+    "sender", "message", "amount", "threat", "level", "player", "goal", "status", "block",
+    "health", "food", "mood", "current_goal", "relation", "trust", "me",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Trigger {
+    Chat,
+    Damage,
+    Threat,
+    Goal,
+    Approach,
+    Ore,
+    Tick,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Gt,
+    Le,
+    Ge,
+    Contains,
+}
+
+#[derive(Debug)]
+struct Condition {
+    var: String,
+    op: Op,
+    value: String,
+}
+
+#[derive(Debug)]
+enum Action {
+    Say(String),
+    /// To whoever triggered it — sender or player
+    Whisper(String),
+    Motor(MotorCommand),
+    Goal(String, GoalPriority),
+}
+
+/// One `on ...` block
+#[derive(Debug)]
+struct Handler {
+    origin: String, // "saudacao.script:3"
+    trigger: Trigger,
+    pattern: Option<String>, // chat: message must contain this
+    every: u64,              // tick: only every N ticks
+    conditions: Vec<Condition>,
+    actions: Vec<Action>,
+    cooldown: Duration,
+    last_fired: Option<Instant>,
+}
+
+/// One bot's copy of every script — its own cooldowns and tick count, so a swarm's bots don't share them
+#[derive(Default)]
+pub struct Scripts {
+    handlers: Vec<Handler>,
+    files: Vec<(PathBuf, Option<SystemTime>)>,
+    checked: Option<Instant>,
+    ticks: u64,
+}
+
+impl Condition {
+    fn holds(&self, vars: &HashMap<&str, String>) -> bool {
+        let Some(actual) = vars.get(self.var.as_str()) else { return false };
+        let expected = interpolate(&self.value, vars);
+        let numbers = actual.parse::<f64>().ok().zip(expected.parse::<f64>().ok());
+        match (self.op, numbers) {
+            (Op::Contains, _) => actual.to_lowercase().contains(&expected.to_lowercase()),
+            (Op::Eq, Some((a, b))) => a == b,
+            (Op::Ne, Some((a, b))) => a != b,
+            (Op::Eq, None) => actual.eq_ignore_ascii_case(&expected),
+            (Op::Ne, None) => !actual.eq_ignore_ascii_case(&expected),
+            (Op::Lt, Some((a, b))) => a < b,
+            (Op::Gt, Some((a, b))) => a > b,
+            (Op::Le, Some((a, b))) => a <= b,
+            (Op::Ge, Some((a, b))) => a >= b,
+            // Bigger/smaller only makes sense for numbers
+            (_, None) => false,
+        }
+    }
+}
+
+/// "bom dia {sender}" → "bom dia Steve". Unknown names stay as they are.
+/// One pass — a value that itself says "{me}" is left alone
+fn interpolate(text: &str, vars: &HashMap<&str, String>) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('}').and_then(|end| Some((end, vars.get(&after[..end])?))) {
+            Some((end, value)) => {
+                out.push_str(value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Split a line into words, "quoted text" counting as one. `#` outside quotes starts a comment
+fn words(line: &str) -> Result<Vec<String>, String> {
+    let mut words = vec![];
+    let mut chars = line.trim().chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '#' {
+            break;
+        } else if c == '"' {
+            chars.next();
+            let mut word = String::new();
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') if chars.peek() == Some(&'"') => word.push(chars.next().unwrap()),
+                    Some(c) => word.push(c),
+                    None => return Err("aspas sem fechar".into()),
+                }
+            }
+            words.push(word);
+        } else {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek().filter(|c| !c.is_whitespace()) {
+                word.push(c);
+                chars.next();
+            }
+            words.push(word);
+        }
+    }
+    Ok(words)
+}
+
+fn number<T: std::str::FromStr>(word: Option<&String>, what: &str) -> Result<T, String> {
+    word.and_then(|w| w.parse().ok()).ok_or_else(|| format!("{} precisa de um número", what))
+}
+
+/// `motor jump | random_look | wander | look <yaw> <pitch> | sprint|sneak|walk <ticks> | goto <x> <y> <z>`
+fn motor(args: &[String]) -> Result<MotorCommand, String> {
+    let arg = |i: usize| args.get(i);
+    Ok(match args.first().map(String::as_str) {
+        Some("jump") => MotorCommand::Jump,
+        Some("random_look") => MotorCommand::RandomLook,
+        Some("wander") => MotorCommand::WanderRandom,
+        Some("look") => MotorCommand::LookAt { yaw: number(arg(1), "look")?, pitch: number(arg(2), "look")? },
+        Some("sprint") => MotorCommand::StartSprint { duration_ticks: number(arg(1), "sprint")? },
+        Some("sneak") => MotorCommand::SneakPulse { duration_ticks: number(arg(1), "sneak")? },
+        Some("walk") => MotorCommand::WalkForward { duration_ticks: number(arg(1), "walk")? },
+        Some("goto") => MotorCommand::GotoBlock { x: number(arg(1), "goto")?, y: number(arg(2), "goto")?, z: number(arg(3), "goto")? },
+        other => return Err(format!("motor não conhece \"{}\"", other.unwrap_or_default())),
+    })
+}
+
+fn priority(word: Option<&String>) -> Result<GoalPriority, String> {
+    Ok(match word.map(|w| w.to_lowercase()).as_deref() {
+        None | Some("medium") => GoalPriority::Medium,
+        Some("critical") => GoalPriority::Critical,
+        Some("high") => GoalPriority::High,
+        Some("low") => GoalPriority::Low,
+        Some("background") => GoalPriority::Background,
+        Some("social") => GoalPriority::Social,
+        Some(other) => return Err(format!("prioridade \"{}\" não existe", other)),
+    })
+}
+
+fn header(origin: String, words: &[String]) -> Result<Handler, String> {
+    let trigger = match words.get(1).map(String::as_str) {
+        Some("chat") => Trigger::Chat,
+        Some("damage") => Trigger::Damage,
+        Some("threat") => Trigger::Threat,
+        Some("goal") => Trigger::Goal,
+        Some("approach") => Trigger::Approach,
+        Some("ore") => Trigger::Ore,
+        Some("tick") => Trigger::Tick,
+        other => return Err(format!("evento \"{}\" não existe (chat, damage, threat, goal, approach, ore, tick)", other.unwrap_or_default())),
+    };
+    let mut handler = Handler {
+        origin,
+        trigger,
+        pattern: None,
+        every: 1,
+        conditions: vec![],
+        actions: vec![],
+        cooldown: Duration::ZERO,
+        last_fired: None,
+    };
+    match (trigger, &words[2..]) {
+        (_, []) => {}
+        (Trigger::Chat, [pattern]) => handler.pattern = Some(pattern.to_lowercase()),
+        (Trigger::Tick, [every, n]) if every == "every" => handler.every = number::<u64>(Some(n), "every")?.max(1),
+        _ => return Err(format!("sobrou coisa depois de \"on {}\"", words[1])),
+    }
+    Ok(handler)
+}
+
+fn statement(handler: &mut Handler, words: &[String]) -> Result<(), String> {
+    let text = || words.get(1).cloned().ok_or_else(|| format!("{} precisa de um texto", words[0]));
+    match words[0].as_str() {
+        "if" => {
+            let [_, var, op, value] = words else {
+                return Err("if <variável> <==|!=|<|>|<=|>=|contains> <valor>".into());
+            };
+            if !VARS.contains(&var.as_str()) {
+                return Err(format!("variável \"{}\" não existe ({})", var, VARS.join(", ")));
+            }
+            let op = match op.as_str() {
+                "==" => Op::Eq,
+                "!=" => Op::Ne,
+                "<" => Op::Lt,
+                ">" => Op::Gt,
+                "<=" => Op::Le,
+                ">=" => Op::Ge,
+                "contains" => Op::Contains,
+                other => return Err(format!("comparação \"{}\" não existe", other)),
+            };
+            handler.conditions.push(Condition { var: var.clone(), op, value: value.clone() });
+            return Ok(());
+        }
+        "cooldown" => {
+            handler.cooldown = Duration::from_secs(number(words.get(1), "cooldown")?);
+            return Ok(());
+        }
+        "say" => handler.actions.push(Action::Say(text()?)),
+        "whisper" => handler.actions.push(Action::Whisper(text()?)),
+        "motor" => handler.actions.push(Action::Motor(motor(&words[1..])?)),
+        "goal" => handler.actions.push(Action::Goal(text()?, priority(words.get(2))?)),
+        other => return Err(format!("não sei o que é \"{}\" (if, cooldown, say, whisper, motor, goal)", other)),
+    }
+    if handler.actions.len() > MAX_ACTIONS {
+        return Err(format!("mais de {} ações num gatilho só", MAX_ACTIONS));
+    }
+    Ok(())
+}
+
+/// Every `on` block in one file. A broken block is reported and skipped, the rest still load
+fn parse(name: &str, text: &str) -> Vec<Handler> {
+    // Err = the block had a mistake, already reported
+    let mut blocks: Vec<Result<Handler, ()>> = vec![];
+    for (n, line) in text.lines().enumerate() {
+        let origin = format!("{}:{}", name, n + 1);
+        let result = words(line).and_then(|words| match (words.first().map(String::as_str), blocks.last_mut()) {
+            (None, _) => Ok(()),
+            (Some("on"), _) => header(origin.clone(), &words).map(|h| blocks.push(Ok(h))),
+            (Some(_), Some(Ok(handler))) => statement(handler, &words),
+            (Some(_), Some(Err(()))) => Ok(()),
+            (Some(_), None) => Err("comando fora de um \"on ...\"".into()),
+        });
+        if let Err(e) = result {
+            warn!("[SCRIPTS] ❌ {}: {}", origin, e);
+            match blocks.last_mut() {
+                // A broken header starts a broken block
+                Some(block) if line.split_whitespace().next() != Some("on") => *block = Err(()),
+                _ => blocks.push(Err(())),
+            }
+        }
+    }
+    blocks.into_iter()
+        .flatten()
+        .filter(|h| {
+            if h.actions.is_empty() {
+                warn!("[SCRIPTS] ⚠️ {}: gatilho sem nenhuma ação, ignorei", h.origin);
+            }
+            !h.actions.is_empty()
+        })
+        .collect()
+}
+
+/// (file, last modified) of every script, sorted so the order is stable
+fn scan(dir: &str) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let Ok(entries) = std::fs::read_dir(dir) else { return vec![] };
+    let mut files: Vec<(PathBuf, Option<SystemTime>)> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|x| x == EXTENSION))
+        .map(|p| {
+            let modified = std::fs::metadata(&p).and_then(|m| m.modified()).ok();
+            (p, modified)
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+/// Re-read the folder if anything in it changed since last time
+fn reload(loaded: &mut Scripts, dir: &str) {
+    if loaded.checked.is_some_and(|c| c.elapsed() < RELOAD_EVERY) {
+        return;
+    }
+    loaded.checked = Some(Instant::now());
+    let files = scan(dir);
+    if files == loaded.files {
+        return;
+    }
+    loaded.handlers = files
+        .iter()
+        .filter_map(|(path, _)| {
+            let name = path.file_name()?.to_string_lossy().to_string();
+            match std::fs::read_to_string(path) {
+                Ok(text) => Some(parse(&name, &text)),
+                Err(e) => {
+                    warn!("[SCRIPTS] ❌ Não consegui ler {}: {}", path.display(), e);
+                    None
+                }
+            }
+        })
+        .flatten()
+        .collect();
+    info!("[SCRIPTS] 📜 {} gatilho(s) de {} arquivo(s) em {}/", loaded.handlers.len(), files.len(), dir);
+    loaded.files = files;
+}
+
+/// What the bot knows right now, plus the event's own `vars`
+fn context(ctx: &Ctx, mut vars: HashMap<&'static str, String>) -> HashMap<&'static str, String> {
+    let who = vars.get("sender").or_else(|| vars.get("player")).cloned();
+    if let Some(who) = who {
+        let memory = ctx.state.brain.memory.lock().unwrap();
+        let profile = memory.social.players.get(&who);
+        vars.insert("relation", profile.map(|p| format!("{:?}", p.relationship)).unwrap_or_else(|| "Stranger".into()));
+        vars.insert("trust", profile.map(|p| p.trust_level).unwrap_or(0).to_string());
+    }
+    vars.insert("mood", format!("{:?}", ctx.state.brain.personality.lock().unwrap().mood));
+    let current_goal = ctx.state.brain.goals.lock().unwrap().current_goal().map(|g| g.name.clone());
+    vars.insert("current_goal", current_goal.unwrap_or_default());
+    vars.insert("health", format!("{:.0}", ctx.bot.health()));
+    vars.insert("food", ctx.bot.hunger().food.to_string());
+    vars.insert("me", ctx.bot.username());
+    vars
+}
+
+/// What a handler's actions come to once the event's vars are filled in
+#[derive(Debug)]
+enum Effect {
+    Motor(MotorCommand),
+    Goal(String, GoalPriority),
+}
+
+fn effects(handler: &Handler, vars: &HashMap<&str, String>) -> Vec<Effect> {
+    let mut effects = vec![];
+    for action in &handler.actions {
+        match action {
+            Action::Say(text) => {
+                let line = interpolate(text, vars);
+                // "{message}" from chat must never turn into a /command
+                if line.starts_with('/') && !text.starts_with('/') {
+                    warn!("[SCRIPTS] ⚠️ {}: não vou mandar \"{}\"", handler.origin, line);
+                    continue;
+                }
+                effects.push(Effect::Motor(MotorCommand::Chat(line)));
+            }
+            Action::Whisper(text) => match vars.get("sender").or_else(|| vars.get("player")) {
+                Some(player) => effects.push(Effect::Motor(MotorCommand::Whisper {
+                    player: player.clone(),
+                    message: interpolate(text, vars),
+                })),
+                None => debug!("[SCRIPTS] {}: whisper sem ninguém pra responder", handler.origin),
+            },
+            Action::Motor(command) => effects.push(Effect::Motor(command.clone())),
+            Action::Goal(name, priority) => effects.push(Effect::Goal(interpolate(name, vars), priority.clone())),
+        }
+    }
+    effects
+}
+
+impl Scripts {
+    /// Handlers for this event past their pattern, `every` and cooldown. Counts the ticks
+    fn ready(&mut self, trigger: Trigger, message: &str) -> Vec<usize> {
+        if trigger == Trigger::Tick {
+            self.ticks += 1;
+        }
+        let message = message.to_lowercase();
+        self.handlers.iter().enumerate()
+            .filter(|(_, h)| h.trigger == trigger)
+            .filter(|(_, h)| h.pattern.as_ref().is_none_or(|p| message.contains(p.as_str())))
+            .filter(|(_, h)| self.ticks.is_multiple_of(h.every))
+            .filter(|(_, h)| h.last_fired.is_none_or(|t| t.elapsed() >= h.cooldown))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// The ready handlers whose `if`s all hold, started on their cooldown → (origin, what they do)
+    fn fire(&mut self, ready: Vec<usize>, vars: &HashMap<&str, String>) -> Vec<(String, Vec<Effect>)> {
+        ready.into_iter()
+            .filter_map(|i| {
+                let handler = &mut self.handlers[i];
+                if !handler.conditions.iter().all(|c| c.holds(vars)) {
+                    return None;
+                }
+                handler.last_fired = Some(Instant::now());
+                debug!("[SCRIPTS] 📜 {} disparou", handler.origin);
+                Some((handler.origin.clone(), effects(handler, vars)))
+            })
+            .collect()
+    }
+}
+
+/// Run every matching handler for this event
+fn fire(ctx: &Ctx, trigger: Trigger, vars: HashMap<&'static str, String>) {
+    let config = Config::load();
+    if !config.scripts_enabled {
+        return;
+    }
+    let fired = {
+        let mut scripts = ctx.state.scripts.lock().unwrap();
+        reload(&mut scripts, &config.scripts_dir);
+        let ready = scripts.ready(trigger, vars.get("message").map(String::as_str).unwrap_or_default());
+        if ready.is_empty() {
+            return;
+        }
+        scripts.fire(ready, &context(ctx, vars))
+    };
+    for (origin, effects) in fired {
+        for effect in effects {
+            match effect {
+                Effect::Motor(command) => ctx.state.motor.queue(command),
+                Effect::Goal(name, priority) => {
+                    let mut goals = ctx.state.brain.goals.lock().unwrap();
+                    // Already on the list — a script firing twice shouldn't pile them up
+                    let open = goals.goals.iter().any(|g| g.name == name && matches!(g.status, GoalStatus::Pending | GoalStatus::Active | GoalStatus::Paused));
+                    if !open {
+                        goals.add_goal(Goal::new(&name, &format!("Script {}", origin), priority));
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn on_tick(ctx: &Ctx) {
+    fire(ctx, Trigger::Tick, HashMap::new());
+}
+
+pub fn on_chat(ctx: &Ctx, chat: &ChatIn) {
+    // Never answer ourselves — a script saying its own trigger would loop
+    if chat.sender.as_deref() == Some(ctx.bot.username().as_str()) {
+        return;
+    }
+    let mut vars = HashMap::from([("message", chat.message.clone())]);
+    if let Some(sender) = &chat.sender {
+        vars.insert("sender", sender.clone());
+    }
+    fire(ctx, Trigger::Chat, vars);
+}
+
+pub fn on_damage(ctx: &Ctx, hit: &Damage) {
+    fire(ctx, Trigger::Damage, HashMap::from([("amount", format!("{:.1}", hit.amount))]));
+}
+
+pub fn on_threat(ctx: &Ctx, seen: &ThreatDetected) {
+    let mut vars = HashMap::from([
+        ("threat", seen.threat.description.clone()),
+        ("level", format!("{:?}", seen.threat.level)),
+    ]);
+    if let Some(player) = &seen.player {
+        vars.insert("player", player.clone());
+    }
+    fire(ctx, Trigger::Threat, vars);
+}
+
+pub fn on_goal(ctx: &Ctx, change: &GoalChanged) {
+    fire(ctx, Trigger::Goal, HashMap::from([("goal", change.goal.clone()), ("status", format!("{:?}", change.status))]));
+}
+
+pub fn on_approach(ctx: &Ctx, near: &PlayerApproached) {
+    fire(ctx, Trigger::Approach, HashMap::from([("player", near.player.clone())]));
+}
+
+pub fn on_ore(ctx: &Ctx, ore: &OreFound) {
+    fire(ctx, Trigger::Ore, HashMap::from([("block", ore.block.clone())]));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&'static str, &str)]) -> HashMap<&'static str, String> {
+        pairs.iter().map(|(k, v)| (*k, v.to_string())).collect()
+    }
+
+    fn loaded(text: &str) -> Scripts {
+        Scripts { handlers: parse("test.script", text), ..Default::default() }
+    }
+
+    /// What fired, as the chat lines and goal names it produced
+    fn said(fired: Vec<(String, Vec<Effect>)>) -> Vec<String> {
+        fired.into_iter()
+            .flat_map(|(_, effects)| effects)
+            .map(|effect| match effect {
+                Effect::Motor(MotorCommand::Chat(line)) => line,
+                Effect::Motor(MotorCommand::Whisper { player, message }) => format!("@{} {}", player, message),
+                Effect::Goal(name, priority) => format!("goal {} {:?}", name, priority),
+                Effect::Motor(other) => format!("{:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn interpolates_once() {
+        let vars = vars(&[("sender", "{me}"), ("me", "Sentinel")]);
+        assert_eq!(interpolate("oi {sender}, sou {me}", &vars), "oi {me}, sou Sentinel");
+        assert_eq!(interpolate("{nada} {sender", &vars), "{nada} {sender");
+        assert_eq!(interpolate("{{me}}", &vars), "{Sentinel}");
+    }
+
+    #[test]
+    fn words_and_quotes() {
+        assert_eq!(words(r#"say "bom dia, {sender}!" # comentário"#).unwrap(), ["say", "bom dia, {sender}!"]);
+        assert_eq!(words(r#"say "ele disse \"oi\"""#).unwrap(), ["say", r#"ele disse "oi""#]);
+        assert!(words(r#"say "sem fim"#).is_err());
+    }
+
+    #[test]
+    fn parses_blocks() {
+        let handlers = parse("test.script", "on chat \"Bom Dia\"\n  if trust >= 5\n  cooldown 60\n  say oi\n  motor goto 1 64 -3\non tick every 100\n  goal Vigiar low\n");
+        assert_eq!(handlers.len(), 2);
+        let chat = &handlers[0];
+        assert_eq!((chat.trigger, chat.pattern.as_deref(), chat.cooldown), (Trigger::Chat, Some("bom dia"), Duration::from_secs(60)));
+        assert_eq!((chat.conditions.len(), chat.actions.len(), chat.origin.as_str()), (1, 2, "test.script:1"));
+        assert!(matches!(chat.actions[1], Action::Motor(MotorCommand::GotoBlock { x: 1, y: 64, z: -3 })));
+        assert_eq!((handlers[1].trigger, handlers[1].every), (Trigger::Tick, 100));
+    }
+
+    #[test]
+    fn broken_block_is_skipped() {
+        let text = "say solto\non chat\n  if nada == 1\n  say nunca\non explode\n  say nunca\non damage\n  dance\non damage\n  say ai\non ore\n";
+        let handlers = parse("test.script", text);
+        // The unknown variable, the unknown event, the unknown statement and the empty block all go
+        assert_eq!(handlers.len(), 1);
+        assert_eq!(handlers[0].origin, "test.script:9");
+    }
+
+    #[test]
+    fn example_loads_whole() {
+        let text = include_str!("../scripts/example.script.example");
+        let blocks = text.lines().filter(|l| l.starts_with("on ")).count();
+        assert_eq!(parse("example.script", text).len(), blocks);
+    }
+
+    #[test]
+    fn fires_when_conditions_hold() {
+        let mut scripts = loaded("on chat \"bom dia\"\n  if relation == friend\n  say \"bom dia {sender}!\"\n  whisper \"{message}\"\n  goal \"Ajudar {sender}\" high\n");
+        let friend = vars(&[("sender", "Steve"), ("message", "Bom dia pessoal"), ("relation", "Friend")]);
+        let ready = scripts.ready(Trigger::Chat, "Bom dia pessoal");
+        assert_eq!(said(scripts.fire(ready, &friend)), ["bom dia Steve!", "@Steve Bom dia pessoal", "goal Ajudar Steve High"]);
+
+        let stranger = vars(&[("sender", "Alex"), ("message", "bom dia"), ("relation", "Stranger")]);
+        let ready = scripts.ready(Trigger::Chat, "bom dia");
+        assert!(scripts.fire(ready, &stranger).is_empty());
+        assert!(scripts.ready(Trigger::Chat, "boa noite").is_empty());
+        assert!(scripts.ready(Trigger::Damage, "").is_empty());
+    }
+
+    #[test]
+    fn numbers_compare_as_numbers() {
+        let mut scripts = loaded("on damage\n  if health < 10\n  if amount >= 2.5\n  say ai\n");
+        let ready = scripts.ready(Trigger::Damage, "");
+        assert_eq!(said(scripts.fire(ready.clone(), &vars(&[("health", "9"), ("amount", "3.0")]))).len(), 1);
+        assert!(scripts.fire(ready.clone(), &vars(&[("health", "12"), ("amount", "3.0")])).is_empty());
+        // Not a number: < and > never hold
+        assert!(scripts.fire(ready, &vars(&[("health", "muita"), ("amount", "3.0")])).is_empty());
+    }
+
+    #[test]
+    fn chat_cant_become_a_command() {
+        let mut scripts = loaded("on chat\n  say \"{message}\"\n  say \"/spawn\"\n");
+        let ready = scripts.ready(Trigger::Chat, "/op Steve");
+        assert_eq!(said(scripts.fire(ready, &vars(&[("message", "/op Steve")]))), ["/spawn"]);
+    }
+
+    #[test]
+    fn cooldown_and_ticks_are_per_bot() {
+        let text = "on chat\n  cooldown 300\n  say oi\non tick every 3\n  motor jump\n";
+        let (mut one, mut two) = (loaded(text), loaded(text));
+        let ready = one.ready(Trigger::Chat, "oi");
+        assert_eq!(one.fire(ready, &HashMap::new()).len(), 1);
+        // Cooling down on the bot that answered, not on the other one
+        assert!(one.ready(Trigger::Chat, "oi").is_empty());
+        assert_eq!(two.ready(Trigger::Chat, "oi").len(), 1);
+
+        let fired = |scripts: &mut Scripts| !scripts.ready(Trigger::Tick, "").is_empty();
+        assert_eq!((0..6).map(|_| fired(&mut one)).collect::<Vec<_>>(), [false, false, true, false, false, true]);
+        assert!(!fired(&mut two));
+    }
+}
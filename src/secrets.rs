@@ -0,0 +1,247 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as B64;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::io::{BufRead, Write};
+use std::num::NonZeroU32;
+use std::sync::RwLock;
+
+// ============================================================
+// SECRETS — Keys and passwords from wherever they're kept, never in the logs
+// gemini_api_key = "cmd:pass show mc/gemini" · "enc:gemini" · "env:GOOGLE_KEY" · "file:/run/secrets/gemini"
+// ============================================================
+
+/// Encrypted store, sealed with SECRETS_PASSPHRASE. Managed with `frankfurt_sentinel secrets ...`
+const DEFAULT_FILE: &str = "secrets.enc";
+const PBKDF2_ROUNDS: u32 = 100_000;
+const SALT_LEN: usize = 16;
+/// Shorter than this isn't worth masking — it'd eat ordinary words
+const MIN_REDACT: usize = 6;
+
+/// Every secret value we've handed out, so the logger can mask them
+static KNOWN: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// What secrets.enc holds on disk
+#[derive(Serialize, Deserialize)]
+struct Sealed {
+    salt: String,
+    nonce: String,
+    data: String,
+}
+
+/// Turns config values into the real secret, opening the encrypted file at most once
+#[derive(Default)]
+pub struct Resolver {
+    vault: Option<Result<BTreeMap<String, String>, String>>,
+}
+
+impl Resolver {
+    /// "prefix:reference" → the secret. Anything without a known prefix is the secret itself
+    pub fn resolve(&mut self, raw: &str) -> Result<String, String> {
+        let value = match raw.split_once(':') {
+            Some(("env", var)) => env::var(var).map_err(|_| format!("variável de ambiente {} não existe", var))?,
+            Some(("file", path)) => std::fs::read_to_string(path)
+                .map_err(|e| format!("não consegui ler {}: {}", path, e))?
+                .trim()
+                .to_string(),
+            Some(("cmd", command)) => run(command)?,
+            Some(("enc", name)) => self.vault()?
+                .get(name)
+                .cloned()
+                .ok_or_else(|| format!("\"{}\" não está em {} (frankfurt_sentinel secrets set {})", name, file(), name))?,
+            _ => raw.to_string(),
+        };
+        remember(&value);
+        Ok(value)
+    }
+
+    fn vault(&mut self) -> Result<&BTreeMap<String, String>, String> {
+        self.vault.get_or_insert_with(open).as_ref().map_err(Clone::clone)
+    }
+}
+
+/// Mask every secret we know about
+pub fn redact(text: &str) -> String {
+    let known = KNOWN.read().unwrap();
+    known.iter().fold(text.to_string(), |text, secret| text.replace(secret.as_str(), "***"))
+}
+
+fn remember(value: &str) {
+    if value.len() < MIN_REDACT {
+        return;
+    }
+    let mut known = KNOWN.write().unwrap();
+    if !known.iter().any(|k| k == value) {
+        known.push(value.to_string());
+        // Longest first, so a secret containing another is masked whole
+        known.sort_by_key(|k| std::cmp::Reverse(k.len()));
+    }
+}
+
+/// Output of a shell command, like `pass show` or `op read`
+fn run(command: &str) -> Result<String, String> {
+    let output = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .output()
+        .map_err(|e| format!("não consegui rodar \"{}\": {}", command, e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let why = stderr.lines().next().map(str::trim).filter(|l| !l.is_empty()).unwrap_or("sem mensagem");
+        return Err(format!("\"{}\" falhou ({}): {}", command, output.status, why));
+    }
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if value.is_empty() {
+        return Err(format!("\"{}\" não imprimiu nada", command));
+    }
+    Ok(value)
+}
+
+fn file() -> String {
+    env::var("SECRETS_FILE").ok().filter(|f| !f.trim().is_empty()).unwrap_or_else(|| DEFAULT_FILE.into())
+}
+
+fn passphrase() -> Result<String, String> {
+    env::var("SECRETS_PASSPHRASE")
+        .ok()
+        .filter(|p| !p.is_empty())
+        .ok_or_else(|| "SECRETS_PASSPHRASE não foi definida — sem ela não abro o cofre".into())
+}
+
+fn key(passphrase: &str, salt: &[u8]) -> LessSafeKey {
+    let mut key = [0u8; 32];
+    let rounds = NonZeroU32::new(PBKDF2_ROUNDS).unwrap();
+    ring::pbkdf2::derive(ring::pbkdf2::PBKDF2_HMAC_SHA256, rounds, salt, passphrase.as_bytes(), &mut key);
+    LessSafeKey::new(UnboundKey::new(&CHACHA20_POLY1305, &key).unwrap())
+}
+
+/// Decrypt the whole store. A missing file is an empty store
+fn open() -> Result<BTreeMap<String, String>, String> {
+    let path = file();
+    let Ok(text) = std::fs::read_to_string(&path) else { return Ok(BTreeMap::new()) };
+    unseal(&text, &passphrase()?).map_err(|e| format!("{} {}", path, e))
+}
+
+/// Encrypt the whole store with a fresh salt and nonce
+fn seal(secrets: &BTreeMap<String, String>) -> Result<(), String> {
+    let text = sealed(secrets, &passphrase()?)?;
+    let path = file();
+    write_private(&path, &text).map_err(|e| format!("não consegui salvar {}: {}", path, e))
+}
+
+/// secrets.enc's contents → the store. Errors read after the file name
+fn unseal(text: &str, passphrase: &str) -> Result<BTreeMap<String, String>, String> {
+    let sealed: Sealed = serde_json::from_str(text).map_err(|e| format!("corrompido: {}", e))?;
+    let decode = |field: &str| B64.decode(field).map_err(|e| format!("corrompido: {}", e));
+    let (salt, nonce, mut data) = (decode(&sealed.salt)?, decode(&sealed.nonce)?, decode(&sealed.data)?);
+    let nonce = Nonce::try_assume_unique_for_key(&nonce).map_err(|_| "corrompido: nonce inválido".to_string())?;
+    let plain = key(passphrase, &salt)
+        .open_in_place(nonce, Aad::empty(), &mut data)
+        .map_err(|_| "não abriu — SECRETS_PASSPHRASE errada?".to_string())?;
+    serde_json::from_slice(plain).map_err(|e| format!("corrompido: {}", e))
+}
+
+/// The store → what secrets.enc holds
+fn sealed(secrets: &BTreeMap<String, String>, passphrase: &str) -> Result<String, String> {
+    let rng = SystemRandom::new();
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill(&mut salt).and_then(|_| rng.fill(&mut nonce)).map_err(|_| "sem fonte de aleatoriedade".to_string())?;
+    let mut data = serde_json::to_vec(secrets).map_err(|e| e.to_string())?;
+    key(passphrase, &salt)
+        .seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::empty(), &mut data)
+        .map_err(|_| "falha ao cifrar".to_string())?;
+    let sealed = Sealed { salt: B64.encode(salt), nonce: B64.encode(nonce), data: B64.encode(data) };
+    serde_json::to_string_pretty(&sealed).map_err(|e| e.to_string())
+}
+
+/// Owner-only on unix (0600), an existing file included — it's ciphertext, but no reason to share it
+fn write_private(path: &str, text: &str) -> std::io::Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path)?;
+    #[cfg(unix)]
+    file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+    file.write_all(text.as_bytes())
+}
+
+/// `frankfurt_sentinel secrets <set|rm|list> [name]`. None = not a secrets command, start the bot
+pub fn command(args: &[String]) -> Option<i32> {
+    if args.first().map(String::as_str) != Some("secrets") {
+        return None;
+    }
+    let result = match (args.get(1).map(String::as_str), args.get(2)) {
+        (Some("list"), _) => open().map(|secrets| {
+            for name in secrets.keys() {
+                println!("{}", name);
+            }
+        }),
+        (Some("set"), Some(name)) => {
+            // Read from stdin, so the value never lands in the shell history
+            eprintln!("valor de {} (uma linha):", name);
+            let mut value = String::new();
+            let _ = std::io::stdin().lock().read_line(&mut value);
+            open().and_then(|mut secrets| {
+                secrets.insert(name.clone(), value.trim().to_string());
+                seal(&secrets)
+            })
+        }
+        (Some("rm"), Some(name)) => open().and_then(|mut secrets| match secrets.remove(name) {
+            Some(_) => seal(&secrets),
+            None => Err(format!("\"{}\" não está em {}", name, file())),
+        }),
+        _ => Err("uso: frankfurt_sentinel secrets list | set <nome> | rm <nome>".into()),
+    };
+    match result {
+        Ok(()) => Some(0),
+        Err(e) => {
+            eprintln!("[SECRETS] ❌ {}", e);
+            Some(1)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store() -> BTreeMap<String, String> {
+        BTreeMap::from([("gemini".to_string(), "AIza-not-a-real-key".to_string()), ("auth".to_string(), "hunter22".to_string())])
+    }
+
+    #[test]
+    fn seal_and_open() {
+        let text = sealed(&store(), "correct horse").unwrap();
+        assert!(!text.contains("hunter22"));
+        assert_eq!(unseal(&text, "correct horse").unwrap(), store());
+        // Fresh salt and nonce each time
+        assert_ne!(text, sealed(&store(), "correct horse").unwrap());
+    }
+
+    #[test]
+    fn wrong_passphrase() {
+        let text = sealed(&store(), "correct horse").unwrap();
+        let error = unseal(&text, "battery staple").unwrap_err();
+        assert!(error.contains("SECRETS_PASSPHRASE"), "{}", error);
+        assert!(unseal("{}", "correct horse").unwrap_err().starts_with("corrompido"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn file_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+        let path = std::env::temp_dir().join(format!("secrets-test-{}.enc", std::process::id()));
+        let path = path.to_str().unwrap();
+        std::fs::write(path, "old").unwrap();
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        write_private(path, "new").unwrap();
+        let mode = std::fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!((mode, std::fs::read_to_string(path).unwrap().as_str()), (0o600, "new"));
+        std::fs::remove_file(path).unwrap();
+    }
+}
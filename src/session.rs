@@ -0,0 +1,53 @@
+use azalea::prelude::*;
+use std::sync::RwLock;
+use std::time::Instant;
+use crate::bot::State;
+
+// ============================================================
+// SESSION — The live client and its state, for code outside the handler
+// API, shutdown, watchdog: "tá conectado? então me dá o bot"
+// ============================================================
+
+/// Everyone connected from this process. The main bot (bot_name) comes first
+static CURRENT: RwLock<Vec<Session>> = RwLock::new(Vec::new());
+
+#[derive(Clone)]
+pub struct Session {
+    pub bot: Client,
+    pub state: State,
+    pub since: Instant,
+}
+
+/// We joined — everyone else can reach the bot now
+pub fn attach(bot: Client, state: State) {
+    let mut sessions = CURRENT.write().unwrap();
+    sessions.retain(|s| s.bot.entity != bot.entity);
+    let main = !state.brain.me.extra();
+    let session = Session { bot, state, since: Instant::now() };
+    if main { sessions.insert(0, session) } else { sessions.push(session) }
+}
+
+pub fn detach(bot: &Client) {
+    CURRENT.write().unwrap().retain(|s| s.bot.entity != bot.entity);
+}
+
+/// The main bot, if it's connected. Swarm extras don't count
+pub fn current() -> Option<Session> {
+    CURRENT.read().unwrap().first().filter(|s| !s.state.brain.me.extra()).cloned()
+}
+
+/// Every bot connected right now, main one first
+pub fn all() -> Vec<Session> {
+    CURRENT.read().unwrap().clone()
+}
+
+/// The session of this client — main bot or extra
+pub fn of(bot: &Client) -> Option<Session> {
+    CURRENT.read().unwrap().iter().find(|s| s.bot.entity == bot.entity).cloned()
+}
+
+/// The bot playing as `nick`, any of them
+pub fn named(nick: &str) -> Option<Session> {
+    let config = crate::config::Config::load();
+    CURRENT.read().unwrap().iter().find(|s| s.state.brain.me.name(&config).eq_ignore_ascii_case(nick)).cloned()
+}
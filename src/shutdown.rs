@@ -0,0 +1,82 @@
+use rand::seq::SliceRandom;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tracing::{info, warn};
+
+// ============================================================
+// SHUTDOWN — Ctrl+C / systemctl stop: save everything, say bye, leave
+// SIGTERM → "flw galera, tenho q ir" → memória salva → desconecta
+// ============================================================
+
+const GOODBYES: &[&str] = &[
+    "flw galera, tenho q ir",
+    "vou nessa, falou",
+    "tenho q sair, depois eu volto",
+    "fui, até mais",
+];
+/// Time for the goodbye to reach the server before we hang up
+const FLUSH: Duration = Duration::from_millis(800);
+
+/// Set once a signal arrives — the disconnect that follows isn't one to come back from
+static STOPPING: AtomicBool = AtomicBool::new(false);
+
+pub fn stopping() -> bool {
+    STOPPING.load(Ordering::Relaxed)
+}
+
+/// Wait for SIGINT or SIGTERM, then close up shop. A second signal kills it on the spot
+pub async fn watch() {
+    let signal = wait().await;
+    STOPPING.store(true, Ordering::Relaxed);
+    info!("[SHUTDOWN] 🛑 {} recebido, salvando e saindo (de novo = sai na hora)", signal);
+    tokio::spawn(async {
+        let signal = wait().await;
+        warn!("[SHUTDOWN] ⚠️ {} de novo, saindo sem esperar", signal);
+        std::process::exit(130);
+    });
+
+    let sessions = crate::session::all();
+    for session in &sessions {
+        let goodbye = GOODBYES.choose(&mut rand::thread_rng()).copied().unwrap_or("flw");
+        crate::events::say(&session.bot, goodbye);
+        {
+            let memory = session.state.brain.memory.lock().unwrap();
+            memory.save();
+            session.state.brain.goals.lock().unwrap().save();
+        }
+        if !session.state.brain.me.extra() {
+            crate::stats::finish(&session.state);
+        }
+    }
+    if !sessions.is_empty() {
+        info!("[SHUTDOWN] 💾 Memória, economia e objetivos salvos");
+        tokio::time::sleep(FLUSH).await;
+        for session in &sessions {
+            session.bot.disconnect();
+        }
+        tokio::time::sleep(FLUSH).await;
+    }
+    info!("[SHUTDOWN] 👋 Tchau");
+    std::process::exit(0);
+}
+
+/// Which signal arrived
+async fn wait() -> &'static str {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        let Ok(mut term) = signal(SignalKind::terminate()) else {
+            let _ = tokio::signal::ctrl_c().await;
+            return "SIGINT";
+        };
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => "SIGINT",
+            _ = term.recv() => "SIGTERM",
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+        "Ctrl+C"
+    }
+}
@@ -0,0 +1,371 @@
+use azalea::BlockPos;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use crate::bot::{State, VisiblePlayer};
+use crate::client::BotClient;
+use crate::cognitive::personality::PersonalityEvent;
+use crate::config::Config;
+use crate::plugins::brain::Judgement;
+use crate::replay::Snapshot;
+use crate::systems::chat_channel::ChatChannel;
+use crate::systems::motor::MotorTick;
+
+// ============================================================
+// SIM — The bot without a server: scripted players, a fake world, the real systems
+// frankfurt_sentinel sim xingamento.jsonl → what it said and did, how it feels after
+// ============================================================
+//
+// One event per line, blank lines and # comments skipped. Time only passes on waits:
+//   {"type":"player","name":"Steve","pos":[3,64,0]}
+//   {"type":"chat","sender":"Steve","message":"PedroRTX seu lixo"}
+//   {"type":"damage","amount":4}
+//   {"type":"wait","ticks":200}
+//   {"type":"leave","name":"Steve"}
+// Starts from the saved memory and goals (or a replay's snapshot) and never writes them back.
+// Replays also carry what the live bot decided: judged lines are checked against
+// what the sim decides, said/goal lines are shown next to it — those hang on dice rolls.
+// No LLM replies and no scripts/ here — both still need a real connection.
+
+/// Don't wait forever on a motor that died
+const MOTOR_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SimEvent {
+    /// Memory, goals and mood to start from — first line of a replay
+    Snapshot(Box<Snapshot>),
+    Wait { ticks: u32 },
+    Chat {
+        sender: String,
+        message: String,
+        #[serde(default)]
+        channel: ChatChannel,
+    },
+    Damage { amount: f32 },
+    Player { name: String, pos: [f64; 3] },
+    Leave { name: String },
+    /// Live: how the last chat line from `player` landed
+    Judged { player: String, directed: bool, trust_delta: i32 },
+    /// Live: we said this
+    Said { message: String },
+    /// Live: a goal moved
+    Goal { goal: String, status: String },
+}
+
+/// Everything the fake server knows, plus what the bot did to it
+struct MockWorld {
+    name: String,
+    position: [f64; 3],
+    health: f32,
+    players: Vec<VisiblePlayer>,
+    tick: u64,
+    transcript: Vec<String>,
+}
+
+impl MockWorld {
+    fn note(&mut self, line: String) {
+        self.transcript.push(format!("[{:>5}] {}", self.tick, line));
+    }
+}
+
+/// A connection to nowhere. Walking is instant, chat goes to the transcript
+#[derive(Clone)]
+pub struct MockClient {
+    world: Arc<Mutex<MockWorld>>,
+}
+
+impl MockClient {
+    pub fn new(name: &str) -> Self {
+        Self {
+            world: Arc::new(Mutex::new(MockWorld {
+                name: name.to_string(),
+                position: [0.5, 64.0, 0.5],
+                health: 20.0,
+                players: vec![],
+                tick: 0,
+                transcript: vec![],
+            })),
+        }
+    }
+
+    /// Everything that happened so far, one line each
+    pub fn transcript(&self) -> Vec<String> {
+        self.world.lock().unwrap().transcript.clone()
+    }
+}
+
+impl BotClient for MockClient {
+    fn username(&self) -> String {
+        self.world.lock().unwrap().name.clone()
+    }
+
+    fn chat(&self, message: &str) {
+        let mut world = self.world.lock().unwrap();
+        let line = format!("<{}> {}", world.name, message);
+        world.note(line);
+    }
+
+    fn jump(&self) {
+        self.world.lock().unwrap().note("🦘 pulou".into());
+    }
+
+    fn position(&self) -> [f64; 3] {
+        self.world.lock().unwrap().position
+    }
+
+    fn health(&self) -> f32 {
+        self.world.lock().unwrap().health
+    }
+
+    fn goto(&self, target: BlockPos) {
+        let mut world = self.world.lock().unwrap();
+        world.position = [target.x as f64 + 0.5, target.y as f64, target.z as f64 + 0.5];
+        world.note(format!("🚶 andou até {} {} {}", target.x, target.y, target.z));
+    }
+
+    fn goto_reached(&self) -> bool {
+        true
+    }
+
+    fn players(&self) -> Vec<VisiblePlayer> {
+        let world = self.world.lock().unwrap();
+        let here = world.position;
+        let distance = |p: &VisiblePlayer| (0..3).map(|i| (p.pos[i] - here[i]).powi(2)).sum::<f64>();
+        let mut players = world.players.clone();
+        players.sort_by(|a, b| distance(a).total_cmp(&distance(b)));
+        players
+    }
+
+    fn headless(&self) -> bool {
+        true
+    }
+}
+
+/// The real bot::State and motor, fed from a scenario instead of a server
+pub struct Sim {
+    pub client: MockClient,
+    pub state: State,
+    ticks: u64,
+    /// Everyone who showed up or spoke, for the summary
+    met: BTreeSet<String>,
+    /// How the last chat line landed here, to hold up against the live one
+    last_judgement: Option<(String, Judgement)>,
+    /// Judged lines that came out the same / differently from live
+    pub agreed: usize,
+    pub diverged: usize,
+}
+
+impl Sim {
+    pub fn new(name: &str) -> Self {
+        let client = MockClient::new(name);
+        let state = State::default();
+        state.motor.start(&client);
+        Self { client, state, ticks: 0, met: BTreeSet::new(), last_judgement: None, agreed: 0, diverged: 0 }
+    }
+
+    pub async fn run(&mut self, event: SimEvent) {
+        match event {
+            SimEvent::Wait { ticks } => {
+                for _ in 0..ticks {
+                    self.tick().await;
+                }
+            }
+            SimEvent::Snapshot(snapshot) => {
+                let Snapshot { memory, goals, personality } = *snapshot;
+                *self.state.brain.memory.lock().unwrap() = memory;
+                *self.state.brain.goals.lock().unwrap() = goals;
+                *self.state.brain.personality.lock().unwrap() = personality;
+                self.note("📼 memória, objetivos e humor da gravação".into());
+            }
+            SimEvent::Chat { sender, message, channel } => {
+                match channel {
+                    ChatChannel::Global => self.note(format!("<{}> {}", sender, message)),
+                    _ => self.note(format!("[{}] <{}> {}", channel.name(), sender, message)),
+                }
+                self.met.insert(sender.clone());
+                let config = Config::load();
+                if !config.is_own_account(&sender) {
+                    let judgement = crate::plugins::brain::absorb_chat(&self.state.brain, &config, channel, &sender, &message);
+                    if judgement.directed {
+                        self.note(format!("   (falando comigo — {})", self.relation(&sender)));
+                    }
+                    self.last_judgement = Some((sender, judgement));
+                }
+            }
+            SimEvent::Judged { player, directed, trust_delta } => {
+                let live = Judgement { directed, trust_delta };
+                match self.last_judgement.take() {
+                    Some((sender, here)) if sender == player && here == live => self.agreed += 1,
+                    here => {
+                        self.diverged += 1;
+                        let here = here.map_or("nada".to_string(), |(p, j)| format!("{} {:?}", p, j));
+                        self.note(format!("❌ ao vivo: {} {:?} — aqui: {}", player, live, here));
+                    }
+                }
+            }
+            SimEvent::Said { message } => self.note(format!("   (ao vivo disse: {})", message)),
+            SimEvent::Goal { goal, status } => self.note(format!("   (ao vivo: objetivo {} → {})", goal, status)),
+            SimEvent::Damage { amount } => {
+                let health = {
+                    let mut world = self.client.world.lock().unwrap();
+                    world.health = (world.health - amount).max(0.0);
+                    world.health
+                };
+                self.note(format!("💥 levou {} de dano (vida {})", amount, health));
+                crate::systems::reaction_delay::on_damage(&mut self.state.reaction.inner.lock().unwrap(), amount, None);
+                crate::bot::flinch(&self.state, health);
+            }
+            SimEvent::Player { name, pos } => {
+                self.note(format!("👤 {} em {:.0} {:.0} {:.0}", name, pos[0], pos[1], pos[2]));
+                self.met.insert(name.clone());
+                let mut world = self.client.world.lock().unwrap();
+                world.players.retain(|p| p.name != name);
+                world.players.push(VisiblePlayer { name, pos, sneaking: false, look: [0.0, 0.0, 1.0] });
+            }
+            SimEvent::Leave { name } => {
+                self.note(format!("👋 {} saiu", name));
+                self.client.world.lock().unwrap().players.retain(|p| p.name != name);
+            }
+        }
+    }
+
+    /// One game tick through the systems that don't need a server, then wait for the motor
+    async fn tick(&mut self) {
+        self.ticks += 1;
+        self.client.world.lock().unwrap().tick = self.ticks;
+        self.state.brain.personality.lock().unwrap().on_event(&PersonalityEvent::TimePassed);
+        if Config::load().features.dreamer {
+            crate::bot::dream(&self.state, 1);
+        }
+        self.state.motor.tick(MotorTick {
+            position: self.client.position(),
+            nearby_players: !self.client.players().is_empty(),
+        });
+        let waited = tokio::time::timeout(MOTOR_TIMEOUT, async {
+            while self.state.motor.view().ticks < self.ticks {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        });
+        if waited.await.is_err() {
+            self.note("⚠️ motor não respondeu".into());
+        }
+    }
+
+    fn note(&self, line: String) {
+        self.client.world.lock().unwrap().note(line);
+    }
+
+    fn relation(&self, player: &str) -> String {
+        let memory = self.state.brain.memory.lock().unwrap();
+        match memory.social.players.get(player) {
+            Some(profile) => format!("{:?}, confiança {}", profile.relationship, profile.trust_level),
+            None => "desconhecido".into(),
+        }
+    }
+
+    /// Mood, goals and what everyone we met means to us now
+    pub fn summary(&self) -> Vec<String> {
+        let mut lines = vec![];
+        {
+            let personality = self.state.brain.personality.lock().unwrap();
+            lines.push(format!("Humor: {:?} ({:.2})", personality.mood, personality.mood_intensity));
+        }
+        lines.extend(self.state.brain.goals.lock().unwrap().context_summary().lines().map(str::to_string));
+        for player in &self.met {
+            lines.push(format!("{}: {}", player, self.relation(player)));
+        }
+        lines.push(format!("Motor: {} comandos em {} ticks", self.state.motor.view().commands_executed, self.ticks));
+        lines
+    }
+}
+
+/// Events in a scenario file, or the line that's wrong
+pub fn parse(text: &str) -> Result<Vec<SimEvent>, String> {
+    text.lines()
+        .enumerate()
+        .map(|(n, line)| (n + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(n, line)| serde_json::from_str(line).map_err(|e| format!("linha {}: {}", n, e)))
+        .collect()
+}
+
+/// `frankfurt_sentinel sim <cenario.jsonl>` or `replay <gravação.jsonl>` — play it, print the
+/// transcript and where the bot ended up. Exits 1 if a judged line came out different from live
+pub async fn command(args: &[String]) -> Option<i32> {
+    let what = args.first().map(String::as_str).filter(|a| ["sim", "replay"].contains(a))?;
+    let Some(path) = args.get(1) else {
+        eprintln!("uso: frankfurt_sentinel {} <arquivo.jsonl>", what);
+        return Some(1);
+    };
+    let events = match std::fs::read_to_string(path).map_err(|e| e.to_string()).and_then(|text| parse(&text)) {
+        Ok(events) => events,
+        Err(e) => {
+            eprintln!("[SIM] ❌ {}: {}", path, e);
+            return Some(1);
+        }
+    };
+    let mut sim = Sim::new(&Config::load().bot_name);
+    for event in events {
+        sim.run(event).await;
+    }
+    for line in sim.client.transcript() {
+        println!("{}", line);
+    }
+    println!();
+    for line in sim.summary() {
+        println!("{}", line);
+    }
+    if sim.agreed + sim.diverged > 0 {
+        println!("Julgamentos iguais ao vivo: {}/{}", sim.agreed, sim.agreed + sim.diverged);
+    }
+    Some(if sim.diverged > 0 { 1 } else { 0 })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scenario_lines() {
+        let events = parse("# insultos\n{\"type\":\"chat\",\"sender\":\"Steve\",\"message\":\"oi\"}\n\n{\"type\":\"wait\",\"ticks\":3}").unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[1], SimEvent::Wait { ticks: 3 }));
+        assert_eq!(parse("{\"type\":\"voar\"}").unwrap_err().split(':').next(), Some("linha 1"));
+    }
+
+    #[tokio::test]
+    async fn plays_a_scenario() {
+        let scenario = r#"
+            {"type":"player","name":"Steve","pos":[3,64,0]}
+            {"type":"chat","sender":"Steve","message":"oi, tudo bem?","channel":"party"}
+            {"type":"judged","player":"Steve","directed":true,"trust_delta":1}
+            {"type":"damage","amount":4}
+            {"type":"wait","ticks":5}
+            {"type":"judged","player":"Alex","directed":false,"trust_delta":0}
+            {"type":"leave","name":"Steve"}
+        "#;
+        let mut sim = Sim::new("Sentinel");
+        // A clean slate, whatever is saved in data/
+        let fresh = Snapshot { memory: Default::default(), goals: Default::default(), personality: Default::default() };
+        sim.run(SimEvent::Snapshot(Box::new(fresh))).await;
+        for event in parse(scenario).unwrap() {
+            sim.run(event).await;
+        }
+
+        let transcript = sim.client.transcript().join("\n");
+        for expected in ["👤 Steve em 3 64 0", "[party] <Steve> oi, tudo bem?", "(falando comigo — ", "💥 levou 4 de dano (vida 16)", "[    5] 👋 Steve saiu"] {
+            assert!(transcript.contains(expected), "sem \"{}\" em:\n{}", expected, transcript);
+        }
+        assert!(!transcript.contains("motor não respondeu"), "{}", transcript);
+        // The party line was judged the way "live" says; the Alex line has nothing to match
+        assert_eq!((sim.agreed, sim.diverged), (1, 1));
+        assert!(transcript.contains("❌ ao vivo: Alex"), "{}", transcript);
+
+        let summary = sim.summary();
+        assert!(summary.iter().any(|l| l.starts_with("Steve: ")), "{:?}", summary);
+        assert!(summary.last().unwrap().ends_with("em 5 ticks"), "{:?}", summary);
+        assert_eq!(sim.client.health(), 16.0);
+    }
+}
@@ -0,0 +1,169 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::io::Write;
+use std::sync::{LazyLock, Mutex};
+use std::time::Instant;
+use tracing::{info, warn};
+use crate::bot::State;
+use crate::config::Config;
+use crate::cognitive::memory::Relationship;
+
+// ============================================================
+// STATS — What one session amounted to
+// "[STATS] 📊 2h13 online · 48 msgs · $0.0312 de LLM · 317 blocos · 1 morte · 2 amigos · 3 objetivos"
+// ============================================================
+
+const HISTORY_FILE: &str = "stats.jsonl";
+
+static CURRENT: LazyLock<Mutex<Option<Running>>> = LazyLock::new(|| Mutex::new(None));
+
+/// The session in progress: counters plus what things looked like when it started
+struct Running {
+    started: DateTime<Utc>,
+    since: Instant,
+    friends: HashSet<String>,
+    goals_completed: u32,
+    counts: Counts,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Counts {
+    pub messages_sent: u32,
+    pub llm_calls: u32,
+    pub tokens_in: u64,
+    pub tokens_out: u64,
+    pub llm_cost_usd: f64,
+    pub blocks_mined: u32,
+    pub deaths: u32,
+}
+
+/// One line of stats.jsonl
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionReport {
+    pub started: DateTime<Utc>,
+    pub ended: DateTime<Utc>,
+    pub uptime_secs: u64,
+    #[serde(flatten)]
+    pub counts: Counts,
+    pub new_friends: Vec<String>,
+    pub goals_completed: u32,
+}
+
+impl SessionReport {
+    pub fn summary(&self) -> String {
+        let minutes = self.uptime_secs / 60;
+        format!(
+            "{}h{:02} online · {} msgs · ${:.4} de LLM ({} chamadas) · {} blocos · {} morte(s) · {} amigo(s) novo(s) · {} objetivo(s)",
+            minutes / 60, minutes % 60, self.counts.messages_sent, self.counts.llm_cost_usd, self.counts.llm_calls,
+            self.counts.blocks_mined, self.counts.deaths, self.new_friends.len(), self.goals_completed,
+        )
+    }
+}
+
+/// Joined the server — start counting from zero
+pub fn begin(state: &State) {
+    let friends = friends(state);
+    let goals_completed = state.brain.goals.lock().unwrap().completed_count;
+    *CURRENT.lock().unwrap() = Some(Running {
+        started: Utc::now(),
+        since: Instant::now(),
+        friends,
+        goals_completed,
+        counts: Counts::default(),
+    });
+}
+
+/// Session over: log it and append it to the history. Only the first call per session reports
+pub fn finish(state: &State) -> Option<SessionReport> {
+    let running = CURRENT.lock().unwrap().take()?;
+    let now_friends = friends(state);
+    let goals_completed = state.brain.goals.lock().unwrap().completed_count;
+    let mut new_friends: Vec<String> = now_friends.difference(&running.friends).cloned().collect();
+    new_friends.sort();
+    let report = SessionReport {
+        started: running.started,
+        ended: Utc::now(),
+        uptime_secs: running.since.elapsed().as_secs(),
+        counts: running.counts,
+        new_friends,
+        goals_completed: goals_completed.saturating_sub(running.goals_completed),
+    };
+    info!("[STATS] 📊 {}", report.summary());
+    append(&report);
+    Some(report)
+}
+
+/// Counters so far, for the dashboard
+pub fn live() -> Option<(u64, Counts)> {
+    CURRENT.lock().unwrap().as_ref().map(|r| (r.since.elapsed().as_secs(), r.counts.clone()))
+}
+
+/// The last `n` sessions, oldest first
+pub fn history(n: usize) -> Vec<SessionReport> {
+    let path = format!("{}/{}", crate::cognitive::memory::data_dir(), HISTORY_FILE);
+    let Ok(text) = std::fs::read_to_string(path) else { return vec![] };
+    let reports: Vec<SessionReport> = text.lines().filter_map(|l| serde_json::from_str(l).ok()).collect();
+    reports[reports.len().saturating_sub(n)..].to_vec()
+}
+
+fn count(f: impl FnOnce(&mut Counts)) {
+    if let Some(running) = CURRENT.lock().unwrap().as_mut() {
+        f(&mut running.counts);
+    }
+}
+
+pub fn message_sent() {
+    count(|c| c.messages_sent += 1);
+}
+
+pub fn block_mined() {
+    count(|c| c.blocks_mined += 1);
+}
+
+pub fn died() {
+    count(|c| c.deaths += 1);
+}
+
+/// One LLM reply, priced by whichever model answered. Only flash/pro have prices; the rest count as free
+pub fn llm_used(model: &str, tokens_in: u64, tokens_out: u64) {
+    let config = Config::load();
+    let [input, output] = if model == config.model_pro {
+        config.pro_price
+    } else if model == config.model_flash {
+        config.flash_price
+    } else {
+        [0.0, 0.0]
+    };
+    let cost = (tokens_in as f64 * input + tokens_out as f64 * output) / 1_000_000.0;
+    count(|c| {
+        c.llm_calls += 1;
+        c.tokens_in += tokens_in;
+        c.tokens_out += tokens_out;
+        c.llm_cost_usd += cost;
+    });
+}
+
+fn friends(state: &State) -> HashSet<String> {
+    let memory = state.brain.memory.lock().unwrap();
+    memory.social.players.values()
+        .filter(|p| matches!(p.relationship, Relationship::Friend | Relationship::BestFriend))
+        .map(|p| p.name.clone())
+        .collect()
+}
+
+fn append(report: &SessionReport) {
+    let dir = crate::cognitive::memory::data_dir();
+    let path = format!("{}/{}", dir, HISTORY_FILE);
+    let line = match serde_json::to_string(report) {
+        Ok(line) => line,
+        Err(e) => return warn!("[STATS] ⚠️ Não consegui serializar o relatório: {}", e),
+    };
+    let written = std::fs::create_dir_all(&dir).and_then(|_| {
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&path)?;
+        writeln!(file, "{}", line)
+    });
+    if let Err(e) = written {
+        warn!("[STATS] ⚠️ Não consegui salvar em {}: {}", path, e);
+    }
+}
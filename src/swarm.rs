@@ -0,0 +1,95 @@
+use azalea::swarm::SwarmBuilder;
+use std::time::Duration;
+use tracing::{info, warn};
+use crate::bot;
+use crate::config::Config;
+
+// ============================================================
+// SWARM — Several characters from one process, each with its own head
+// [[swarm]] name = "Zezin_" → PedroRTX + Zezin_ on the same server, memories in data/swarm/Zezin_/
+// ============================================================
+//
+// Config, the LLM key and the bus handlers are shared. Memory, goals, mood and
+// every per-tick system live in each bot's own State. The API, replays and the
+// watchdog follow the main bot (bot_name); stats and the profiler add everyone up.
+
+/// Subfolder of the data dir the extra bots keep their memory in
+const FOLDER: &str = "swarm";
+/// Bots join one after the other, this far apart, so the server doesn't see a wave
+const JOIN_DELAY: Duration = Duration::from_secs(5);
+
+/// Who one bot of the swarm is. The default is the main bot, the only one outside a swarm
+#[derive(Debug, Clone, Default)]
+pub struct Identity {
+    name: Option<String>, // None = config.bot_name, which follows account rotation
+    pub persona: String,  // Extra lines for the LLM prompt; empty = just the usual Pedro
+    pub folder: Option<String>, // Where memory and goals live; None = data_dir()
+}
+
+impl Identity {
+    /// One of the [[swarm]] bots
+    pub fn extra(&self) -> bool {
+        self.name.is_some()
+    }
+
+    /// Our nick in game
+    pub fn name(&self, config: &Config) -> String {
+        self.name.clone().unwrap_or_else(|| config.bot_name.clone())
+    }
+}
+
+/// The main bot first, then every [[swarm]] bot
+fn crew(config: &Config) -> Vec<(String, String, Identity)> {
+    let data = crate::cognitive::memory::data_dir();
+    let mut crew = vec![(config.bot_name.clone(), config.bot_email.clone(), Identity::default())];
+    for member in &config.swarm {
+        let me = Identity {
+            name: Some(member.name.clone()),
+            persona: member.persona.clone(),
+            folder: Some(format!("{}/{}/{}", data, FOLDER, member.name)),
+        };
+        crew.push((member.name.clone(), member.email.clone(), me));
+    }
+    crew
+}
+
+/// Play every bot on the main server until the process stops. Azalea reconnects each one, on our schedule
+pub async fn run(config: &Config) -> anyhow::Result<()> {
+    let profile = config.servers[0].clone();
+    let address = profile.address();
+    let opts = match profile.minecraft_proxy().await {
+        Ok(Some(proxy)) => {
+            info!("Joining through SOCKS5 proxy {}", proxy.addr);
+            azalea::JoinOpts::new().proxy(proxy)
+        }
+        Ok(None) => azalea::JoinOpts::new(),
+        Err(e) => anyhow::bail!("proxy failed: {}", e),
+    };
+    // Each bot's reconnect wait is set by SwarmBackoff when it drops — kick reason and backoff, like solo
+    let mut builder = SwarmBuilder::new()
+        .add_plugins(crate::reconnect::SwarmBackoff)
+        .set_handler(bot::handle)
+        .join_delay(JOIN_DELAY);
+    let crew = crew(config);
+    let names: Vec<&str> = crew.iter().map(|(name, ..)| name.as_str()).collect();
+    info!("[SWARM] 🐝 {} bots em {} ({}): {}", crew.len(), profile.name, address, names.join(", "));
+    for (name, email, me) in crew.iter().cloned() {
+        let account = if email.is_empty() {
+            azalea::Account::offline(&name)
+        } else {
+            match azalea::Account::microsoft(&email).await {
+                Ok(account) => account,
+                Err(e) => {
+                    warn!("[SWARM] ⚠️ Login da Microsoft falhou pra {} ({}), ele fica de fora: {:?}", name, email, e);
+                    crate::events::alert("auth", format!("🔑 Login da Microsoft falhou pra {}: {}", email, e));
+                    continue;
+                }
+            }
+        };
+        builder = builder.add_account_with_state_and_opts(account, bot::State::for_bot(me), opts.clone());
+    }
+    tokio::spawn(crate::roles::run()); // Idle until [roles] gives someone a job
+    builder.start(address.as_str()).await;
+    info!("[SWARM] Swarm parou.");
+    Ok(())
+}
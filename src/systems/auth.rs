@@ -0,0 +1,148 @@
+use std::time::{Duration, Instant};
+use tracing::{error, info};
+
+// ============================================================
+// AUTH — AuthMe / nLogin prompts on offline-mode servers
+// "Please, register with /register <password> <password>" → /register senha senha
+// ============================================================
+
+/// The server nags every few seconds — don't answer every nag
+const RESEND_AFTER: Duration = Duration::from_secs(8);
+/// Give up after this many tries and tell the operator
+const MAX_ATTEMPTS: u32 = 3;
+/// AuthMe asks right on join; no prompt by now means the server doesn't use it
+const PROMPT_GRACE: Duration = Duration::from_secs(30);
+
+const REGISTER_PROMPTS: &[&str] = &[
+    "/register", "registre-se", "registrar-se", "please register", "use /register",
+];
+const LOGIN_PROMPTS: &[&str] = &[
+    "/login", "logue-se", "faça login", "faca login", "please login", "please, login",
+];
+const SUCCESS: &[&str] = &[
+    "successful login", "successfully logged", "successfully registered", "logged in successfully",
+    "logado com sucesso", "login realizado", "login efetuado", "registrado com sucesso", "registro efetuado",
+    "autenticado com sucesso",
+];
+const WRONG_PASSWORD: &[&str] = &["wrong password", "senha incorreta", "senha errada", "incorrect password"];
+const ALREADY_REGISTERED: &[&str] = &["already registered", "já está registrado", "ja esta registrado", "já registrado"];
+const NOT_REGISTERED: &[&str] = &["not registered", "não está registrado", "nao esta registrado", "não registrado"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AuthCommand {
+    Register,
+    Login,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AuthPhase {
+    Waiting,  // Joined, no prompt yet
+    NotAsked, // Grace period over without a prompt — no auth plugin here
+    Sent,     // Answered a prompt, waiting for the verdict
+    LoggedIn,
+    Failed,   // Out of attempts or wrong password — needs a human
+}
+
+#[derive(Debug)]
+pub struct AuthSession {
+    pub phase: AuthPhase,
+    pub attempts: u32,
+    last_command: Option<(AuthCommand, Instant)>,
+    joined_at: Instant,
+}
+
+impl Default for AuthSession {
+    fn default() -> Self {
+        Self { phase: AuthPhase::Waiting, attempts: 0, last_command: None, joined_at: Instant::now() }
+    }
+}
+
+fn has_any(lower: &str, needles: &[&str]) -> bool {
+    needles.iter().any(|n| lower.contains(n))
+}
+
+impl AuthSession {
+    /// Fresh login — whatever happened on the last connection doesn't count
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Nothing asked for a login in time: players typing "/login" later are just chatting
+    fn settle(&mut self) {
+        if self.phase == AuthPhase::Waiting && self.joined_at.elapsed() >= PROMPT_GRACE {
+            info!("[AUTH] 🤷 Nenhum pedido de /login em {}s, servidor sem AuthMe", PROMPT_GRACE.as_secs());
+            self.phase = AuthPhase::NotAsked;
+        }
+    }
+
+    /// Read a system message. Returns the command to send (without the password), if any
+    pub fn on_message(&mut self, message: &str) -> Option<AuthCommand> {
+        if matches!(self.phase, AuthPhase::LoggedIn | AuthPhase::Failed | AuthPhase::NotAsked) {
+            return None;
+        }
+        let lower = message.to_lowercase();
+
+        if has_any(&lower, SUCCESS) {
+            self.phase = AuthPhase::LoggedIn;
+            info!("[AUTH] ✅ Autenticado");
+            return None;
+        }
+        if has_any(&lower, WRONG_PASSWORD) {
+            error!("[AUTH] ❌ Senha errada — confere AUTH_PASSWORD no .env");
+            crate::events::alert("auth", "🔑 Senha errada no /login — confere AUTH_PASSWORD".into());
+            self.phase = AuthPhase::Failed;
+            return None;
+        }
+
+        // Switch command when the server says we picked the wrong one
+        let command = if has_any(&lower, ALREADY_REGISTERED) {
+            AuthCommand::Login
+        } else if has_any(&lower, NOT_REGISTERED) || has_any(&lower, REGISTER_PROMPTS) {
+            AuthCommand::Register
+        } else if has_any(&lower, LOGIN_PROMPTS) {
+            AuthCommand::Login
+        } else {
+            return None;
+        };
+
+        let switched = self.last_command.is_some_and(|(last, _)| last != command);
+        if !switched && self.last_command.is_some_and(|(_, at)| at.elapsed() < RESEND_AFTER) {
+            return None;
+        }
+        if self.attempts >= MAX_ATTEMPTS {
+            error!("[AUTH] ❌ Desisti depois de {} tentativas ({:?}) — servidor continua pedindo", self.attempts, command);
+            crate::events::alert("auth", format!("🔑 Desisti do {:?} depois de {} tentativas", command, self.attempts));
+            self.phase = AuthPhase::Failed;
+            return None;
+        }
+        self.attempts += 1;
+        self.phase = AuthPhase::Sent;
+        self.last_command = Some((command, Instant::now()));
+        info!("[AUTH] 🔑 {:?} (tentativa {}/{})", command, self.attempts, MAX_ATTEMPTS);
+        Some(command)
+    }
+
+    /// Until we're in, prompts look like chat ("...with the command: /login") — keep them away from the chat parser
+    pub fn intercepts(&mut self, message: &str) -> bool {
+        self.settle();
+        if matches!(self.phase, AuthPhase::LoggedIn | AuthPhase::NotAsked) {
+            return false;
+        }
+        let lower = message.to_lowercase();
+        has_any(&lower, REGISTER_PROMPTS)
+            || has_any(&lower, LOGIN_PROMPTS)
+            || has_any(&lower, SUCCESS)
+            || has_any(&lower, WRONG_PASSWORD)
+            || has_any(&lower, ALREADY_REGISTERED)
+            || has_any(&lower, NOT_REGISTERED)
+    }
+}
+
+impl AuthCommand {
+    pub fn with_password(&self, password: &str) -> String {
+        match self {
+            AuthCommand::Register => format!("/register {} {}", password, password),
+            AuthCommand::Login => format!("/login {}", password),
+        }
+    }
+}
@@ -1,10 +1,28 @@
 use serde::{Deserialize, Serialize};
-use azalea::BlockPos;
+use azalea::prelude::*;
+use azalea::pathfinder::goals::{InverseGoal, RadiusGoal};
+use azalea::{BlockPos, Vec3};
 use std::collections::HashMap;
+use std::time::Duration;
+use crate::systems::inventory::Inventory;
+use crate::systems::smart_mining::block_at;
+use crate::systems::storage::StorageIndex;
+use tracing::info;
 
 // ============================================================
 // BUILDER — Blueprint-based construction
 // ============================================================
+//
+// A build goes block by block in build_order: walk within reach, hold the right item,
+// click the face of a block next to the spot, check the world changed. Missing items
+// come out of the base chests or become goals; a spot that keeps failing is skipped.
+
+/// Longest walk to the next block
+const REACH: Duration = Duration::from_secs(20);
+/// Tries on one spot before giving up on it and moving on
+pub const MAX_ATTEMPTS: u32 = 3;
+/// What a block can go into without digging first
+const REPLACEABLE: &[&str] = &["air", "cave_air", "short_grass", "tall_grass", "fern", "snow", "dead_bush"];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockPlacement {
@@ -23,6 +41,15 @@ pub struct Blueprint {
 }
 
 impl Blueprint {
+    /// "casa", "farm"... → the blueprint, for commands
+    pub fn named(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "casa" | "house" => Some(Self::survival_house()),
+            "farm" | "fazenda" | "trigo" => Some(Self::wheat_farm()),
+            _ => None,
+        }
+    }
+
     /// Create a simple 5x4x5 survival house
     pub fn survival_house() -> Self {
         let mut blocks = vec![];
@@ -136,6 +163,13 @@ pub enum BuildState {
     Paused,
 }
 
+/// Missing blueprint materials
+#[derive(Debug, Clone, Default)]
+pub struct MaterialPlan {
+    pub withdraw: Vec<(String, u32)>, // Sitting in the base chests
+    pub gather: Vec<(String, u32)>,   // Have to go get it
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Builder {
     pub state: BuildState,
@@ -145,6 +179,8 @@ pub struct Builder {
     pub total_blocks: usize,
     pub builds_completed: u32,
     pub available_blueprints: Vec<String>,
+    #[serde(default)]
+    pub attempts: u32, // Failed tries on the current spot
 }
 
 impl Default for Builder {
@@ -163,24 +199,73 @@ impl Default for Builder {
                 "Torre de Vigia".into(),
                 "Sala de Encantamento".into(),
             ],
+            attempts: 0,
         }
     }
 }
 
 impl Builder {
     pub fn start_build(&mut self, blueprint: Blueprint, origin: [i32; 3]) {
-        println!("[BUILDER] 🏗 Starting: {} at {:?}", blueprint.name, origin);
-        println!("[BUILDER] Materials needed:");
+        info!("[BUILDER] 🏗 Starting: {} at {:?}", blueprint.name, origin);
+        info!("[BUILDER] Materials needed:");
         for (mat, count) in &blueprint.required_materials {
-            println!("  - {} x{}", mat, count);
+            info!("  - {} x{}", mat, count);
         }
         self.total_blocks = blueprint.blocks.len();
         self.blocks_placed = 0;
         self.current_blueprint = Some(blueprint);
         self.build_origin = Some(origin);
+        self.attempts = 0;
         self.state = BuildState::GatheringMaterials;
     }
 
+    /// Working on something (gathering or placing), not idle, paused or done
+    pub fn is_active(&self) -> bool {
+        matches!(self.state, BuildState::GatheringMaterials | BuildState::Placing)
+    }
+
+    /// Items still needed for the blocks not placed yet
+    pub fn remaining_materials(&self) -> HashMap<String, u32> {
+        let mut needed = HashMap::new();
+        let Some(blueprint) = &self.current_blueprint else { return needed };
+        for idx in blueprint.build_order.iter().skip(self.blocks_placed) {
+            if let Some(placement) = blueprint.blocks.get(*idx) {
+                *needed.entry(item_for(&placement.block).to_string()).or_insert(0) += 1;
+            }
+        }
+        // One bucket does every water block
+        if let Some(buckets) = needed.get_mut("water_bucket") {
+            *buckets = 1;
+        }
+        needed
+    }
+
+    /// Blueprint materials we're short on, split by where they'll come from
+    pub fn material_plan(&self, carrying: &HashMap<String, u32>, storage: &StorageIndex) -> MaterialPlan {
+        if self.current_blueprint.is_none() {
+            return MaterialPlan::default();
+        }
+        let stored = storage.totals();
+        let mut withdraw = vec![];
+        let mut gather = vec![];
+        for (item, needed) in &self.remaining_materials() {
+            let short = needed.saturating_sub(carrying.get(item).copied().unwrap_or(0));
+            if short == 0 {
+                continue;
+            }
+            let from_chest = short.min(stored.get(item).copied().unwrap_or(0));
+            if from_chest > 0 {
+                withdraw.push((item.clone(), from_chest));
+            }
+            if short > from_chest {
+                gather.push((item.clone(), short - from_chest));
+            }
+        }
+        withdraw.sort();
+        gather.sort();
+        MaterialPlan { withdraw, gather }
+    }
+
     /// Get the next block to place
     pub fn next_placement(&self) -> Option<(BlockPos, &str)> {
         let blueprint = self.current_blueprint.as_ref()?;
@@ -203,12 +288,13 @@ impl Builder {
     }
 
     pub fn record_placement(&mut self) {
+        self.attempts = 0;
         self.blocks_placed += 1;
         if self.blocks_placed >= self.total_blocks {
             self.state = BuildState::Finished;
             self.builds_completed += 1;
             if let Some(bp) = &self.current_blueprint {
-                println!("[BUILDER] ✅ Build complete: {}", bp.name);
+                info!("[BUILDER] ✅ Build complete: {}", bp.name);
             }
         }
     }
@@ -230,3 +316,96 @@ impl Builder {
         }
     }
 }
+
+/// The item that puts `block` down. Farmland is hoed, then planted with these
+pub fn item_for(block: &str) -> &str {
+    match block {
+        "water" => "water_bucket",
+        "farmland" => "wheat_seeds",
+        other => other,
+    }
+}
+
+/// How one block went
+#[derive(Debug, Clone, PartialEq)]
+pub enum Placement {
+    Placed,
+    AlreadyThere,
+    Missing(String),     // Not carrying this item
+    Failed(&'static str), // Why, for the log
+}
+
+/// Look at the middle of `target`'s face toward `from`, and right-click it
+async fn click_face(bot: &Client, target: BlockPos, from: BlockPos) {
+    let center = |p: BlockPos| Vec3::new(p.x as f64 + 0.5, p.y as f64 + 0.5, p.z as f64 + 0.5);
+    let (a, b) = (center(target), center(from));
+    bot.look_at(Vec3::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0, (a.z + b.z) / 2.0));
+    bot.wait_ticks(3).await;
+    bot.block_interact(target);
+    bot.wait_ticks(6).await;
+}
+
+/// Put `block` at `pos`: walk close, hold it, click a solid neighbour's face, check it's there
+pub async fn place(bot: &Client, pos: BlockPos, block: &str) -> Placement {
+    let Some(current) = block_at(bot, pos) else { return Placement::Failed("chunk não carregado") };
+    if current == block {
+        return Placement::AlreadyThere;
+    }
+    let farmland = block == "farmland";
+    if farmland && !matches!(current.as_str(), "dirt" | "grass_block" | "dirt_path") {
+        return Placement::Failed("farmland só em terra");
+    }
+    if !farmland && !REPLACEABLE.contains(&current.as_str()) {
+        return Placement::Failed("tem bloco no lugar");
+    }
+    let carrying = Inventory::read(bot);
+    let Some(slot) = carrying.slot_of(item_for(block)) else { return Placement::Missing(item_for(block).to_string()) };
+    let hoe = carrying.tools_of("hoe").first().map(|pick| pick.slot);
+    if farmland && hoe.is_none() {
+        return Placement::Missing("wooden_hoe".into());
+    }
+
+    let center = Vec3::new(pos.x as f64 + 0.5, pos.y as f64 + 0.5, pos.z as f64 + 0.5);
+    if tokio::time::timeout(REACH, bot.goto(RadiusGoal::new(center, 3.5))).await.is_err() {
+        bot.stop_pathfinding();
+        return Placement::Failed("não consegui chegar");
+    }
+    // Standing where the block goes — step aside first
+    let feet = BlockPos::from(bot.position());
+    if !farmland && (feet == pos || feet.up(1) == pos) {
+        let _ = tokio::time::timeout(REACH, bot.goto(InverseGoal(RadiusGoal::new(center, 1.5)))).await;
+    }
+
+    if farmland {
+        // Hoe the dirt, then seeds on top
+        if let Some(hoe) = hoe && crate::systems::inventory::equip(bot, hoe) {
+            click_face(bot, pos, pos.up(1)).await;
+        }
+        if let Some(seeds) = Inventory::read(bot).slot_of("wheat_seeds") && crate::systems::inventory::equip(bot, seeds) {
+            click_face(bot, pos, pos.up(1)).await;
+        }
+        return match block_at(bot, pos).as_deref() {
+            Some("farmland") => Placement::Placed,
+            _ => Placement::Failed("a enxada não pegou"),
+        };
+    }
+
+    if !crate::systems::inventory::equip(bot, slot) {
+        return Placement::Failed("não consegui segurar o item");
+    }
+    bot.wait_ticks(2).await;
+    // Below first (bottom-up builds always have it), then the sides, then above
+    let neighbours = [pos.down(1), pos.north(1), pos.south(1), pos.east(1), pos.west(1), pos.up(1)];
+    let solid = |p: &BlockPos| block_at(bot, *p).is_some_and(|b| !REPLACEABLE.contains(&b.as_str()) && b != "water" && b != "lava");
+    let Some(support) = neighbours.into_iter().find(solid) else { return Placement::Failed("nada pra apoiar o bloco") };
+    click_face(bot, support, pos).await;
+    if block == "water" {
+        // Buckets go by where we look, not the clicked face
+        bot.start_use_item();
+        bot.wait_ticks(6).await;
+    }
+    match block_at(bot, pos) {
+        Some(now) if now == block => Placement::Placed,
+        _ => Placement::Failed("o bloco não apareceu"),
+    }
+}
@@ -0,0 +1,280 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use crate::systems::world_scanner::AreaScan;
+use tracing::info;
+
+// ============================================================
+// CAVE MAP — Corridors and junctions we walked underground
+// Nodes every few blocks, segments carry ores/hazards, open branches = "ainda n fui ali"
+// ============================================================
+
+/// New node once we're this far from every known one
+const NODE_SPACING: f64 = 8.0;
+/// An opening this close to a node we've been to is already explored
+const BRANCH_EXPLORED: f64 = 10.0;
+/// Keep the save file sane
+const MAX_NODES: usize = 2000;
+/// Further than this between two scans is a teleport or a respawn, not a walk
+const JUMP: f64 = 32.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaveNode {
+    pub id: u32,
+    pub pos: [i32; 3],
+    pub entrance: bool, // Connects to the surface
+    pub open_branches: Vec<[i32; 3]>, // Openings seen from here we never walked into
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaveSegment {
+    pub from: u32,
+    pub to: u32,
+    pub length: f64,
+    pub ores: HashMap<String, u32>,
+    pub hazards: Vec<String>, // "lava", "água", "queda", "escuro"
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct CaveMap {
+    pub nodes: Vec<CaveNode>,
+    pub segments: Vec<CaveSegment>,
+    next_id: u32,
+    #[serde(skip)]
+    current: Option<u32>, // Node we're at/after right now
+    #[serde(skip)]
+    last_pos: Option<[i32; 3]>, // Where the last scan saw us
+    #[serde(skip)]
+    from_surface: bool, // Walked in from open sky, so the next new node is a way in
+}
+
+fn dist(a: [i32; 3], b: [i32; 3]) -> f64 {
+    (((a[0] - b[0]).pow(2) + (a[1] - b[1]).pow(2) + (a[2] - b[2]).pow(2)) as f64).sqrt()
+}
+
+/// What makes this stretch of cave nasty
+fn hazards(scan: &AreaScan) -> Vec<String> {
+    let counts = &scan.blocks.block_counts;
+    let count = |name: &str| counts.get(name).copied().unwrap_or(0);
+    let mut found = vec![];
+    if count("lava") > 0 {
+        found.push("lava".to_string());
+    }
+    if count("water") > 20 {
+        found.push("água".to_string());
+    }
+    if scan.tall_air_columns >= 4 {
+        found.push("queda".to_string());
+    }
+    if scan.dark_spots.len() >= 10 {
+        found.push("escuro".to_string());
+    }
+    found
+}
+
+impl CaveMap {
+    /// Feed each world scan. Underground → extend the graph; surfacing marks the way out
+    pub fn observe(&mut self, pos: [i32; 3], underground: bool, scan: &AreaScan) {
+        // Teleported or respawned: no corridor from the last node, and no way in or out found here
+        if self.last_pos.is_some_and(|last| dist(last, pos) > JUMP) {
+            self.current = None;
+            self.from_surface = false;
+        }
+        self.last_pos = Some(pos);
+
+        if !underground {
+            self.from_surface = true;
+            if let Some(id) = self.current.take()
+                && let Some(node) = self.nodes.iter_mut().find(|n| n.id == id)
+                && !node.entrance
+            {
+                info!("[CAVE] 🚪 Saída da caverna em {:?}", node.pos);
+                node.entrance = true;
+            }
+            return;
+        }
+
+        let nearest = self.nodes.iter()
+            .map(|n| (n.id, dist(n.pos, pos)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .filter(|(_, d)| *d <= NODE_SPACING)
+            .map(|(id, _)| id);
+
+        let here = match nearest {
+            Some(id) => id,
+            None => {
+                if self.nodes.len() >= MAX_NODES {
+                    return;
+                }
+                let id = self.next_id;
+                self.next_id += 1;
+                // Just came down from the surface = this is a way in
+                let entrance = self.current.is_none() && self.from_surface;
+                self.nodes.push(CaveNode { id, pos, entrance, open_branches: vec![] });
+                if entrance {
+                    info!("[CAVE] 🕳️ Entrada de caverna em {:?}", pos);
+                }
+                id
+            }
+        };
+
+        // Walked from the last node to this one → corridor
+        if let Some(prev) = self.current
+            && prev != here
+        {
+            let known = self.segments.iter_mut()
+                .find(|s| (s.from == prev && s.to == here) || (s.from == here && s.to == prev));
+            match known {
+                Some(segment) => {
+                    for h in hazards(scan) {
+                        if !segment.hazards.contains(&h) {
+                            segment.hazards.push(h);
+                        }
+                    }
+                }
+                None => {
+                    let from = self.node(prev).map(|n| n.pos).unwrap_or(pos);
+                    let mut ores = HashMap::new();
+                    for r in &scan.resources {
+                        *ores.entry(r.block_type.clone()).or_insert(0) += 1;
+                    }
+                    self.segments.push(CaveSegment {
+                        from: prev,
+                        to: here,
+                        length: dist(from, pos),
+                        ores,
+                        hazards: hazards(scan),
+                    });
+                }
+            }
+        }
+        self.current = Some(here);
+
+        // Openings we can see from here, minus anywhere we've already stood
+        let visited: Vec<[i32; 3]> = self.nodes.iter().map(|n| n.pos).collect();
+        let fresh: Vec<[i32; 3]> = scan.openings.iter()
+            .filter(|o| visited.iter().all(|v| dist(*v, **o) > BRANCH_EXPLORED))
+            .copied()
+            .collect();
+        for node in self.nodes.iter_mut() {
+            node.open_branches.retain(|b| dist(*b, pos) > BRANCH_EXPLORED);
+            if node.id == here {
+                node.open_branches = fresh.clone();
+            }
+        }
+    }
+
+    fn node(&self, id: u32) -> Option<&CaveNode> {
+        self.nodes.iter().find(|n| n.id == id)
+    }
+
+    fn nearest_node(&self, pos: [i32; 3]) -> Option<&CaveNode> {
+        self.nodes.iter().min_by(|a, b| dist(a.pos, pos).total_cmp(&dist(b.pos, pos)))
+    }
+
+    /// Known corridors from here to the closest way out (node positions, in order)
+    pub fn route_to_exit(&self, from: [i32; 3]) -> Option<Vec<[i32; 3]>> {
+        let start = self.nearest_node(from)?.id;
+        let mut came_from: HashMap<u32, u32> = HashMap::new();
+        let mut queue = VecDeque::from([start]);
+        came_from.insert(start, start);
+        while let Some(id) = queue.pop_front() {
+            if self.node(id).is_some_and(|n| n.entrance) {
+                let mut path = vec![];
+                let mut at = id;
+                while at != start {
+                    path.push(self.node(at)?.pos);
+                    at = came_from[&at];
+                }
+                path.push(self.node(start)?.pos);
+                path.reverse();
+                return Some(path);
+            }
+            for s in &self.segments {
+                let next = if s.from == id { s.to } else if s.to == id { s.from } else { continue };
+                if let std::collections::hash_map::Entry::Vacant(e) = came_from.entry(next) {
+                    e.insert(id);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    /// Closest opening we never walked into
+    pub fn unexplored_branch(&self, from: [i32; 3]) -> Option<[i32; 3]> {
+        self.nodes.iter()
+            .flat_map(|n| n.open_branches.iter())
+            .min_by(|a, b| dist(**a, from).total_cmp(&dist(**b, from)))
+            .copied()
+    }
+
+    pub fn context_summary(&self) -> String {
+        if self.nodes.is_empty() {
+            return String::new();
+        }
+        let branches: usize = self.nodes.iter().map(|n| n.open_branches.len()).sum();
+        let mut ores: HashMap<&str, u32> = HashMap::new();
+        for s in &self.segments {
+            for (ore, n) in &s.ores {
+                *ores.entry(ore.as_str()).or_insert(0) += n;
+            }
+        }
+        let mut ores: Vec<(&str, u32)> = ores.into_iter().collect();
+        ores.sort_by_key(|(_, n)| std::cmp::Reverse(*n));
+        let ores = ores.iter().take(3).map(|(o, n)| format!("{} x{}", o, n)).collect::<Vec<_>>().join(", ");
+        format!(
+            "Cavernas mapeadas: {} trechos, {} entradas, {} caminhos inexplorados{}",
+            self.segments.len(),
+            self.nodes.iter().filter(|n| n.entrance).count(),
+            branches,
+            if ores.is_empty() { String::new() } else { format!(" | minério visto: {}", ores) },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::visual_cortex::BlockScan;
+
+    fn scan() -> AreaScan {
+        AreaScan {
+            blocks: BlockScan {
+                block_counts: HashMap::new(),
+                total_blocks: 0,
+                air_percentage: 0.0,
+                light_avg: 0.0,
+                unique_types: 0,
+                center: [0, 0, 0],
+            },
+            resources: vec![],
+            light_here: 0,
+            roof: 10,
+            dark_spots: vec![],
+            landmarks: vec![],
+            tall_air_columns: 0,
+            openings: vec![],
+            containers: vec![],
+            box_min: [0, 0, 0],
+            box_max: [0, 0, 0],
+        }
+    }
+
+    #[test]
+    fn test_teleport_isnt_a_corridor() {
+        let mut caves = CaveMap::default();
+        caves.observe([0, 70, 0], false, &scan());
+        caves.observe([0, 40, 0], true, &scan());
+        caves.observe([10, 40, 0], true, &scan());
+        assert_eq!(caves.segments.len(), 1);
+        assert!(caves.nodes[0].entrance);
+
+        // Respawned far away, still underground: a new node, not joined to the old ones, not a way in
+        caves.observe([500, 30, 500], true, &scan());
+        assert_eq!(caves.segments.len(), 1);
+        assert!(!caves.nodes[2].entrance);
+        // Teleported up to the surface: the node we left isn't an exit
+        caves.observe([-500, 70, 0], false, &scan());
+        assert!(!caves.nodes[2].entrance);
+    }
+}
@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+use crate::config::Config;
+
+// ============================================================
+// CHAT CHANNELS — Party / team / faction chat vs global
+// "[Party] Fulano: bora pro nether" → answer with /p, not in global
+// ============================================================
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChatChannel {
+    #[default]
+    Global,
+    Party,
+    Team,
+    Faction,
+}
+
+/// Leading tags that mark a non-global channel (lowercase, without brackets)
+const CHANNEL_TAGS: &[(&str, ChatChannel)] = &[
+    ("party", ChatChannel::Party),
+    ("p", ChatChannel::Party),
+    ("grupo", ChatChannel::Party),
+    ("team", ChatChannel::Team),
+    ("time", ChatChannel::Team),
+    ("equipe", ChatChannel::Team),
+    ("faction", ChatChannel::Faction),
+    ("facção", ChatChannel::Faction),
+    ("faccao", ChatChannel::Faction),
+    ("f", ChatChannel::Faction),
+    ("clan", ChatChannel::Faction),
+    ("guild", ChatChannel::Faction),
+];
+
+impl ChatChannel {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChatChannel::Global => "global",
+            ChatChannel::Party => "party",
+            ChatChannel::Team => "team",
+            ChatChannel::Faction => "faction",
+        }
+    }
+
+    /// Send `message` in this channel (global goes out as-is)
+    pub fn wrap(&self, message: &str, config: &Config) -> String {
+        let command = match self {
+            ChatChannel::Global => return message.to_string(),
+            ChatChannel::Party => &config.party_chat_command,
+            ChatChannel::Team => &config.team_chat_command,
+            ChatChannel::Faction => &config.faction_chat_command,
+        };
+        format!("{} {}", command, message)
+    }
+}
+
+/// "[Party] ..." / "(Team) ..." / "Party > ..." → channel + the rest
+fn strip_channel_tag(message: &str) -> (ChatChannel, &str) {
+    let trimmed = message.trim_start();
+    let (tag, rest) = if let Some(inner) = trimmed.strip_prefix('[').or_else(|| trimmed.strip_prefix('(')) {
+        match inner.find([']', ')']) {
+            Some(end) => (&inner[..end], &inner[end + 1..]),
+            None => return (ChatChannel::Global, message),
+        }
+    } else if let Some((tag, rest)) = trimmed.split_once(['>', '»']) {
+        (tag, rest)
+    } else {
+        return (ChatChannel::Global, message);
+    };
+
+    let tag = tag.trim().to_lowercase();
+    match CHANNEL_TAGS.iter().find(|(t, _)| *t == tag) {
+        Some((_, channel)) => (*channel, rest.trim_start()),
+        None => (ChatChannel::Global, message),
+    }
+}
+
+/// "<Name> msg" or "Name: msg" → (sender, content)
+fn split_sender(message: &str) -> Option<(&str, &str)> {
+    if let Some(inner) = message.strip_prefix('<')
+        && let Some(end) = inner.find('>')
+    {
+        return Some((&inner[..end], inner[end + 1..].trim()));
+    }
+    let (sender, content) = message.split_once(':')?;
+    let sender = sender.trim();
+    let valid = !sender.is_empty()
+        && sender.len() <= 16
+        && sender.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    valid.then(|| (sender, content.trim()))
+}
+
+/// Which channel a party/team/faction message came in on, and who said what.
+/// Global chat returns None — the regular "<Name> msg" parser handles it
+pub fn parse_channel_message(message: &str) -> Option<(ChatChannel, &str, &str)> {
+    let (channel, rest) = strip_channel_tag(message);
+    if channel == ChatChannel::Global {
+        return None;
+    }
+    let (sender, content) = split_sender(rest)?;
+    Some((channel, sender, content))
+}
+
+/// "Fulano has invited you to join their party" → "Fulano"
+pub fn parse_party_invite(message: &str) -> Option<&str> {
+    let lower = message.to_lowercase();
+    let markers = [
+        " has invited you to join", " invited you to", " te convidou", " convidou você", " convidou voce",
+    ];
+    if let Some(index) = markers.iter().find_map(|m| lower.find(m)) {
+        let before = message[..index].trim();
+        // Skip any channel tag like "[Party]"
+        return before.rsplit([' ', ']', ')']).next().filter(|n| !n.is_empty());
+    }
+    let from = lower.find("party invite from ")?;
+    message[from + "party invite from ".len()..].split_whitespace().next()
+}
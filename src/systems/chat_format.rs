@@ -0,0 +1,246 @@
+use azalea::prelude::*;
+use azalea::chat::ChatPacket;
+use regex::Regex;
+use std::sync::{LazyLock, Mutex};
+use crate::config::Config;
+
+// ============================================================
+// CHAT FORMAT — Who said what, on servers that aren't vanilla
+// "[world] [VIP] ~Nick » msg" / "§6[Admin] Fulano: msg" / "<Fulano> msg"
+// Player chat packets carry the sender's UUID, so those never go through the text at all
+// ============================================================
+
+/// Per-server chat layout, picked with CHAT_FORMAT
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChatFormat {
+    Auto,           // Try every built-in layout
+    Vanilla,        // <Name> msg
+    Essentials,     // [Rank] Name: msg / Name » msg
+    Custom(String), // CHAT_FORMAT_PATTERN, e.g. "{name} » {message}"
+}
+
+/// What goes between the name and the message in plugin formats
+const SEPARATORS: &[&str] = &[": ", " » ", " >> ", " -> ", " > ", " | "];
+/// Essentials puts this in front of nicknames
+const NICK_PREFIX: char = '~';
+/// CHAT_SENDER_REGEX compiled, along with the pattern it came from
+static SENDER_REGEX: LazyLock<Mutex<Option<(String, Regex)>>> = LazyLock::new(|| Mutex::new(None));
+
+impl ChatFormat {
+    pub fn from_config(config: &Config) -> Self {
+        match config.chat_format.to_lowercase().as_str() {
+            "vanilla" => ChatFormat::Vanilla,
+            "essentials" | "essentialschat" => ChatFormat::Essentials,
+            "custom" if config.chat_format_pattern.contains("{name}") => {
+                ChatFormat::Custom(config.chat_format_pattern.clone())
+            }
+            _ => ChatFormat::Auto,
+        }
+    }
+
+    /// A chat line (colors already stripped) → (sender, content). None for system messages.
+    /// Auto only trusts a "Name: msg" line when `online(Name)` — plugins write "Aviso: ..." too
+    pub fn parse<'a>(&self, line: &'a str, online: &dyn Fn(&str) -> bool) -> Option<(&'a str, &'a str)> {
+        if let Some(parsed) = parse_regex(line) {
+            return Some(parsed);
+        }
+        match self {
+            ChatFormat::Vanilla => parse_vanilla(line),
+            ChatFormat::Essentials => parse_separated(line, SEPARATORS),
+            ChatFormat::Custom(pattern) => parse_custom(pattern, line),
+            ChatFormat::Auto => parse_vanilla(line)
+                .or_else(|| parse_separated(line, SEPARATORS).filter(|(sender, _)| online(sender))),
+        }
+    }
+}
+
+/// Sender (real username) and content straight from the packet. None for system messages —
+/// plugin-formatted chat arrives as those, and only the text can tell who wrote it
+pub fn from_packet(bot: &Client, chat: &ChatPacket) -> Option<(String, String)> {
+    match chat {
+        ChatPacket::System(_) => None,
+        ChatPacket::Player(p) => {
+            let name = bot.tab_list().get(&p.sender).map(|player| player.profile.name.clone())
+                .unwrap_or_else(|| display_name(&p.chat_type.name.to_string()));
+            Some((name, p.body.content.clone()))
+        }
+        // Unsigned, no UUID — the display name is all there is
+        ChatPacket::Disguised(p) => {
+            let name = resolve_name(bot, &display_name(&p.chat_type.name.to_string()));
+            Some((name, strip_colors(&p.message.to_string())))
+        }
+    }
+}
+
+/// "[VIP] §6Fulano" → "Fulano"
+fn display_name(shown: &str) -> String {
+    let shown = strip_colors(shown);
+    strip_tags(&shown).rsplit(' ').next().unwrap_or_default().to_string()
+}
+
+/// CHAT_SENDER_REGEX, tried before the built-in layouts: named groups `name` and `message`
+fn parse_regex(line: &str) -> Option<(&str, &str)> {
+    let pattern = Config::load().chat_sender_regex.clone();
+    if pattern.is_empty() {
+        return None;
+    }
+    let regex = {
+        let mut cached = SENDER_REGEX.lock().unwrap();
+        if cached.as_ref().is_none_or(|(p, _)| *p != pattern) {
+            *cached = Some((pattern.clone(), Regex::new(&pattern).ok()?)); // validate() already complained
+        }
+        cached.as_ref()?.1.clone()
+    };
+    let captures = regex.captures(line)?;
+    let (sender, message) = (captures.name("name")?.as_str(), captures.name("message")?.as_str());
+    is_name(sender).then(|| (sender, message.trim()))
+}
+
+/// Drop legacy color/format codes: "§6", "&l", and the "§x§r§r§g§g§b§b" hex form
+pub fn strip_colors(message: &str) -> String {
+    let chars: Vec<char> = message.chars().collect();
+    let mut out = String::with_capacity(message.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let is_code = chars.get(i + 1).is_some_and(|n| "0123456789abcdefklmnorx".contains(n.to_ascii_lowercase()))
+            && match chars[i] {
+                '§' => true,
+                '&' => ampersand_code(out.chars().next_back(), chars[i + 1], chars.get(i + 2).copied()),
+                _ => false,
+            };
+        if is_code {
+            i += 2;
+        } else {
+            out.push(chars[i]);
+            i += 1;
+        }
+    }
+    out
+}
+
+/// Is "&" + `code` a color code, or just text? Anywhere but inside a word it's a code. Inside one
+/// ("Fulano&r:") only in lowercase and when it doesn't run into more word: "rock&roll", "R&D" stay
+fn ampersand_code(before: Option<char>, code: char, after: Option<char>) -> bool {
+    if !before.is_some_and(char::is_alphanumeric) {
+        return true;
+    }
+    !code.is_uppercase() && !after.is_some_and(|c| c.is_lowercase())
+}
+
+/// "[world] [VIP] (Mod) rest" → "rest"
+fn strip_tags(text: &str) -> &str {
+    let mut rest = text.trim_start();
+    loop {
+        let close = match rest.chars().next() {
+            Some('[') => ']',
+            Some('(') => ')',
+            Some('{') => '}',
+            _ => return rest,
+        };
+        match rest.find(close) {
+            Some(end) => rest = rest[end + 1..].trim_start(),
+            None => return rest,
+        }
+    }
+}
+
+/// Plausible player name (or ~nickname)
+fn is_name(name: &str) -> bool {
+    let name = name.trim_start_matches(NICK_PREFIX);
+    !name.is_empty()
+        && name.chars().count() <= 32
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// "<Fulano> msg", also "<[VIP] Fulano> msg" and "[world] <Fulano> msg"
+fn parse_vanilla(line: &str) -> Option<(&str, &str)> {
+    let line = strip_tags(line);
+    let inner = line.strip_prefix('<')?;
+    let end = inner.find('>')?;
+    let sender = strip_tags(&inner[..end]).rsplit(' ').next()?;
+    is_name(sender).then(|| (sender, inner[end + 1..].trim()))
+}
+
+/// "[Rank] Fulano: msg" / "Admin Fulano » msg" — last word before the separator is the name
+fn parse_separated<'a>(line: &'a str, separators: &[&str]) -> Option<(&'a str, &'a str)> {
+    let line = strip_tags(line);
+    let (index, separator) = separators.iter()
+        .filter_map(|s| line.find(s).map(|i| (i, *s)))
+        .min_by_key(|(i, _)| *i)?;
+    let sender = strip_tags(&line[..index]).rsplit(' ').next()?;
+    is_name(sender).then(|| (sender, line[index + separator.len()..].trim()))
+}
+
+/// "{prefix} {name} » {message}" → split on whatever sits between {name} and {message}
+fn parse_custom<'a>(pattern: &str, line: &'a str) -> Option<(&'a str, &'a str)> {
+    let after_name = pattern.split_once("{name}")?.1;
+    let separator = after_name.split_once("{message}").map_or(after_name, |(s, _)| s);
+    if separator.trim().is_empty() {
+        return parse_separated(line, &[" "]);
+    }
+    parse_separated(line, &[separator])
+}
+
+/// Nickname or display name → real username, using the tab list.
+/// Unknown names come back unchanged (minus the nickname marker)
+pub fn resolve_name(bot: &Client, name: &str) -> String {
+    find_in_tab(bot, name).unwrap_or_else(|| name.trim_start_matches(NICK_PREFIX).to_string())
+}
+
+/// Someone on the tab list goes by this name or nickname
+pub fn is_online(bot: &Client, name: &str) -> bool {
+    find_in_tab(bot, name).is_some()
+}
+
+fn find_in_tab(bot: &Client, name: &str) -> Option<String> {
+    let bare = name.trim_start_matches(NICK_PREFIX);
+    let tab = bot.tab_list();
+    let found = tab.values().find(|p| {
+        p.profile.name.eq_ignore_ascii_case(bare)
+            || p.display_name.as_ref().is_some_and(|d| {
+                let shown = strip_colors(&d.to_string());
+                strip_tags(&shown).rsplit(' ').next()
+                    .is_some_and(|n| n.trim_start_matches(NICK_PREFIX).eq_ignore_ascii_case(bare))
+            })
+    });
+    found.map(|p| p.profile.name.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn online(name: &str) -> bool {
+        name.trim_start_matches(NICK_PREFIX) == "Fulano"
+    }
+
+    #[test]
+    fn test_vanilla_line() {
+        let format = ChatFormat::Vanilla;
+        assert_eq!(format.parse("<Fulano> oi gente", &online), Some(("Fulano", "oi gente")));
+        assert_eq!(ChatFormat::Auto.parse("[world] <[VIP] Fulano> bora minerar", &online), Some(("Fulano", "bora minerar")));
+    }
+
+    #[test]
+    fn test_separated_line() {
+        let line = strip_colors("&7[VIP] &6~Fulano &f» &rtem ferro?");
+        assert_eq!(ChatFormat::Auto.parse(&line, &online), Some(("~Fulano", "tem ferro?")));
+        assert_eq!(ChatFormat::Essentials.parse("[Admin] Beltrano: oi", &online), Some(("Beltrano", "oi")));
+    }
+
+    #[test]
+    fn test_system_line_with_colon() {
+        // Looks like "Name: msg", but nobody called Aviso is online
+        assert_eq!(ChatFormat::Auto.parse("Aviso: o servidor reinicia em 5 minutos", &online), None);
+        assert_eq!(ChatFormat::Auto.parse("Você entrou no mundo survival", &online), None);
+    }
+
+    #[test]
+    fn test_ampersand_in_text() {
+        assert_eq!(strip_colors("R&D e rock&roll"), "R&D e rock&roll");
+        assert_eq!(strip_colors("sal & pimenta"), "sal & pimenta");
+        assert_eq!(strip_colors("&6Fulano&r: oi §lforte"), "Fulano: oi forte");
+        assert_eq!(strip_colors("[VIP]&aFulano"), "[VIP]Fulano");
+        assert_eq!(strip_colors("Fulano&6Oi"), "FulanoOi");
+    }
+}
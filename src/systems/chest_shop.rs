@@ -0,0 +1,207 @@
+use azalea::prelude::*;
+use azalea::pathfinder::goals::RadiusGoal;
+use azalea::{BlockPos, Vec3};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use simdnbt::owned::{NbtCompound, NbtList};
+use std::time::Duration;
+use crate::systems::economy::normalize_item_name;
+use crate::systems::inventory::Inventory;
+use tracing::{info, warn};
+
+// ============================================================
+// CHEST SHOP — Reads shop signs, buys when it's worth it
+// "pra que minerar se o cara vende a 5 conto?"
+// ============================================================
+
+/// A ChestShop-style sign:
+///   line 1: owner     line 2: quantity
+///   line 3: prices ("B 10 : 5 S")     line 4: item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChestShop {
+    pub owner: String,
+    pub item: String,
+    pub quantity: u32,
+    pub buy_price: Option<f32>,  // What WE pay to buy from the shop
+    pub sell_price: Option<f32>, // What the shop pays us
+    pub pos: [i32; 3],
+    pub seen_at: DateTime<Utc>,
+    #[serde(default)]
+    pub last_purchase: Option<DateTime<Utc>>,
+}
+
+impl ChestShop {
+    /// Server money per single item
+    pub fn unit_buy_price(&self) -> Option<f32> {
+        self.buy_price.map(|p| p / self.quantity.max(1) as f32)
+    }
+
+    /// Don't spam-click the same shop
+    pub fn can_buy_now(&self) -> bool {
+        self.last_purchase
+            .is_none_or(|t| Utc::now().signed_duration_since(t).num_minutes() >= 5)
+    }
+}
+
+// === Sign text ===
+
+/// Flatten a text component (plain string, JSON string or compound) into text
+fn component_text(tag: &simdnbt::owned::NbtTag) -> String {
+    if let Some(s) = tag.string() {
+        return string_text(&s.to_str());
+    }
+    tag.compound().map(compound_text).unwrap_or_default()
+}
+
+/// Old servers store lines as JSON strings, new ones as plain text
+fn string_text(s: &str) -> String {
+    match serde_json::from_str::<serde_json::Value>(s) {
+        Ok(json) => json_text(&json),
+        Err(_) => s.to_string(),
+    }
+}
+
+fn compound_text(compound: &NbtCompound) -> String {
+    let mut text = compound.string("text").map(|s| s.to_str().into_owned()).unwrap_or_default();
+    if let Some(extra) = compound.list("extra") {
+        text.push_str(&list_text(extra));
+    }
+    text
+}
+
+fn list_text(list: &NbtList) -> String {
+    if let Some(strings) = list.strings() {
+        strings.iter().map(|s| s.to_str().into_owned()).collect()
+    } else if let Some(compounds) = list.compounds() {
+        compounds.iter().map(compound_text).collect()
+    } else {
+        String::new()
+    }
+}
+
+fn json_text(json: &serde_json::Value) -> String {
+    match json {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(parts) => parts.iter().map(json_text).collect(),
+        serde_json::Value::Object(obj) => {
+            let mut text = obj.get("text").and_then(|t| t.as_str()).unwrap_or_default().to_string();
+            if let Some(serde_json::Value::Array(extra)) = obj.get("extra") {
+                text.extend(extra.iter().map(json_text));
+            }
+            text
+        }
+        _ => String::new(),
+    }
+}
+
+/// The four front lines of a sign block entity
+pub fn sign_lines(nbt: &NbtCompound) -> Vec<String> {
+    let Some(messages) = nbt.compound("front_text").and_then(|f| f.list("messages")) else {
+        return vec![];
+    };
+    if let Some(strings) = messages.strings() {
+        return strings.iter()
+            .map(|s| string_text(&s.to_str()))
+            .collect();
+    }
+    if let Some(compounds) = messages.compounds() {
+        // Mixed lists arrive wrapped as { "": value } compounds
+        return compounds.iter()
+            .map(|c| match c.get("") {
+                Some(inner) => component_text(inner),
+                None => compound_text(c),
+            })
+            .collect();
+    }
+    vec![]
+}
+
+// === Parsing ===
+
+/// "B 10 : 5 S" / "B10" / "S 5" / "B free" → (buy, sell)
+fn parse_prices(line: &str) -> (Option<f32>, Option<f32>) {
+    let mut buy = None;
+    let mut sell = None;
+    for part in line.to_lowercase().split(':') {
+        let number = if part.contains("free") {
+            Some(0.0)
+        } else {
+            part.chars()
+                .filter(|c| c.is_ascii_digit() || *c == '.')
+                .collect::<String>()
+                .parse::<f32>()
+                .ok()
+        };
+        if part.contains('b') {
+            buy = number;
+        } else if part.contains('s') {
+            sell = number;
+        }
+    }
+    (buy, sell)
+}
+
+/// "Iron Ingot" / "Diamond Sword#3f" → "iron_ingot" / "diamond_sword"
+fn parse_item(line: &str) -> String {
+    let name = line.split('#').next().unwrap_or(line).trim().to_lowercase().replace(' ', "_");
+    normalize_item_name(&name)
+}
+
+pub fn parse_sign(lines: &[String], pos: [i32; 3]) -> Option<ChestShop> {
+    if lines.len() < 4 {
+        return None;
+    }
+    let quantity = lines[1].trim().parse::<u32>().ok().filter(|q| *q > 0)?;
+    let (buy_price, sell_price) = parse_prices(&lines[2]);
+    if buy_price.is_none() && sell_price.is_none() {
+        return None;
+    }
+    let owner = lines[0].trim().to_string();
+    let item = parse_item(&lines[3]);
+    if owner.is_empty() || item.is_empty() || item == "?" {
+        return None;
+    }
+    Some(ChestShop {
+        owner,
+        item,
+        quantity,
+        buy_price,
+        sell_price,
+        pos,
+        seen_at: Utc::now(),
+        last_purchase: None,
+    })
+}
+
+// === Buying ===
+
+/// Walk up to the shop sign and right-click it (ChestShop: right-click = buy).
+/// Returns how many items actually landed in our inventory.
+pub async fn buy(bot: Client, shop: ChestShop) -> u32 {
+    let sign = Vec3::new(shop.pos[0] as f64 + 0.5, shop.pos[1] as f64 + 0.5, shop.pos[2] as f64 + 0.5);
+    info!("[SHOP] 🛒 Indo comprar {} x{} na loja do {}", shop.item, shop.quantity, shop.owner);
+
+    let arrived = tokio::time::timeout(
+        Duration::from_secs(45),
+        bot.goto(RadiusGoal::new(sign, 3.0)),
+    ).await;
+    if arrived.is_err() {
+        bot.stop_pathfinding();
+        warn!("[SHOP] ❌ Não consegui chegar na loja");
+        return 0;
+    }
+
+    let before = Inventory::read(&bot).count_of(&shop.item);
+    bot.look_at(sign);
+    bot.wait_ticks(5).await;
+    bot.block_interact(BlockPos::new(shop.pos[0], shop.pos[1], shop.pos[2]));
+    bot.wait_ticks(20).await;
+
+    let bought = Inventory::read(&bot).count_of(&shop.item).saturating_sub(before);
+    if bought > 0 {
+        info!("[SHOP] ✅ Comprei {} x{}", shop.item, bought);
+    } else {
+        warn!("[SHOP] ❌ Compra não rolou (sem grana? loja vazia?)");
+    }
+    bought
+}
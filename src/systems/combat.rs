@@ -1,4 +1,14 @@
+use azalea::prelude::*;
+use azalea::ecs::entity::Entity;
+use azalea::entity::{Physics, Position};
+use azalea::world::MinecraftEntityId;
+use azalea::{SprintDirection, Vec3, WalkDirection};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use crate::systems::world_scanner::NearbyEntity;
+use crate::systems::inventory::Inventory;
+use tracing::{debug, info};
 
 // ============================================================
 // COMBAT — Intelligent fighting
@@ -26,6 +36,19 @@ pub enum ThreatType {
 }
 
 impl ThreatType {
+    /// Entity feed kind ("zombie", "husk") → threat
+    pub fn from_kind(kind: &str) -> Self {
+        match kind {
+            "zombie" | "husk" | "drowned" | "zombie_villager" => ThreatType::Zombie,
+            "skeleton" | "stray" | "bogged" | "wither_skeleton" | "pillager" => ThreatType::Skeleton,
+            "creeper" => ThreatType::Creeper,
+            "spider" | "cave_spider" => ThreatType::Spider,
+            "enderman" => ThreatType::Enderman,
+            "witch" => ThreatType::Witch,
+            _ => ThreatType::Unknown,
+        }
+    }
+
     /// Priority: higher = more dangerous
     pub fn danger_level(&self) -> u8 {
         match self {
@@ -75,6 +98,8 @@ pub struct CombatSystem {
     pub flee_hp_threshold: f32,     // HP below which we run
     pub engage_hp_threshold: f32,   // HP above which we fight
     pub has_shield: bool,
+    #[serde(default)]
+    pub weapon: Option<String>, // Best weapon we're carrying
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -95,6 +120,7 @@ impl Default for CombatSystem {
             flee_hp_threshold: 6.0,
             engage_hp_threshold: 10.0,
             has_shield: false,
+            weapon: None,
         }
     }
 }
@@ -126,8 +152,8 @@ impl CombatSystem {
             return CombatDecision::Tower; // Tower up
         }
 
-        // Single creeper close?
-        if top_threat.threat_type == ThreatType::Creeper && top_threat.distance < 4.0 {
+        // Creeper close and we can't take a blast? Otherwise it's hit-and-run
+        if top_threat.threat_type == ThreatType::Creeper && top_threat.distance < 4.0 && hp < self.engage_hp_threshold {
             self.state = CombatState::Retreating;
             return CombatDecision::Flee;
         }
@@ -138,6 +164,29 @@ impl CombatSystem {
         CombatDecision::Fight(tactic, top_threat.entity_id)
     }
 
+    /// Hostiles from the entity feed become our current threats (hostile players are added by whoever judged them)
+    pub fn update_threats(&mut self, entities: &[NearbyEntity], radius: f64) {
+        self.current_threats = entities.iter()
+            .filter(|e| e.hostile && e.distance <= radius)
+            .map(|e| ThreatInfo {
+                threat_type: ThreatType::from_kind(&e.kind),
+                distance: e.distance,
+                entity_id: e.entity_id as u32,
+            })
+            .collect();
+        self.state = match (&self.state, self.current_threats.is_empty()) {
+            (_, true) => CombatState::Peaceful,
+            (CombatState::Peaceful, false) => CombatState::Alert,
+            (state, false) => state.clone(),
+        };
+    }
+
+    /// What we'd fight with, straight from the inventory
+    pub fn update_gear(&mut self, inventory: &Inventory) {
+        self.has_shield = inventory.has_shield();
+        self.weapon = inventory.best_weapon().map(|w| w.item);
+    }
+
     pub fn record_kill(&mut self) {
         self.kills += 1;
         self.update_kd();
@@ -158,8 +207,10 @@ impl CombatSystem {
 
     pub fn context_summary(&self) -> String {
         format!(
-            "Combate: {:?} | K/D: {}/{} ({:.1}) | Ameaças: {}",
-            self.state, self.kills, self.deaths, self.kd_ratio, self.current_threats.len()
+            "Combate: {:?} | K/D: {}/{} ({:.1}) | Ameaças: {} | Arma: {}{}",
+            self.state, self.kills, self.deaths, self.kd_ratio, self.current_threats.len(),
+            self.weapon.as_deref().unwrap_or("só a mão"),
+            if self.has_shield { " + escudo" } else { "" },
         )
     }
 }
@@ -171,3 +222,214 @@ pub enum CombatDecision {
     Flee,
     Tower,
 }
+
+// === Executor ===
+
+/// Survival melee reach, feet to feet
+const REACH: f64 = 3.0;
+/// Only walk up to fights this close; further off we just keep an eye on them
+const ENGAGE_RADIUS: f64 = 10.0;
+/// Hit-and-run: ticks spent backing off after each swing
+const BACK_OFF_TICKS: u32 = 15;
+/// A target we hit that's gone from the feed this soon after died to us
+const KILL_WINDOW: Duration = Duration::from_secs(2);
+
+/// Something worth telling the rest of the bot about
+#[derive(Debug, Clone, PartialEq)]
+pub enum Moment {
+    Engaged(u32),
+    Fled,
+    Killed(u32),
+}
+
+/// The executor's bookkeeping between ticks. Not saved — a fight doesn't survive a restart
+#[derive(Debug, Default)]
+pub struct Fighter {
+    target: Option<u32>,
+    fleeing: bool,
+    last_hit: Option<(u32, Instant)>,
+    back_off: u32,
+    strafe: WalkDirection,
+    strafe_left: u32,
+    windup: u32, // Ticks spent trying to get airborne for a crit
+}
+
+impl Fighter {
+    pub fn engaged(&self) -> bool {
+        self.target.is_some() || self.fleeing
+    }
+
+    /// Our last target vanished from the feed right after we hit it
+    fn check_kill(&mut self, threats: &[ThreatInfo]) -> Option<u32> {
+        let (id, at) = self.last_hit?;
+        if threats.iter().any(|t| t.entity_id == id) {
+            return None;
+        }
+        self.last_hit = None;
+        (at.elapsed() < KILL_WINDOW).then_some(id)
+    }
+
+    /// Let go of the keys
+    fn disengage(&mut self, bot: &Client) {
+        if self.engaged() {
+            bot.walk(WalkDirection::None);
+            debug!("[COMBAT] 😮‍💨 Acabou a briga");
+        }
+        self.target = None;
+        self.fleeing = false;
+        self.back_off = 0;
+    }
+
+    /// Side-step left and right so we're not a standing target
+    fn strafe(&mut self, bot: &Client) {
+        if self.strafe_left == 0 {
+            self.strafe = if self.strafe == WalkDirection::Left { WalkDirection::Right } else { WalkDirection::Left };
+            self.strafe_left = rand::thread_rng().gen_range(8..24);
+        }
+        self.strafe_left -= 1;
+        bot.walk(self.strafe);
+    }
+
+    fn swing(&mut self, bot: &Client, entity: Entity, id: u32, crit: bool) {
+        bot.attack(entity);
+        self.windup = 0;
+        self.last_hit = Some((id, Instant::now()));
+        debug!("[COMBAT] ⚔️ Golpe em #{}{}", id, if crit { " (crítico)" } else { "" });
+    }
+}
+
+/// The ECS entity and position behind a feed id
+fn locate(bot: &Client, id: u32) -> Option<(Entity, Vec3)> {
+    let entity = bot.ecs_entity_by_minecraft_entity(MinecraftEntityId(id as i32))?;
+    let pos = bot.get_entity_component::<Position>(entity)?;
+    Some((entity, Vec3::new(pos.x, pos.y, pos.z)))
+}
+
+/// Turn our back on `from` and sprint
+fn run_from(bot: &Client, from: Vec3) {
+    let here = bot.position();
+    let away = Vec3::new(here.x * 2.0 - from.x, here.y + 1.6, here.z * 2.0 - from.z);
+    bot.look_at(away);
+    bot.sprint(SprintDirection::Forward);
+}
+
+/// One tick of fighting whatever `decision` says. `reacting` = still panicking from a hit,
+/// reaction_delay has the body until it's done looking around
+pub fn step(bot: &Client, fighter: &mut Fighter, decision: &CombatDecision, threats: &[ThreatInfo], reacting: bool) -> Option<Moment> {
+    if let Some(id) = fighter.check_kill(threats) {
+        return Some(Moment::Killed(id));
+    }
+    if reacting {
+        return None;
+    }
+    match decision {
+        CombatDecision::DoNothing => {
+            fighter.disengage(bot);
+            None
+        }
+        // No tower builder yet — running does the same job of not being there
+        CombatDecision::Flee | CombatDecision::Tower => {
+            let threat = threats.first().and_then(|t| locate(bot, t.entity_id));
+            let Some((_, from)) = threat else {
+                fighter.disengage(bot);
+                return None;
+            };
+            run_from(bot, from);
+            fighter.target = None;
+            (!std::mem::replace(&mut fighter.fleeing, true)).then_some(Moment::Fled)
+        }
+        CombatDecision::Fight(tactic, id) => {
+            let Some((entity, at)) = locate(bot, *id) else {
+                fighter.disengage(bot);
+                return None;
+            };
+            let distance = bot.position().distance_to(at);
+            let mut moment = None;
+            if fighter.target != Some(*id) {
+                if distance > ENGAGE_RADIUS {
+                    fighter.disengage(bot);
+                    return None;
+                }
+                if let Some(weapon) = Inventory::read(bot).best_weapon() {
+                    crate::systems::inventory::equip(bot, weapon.slot);
+                }
+                info!("[COMBAT] ⚔️ Partindo pra cima de #{} ({:?}, {:.1} blocos)", id, tactic, distance);
+                fighter.target = Some(*id);
+                fighter.fleeing = false;
+                fighter.back_off = 0;
+                moment = Some(Moment::Engaged(*id));
+            }
+            execute(bot, fighter, tactic, entity, *id, at, distance);
+            moment
+        }
+    }
+}
+
+/// Move and swing for this tick's tactic
+fn execute(bot: &Client, fighter: &mut Fighter, tactic: &CombatTactic, entity: Entity, id: u32, at: Vec3, distance: f64) {
+    // Endermen: look at the legs, never the face, and only hit one that came to us
+    let aim = if *tactic == CombatTactic::AvoidEyes { at.up(0.3) } else { at.up(1.2) };
+    bot.look_at(aim);
+
+    if fighter.back_off > 0 {
+        fighter.back_off -= 1;
+        bot.walk(WalkDirection::Backward);
+        return;
+    }
+
+    if distance > REACH {
+        match tactic {
+            CombatTactic::AvoidEyes => bot.walk(WalkDirection::None),
+            CombatTactic::Flee => run_from(bot, at),
+            // Zig-zag in so arrows miss
+            CombatTactic::ShieldAndClose | CombatTactic::PvP => {
+                fighter.strafe_left = fighter.strafe_left.saturating_sub(1);
+                bot.walk(if fighter.strafe == WalkDirection::Left { WalkDirection::ForwardLeft } else { WalkDirection::ForwardRight });
+                if fighter.strafe_left == 0 {
+                    fighter.strafe = if fighter.strafe == WalkDirection::Left { WalkDirection::Right } else { WalkDirection::Left };
+                    fighter.strafe_left = 10;
+                }
+            }
+            _ => bot.sprint(SprintDirection::Forward),
+        }
+        return;
+    }
+
+    let ready = !bot.has_attack_cooldown();
+    match tactic {
+        CombatTactic::Flee => run_from(bot, at),
+        // Creepers, witches: one full-strength hit, then get out of range while it recharges
+        CombatTactic::SprintHitRetreat => {
+            if ready {
+                fighter.swing(bot, entity, id, false);
+                fighter.back_off = BACK_OFF_TICKS;
+            } else {
+                bot.walk(WalkDirection::Backward);
+            }
+        }
+        CombatTactic::AvoidEyes => {
+            bot.walk(WalkDirection::None);
+            if ready {
+                fighter.swing(bot, entity, id, false);
+            }
+        }
+        // Jump when the swing is charged, hit on the way down for the crit; strafe while it charges
+        CombatTactic::CriticalHit | CombatTactic::ShieldAndClose | CombatTactic::PvP => {
+            if !ready {
+                fighter.strafe(bot);
+                return;
+            }
+            bot.walk(WalkDirection::None);
+            let Some(physics) = bot.get_component::<Physics>() else { return };
+            // Low ceiling, cobweb... no jump coming — a plain hit beats none
+            if physics.on_ground() && fighter.windup >= 10 {
+                fighter.swing(bot, entity, id, false);
+            } else if physics.on_ground() {
+                fighter.windup += 1;
+                bot.jump();
+            } else if physics.velocity.y < 0.0 {
+                fighter.swing(bot, entity, id, true);
+            }
+        }
+    }
+}
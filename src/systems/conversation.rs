@@ -0,0 +1,144 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use tracing::info;
+
+// ============================================================
+// CONVERSATION — Per-player chat lifecycle
+// greeting → topic → follow-up → closing
+// "e aí, conseguiu a farm?" ... "flw mn"
+// ============================================================
+
+/// Nobody said anything for this long → the conversation just died
+const IDLE_TIMEOUT_SECS: i64 = 180;
+/// After this many exchanges we start wrapping up
+const MAX_TURNS: u32 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ConversationPhase {
+    Greeting, // Just started, saying hi
+    Topic,    // Talking about something specific
+    FollowUp, // We asked something, they're answering
+    Closing,  // Wrapping up — next reply says bye
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Conversation {
+    pub player: String,
+    pub phase: ConversationPhase,
+    pub topic: Option<String>,
+    pub turns: u32, // Messages from them
+    pub our_turns: u32,
+    pub last_question: Option<String>, // Our last follow-up question
+    pub started_at: DateTime<Utc>,
+    pub last_message: DateTime<Utc>,
+}
+
+/// Keyword → topic label (first match wins)
+const TOPICS: &[(&[&str], &str)] = &[
+    (&["redstone", "piston", "repeater", "comparator"], "redstone"),
+    (&["farm", "plantação", "iron farm", "xp farm"], "farms"),
+    (&["diamante", "diamond", "minera", "caverna", "minério"], "mineração"),
+    (&["build", "constru", "casa", "castelo", "base"], "construção"),
+    (&["troca", "trade", "vende", "compra", "preço"], "troca"),
+    (&["pvp", "treta", "mata", "kill", "briga"], "pvp"),
+    (&["encanta", "enchant", "livro", "mending"], "encantamentos"),
+    (&["nether", "o end", "dragão", "elytra", "wither"], "progressão"),
+    (&["lag", "tps", "servidor", "server", "plugin"], "servidor"),
+];
+
+const GOODBYES: &[&str] = &[
+    "flw", "falou", "vlw", "tchau", "fui", "bye", "até mais", "ate mais",
+    "boa noite", "gn", "vou sair", "vou off", "to indo", "tô indo",
+];
+
+/// Which topic the message is about, if any
+pub fn detect_topic(message: &str) -> Option<&'static str> {
+    let lower = message.to_lowercase();
+    TOPICS.iter()
+        .find(|(keywords, _)| keywords.iter().any(|k| lower.contains(k)))
+        .map(|(_, topic)| *topic)
+}
+
+pub fn is_goodbye(message: &str) -> bool {
+    let lower = message.to_lowercase();
+    let words: Vec<&str> = lower.split(|c: char| !c.is_alphanumeric()).collect();
+    GOODBYES.iter().any(|g| if g.contains(' ') { lower.contains(g) } else { words.contains(g) })
+}
+
+impl Conversation {
+    pub fn new(player: &str) -> Self {
+        Self {
+            player: player.to_string(),
+            phase: ConversationPhase::Greeting,
+            topic: None,
+            turns: 0,
+            our_turns: 0,
+            last_question: None,
+            started_at: Utc::now(),
+            last_message: Utc::now(),
+        }
+    }
+
+    pub fn is_idle(&self) -> bool {
+        Utc::now().signed_duration_since(self.last_message).num_seconds() > IDLE_TIMEOUT_SECS
+    }
+
+    /// They said something — move the lifecycle along
+    pub fn on_their_message(&mut self, message: &str) {
+        self.turns += 1;
+        self.last_message = Utc::now();
+
+        if let Some(topic) = detect_topic(message) {
+            if self.topic.as_deref() != Some(topic) {
+                info!("[CONVO] 💭 {} mudou de assunto: {}", self.player, topic);
+            }
+            self.topic = Some(topic.to_string());
+        }
+
+        self.phase = if is_goodbye(message) || self.turns >= MAX_TURNS {
+            ConversationPhase::Closing
+        } else if self.phase == ConversationPhase::FollowUp {
+            ConversationPhase::FollowUp // This is their answer to our question
+        } else if self.topic.is_some() || self.turns >= 2 {
+            ConversationPhase::Topic
+        } else {
+            ConversationPhase::Greeting
+        };
+    }
+
+    /// We replied. Returns true when that reply ended the conversation
+    pub fn on_our_reply(&mut self, reply: &str) -> bool {
+        self.our_turns += 1;
+        self.last_message = Utc::now();
+        if self.phase == ConversationPhase::Closing {
+            return true;
+        }
+        if reply.trim_end().ends_with('?') {
+            self.last_question = Some(reply.to_string());
+            self.phase = ConversationPhase::FollowUp;
+        } else if self.phase != ConversationPhase::Greeting {
+            self.phase = ConversationPhase::Topic;
+        }
+        false
+    }
+
+    /// Phase + what to do next, for the LLM prompt
+    pub fn describe(&self) -> String {
+        let topic = self.topic.as_deref().unwrap_or("nada específico");
+        let instruction = match self.phase {
+            ConversationPhase::Greeting => "cumprimenta de volta e pergunta o que ele tá fazendo / o que ele quer".to_string(),
+            ConversationPhase::Topic if self.our_turns > 0 && self.our_turns.is_multiple_of(2) => format!(
+                "continua no assunto ({}) e faz UMA pergunta de follow-up pra manter a conversa", topic
+            ),
+            ConversationPhase::Topic => format!("responde sobre {}, sem mudar de assunto do nada", topic),
+            ConversationPhase::FollowUp => format!(
+                "você perguntou \"{}\" — reage à resposta dele", self.last_question.as_deref().unwrap_or("")
+            ),
+            ConversationPhase::Closing => "encerra a conversa de boa ('flw', 'tmj', 'fui minerar'), sem pergunta".into(),
+        };
+        format!(
+            "\n💬 CONVERSA com {}: fase {:?}, assunto: {}, {} msgs dele. Próximo passo: {}.",
+            self.player, self.phase, topic, self.turns, instruction
+        )
+    }
+}
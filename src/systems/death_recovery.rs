@@ -0,0 +1,175 @@
+use azalea::prelude::*;
+use azalea::ecs::query::With;
+use azalea::entity::{metadata::ItemItem, Position};
+use azalea::pathfinder::goals::RadiusGoal;
+use azalea::Vec3;
+use chrono::Utc;
+use std::collections::HashMap;
+use std::time::Duration;
+use crate::cognitive::memory::{Location, LocationType, Memory};
+use crate::systems::server_events::DeathCause;
+use crate::systems::spider_sense::distance;
+use crate::systems::world_scanner::NearbyEntity;
+use tracing::info;
+
+// ============================================================
+// DEATH RECOVERY — Run back and grab the pile before it despawns
+// "morri em 120 64 -30 com 3 ferro" → respawn, corre lá, pega o que sobrou
+// ============================================================
+
+/// Name of the death point in spatial memory — only the last death matters
+pub const DEATH_POINT: &str = "Onde morri";
+/// Dropped items vanish after 5 minutes of the chunk being loaded
+const DESPAWN: Duration = Duration::from_secs(300);
+/// Don't start a run with less than this left — we'd arrive to nothing
+const MIN_LEFT: Duration = Duration::from_secs(30);
+/// Drops scatter a few blocks around the body
+const PILE_RADIUS: f64 = 8.0;
+/// Items at most this many at a time — more means somebody else's stash
+const MAX_PICKUPS: usize = 40;
+/// Whoever killed us, this close to the body, is waiting for us to come back
+const KILLER_RADIUS: f64 = 24.0;
+
+/// The dimension we're in, as the server names it
+pub fn dimension(bot: &Client) -> Option<String> {
+    bot.get_component::<azalea::world::InstanceName>().map(|name| name.to_string())
+}
+
+/// The player or mob that killed us is still in the feed, near where we fell
+pub fn killer_nearby(cause: Option<&DeathCause>, coords: [i32; 3], nearby: &[NearbyEntity]) -> bool {
+    let killer = match cause {
+        Some(DeathCause::Player(name)) => name.to_lowercase(),
+        Some(DeathCause::Mob(mob)) => mob.to_lowercase().replace(' ', "_"),
+        _ => return false,
+    };
+    let body = [coords[0] as f64 + 0.5, coords[1] as f64, coords[2] as f64 + 0.5];
+    nearby.iter().any(|e| {
+        (e.name.to_lowercase() == killer || e.kind == killer) && distance(e.position, body) <= KILLER_RADIUS
+    })
+}
+
+/// We died here carrying `holdings`. Causes that destroy the drops (lava, void) leave nothing to fetch,
+/// and neither does a killer camping the body
+pub fn record(
+    memory: &mut Memory,
+    coords: [i32; 3],
+    dimension: Option<String>,
+    holdings: HashMap<String, u32>,
+    cause: Option<&DeathCause>,
+    camped: bool,
+) {
+    let gone = matches!(cause, Some(DeathCause::Lava | DeathCause::Void));
+    let notes = if gone {
+        "caiu tudo na lava/void".to_string()
+    } else if camped {
+        "quem me matou ainda tá lá".to_string()
+    } else {
+        holdings.iter().map(|(item, n)| format!("{} x{}", item, n)).collect::<Vec<_>>().join(", ")
+    };
+    memory.spatial.remember_location(Location {
+        name: DEATH_POINT.into(),
+        coords,
+        location_type: LocationType::DeathPoint,
+        notes,
+        discovered_at: Utc::now(),
+    });
+    memory.inventory.death_drops = if gone || camped { HashMap::new() } else { holdings };
+    memory.inventory.death_dimension = dimension;
+}
+
+/// Where the pile is, if it's still worth the run from the dimension we're in now
+pub fn pending(memory: &Memory, dimension: Option<&str>) -> Option<[i32; 3]> {
+    if memory.inventory.death_drops.is_empty() {
+        return None;
+    }
+    if let (Some(there), Some(here)) = (memory.inventory.death_dimension.as_deref(), dimension)
+        && there != here
+    {
+        return None;
+    }
+    let point = memory.spatial.locations.iter().find(|l| l.name == DEATH_POINT)?;
+    let age = (Utc::now() - point.discovered_at).to_std().unwrap_or_default();
+    (age + MIN_LEFT < DESPAWN).then_some(point.coords)
+}
+
+/// Time left before the pile despawns
+pub fn time_left(memory: &Memory) -> Duration {
+    let Some(point) = memory.spatial.locations.iter().find(|l| l.name == DEATH_POINT) else { return Duration::ZERO };
+    DESPAWN.saturating_sub((Utc::now() - point.discovered_at).to_std().unwrap_or_default())
+}
+
+/// Done with the pile, got it or not
+pub fn close(memory: &mut Memory) {
+    memory.inventory.death_drops.clear();
+}
+
+/// Item entities lying around `center`, nearest first
+fn drops_near(bot: &Client, center: Vec3) -> Vec<Vec3> {
+    bot.nearest_entities_by::<(), With<ItemItem>>(|_: ()| true)
+        .into_iter()
+        .filter_map(|entity| bot.get_entity_component::<Position>(entity).map(|p| Vec3::new(p.x, p.y, p.z)))
+        .filter(|pos| pos.distance_to(center) <= PILE_RADIUS)
+        .take(MAX_PICKUPS)
+        .collect()
+}
+
+/// Walk to the death point and over every drop around it. Returns how many item stacks we walked onto
+pub async fn recover(bot: &Client, coords: [i32; 3], budget: Duration) -> usize {
+    let center = Vec3::new(coords[0] as f64 + 0.5, coords[1] as f64, coords[2] as f64 + 0.5);
+    info!("[DEATH] 🏃 Voltando pra {} {} {} pegar minhas coisas ({}s)", coords[0], coords[1], coords[2], budget.as_secs());
+    if tokio::time::timeout(budget, bot.goto(RadiusGoal::new(center, 2.0))).await.is_err() {
+        bot.stop_pathfinding();
+        return 0;
+    }
+    let mut walked = 0;
+    for drop in drops_near(bot, center) {
+        if tokio::time::timeout(Duration::from_secs(8), bot.goto(RadiusGoal::new(drop, 0.5))).await.is_err() {
+            bot.stop_pathfinding();
+            continue;
+        }
+        walked += 1;
+    }
+    bot.wait_ticks(10).await; // Let the last pickups land
+    walked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seen(name: &str, kind: &str, position: [f64; 3]) -> NearbyEntity {
+        NearbyEntity {
+            name: name.into(),
+            kind: kind.into(),
+            position,
+            distance: 0.0,
+            entity_id: 1,
+            hostile: kind != "player",
+            closing_speed: 0.0,
+            fusing: false,
+            sneaking: false,
+            look: [0.0, 0.0, 1.0],
+        }
+    }
+
+    #[test]
+    fn test_killer_camping_the_body() {
+        let feed = [seen("Fulano", "player", [10.0, 64.0, 10.0]), seen("zombie", "zombie", [100.0, 64.0, 0.0])];
+        assert!(killer_nearby(Some(&DeathCause::Player("fulano".into())), [0, 64, 0], &feed));
+        // Zombie's there, but wandered off
+        assert!(!killer_nearby(Some(&DeathCause::Mob("Zombie".into())), [0, 64, 0], &feed));
+        assert!(!killer_nearby(Some(&DeathCause::Fall), [0, 64, 0], &feed));
+    }
+
+    #[test]
+    fn test_pile_only_from_its_dimension() {
+        let mut memory = Memory::default();
+        let holdings = HashMap::from([("iron_ingot".to_string(), 3)]);
+        record(&mut memory, [1, 64, 1], Some("minecraft:the_nether".into()), holdings, None, false);
+        assert_eq!(pending(&memory, Some("minecraft:the_nether")), Some([1, 64, 1]));
+        assert_eq!(pending(&memory, Some("minecraft:overworld")), None);
+
+        record(&mut memory, [1, 64, 1], None, HashMap::from([("iron_ingot".to_string(), 3)]), None, true);
+        assert_eq!(pending(&memory, None), None);
+    }
+}
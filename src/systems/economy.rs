@@ -1,6 +1,11 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
+use crate::config::Config;
+use crate::systems::negotiation::Negotiation;
+use crate::systems::chest_shop::ChestShop;
+use crate::systems::escrow::{EscrowPhase, EscrowTrade};
+use tracing::info;
 
 // ============================================================
 // ECONOMY — Debt, Favors, Negotiation & Loan Sharking
@@ -14,6 +19,84 @@ pub struct Debt {
     pub created_at: DateTime<Utc>,
     pub reason: String,
     pub paid: bool,
+    #[serde(default)]
+    pub interest: u32, // Extra items accrued for paying late
+    #[serde(default)]
+    pub due_by: Option<DateTime<Utc>>, // Trade payments: must arrive before this
+}
+
+impl Debt {
+    /// Principal + whatever interest piled up
+    pub fn amount_due(&self) -> u32 {
+        self.quantity + self.interest
+    }
+}
+
+/// Reason on a debt for help we asked for — the only debts paid back as favors
+const HELP_REASON: &str = "me ajudou quando eu precisei";
+
+/// How late debts grow (from config)
+#[derive(Debug, Clone, Copy)]
+pub struct InterestPolicy {
+    pub daily_rate: f32,
+    pub grace_days: u32,
+    pub max_multiplier: f32,
+}
+
+impl InterestPolicy {
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            daily_rate: config.debt_interest_rate.max(0.0),
+            grace_days: config.debt_grace_days,
+            max_multiplier: config.debt_interest_cap.max(1.0),
+        }
+    }
+
+    /// Interest owed on a debt of this age — computed from scratch so re-running is harmless
+    pub fn interest_for(&self, debt: &Debt) -> u32 {
+        let days = Utc::now().signed_duration_since(debt.created_at).num_days();
+        let overdue = (days - self.grace_days as i64).max(0) as f32;
+        let raw = (debt.quantity as f32 * self.daily_rate * overdue).floor();
+        let cap = debt.quantity as f32 * (self.max_multiplier - 1.0);
+        raw.min(cap) as u32
+    }
+}
+
+/// What kind of customer is this? Decides lending limits and collateral
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CreditTier {
+    Vip,
+    Normal,
+    Blacklist,
+}
+
+impl CreditTier {
+    /// Max value we'll hand over on credit, in iron equivalents
+    pub fn lending_limit(&self) -> u32 {
+        match self {
+            CreditTier::Vip => 64,
+            CreditTier::Normal => 20,
+            CreditTier::Blacklist => 0,
+        }
+    }
+
+    /// Fraction of the loan's value they must leave with us first
+    pub fn collateral_ratio(&self) -> f32 {
+        match self {
+            CreditTier::Vip => 0.0,
+            CreditTier::Normal => 0.5,
+            CreditTier::Blacklist => 1.0,
+        }
+    }
+
+    /// How the LLM should see this relationship
+    pub fn describe(&self) -> &'static str {
+        match self {
+            CreditTier::Vip => "VIP (paga certinho, pode emprestar sem garantia)",
+            CreditTier::Normal => "normal (empresta com garantia)",
+            CreditTier::Blacklist => "LISTA NEGRA (caloteiro, não empresta nada)",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,14 +115,46 @@ pub struct PlayerLedger {
     pub total_received_from_them: HashMap<String, u32>,
     pub credit_score: i32,                // -100 (deadbeat) to 100 (reliable)
     pub trade_count: u32,
+    #[serde(default)]
+    pub last_reminder: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub reminders_sent: u32,
+    #[serde(default)]
+    pub scammer: bool, // Took the goods and ran — permanent blacklist
+}
+
+/// A deal where we delivered and the payment never came
+#[derive(Debug, Clone)]
+pub struct ScamReport {
+    pub player: String,
+    pub item: String,
+    pub quantity: u32,
+    pub left_server: bool,
+}
+
+/// How long a trade partner has to pay after we deliver
+const PAYMENT_TIMEOUT_SECS: i64 = 300;
+
+/// How hard we lean on someone who owes us
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CollectionStage {
+    Gentle,      // "lembra dos 3 ferro?"
+    Firm,        // "mano cadê"
+    Threat,      // "vou espalhar pro server"
+    Blacklisted, // No more services until paid
 }
 
+/// Debts younger than this aren't worth nagging about
+const REMINDER_AFTER_HOURS: i64 = 24;
+/// Minimum gap between two reminders to the same player
+const REMINDER_COOLDOWN_HOURS: i64 = 6;
+
 impl PlayerLedger {
     /// Calculate the net balance (positive = they owe us more)
     pub fn net_balance(&self) -> i32 {
         let owed_to_us: i32 = self.debts_owed_to_us.iter()
             .filter(|d| !d.paid)
-            .map(|d| d.quantity as i32)
+            .map(|d| d.amount_due() as i32)
             .sum();
         let we_owe: i32 = self.debts_we_owe.iter()
             .filter(|d| !d.paid)
@@ -75,17 +190,128 @@ impl PlayerLedger {
         self.credit_score = (paid_debts as i32 * 5 - unpaid_debts as i32 * 3 - old_debts * 10)
             .clamp(-100, 100);
     }
+
+    pub fn tier(&self) -> CreditTier {
+        if self.scammer || self.credit_score <= -40 || self.collection_stage() == Some(CollectionStage::Blacklisted) {
+            CreditTier::Blacklist
+        } else if self.credit_score >= 50 && self.trade_count >= 5 {
+            CreditTier::Vip
+        } else {
+            CreditTier::Normal
+        }
+    }
+
+    pub fn oldest_unpaid_debt(&self) -> Option<&Debt> {
+        self.debts_owed_to_us.iter()
+            .filter(|d| !d.paid)
+            .min_by_key(|d| d.created_at)
+    }
+
+    /// Escalates with how old the debt is and how bad their credit got
+    pub fn collection_stage(&self) -> Option<CollectionStage> {
+        let debt = self.oldest_unpaid_debt()?;
+        let age_hours = Utc::now().signed_duration_since(debt.created_at).num_hours();
+        if age_hours < REMINDER_AFTER_HOURS {
+            return None;
+        }
+
+        let stage = if age_hours >= 24 * 7 || self.credit_score <= -60 {
+            CollectionStage::Blacklisted
+        } else if age_hours >= 24 * 4 || self.credit_score <= -30 {
+            CollectionStage::Threat
+        } else if age_hours >= 24 * 2 || self.reminders_sent >= 2 {
+            CollectionStage::Firm
+        } else {
+            CollectionStage::Gentle
+        };
+        Some(stage)
+    }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+/// Where a price signal came from — trades we did ourselves count the most
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PriceSource {
+    OwnTrade,      // We actually swapped items at this rate
+    ObservedTrade, // "troquei 1 dima por 8 ferro" in chat
+    ChatMention,   // "vendo dima a 10 ferro" — talk is cheap
+}
+
+impl PriceSource {
+    /// How far one observation pulls the estimate toward itself
+    fn weight(&self) -> f32 {
+        match self {
+            PriceSource::OwnTrade => 0.5,
+            PriceSource::ObservedTrade => 0.3,
+            PriceSource::ChatMention => 0.15,
+        }
+    }
+}
+
+/// Learned market price, in iron ingot equivalents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketPrice {
+    pub estimate: f32,
+    pub samples: u32,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// One point in our net-worth history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WealthSnapshot {
+    pub at: DateTime<Utc>,
+    pub assets: f32,      // Inventory + storage, valued at market prices
+    pub receivables: f32, // Debts owed to us
+    pub payables: f32,    // Debts we owe
+}
+
+impl WealthSnapshot {
+    pub fn net_worth(&self) -> f32 {
+        self.assets + self.receivables - self.payables
+    }
+}
+
+/// Iron is the currency everything else is priced in
+const NUMERAIRE: &str = "iron_ingot";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Economy {
     pub ledgers: HashMap<String, PlayerLedger>,
-    pub item_values: HashMap<String, u32>, // Subjective item value
+    pub item_values: HashMap<String, u32>, // Base prior, before the server teaches us otherwise
+    #[serde(default)]
+    pub market: HashMap<String, MarketPrice>, // Learned from trades & chat
+    #[serde(default)]
+    pub negotiations: HashMap<String, Negotiation>, // Open haggles, by player
+    #[serde(default)]
+    pub shops: HashMap<String, ChestShop>, // Known chest shops, by "x,y,z"
+    #[serde(default)]
+    pub escrows: Vec<EscrowTrade>, // Escrow trades, open and finished
+    #[serde(default)]
+    pub wealth_history: Vec<WealthSnapshot>,
     pub total_trades: u32,
 }
 
+impl Default for Economy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Economy {
     pub fn new() -> Self {
+        Economy {
+            ledgers: HashMap::new(),
+            item_values: Self::base_item_values(),
+            market: HashMap::new(),
+            negotiations: HashMap::new(),
+            shops: HashMap::new(),
+            escrows: Vec::new(),
+            wealth_history: Vec::new(),
+            total_trades: 0,
+        }
+    }
+
+    /// Starting price table (in "iron ingot equivalents")
+    pub fn base_item_values() -> HashMap<String, u32> {
         let mut item_values = HashMap::new();
         // Base item values (in "iron ingot equivalents")
         item_values.insert("diamond".into(), 10);
@@ -102,16 +328,23 @@ impl Economy {
         item_values.insert("elytra".into(), 200);
         item_values.insert("totem_of_undying".into(), 80);
         item_values.insert("redstone".into(), 0);
+        item_values
+    }
 
-        Economy {
-            ledgers: HashMap::new(),
-            item_values,
-            total_trades: 0,
+    /// Fill in base prices for items an older save didn't know about
+    pub fn merge_missing_values(&mut self) -> usize {
+        let mut added = 0;
+        for (item, value) in Self::base_item_values() {
+            if let std::collections::hash_map::Entry::Vacant(e) = self.item_values.entry(item) {
+                e.insert(value);
+                added += 1;
+            }
         }
+        added
     }
 
     pub fn get_ledger(&mut self, player: &str) -> &mut PlayerLedger {
-        self.ledgers.entry(player.to_string()).or_insert_with(PlayerLedger::default)
+        self.ledgers.entry(player.to_string()).or_default()
     }
 
     /// Record that we gave an item to a player
@@ -124,27 +357,491 @@ impl Economy {
             created_at: Utc::now(),
             reason: reason.to_string(),
             paid: false,
+            interest: 0,
+            due_by: None,
         });
         ledger.update_credit_score();
-        println!("[ECONOMY] 📝 {} agora deve {} x{} (razão: {})", player, item, quantity, reason);
+        info!("[ECONOMY] 📝 {} agora deve {} x{} (razão: {})", player, item, quantity, reason);
+    }
+
+    /// Price of a truce with a rival — booked like any debt so payment settles it
+    pub fn demand_restitution(&mut self, player: &str, item: &str, quantity: u32, grievance: &str) {
+        let ledger = self.get_ledger(player);
+        ledger.debts_owed_to_us.push(Debt {
+            item: item.to_string(),
+            quantity,
+            created_at: Utc::now(),
+            reason: format!("reparação: {}", grievance),
+            paid: false,
+            interest: 0,
+            due_by: None,
+        });
+        info!("[ECONOMY] ⚖️ {} deve {} x{} de reparação", player, item, quantity);
+    }
+
+    /// Oldest unpaid debt of theirs as (item, amount due)
+    pub fn outstanding_debt(&self, player: &str) -> Option<(String, u32)> {
+        self.ledgers.get(player)?
+            .oldest_unpaid_debt()
+            .map(|d| (d.item.clone(), d.amount_due()))
+    }
+
+    /// We handed over goods on a deal — they now owe the agreed price
+    pub fn record_sale(&mut self, player: &str, item: &str, quantity: u32, price_item: &str, price_qty: u32) {
+        let ledger = self.get_ledger(player);
+        *ledger.total_given_to_them.entry(item.to_string()).or_insert(0) += quantity;
+        ledger.debts_owed_to_us.push(Debt {
+            item: price_item.to_string(),
+            quantity: price_qty,
+            created_at: Utc::now(),
+            reason: format!("pagamento por {} x{}", item, quantity),
+            paid: false,
+            interest: 0,
+            due_by: Some(Utc::now() + chrono::Duration::seconds(PAYMENT_TIMEOUT_SECS)),
+        });
+        ledger.update_credit_score();
+        info!("[ECONOMY] 🧾 {} levou {} x{}, deve {} x{}", player, item, quantity, price_item, price_qty);
     }
 
     /// Record that a player gave us something
-    pub fn record_received(&mut self, player: &str, item: &str, quantity: u32) {
+    /// Returns how many of their debts this payment settled
+    pub fn record_received(&mut self, player: &str, item: &str, quantity: u32) -> u32 {
         let ledger = self.get_ledger(player);
         *ledger.total_received_from_them.entry(item.to_string()).or_insert(0) += quantity;
 
-        // Try to mark debts as paid
+        // Try to mark their debts to us as paid (oldest first)
         let mut remaining = quantity;
-        for debt in ledger.debts_we_owe.iter_mut().filter(|d| !d.paid && d.item == item) {
-            if remaining >= debt.quantity {
-                remaining -= debt.quantity;
+        let mut settled = 0;
+        for debt in ledger.debts_owed_to_us.iter_mut().filter(|d| !d.paid && d.item == item) {
+            if remaining >= debt.amount_due() {
+                remaining -= debt.amount_due();
                 debt.paid = true;
+                settled += 1;
             }
         }
 
+        ledger.trade_count += 1;
         ledger.update_credit_score();
+        if ledger.oldest_unpaid_debt().is_none() {
+            // Clean slate — next debt starts with a gentle reminder again
+            ledger.reminders_sent = 0;
+            ledger.last_reminder = None;
+        }
         self.total_trades += 1;
+        settled
+    }
+
+    /// A friend came through when we asked for help — we owe them one
+    pub fn record_help_received(&mut self, player: &str, item: &str, quantity: u32) {
+        let ledger = self.get_ledger(player);
+        *ledger.total_received_from_them.entry(item.to_string()).or_insert(0) += quantity;
+        ledger.debts_we_owe.push(Debt {
+            item: item.to_string(),
+            quantity,
+            created_at: Utc::now(),
+            reason: HELP_REASON.into(),
+            paid: false,
+            interest: 0,
+            due_by: None,
+        });
+        ledger.favors.push(Favor {
+            description: format!("me deu {} x{} quando pedi ajuda", item, quantity),
+            weight: -(quantity.min(10) as i32),
+            timestamp: Utc::now(),
+        });
+        info!("[ECONOMY] 🤝 Devo {} x{} pro {} (ajuda)", item, quantity, player);
+    }
+
+    /// Favors we still have to pay back: (player, item, quantity). Only help we asked for —
+    /// escrow refunds and trade debts settle their own way
+    pub fn unpaid_favors(&self) -> Vec<(String, String, u32)> {
+        self.ledgers.iter()
+            .flat_map(|(player, ledger)| {
+                ledger.debts_we_owe.iter()
+                    .filter(|d| !d.paid && d.reason == HELP_REASON)
+                    .map(move |d| (player.clone(), d.item.clone(), d.amount_due()))
+            })
+            .collect()
+    }
+
+    /// We paid back what we owed them
+    pub fn record_repaid(&mut self, player: &str, item: &str, quantity: u32) {
+        let ledger = self.get_ledger(player);
+        *ledger.total_given_to_them.entry(item.to_string()).or_insert(0) += quantity;
+        if let Some(debt) = ledger.debts_we_owe.iter_mut().find(|d| !d.paid && d.item == item && d.reason == HELP_REASON) {
+            debt.paid = true;
+        }
+        ledger.favors.push(Favor {
+            description: format!("devolvi {} x{}", item, quantity),
+            weight: quantity.min(10) as i32,
+            timestamp: Utc::now(),
+        });
+        ledger.update_credit_score();
+        info!("[ECONOMY] ✅ Paguei {} x{} pro {}", item, quantity, player);
+    }
+
+    // ========================================================
+    // PRICING — What's stuff actually worth on this server?
+    // ========================================================
+
+    /// Current value of one unit, in iron equivalents (learned > base > guess)
+    pub fn value_of(&self, item: &str) -> f32 {
+        if item == NUMERAIRE {
+            return 1.0;
+        }
+        self.market.get(item)
+            .map(|p| p.estimate)
+            .or_else(|| self.item_values.get(item).map(|v| *v as f32))
+            .unwrap_or(1.0)
+    }
+
+    pub fn knows_item(&self, item: &str) -> bool {
+        self.item_values.contains_key(item) || self.market.contains_key(item)
+    }
+
+    /// Someone swapped `qty_a` of `item_a` for `qty_b` of `item_b` — nudge both prices
+    pub fn observe_exchange(&mut self, item_a: &str, qty_a: u32, item_b: &str, qty_b: u32, source: PriceSource) {
+        if qty_a == 0 || qty_b == 0 || item_a == item_b {
+            return;
+        }
+        // Each side implies a price for the other, using what we believe right now
+        let implied_a = self.value_of(item_b) * qty_b as f32 / qty_a as f32;
+        let implied_b = self.value_of(item_a) * qty_a as f32 / qty_b as f32;
+        self.update_price(item_a, implied_a, source);
+        self.update_price(item_b, implied_b, source);
+    }
+
+    fn update_price(&mut self, item: &str, implied: f32, source: PriceSource) {
+        if item == NUMERAIRE || !implied.is_finite() {
+            return;
+        }
+        let current = self.value_of(item);
+        // One troll saying "1 dima = 500 ferro" shouldn't wreck the market
+        let implied = if current > 0.0 {
+            implied.clamp(current / 4.0, current * 4.0)
+        } else {
+            implied.min(4.0)
+        };
+
+        let price = self.market.entry(item.to_string()).or_insert(MarketPrice {
+            estimate: current,
+            samples: 0,
+            updated_at: Utc::now(),
+        });
+        price.estimate += source.weight() * (implied - price.estimate);
+        price.samples += 1;
+        price.updated_at = Utc::now();
+        info!("[ECONOMY] 📈 {} agora vale ~{:.1} ferro ({:?})", item, price.estimate, source);
+    }
+
+    /// Pull "vendo 1 dima por 9 ferro" / "1 diamante = 8 ferro" out of chat
+    pub fn parse_price_mention(&self, message: &str) -> Option<(String, u32, String, u32, PriceSource)> {
+        let msg = message.to_lowercase();
+        let past = ["vendi", "comprei", "troquei", "paguei"];
+        let present = ["vendo", "compro", "troco", "pago", "vale", "="];
+        let source = if past.iter().any(|w| msg.contains(w)) {
+            PriceSource::ObservedTrade
+        } else if present.iter().any(|w| msg.contains(w)) {
+            PriceSource::ChatMention
+        } else {
+            return None;
+        };
+
+        let (left, right) = msg.split_once(" por ")
+            .or_else(|| msg.split_once('='))
+            .or_else(|| msg.split_once(" vale "))?;
+        let (item_a, qty_a) = self.parse_amount(left)?;
+        let (item_b, qty_b) = self.parse_amount(right)?;
+        Some((item_a, qty_a, item_b, qty_b, source))
+    }
+
+    /// "te dou 5 ferro" → ("iron_ingot", 5)
+    pub fn parse_offer(&self, message: &str) -> Option<(String, u32)> {
+        self.parse_amount(&message.to_lowercase())
+    }
+
+    /// "preciso de 10 ferro" / "to sem comida" → what they're short on
+    pub fn parse_need(&self, message: &str) -> Option<(String, u32)> {
+        let phrases = [
+            "preciso de", "precisando de", "to sem", "tô sem", "alguem tem", "alguém tem",
+            "acabou meu", "acabou minha", "acabou o", "acabou a", "falta",
+        ];
+        let lower = message.to_lowercase();
+        let rest = phrases.iter().find_map(|p| lower.find(p).map(|i| &lower[i + p.len()..]))?;
+        self.parse_amount(rest)
+    }
+
+    /// A present, no strings attached — they don't owe us items, just a bit of goodwill
+    pub fn record_present(&mut self, player: &str, item: &str, quantity: u32) {
+        let ledger = self.get_ledger(player);
+        *ledger.total_given_to_them.entry(item.to_string()).or_insert(0) += quantity;
+        ledger.favors.push(Favor {
+            description: format!("dei {} x{} de presente", item, quantity),
+            weight: quantity.min(10) as i32,
+            timestamp: Utc::now(),
+        });
+        info!("[ECONOMY] 🎁 Presente pro {}: {} x{}", player, item, quantity);
+    }
+
+    /// Drop haggles that went quiet, and `player`'s last one once it settled (its closing reply is already out)
+    pub fn forget_settled_negotiations(&mut self, player: &str) {
+        self.negotiations.retain(|name, n| !n.is_stale() && (name != player || n.is_active()));
+    }
+
+    /// "5 ferro" → ("iron_ingot", 5); quantity defaults to 1
+    fn parse_amount(&self, text: &str) -> Option<(String, u32)> {
+        let mut quantity = None;
+        let mut item = None;
+        for word in text.split(|c: char| !c.is_alphanumeric() && c != '_') {
+            if word.is_empty() {
+                continue;
+            }
+            if let Ok(n) = word.parse::<u32>() {
+                quantity.get_or_insert(n);
+            } else if item.is_none() {
+                let name = normalize_item_name(word);
+                if self.knows_item(&name) {
+                    item = Some(name);
+                }
+            }
+        }
+        Some((item?, quantity.unwrap_or(1)))
+    }
+
+    // ========================================================
+    // COLLECTIONS — "lembra daquele ferro?"
+    // ========================================================
+
+    pub fn tier_of(&self, player: &str) -> CreditTier {
+        self.ledgers.get(player)
+            .map(|l| l.tier())
+            .unwrap_or(CreditTier::Normal)
+    }
+
+    /// Deadbeats get nothing from us — no trades, no help, no escort
+    pub fn is_blacklisted(&self, player: &str) -> bool {
+        self.tier_of(player) == CreditTier::Blacklist
+    }
+
+    /// Flag everyone who got goods from us and skipped the payment —
+    /// either the deadline passed or they logged off still owing
+    pub fn check_scams(&mut self, is_online: impl Fn(&str) -> bool) -> Vec<ScamReport> {
+        let now = Utc::now();
+        let mut reports = Vec::new();
+        for (player, ledger) in self.ledgers.iter_mut().filter(|(_, l)| !l.scammer) {
+            let online = is_online(player);
+            let skipped = ledger.debts_owed_to_us.iter()
+                .filter(|d| !d.paid)
+                .find(|d| d.due_by.is_some_and(|due| now > due || !online));
+            if let Some(debt) = skipped {
+                reports.push(ScamReport {
+                    player: player.clone(),
+                    item: debt.item.clone(),
+                    quantity: debt.amount_due(),
+                    left_server: !online,
+                });
+                ledger.scammer = true;
+                ledger.credit_score = -100;
+                info!("[ECONOMY] 🚨 GOLPE: {} não pagou {} x{}", player, debt.item, debt.quantity);
+            }
+        }
+        reports
+    }
+
+    /// Grow overdue debts according to policy; returns how many changed
+    pub fn accrue_interest(&mut self, policy: &InterestPolicy) -> usize {
+        let mut changed = 0;
+        for (player, ledger) in self.ledgers.iter_mut() {
+            for debt in ledger.debts_owed_to_us.iter_mut().filter(|d| !d.paid) {
+                let interest = policy.interest_for(debt);
+                if interest != debt.interest {
+                    info!("[ECONOMY] 💸 Juros: {} agora deve {} x{} (+{})",
+                        player, debt.item, debt.quantity + interest, interest);
+                    debt.interest = interest;
+                    changed += 1;
+                }
+            }
+        }
+        changed
+    }
+
+    /// Reminder to whisper this player, if one is due (rate-limited per player)
+    pub fn collection_notice(&mut self, player: &str) -> Option<String> {
+        let ledger = self.ledgers.get_mut(player)?;
+        let stage = ledger.collection_stage()?;
+        if ledger.last_reminder.is_some_and(|t| {
+            Utc::now().signed_duration_since(t).num_hours() < REMINDER_COOLDOWN_HOURS
+        }) {
+            return None;
+        }
+
+        let debt = ledger.oldest_unpaid_debt()?;
+        let item = debt.item.replace('_', " ");
+        let qty = debt.amount_due();
+        let days = Utc::now().signed_duration_since(debt.created_at).num_days().max(1);
+        let msg = match stage {
+            CollectionStage::Gentle => format!(
+                "eai, lembra dos {} {} q te emprestei? sem pressa mas n esquece", qty, item
+            ),
+            CollectionStage::Firm => format!(
+                "mano ja faz {} dias, cadê meus {} {}?", days, qty, item
+            ),
+            CollectionStage::Threat => format!(
+                "{} dias e nada dos {} {}. vou começar a falar pro server q vc é caloteiro", days, qty, item
+            ),
+            CollectionStage::Blacklisted => format!(
+                "até pagar os {} {} n faço mais nada por vc. nada.", qty, item
+            ),
+        };
+
+        ledger.last_reminder = Some(Utc::now());
+        ledger.reminders_sent += 1;
+        info!("[ECONOMY] 📨 Cobrando {} ({:?}): {} x{}", player, stage, item, qty);
+        Some(msg)
+    }
+
+    // ========================================================
+    // WEALTH — How rich are we, really?
+    // ========================================================
+
+    /// Market value of a pile of items, in iron
+    pub fn appraise(&self, items: &HashMap<String, u32>) -> f32 {
+        items.iter()
+            .filter(|(item, _)| self.knows_item(item))
+            .map(|(item, count)| self.value_of(item) * *count as f32)
+            .sum()
+    }
+
+    /// Value everything we own and owe, and remember it
+    pub fn record_wealth(&mut self, holdings: &HashMap<String, u32>, stored: &HashMap<String, u32>) -> WealthSnapshot {
+        let debt_value = |debts: &[Debt]| -> f32 {
+            debts.iter()
+                .filter(|d| !d.paid)
+                .map(|d| self.value_of(&d.item) * d.amount_due() as f32)
+                .sum()
+        };
+        let snapshot = WealthSnapshot {
+            at: Utc::now(),
+            assets: self.appraise(holdings) + self.appraise(stored),
+            receivables: self.ledgers.values().map(|l| debt_value(&l.debts_owed_to_us)).sum(),
+            payables: self.ledgers.values().map(|l| debt_value(&l.debts_we_owe)).sum(),
+        };
+        self.wealth_history.push(snapshot.clone());
+        if self.wealth_history.len() > 288 {
+            self.wealth_history.remove(0); // ~1 day at one snapshot per 5 min
+        }
+        snapshot
+    }
+
+    /// Change in net worth across the recorded history
+    pub fn wealth_trend(&self) -> f32 {
+        match (self.wealth_history.first(), self.wealth_history.last()) {
+            (Some(first), Some(last)) => last.net_worth() - first.net_worth(),
+            _ => 0.0,
+        }
+    }
+
+    // ========================================================
+    // ESCROW — Verified simultaneous trades
+    // ========================================================
+
+    pub fn open_escrow(&mut self, trade: EscrowTrade) {
+        self.escrows.push(trade);
+        if self.escrows.len() > 50 {
+            self.escrows.remove(0);
+        }
+    }
+
+    pub fn escrow_mut(&mut self, player: &str) -> Option<&mut EscrowTrade> {
+        self.escrows.iter_mut().rev().find(|e| e.player == player && e.is_open())
+    }
+
+    pub fn has_open_escrow(&self, player: &str) -> bool {
+        self.escrows.iter().any(|e| e.player == player && e.is_open())
+    }
+
+    /// A pickup from this player — count it toward their escrow side.
+    /// Returns the trade if it counted (phase is `Releasing` once fully paid)
+    pub fn escrow_deposit(&mut self, player: &str, item: &str, quantity: u32) -> Option<EscrowTrade> {
+        let trade = self.escrow_mut(player)?;
+        if trade.phase != EscrowPhase::AwaitingDeposit || trade.they_give.0 != item {
+            return None;
+        }
+        trade.on_deposit(item, quantity);
+        let snapshot = trade.clone();
+        *self.get_ledger(player).total_received_from_them.entry(item.to_string()).or_insert(0) += quantity;
+        Some(snapshot)
+    }
+
+    /// Expire stale escrows; returns the ones that need a refund
+    pub fn expire_escrows(&mut self) -> Vec<EscrowTrade> {
+        self.escrows.iter_mut()
+            .filter_map(|e| (e.check_timeout() && e.phase == EscrowPhase::Refunding).then(|| e.clone()))
+            .collect()
+    }
+
+    /// Release/refund finished — close the books on it
+    pub fn close_escrow(&mut self, player: &str, phase: EscrowPhase) {
+        let Some(trade) = self.escrow_mut(player) else { return };
+        trade.finish(phase);
+        let (item, qty) = trade.we_give.clone();
+        if phase == EscrowPhase::Completed {
+            let ledger = self.get_ledger(player);
+            *ledger.total_given_to_them.entry(item).or_insert(0) += qty;
+            ledger.trade_count += 1;
+            ledger.update_credit_score();
+            self.total_trades += 1;
+        }
+    }
+
+    // ========================================================
+    // SHOPS — Chest shops around the server
+    // ========================================================
+
+    /// Returns true if this is a shop we didn't know about
+    pub fn record_shop(&mut self, shop: ChestShop) -> bool {
+        let key = format!("{},{},{}", shop.pos[0], shop.pos[1], shop.pos[2]);
+        let last_purchase = self.shops.get(&key).and_then(|s| s.last_purchase);
+        let is_new = !self.shops.contains_key(&key);
+        self.shops.insert(key, ChestShop { last_purchase, ..shop });
+        is_new
+    }
+
+    /// Iron per unit of server money, judged from shops selling things we can price.
+    /// `except` leaves one shop out — the shop being judged can't vouch for its own price
+    pub fn money_rate(&self, except: Option<[i32; 3]>) -> Option<f32> {
+        let mut rates: Vec<f32> = self.shops.values()
+            .filter(|s| Some(s.pos) != except)
+            .filter(|s| self.knows_item(&s.item) && self.value_of(&s.item) > 0.0)
+            .filter_map(|s| {
+                let unit = s.unit_buy_price().filter(|p| *p > 0.0)?;
+                Some(self.value_of(&s.item) / unit)
+            })
+            .collect();
+        if rates.is_empty() {
+            return None;
+        }
+        rates.sort_by(|a, b| a.total_cmp(b));
+        Some(rates[rates.len() / 2])
+    }
+
+    /// Cheapest shop selling `item` for less than it's worth to us, priced at the rate every
+    /// other shop sets. A shop with nothing to compare against is taken at its word
+    pub fn best_shop_for(&self, item: &str) -> Option<&ChestShop> {
+        let worth = self.value_of(item);
+        self.shops.values()
+            .filter(|s| s.item == item && s.can_buy_now())
+            .filter_map(|s| Some((s, s.unit_buy_price()?)))
+            .filter(|(s, unit)| self.money_rate(Some(s.pos)).is_none_or(|rate| unit * rate < worth))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(s, _)| s)
+    }
+
+    pub fn mark_shop_visited(&mut self, pos: [i32; 3]) {
+        let key = format!("{},{},{}", pos[0], pos[1], pos[2]);
+        if let Some(shop) = self.shops.get_mut(&key) {
+            shop.last_purchase = Some(Utc::now());
+        }
     }
 
     /// Should we give this player what they asked for?
@@ -154,6 +851,10 @@ impl Economy {
             None => return TradeDecision::Cautious("nunca negociei com vc antes".into()),
         };
 
+        if self.is_blacklisted(player) {
+            return TradeDecision::Refuse("n faço negócio com caloteiro. paga o q deve".into());
+        }
+
         // Check credit score
         if ledger.credit_score < -20 {
             let unpaid: u32 = ledger.debts_owed_to_us.iter()
@@ -175,9 +876,10 @@ impl Economy {
             ));
         }
 
-        // Check item value
-        let value = self.item_values.get(item).copied().unwrap_or(1) * quantity;
-        if value > 20 {
+        // Check item value against what this tier may borrow
+        let tier = ledger.tier();
+        let value = (self.value_of(item) * quantity as f32).round() as u32;
+        if value > tier.lending_limit() {
             return TradeDecision::Negotiate(format!(
                 "{} x{} é muito caro. o que vc tem pra trocar?",
                 item, quantity
@@ -190,10 +892,15 @@ impl Economy {
         }
 
         // Fair trade
-        if ledger.credit_score > 30 {
+        if tier == CreditTier::Vip {
+            TradeDecision::Accept("toma, cliente vip tem crédito aqui".into())
+        } else if ledger.credit_score > 30 {
             TradeDecision::Accept("toma, vc é gnt boa".into())
         } else {
-            TradeDecision::Negotiate("depende, o que vc me dá em troca?".into())
+            let collateral = (value as f32 * tier.collateral_ratio()).ceil() as u32;
+            TradeDecision::Negotiate(format!(
+                "depende. deixa {} ferro de garantia q eu te passo", collateral.max(1)
+            ))
         }
     }
 
@@ -205,10 +912,7 @@ impl Economy {
         we_have: &[String],  // Items in our inventory
         they_have: &[String], // Items we think they have
     ) -> Option<String> {
-        let ledger = match self.ledgers.get(player) {
-            Some(l) => l,
-            None => return None,
-        };
+        let ledger = self.ledgers.get(player)?;
 
         // Only trade with people we somewhat trust
         if ledger.credit_score < -10 {
@@ -224,36 +928,69 @@ impl Economy {
         // What do they have that we want?
         let valuable_they_have: Vec<&String> = they_have.iter()
             .filter(|i| {
-                self.item_values.get(i.as_str()).copied().unwrap_or(0) > 3
+                self.knows_item(i) && self.value_of(i) > 3.0
             })
             .collect();
 
-        if let Some(want) = valuable_they_have.first() {
-            Some(format!(
-                "eai {}, vi que vc ta precisando de {}. te faço por {} unidades de {}, bora?",
-                player, player_needs, 1, want
-            ))
-        } else {
-            None
-        }
+        valuable_they_have.first().map(|want| format!(
+            "eai {}, vi que vc ta precisando de {}. te faço por {} unidades de {}, bora?",
+            player, player_needs, 1, want
+        ))
     }
 
     pub fn context_summary(&self) -> String {
         let mut s = format!("Total trades: {}\n", self.total_trades);
+        if let Some(wealth) = self.wealth_history.last() {
+            s.push_str(&format!(
+                "Patrimônio: {:.0} ferro (itens {:.0} + a receber {:.0} - devendo {:.0}), tendência {:+.0}\n",
+                wealth.net_worth(), wealth.assets, wealth.receivables, wealth.payables, self.wealth_trend()
+            ));
+        }
         for (player, ledger) in &self.ledgers {
             let balance = ledger.net_balance();
             let credit = ledger.credit_score;
             s.push_str(&format!(
-                "  {}: balanço={} crédito={}\n",
+                "  {}: balanço={} crédito={} tier={}\n",
                 player,
                 if balance > 0 { format!("devem {} itens", balance) } else if balance < 0 { format!("devemos {} itens", balance.abs()) } else { "zerado".into() },
-                credit
+                credit,
+                ledger.tier().describe()
             ));
         }
+        let mut learned: Vec<_> = self.market.iter().filter(|(_, p)| p.samples > 0).collect();
+        learned.sort_by_key(|(_, p)| std::cmp::Reverse(p.samples));
+        if !learned.is_empty() {
+            s.push_str("Preços no servidor (em ferro):");
+            for (item, price) in learned.iter().take(8) {
+                s.push_str(&format!(" {}≈{:.1}", item, price.estimate));
+            }
+            s.push('\n');
+        }
         s
     }
 }
 
+/// Map chat slang / Portuguese item names onto registry ids ("diamante" → "diamond")
+pub fn normalize_item_name(name: &str) -> String {
+    let name = name.trim().to_lowercase();
+    let name = name.trim_start_matches("minecraft:");
+    let canonical = match name {
+        "diamante" | "diamantes" | "dima" | "diamonds" => "diamond",
+        "ferro" | "iron" => "iron_ingot",
+        "ouro" | "gold" => "gold_ingot",
+        "esmeralda" | "esmeraldas" | "emeralds" => "emerald",
+        "netherite" => "netherite_ingot",
+        "comida" | "pao" | "pão" => "bread",
+        "carvao" | "carvão" => "coal",
+        "pedra" | "cobble" => "cobblestone",
+        "madeira" | "tronco" => "oak_log",
+        "maça dourada" | "gapple" => "enchanted_golden_apple",
+        "totem" => "totem_of_undying",
+        other => other,
+    };
+    canonical.to_string()
+}
+
 #[derive(Debug, Clone)]
 pub enum TradeDecision {
     Accept(String),    // Give with a comment
@@ -261,3 +998,56 @@ pub enum TradeDecision {
     Negotiate(String), // Counter-offer
     Cautious(String),  // Unsure, proceed carefully
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_help_is_a_favor() {
+        let mut economy = Economy::new();
+        economy.record_help_received("Steve", "iron_ingot", 5);
+        economy.get_ledger("Alex").debts_we_owe.push(Debt {
+            item: "diamond".into(),
+            quantity: 2,
+            created_at: Utc::now(),
+            reason: "devolução de escrow".into(),
+            paid: false,
+            interest: 0,
+            due_by: None,
+        });
+        assert_eq!(economy.unpaid_favors(), vec![("Steve".to_string(), "iron_ingot".to_string(), 5)]);
+        economy.record_repaid("Steve", "iron_ingot", 5);
+        assert!(economy.unpaid_favors().is_empty());
+    }
+
+    fn shop(item: &str, price: f32, pos: [i32; 3]) -> ChestShop {
+        ChestShop {
+            owner: "Shopkeeper".into(),
+            item: item.into(),
+            quantity: 1,
+            buy_price: Some(price),
+            sell_price: None,
+            pos,
+            seen_at: Utc::now(),
+            last_purchase: None,
+        }
+    }
+
+    #[test]
+    fn test_shop_judged_by_the_others() {
+        let mut economy = Economy::new();
+        economy.item_values.insert("bread".into(), 1);
+        economy.item_values.insert("diamond".into(), 10);
+        // Alone, a shop sets its own rate — no way to call it expensive
+        economy.record_shop(shop("diamond", 500.0, [0, 64, 0]));
+        assert!(economy.money_rate(Some([0, 64, 0])).is_none());
+        assert!(economy.best_shop_for("diamond").is_some());
+        // Bread at 10 money says 1 iron ≈ 10 money, so a 500 money diamond is a rip-off
+        economy.record_shop(shop("bread", 10.0, [5, 64, 0]));
+        assert_eq!(economy.money_rate(Some([0, 64, 0])), Some(0.1));
+        assert!(economy.best_shop_for("diamond").is_none());
+        economy.record_shop(shop("diamond", 50.0, [9, 64, 0]));
+        assert_eq!(economy.best_shop_for("diamond").map(|s| s.pos), Some([9, 64, 0]));
+    }
+}
@@ -0,0 +1,208 @@
+use azalea::prelude::*;
+use azalea::block::BlockStates;
+use azalea::container::ContainerClientExt;
+use azalea::inventory::operations::PickupClick;
+use azalea::pathfinder::goals::RadiusGoal;
+use azalea::registry::builtin::BlockKind;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use crate::cognitive::memory::Memory;
+use crate::systems::inventory::{self, Inventory};
+use crate::systems::inventory_manager::{categorize_item, ItemCategory};
+use crate::systems::storage;
+use tracing::{info, warn};
+
+// ============================================================
+// ENDER CHEST — The stash death can't touch
+// "perdi tudo" → "perdi o que tava no bolso, o diamante tá no ender"
+// ============================================================
+
+/// Walk this far at most to reach a known ender chest
+const ENDER_REACH: f64 = 48.0;
+/// Crafting table must be this close to bother crafting
+const TABLE_REACH: f64 = 16.0;
+/// Goals that tend to end in a death screen
+const RISKY_GOALS: &[&str] = &["Minerar", "Diamante", "caverna", "Explorar", "Nether"];
+/// Crafting grid slots around the middle one
+const RING: [usize; 8] = [1, 2, 3, 4, 6, 7, 8, 9];
+const CENTER: usize = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct EnderStash {
+    pub spots: Vec<[i32; 3]>,             // Ender chests we've seen placed
+    pub manifest: HashMap<String, u32>,   // What's inside (it's the same inventory everywhere)
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+fn dist(a: [i32; 3], b: [i32; 3]) -> f64 {
+    (((a[0] - b[0]).pow(2) + (a[1] - b[1]).pow(2) + (a[2] - b[2]).pow(2)) as f64).sqrt()
+}
+
+impl EnderStash {
+    /// True when it's a new one
+    pub fn remember_spot(&mut self, pos: [i32; 3]) -> bool {
+        if self.spots.contains(&pos) {
+            return false;
+        }
+        self.spots.push(pos);
+        true
+    }
+
+    pub fn forget_spot(&mut self, pos: [i32; 3]) {
+        self.spots.retain(|p| *p != pos);
+    }
+
+    pub fn nearest_spot(&self, near: [i32; 3], reach: f64) -> Option<[i32; 3]> {
+        self.spots.iter()
+            .filter(|p| dist(**p, near) <= reach)
+            .min_by(|a, b| dist(**a, near).total_cmp(&dist(**b, near)))
+            .copied()
+    }
+
+    pub fn record(&mut self, contents: HashMap<String, u32>) {
+        self.manifest = contents;
+        self.updated_at = Some(Utc::now());
+    }
+
+    pub fn context_summary(&self) -> String {
+        if self.manifest.is_empty() {
+            return String::new();
+        }
+        let mut items: Vec<(&String, &u32)> = self.manifest.iter().collect();
+        items.sort_by_key(|(_, n)| std::cmp::Reverse(**n));
+        let items = items.iter().take(6).map(|(i, n)| format!("{} x{}", i, n)).collect::<Vec<_>>().join(", ");
+        format!("Ender chest (não perco se morrer): {}", items)
+    }
+}
+
+/// Valuables in the backpack/hotbar
+pub fn valuables(inventory: &Inventory) -> Vec<String> {
+    let mut items: Vec<String> = inventory.counts()
+        .into_keys()
+        .filter(|item| categorize_item(item) == ItemCategory::Valuable && item != "ender_chest")
+        .collect();
+    items.sort();
+    items
+}
+
+/// Further than `far_from_home`, deep down, or about to do something that gets people killed
+pub fn is_risky(pos: [i32; 3], home: Option<[i32; 3]>, goal: Option<&str>, far_from_home: f64) -> bool {
+    let far = home.is_some_and(|h| dist(pos, h) > far_from_home);
+    let deep = pos[1] < 0;
+    let risky_goal = goal.is_some_and(|g| RISKY_GOALS.iter().any(|r| g.contains(r)));
+    far || deep || risky_goal
+}
+
+/// 8 obsidian in one stack + an eye of ender
+pub fn can_craft(inventory: &Inventory) -> bool {
+    inventory.count_of("eye_of_ender") > 0 && obsidian_slot(inventory).is_some()
+}
+
+fn obsidian_slot(inventory: &Inventory) -> Option<usize> {
+    inventory::INVENTORY_SLOTS.into_iter().find(|i| {
+        inventory.stack_at(*i).is_some_and(|s| inventory::item_id(s) == "obsidian" && s.count() >= 8)
+    })
+}
+
+fn here(bot: &Client) -> [i32; 3] {
+    let pos = bot.position();
+    [pos.x.floor() as i32, pos.y.floor() as i32, pos.z.floor() as i32]
+}
+
+/// Find a crafting table and make an ender chest on it
+async fn craft(bot: &Client) -> bool {
+    let table_states = BlockStates::from(&[BlockKind::CraftingTable][..]);
+    let Some(table) = bot.world().read().find_block(bot.position(), &table_states) else {
+        return false;
+    };
+    if table.center().distance_to(bot.position()) > TABLE_REACH {
+        return false;
+    }
+    let arrived = tokio::time::timeout(Duration::from_secs(20), bot.goto(RadiusGoal::new(table.center(), 3.0))).await;
+    if arrived.is_err() {
+        bot.stop_pathfinding();
+        return false;
+    }
+    let Some(container) = bot.open_container_at(table).await else {
+        return false;
+    };
+    bot.wait_ticks(4).await;
+    let Some(menu) = container.menu() else {
+        return false;
+    };
+    // Crafting table menu: 0 result, 1-9 grid, then the player inventory
+    let player = menu.player_slots_range();
+    let find = |item: &str, min: i32| player.clone().find(|i| {
+        menu.slot(*i).is_some_and(|s| s.is_present() && inventory::item_id(s) == item && s.count() >= min)
+    });
+    let (Some(obsidian), Some(eye)) = (find("obsidian", 8), find("eye_of_ender", 1)) else {
+        container.close();
+        return false;
+    };
+
+    // Pick up the stack, drop one in each slot, put the rest back
+    container.click(PickupClick::Left { slot: Some(obsidian as u16) });
+    for slot in RING {
+        container.click(PickupClick::Right { slot: Some(slot as u16) });
+    }
+    container.click(PickupClick::Left { slot: Some(obsidian as u16) });
+    container.click(PickupClick::Left { slot: Some(eye as u16) });
+    container.click(PickupClick::Right { slot: Some(CENTER as u16) });
+    container.click(PickupClick::Left { slot: Some(eye as u16) });
+    bot.wait_ticks(4).await;
+    container.shift_click(0usize);
+    bot.wait_ticks(4).await;
+    container.close();
+
+    let crafted = Inventory::read(bot).count_of("ender_chest") > 0;
+    if crafted {
+        info!("[ENDER] 🛠️ Craftei um ender chest");
+    }
+    crafted
+}
+
+/// Put the ender chest we're carrying down next to us
+async fn place(bot: &Client) -> Option<[i32; 3]> {
+    let slot = Inventory::read(bot).slot_of("ender_chest")?;
+    let spot = storage::place_beside(bot, slot).await?;
+    info!("[ENDER] 📦 Ender chest colocado em {:?}", spot);
+    Some(spot)
+}
+
+/// Get the valuables into an ender chest: a known one, or one we carry/craft and place. Returns items moved
+pub async fn protect(bot: &Client, memory: &Arc<Mutex<Memory>>) -> u32 {
+    let items = valuables(&Inventory::read(bot));
+    if items.is_empty() {
+        return 0;
+    }
+    let known = memory.lock().unwrap().inventory.ender.nearest_spot(here(bot), ENDER_REACH);
+    let spot = match known {
+        Some(spot) => Some(spot),
+        None => {
+            if Inventory::read(bot).count_of("ender_chest") == 0 && !craft(bot).await {
+                None
+            } else {
+                place(bot).await
+            }
+        }
+    };
+    let Some(spot) = spot else {
+        warn!("[ENDER] ❌ Sem ender chest por perto e não consegui fazer um");
+        return 0;
+    };
+
+    let Some((moved, contents)) = storage::deposit(bot, spot, &items).await else {
+        memory.lock().unwrap().inventory.ender.forget_spot(spot);
+        return 0;
+    };
+    let total: u32 = moved.values().sum();
+    let mut memory = memory.lock().unwrap();
+    memory.inventory.ender.remember_spot(spot);
+    memory.inventory.ender.record(contents.items);
+    memory.save();
+    info!("[ENDER] 🔐 {} itens valiosos no ender chest", total);
+    total
+}
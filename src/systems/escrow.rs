@@ -0,0 +1,172 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use tracing::info;
+
+// ============================================================
+// ESCROW — "joga o teu primeiro, eu confiro e solto o meu"
+// Both sides drop at a marked spot; we only release after the
+// payment is verified through our own pickups.
+// ============================================================
+
+/// How long they have to drop their side once terms are agreed
+const DEPOSIT_TIMEOUT_SECS: i64 = 180;
+/// How long throwing our side may take before we give up and hand theirs back
+const RELEASE_TIMEOUT_SECS: i64 = 300;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum EscrowPhase {
+    AwaitingDeposit, // Told them where to drop, waiting for their items
+    Releasing,       // Payment verified, throwing our side
+    Completed,
+    Refunding,       // Timed out with a partial deposit — giving it back
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EscrowTrade {
+    pub player: String,
+    pub spot: [i32; 3],
+    pub we_give: (String, u32),
+    pub they_give: (String, u32),
+    pub received: u32, // How much of their side we've picked up so far
+    #[serde(default)]
+    pub delivered: u32, // How much of our side actually went out
+    pub phase: EscrowPhase,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl EscrowTrade {
+    pub fn new(player: &str, spot: [i32; 3], we_give: (String, u32), they_give: (String, u32)) -> Self {
+        Self {
+            player: player.to_string(),
+            spot,
+            we_give,
+            they_give,
+            received: 0,
+            delivered: 0,
+            phase: EscrowPhase::AwaitingDeposit,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    /// The chat line telling them what to drop and where
+    pub fn instructions(&self) -> String {
+        format!(
+            "fechou. joga {} {} no chão aqui em {} {} {}, eu confiro e solto teus {} {}",
+            self.they_give.1, self.they_give.0.replace('_', " "),
+            self.spot[0], self.spot[1], self.spot[2],
+            self.we_give.1, self.we_give.0.replace('_', " ")
+        )
+    }
+
+    pub fn is_open(&self) -> bool {
+        matches!(self.phase, EscrowPhase::AwaitingDeposit | EscrowPhase::Releasing | EscrowPhase::Refunding)
+    }
+
+    /// We picked up something they threw. Returns true once their side is fully paid
+    pub fn on_deposit(&mut self, item: &str, quantity: u32) -> bool {
+        if self.phase != EscrowPhase::AwaitingDeposit || item != self.they_give.0 {
+            return false;
+        }
+        self.received += quantity;
+        self.updated_at = Utc::now();
+        if self.received >= self.they_give.1 {
+            self.phase = EscrowPhase::Releasing;
+            info!("[ESCROW] ✅ {} pagou {} x{}, liberando meu lado", self.player, item, self.received);
+            return true;
+        }
+        info!("[ESCROW] 📥 {} depositou {}/{} {}", self.player, self.received, self.they_give.1, item);
+        false
+    }
+
+    /// Deadline passed → refund whatever arrived, or just fail. Waiting on their deposit, or
+    /// stuck releasing ours (a restart mid-throw, a release that never got its turn)
+    pub fn check_timeout(&mut self) -> bool {
+        let age = |since: DateTime<Utc>| Utc::now().signed_duration_since(since).num_seconds();
+        let expired = match self.phase {
+            EscrowPhase::AwaitingDeposit => age(self.created_at) > DEPOSIT_TIMEOUT_SECS,
+            EscrowPhase::Releasing => age(self.updated_at) > RELEASE_TIMEOUT_SECS,
+            _ => false,
+        };
+        if expired {
+            self.phase = if self.received > 0 { EscrowPhase::Refunding } else { EscrowPhase::Failed };
+            self.updated_at = Utc::now();
+            info!("[ESCROW] ⌛ Troca com {} expirou ({:?})", self.player, self.phase);
+        }
+        expired
+    }
+
+    /// How much of theirs still goes back: what they paid for the part of ours that never went out
+    pub fn refund_due(&self) -> u32 {
+        if self.we_give.1 == 0 {
+            return self.received;
+        }
+        // Rounded in their favor — kept is rounded down
+        let kept = u64::from(self.received) * u64::from(self.delivered.min(self.we_give.1)) / u64::from(self.we_give.1);
+        self.received - kept as u32
+    }
+
+    pub fn finish(&mut self, phase: EscrowPhase) {
+        self.phase = phase;
+        self.updated_at = Utc::now();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade() -> EscrowTrade {
+        EscrowTrade::new("Steve", [0, 64, 0], ("diamond".into(), 4), ("iron_ingot".into(), 32))
+    }
+
+    #[test]
+    fn test_paid_in_full_releases() {
+        let mut trade = trade();
+        assert!(!trade.on_deposit("gold_ingot", 32));
+        assert!(!trade.on_deposit("iron_ingot", 16));
+        assert_eq!(trade.phase, EscrowPhase::AwaitingDeposit);
+        assert!(trade.on_deposit("iron_ingot", 16));
+        assert_eq!(trade.phase, EscrowPhase::Releasing);
+        assert!(!trade.check_timeout());
+    }
+
+    #[test]
+    fn test_deposit_deadline() {
+        let mut trade = trade();
+        trade.on_deposit("iron_ingot", 10);
+        trade.created_at -= chrono::Duration::seconds(DEPOSIT_TIMEOUT_SECS + 1);
+        assert!(trade.check_timeout());
+        assert_eq!(trade.phase, EscrowPhase::Refunding);
+        assert_eq!(trade.refund_due(), 10);
+
+        let mut empty = self::trade();
+        empty.created_at -= chrono::Duration::seconds(DEPOSIT_TIMEOUT_SECS + 1);
+        assert!(empty.check_timeout());
+        assert_eq!(empty.phase, EscrowPhase::Failed);
+    }
+
+    #[test]
+    fn test_stuck_release_gets_refunded() {
+        let mut trade = trade();
+        trade.on_deposit("iron_ingot", 32);
+        trade.updated_at -= chrono::Duration::seconds(RELEASE_TIMEOUT_SECS + 1);
+        assert!(trade.check_timeout());
+        assert_eq!(trade.phase, EscrowPhase::Refunding);
+        assert_eq!(trade.refund_due(), 32);
+    }
+
+    #[test]
+    fn test_partial_delivery_refunds_the_rest() {
+        let mut trade = trade();
+        trade.on_deposit("iron_ingot", 32);
+        trade.delivered = 3; // 3 of 4 diamonds went out: they paid 8 iron for the missing one
+        assert_eq!(trade.refund_due(), 8);
+        trade.delivered = 4;
+        assert_eq!(trade.refund_due(), 0);
+        trade.delivered = 0;
+        assert_eq!(trade.refund_due(), 32);
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use azalea::BlockPos;
+use tracing::info;
 
 // ============================================================
 // FARMER — Automated farming knowledge
@@ -95,7 +96,7 @@ impl Default for Farmer {
 impl Farmer {
     /// Register a new farm
     pub fn register_farm(&mut self, origin: [i32; 3], crop: CropType, size: [i32; 2]) {
-        println!("[FARMER] 🌾 Registered {:?} farm at {:?} ({}x{})", crop, origin, size[0], size[1]);
+        info!("[FARMER] 🌾 Registered {:?} farm at {:?} ({}x{})", crop, origin, size[0], size[1]);
         self.farms.push(FarmPlot {
             origin,
             crop,
@@ -0,0 +1,296 @@
+use azalea::prelude::*;
+use azalea::BlockPos;
+use azalea::registry::builtin::BlockKind;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+use crate::systems::spider_sense::distance;
+use crate::systems::world_scanner::NearbyEntity;
+use tracing::info;
+
+// ============================================================
+// GRIEF WATCH — Snapshot the base, diff it, find who did it
+// "sumiu 12 bloco da minha casa e o Fulano tava la" → confront
+// ============================================================
+
+/// Horizontal half-size of the watched box around home
+const WATCH_RADIUS: i32 = 20;
+/// Vertical reach below/above home
+const WATCH_DOWN: i32 = 6;
+const WATCH_UP: i32 = 14;
+/// Snapshot this often (a full box read is ~35k blocks)
+const SNAPSHOT_EVERY: Duration = Duration::from_secs(30);
+/// Too far from home and the chunks aren't loaded anyway
+const MAX_HOME_DISTANCE: f64 = 96.0;
+/// Who was near a block when it vanished — anyone within this is a suspect
+const SUSPECT_RANGE: f64 = 6.0;
+/// Foreign breaks before we call it grief (one block is someone grabbing a torch)
+const GRIEF_THRESHOLD: usize = 4;
+/// Proximity log window
+const LOG_FOR: Duration = Duration::from_secs(120);
+/// Marks our own position in the proximity log
+const US: &str = "";
+/// Blocks a player has to have been nearest to before we name them as the culprit
+const BLAME_MIN: usize = 3;
+/// Mobs that take blocks out by themselves: blasts, endermen picking them up
+const BLOCK_TAKERS: &[&str] = &["creeper", "tnt", "tnt_minecart", "enderman", "wither", "ghast", "fireball"];
+/// A block this close to one of them when it went missing is on them, not on a player
+const TAKER_RANGE: f64 = 8.0;
+
+/// Blocks that disappear on their own (decay, harvest, melt, burn out)
+const VOLATILE: &[&str] = &[
+    "leaves", "wheat", "carrots", "potatoes", "beetroots", "melon", "pumpkin", "sugar_cane", "short_grass",
+    "tall_grass", "fern", "snow", "fire", "water", "lava", "vine", "kelp", "seagrass", "flower", "sapling",
+    "mushroom", "bamboo", "cactus", "sweet_berry", "cocoa", "scaffolding", "ice",
+];
+
+fn is_volatile(block: &str) -> bool {
+    VOLATILE.iter().any(|v| block.contains(v))
+}
+
+#[derive(Debug, Clone)]
+pub struct GriefReport {
+    pub broken: Vec<([i32; 3], String)>,
+    pub tnt: Vec<[i32; 3]>,
+    pub culprit: Option<String>, // Most blocks blamed on them
+    pub suspects: Vec<String>,   // Everyone near the damage
+}
+
+impl GriefReport {
+    pub fn describe(&self) -> String {
+        let mut s = format!("{} blocos quebrados na base", self.broken.len());
+        if !self.tnt.is_empty() {
+            s.push_str(&format!(", {} TNT colocada", self.tnt.len()));
+        }
+        match &self.culprit {
+            Some(c) => s.push_str(&format!(" (provável: {})", c)),
+            None if !self.suspects.is_empty() => s.push_str(&format!(" (suspeitos: {})", self.suspects.join(", "))),
+            None => s.push_str(" (ninguém visto)"),
+        }
+        s
+    }
+}
+
+#[derive(Debug)]
+pub struct GriefWatch {
+    snapshot: HashMap<[i32; 3], String>, // Non-air blocks around home, last pass
+    home: Option<[i32; 3]>,
+    visits: VecDeque<(Instant, String, [f64; 3])>, // Who was where near home (US = ourselves)
+    takers: VecDeque<(Instant, [f64; 3])>, // Creepers, lit TNT, endermen near home
+    last_snapshot: Instant,
+}
+
+impl Default for GriefWatch {
+    fn default() -> Self {
+        Self {
+            snapshot: HashMap::new(),
+            home: None,
+            visits: VecDeque::new(),
+            takers: VecDeque::new(),
+            last_snapshot: Instant::now(),
+        }
+    }
+}
+
+impl GriefWatch {
+    /// Each grief_watch run (once a second by default): log who's hanging around the base (us included)
+    pub fn observe(&mut self, home: [i32; 3], bot_pos: [f64; 3], entities: &[NearbyEntity]) {
+        let center = [home[0] as f64, home[1] as f64, home[2] as f64];
+        let range = WATCH_RADIUS as f64 + SUSPECT_RANGE;
+        let now = Instant::now();
+        // Once a second per player is plenty
+        let fresh = |visits: &VecDeque<(Instant, String, [f64; 3])>, name: &str| {
+            !visits.iter().rev().take(32).any(|(t, n, _)| n == name && now.duration_since(*t) < Duration::from_secs(1))
+        };
+        if distance(bot_pos, center) <= range && fresh(&self.visits, US) {
+            self.visits.push_back((now, US.into(), bot_pos));
+        }
+        for e in entities.iter().filter(|e| e.is_player() && distance(e.position, center) <= range) {
+            if fresh(&self.visits, &e.name) {
+                self.visits.push_back((now, e.name.clone(), e.position));
+            }
+        }
+        for e in entities.iter().filter(|e| BLOCK_TAKERS.contains(&e.kind.as_str()) && distance(e.position, center) <= range) {
+            self.takers.push_back((now, e.position));
+        }
+        while self.visits.front().is_some_and(|(t, _, _)| t.elapsed() > LOG_FOR) {
+            self.visits.pop_front();
+        }
+        while self.takers.front().is_some_and(|(t, _)| t.elapsed() > LOG_FOR) {
+            self.takers.pop_front();
+        }
+    }
+
+    /// Every few seconds: snapshot the base and diff. Some(report) when it got griefed
+    pub fn check(&mut self, bot: &Client, home: [i32; 3]) -> Option<GriefReport> {
+        if self.last_snapshot.elapsed() < SNAPSHOT_EVERY {
+            return None;
+        }
+        self.last_snapshot = Instant::now();
+
+        // New home = new baseline
+        if self.home != Some(home) {
+            self.home = Some(home);
+            self.snapshot.clear();
+        }
+        let pos = bot.position();
+        let center = [home[0] as f64, home[1] as f64, home[2] as f64];
+        if distance([pos.x, pos.y, pos.z], center) > MAX_HOME_DISTANCE {
+            return None;
+        }
+
+        let current = snapshot(bot, home);
+        if self.snapshot.is_empty() {
+            info!("[GRIEF] 📸 Base fotografada: {} blocos", current.len());
+            self.snapshot = current;
+            return None;
+        }
+
+        let window = self.last_snapshot.checked_sub(SNAPSHOT_EVERY * 2).unwrap_or(self.last_snapshot);
+        let mut broken = vec![];
+        let mut blame: HashMap<String, usize> = HashMap::new();
+        let mut suspects: Vec<String> = vec![];
+        for (p, old) in &self.snapshot {
+            if current.contains_key(p) || is_volatile(old) || old == "tnt" {
+                continue;
+            }
+            // Unloaded since the last pass isn't broken
+            if !is_loaded_air(bot, *p) {
+                continue;
+            }
+            match self.nearest_visitor(*p, window) {
+                Some(who) if who == US => continue, // We broke it ourselves
+                Some(_) if self.taker_near(*p, window) => {} // A blast or an enderman, whoever stood by
+                Some(who) => {
+                    *blame.entry(who.clone()).or_insert(0) += 1;
+                    if !suspects.contains(&who) {
+                        suspects.push(who);
+                    }
+                }
+                None => {}
+            }
+            broken.push((*p, old.clone()));
+        }
+        let tnt: Vec<[i32; 3]> = current.iter()
+            .filter(|(p, b)| *b == "tnt" && self.snapshot.get(*p).is_none_or(|old| old != "tnt"))
+            .map(|(p, _)| *p)
+            .collect();
+        for p in &tnt {
+            if let Some(who) = self.nearest_visitor(*p, window)
+                && who != US
+            {
+                *blame.entry(who.clone()).or_insert(0) += GRIEF_THRESHOLD;
+                if !suspects.contains(&who) {
+                    suspects.push(who);
+                }
+            }
+        }
+
+        // Keep what we can't see right now from the old snapshot
+        let mut merged = current;
+        for (p, b) in &self.snapshot {
+            if !merged.contains_key(p) && !is_loaded(bot, *p) {
+                merged.insert(*p, b.clone());
+            }
+        }
+        self.snapshot = merged;
+
+        if broken.len() < GRIEF_THRESHOLD && tnt.is_empty() {
+            return None;
+        }
+        Some(GriefReport { broken, tnt, culprit: culprit(blame), suspects })
+    }
+
+    /// A creeper, lit TNT or enderman was around this block during the window
+    fn taker_near(&self, p: [i32; 3], since: Instant) -> bool {
+        let block = [p[0] as f64 + 0.5, p[1] as f64 + 0.5, p[2] as f64 + 0.5];
+        self.takers.iter().any(|(t, at)| *t >= since && distance(*at, block) <= TAKER_RANGE)
+    }
+
+    /// Who stood closest to a block during the window (US = ourselves)
+    fn nearest_visitor(&self, p: [i32; 3], since: Instant) -> Option<String> {
+        let block = [p[0] as f64 + 0.5, p[1] as f64 + 0.5, p[2] as f64 + 0.5];
+        self.visits.iter()
+            .filter(|(t, _, _)| *t >= since)
+            .map(|(_, who, at)| (who, distance(*at, block)))
+            .filter(|(_, d)| *d <= SUSPECT_RANGE)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(who, _)| who.clone())
+    }
+}
+
+/// Whoever was nearest to the most breaks — only if it's a pattern. One player walking past
+/// a single hole isn't enough to call them out
+fn culprit(blame: HashMap<String, usize>) -> Option<String> {
+    blame.into_iter()
+        .filter(|(_, n)| *n >= BLAME_MIN)
+        .max_by_key(|(_, n)| *n)
+        .map(|(who, _)| who)
+}
+
+/// Every non-air block in the watched box that's loaded right now
+fn snapshot(bot: &Client, home: [i32; 3]) -> HashMap<[i32; 3], String> {
+    let world = bot.world();
+    let world = world.read();
+    let mut blocks = HashMap::new();
+    for x in home[0] - WATCH_RADIUS..=home[0] + WATCH_RADIUS {
+        for z in home[2] - WATCH_RADIUS..=home[2] + WATCH_RADIUS {
+            for y in home[1] - WATCH_DOWN..=home[1] + WATCH_UP {
+                let Some(state) = world.get_block_state(BlockPos::new(x, y, z)) else { continue };
+                if state.is_air() {
+                    continue;
+                }
+                let name = BlockKind::from(state).to_string();
+                blocks.insert([x, y, z], name.trim_start_matches("minecraft:").to_string());
+            }
+        }
+    }
+    blocks
+}
+
+fn is_loaded_air(bot: &Client, p: [i32; 3]) -> bool {
+    bot.world().read().get_block_state(BlockPos::new(p[0], p[1], p[2])).is_some_and(|b| b.is_air())
+}
+
+fn is_loaded(bot: &Client, p: [i32; 3]) -> bool {
+    bot.world().read().get_block_state(BlockPos::new(p[0], p[1], p[2])).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity(name: &str, kind: &str, position: [f64; 3]) -> NearbyEntity {
+        NearbyEntity {
+            name: name.into(),
+            kind: kind.into(),
+            position,
+            distance: 0.0,
+            entity_id: 0,
+            hostile: kind != "player",
+            closing_speed: 0.0,
+            fusing: false,
+            sneaking: false,
+            look: [0.0, 0.0, 1.0],
+        }
+    }
+
+    #[test]
+    fn test_one_break_doesnt_name_anyone() {
+        let blame = HashMap::from([("Steve".to_string(), 1), ("Alex".to_string(), BLAME_MIN)]);
+        assert_eq!(culprit(blame), Some("Alex".to_string()));
+        assert_eq!(culprit(HashMap::from([("Steve".to_string(), BLAME_MIN - 1)])), None);
+    }
+
+    #[test]
+    fn test_creeper_takes_the_blame() {
+        let mut watch = GriefWatch::default();
+        let since = Instant::now();
+        watch.observe([0, 64, 0], [100.0, 64.0, 100.0], &[
+            entity("Steve", "player", [2.0, 64.0, 2.0]),
+            entity("creeper", "creeper", [3.0, 64.0, 3.0]),
+        ]);
+        assert_eq!(watch.nearest_visitor([2, 64, 2], since), Some("Steve".to_string()));
+        assert!(watch.taker_near([2, 64, 2], since));
+        assert!(!watch.taker_near([18, 64, -18], since));
+    }
+}
@@ -0,0 +1,218 @@
+use azalea::prelude::*;
+use azalea::block::{fluid_state::FluidKind, BlockState, BlockTrait};
+use azalea::entity::{mining::get_mine_progress, ActiveEffects, Attributes, FluidOnEyes, Physics};
+use azalea::inventory::{components, operations::SwapClick, ItemStack};
+use azalea::registry::builtin::{EntityKind, ItemKind};
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+// ============================================================
+// INVENTORY — What we're actually carrying, read off the real slots
+// "tenho picareta?" / "tem comida?" / "cabe mais coisa?" — no more guessing
+// ============================================================
+
+/// Player inventory menu: main inventory + hotbar
+pub const INVENTORY_SLOTS: RangeInclusive<usize> = 9..=44;
+/// Hotbar inside the player menu (hotbar index = slot - 36)
+const HOTBAR_SLOTS: RangeInclusive<usize> = 36..=44;
+/// Offhand slot in the player menu
+const OFFHAND_SLOT: usize = 45;
+/// Edible, but you pay for it
+const BAD_FOOD: &[&str] = &["rotten_flesh", "spider_eye", "poisonous_potato", "pufferfish", "suspicious_stew", "chorus_fruit"];
+/// Only eaten when things are bad
+const SAVED_FOOD: &[&str] = &["golden_apple", "enchanted_golden_apple"];
+
+/// Registry id without the namespace ("minecraft:diamond" → "diamond")
+pub fn item_id(stack: &ItemStack) -> String {
+    stack.kind().to_string().trim_start_matches("minecraft:").to_string()
+}
+
+/// Tool/weapon material, worst to best. 0 = not a tiered item
+pub fn material_tier(item: &str) -> u8 {
+    match item.split('_').next().unwrap_or("") {
+        "wooden" | "golden" => 1,
+        "stone" | "copper" => 2,
+        "iron" => 3,
+        "diamond" => 4,
+        "netherite" => 5,
+        _ => 0,
+    }
+}
+
+/// (uses left, max uses) for anything that wears out
+pub fn durability(stack: &ItemStack) -> Option<(u32, u32)> {
+    let max = stack.get_component::<components::MaxDamage>()?.amount;
+    let damage = stack.get_component::<components::Damage>().map_or(0, |d| d.amount);
+    Some(((max - damage).max(0) as u32, max.max(0) as u32))
+}
+
+/// A slot worth reaching for
+#[derive(Debug, Clone)]
+pub struct SlotPick {
+    pub slot: usize, // Player menu index
+    pub item: String,
+}
+
+/// Snapshot of the player inventory. Cheap to take, read it fresh each time
+#[derive(Debug, Clone)]
+pub struct Inventory {
+    slots: Vec<ItemStack>,
+    selected: u8,
+}
+
+impl Inventory {
+    /// Always the player's own inventory, even with a chest open
+    pub fn read(bot: &Client) -> Self {
+        bot.query_self::<&azalea::entity::inventory::Inventory, _>(|inv| Self {
+            slots: inv.inventory_menu.slots().to_vec(),
+            selected: inv.selected_hotbar_slot,
+        })
+    }
+
+    /// (menu index, stack) for everything we're carrying
+    fn stacks(&self) -> impl Iterator<Item = (usize, &ItemStack)> {
+        self.slots.iter()
+            .enumerate()
+            .filter(|(i, s)| INVENTORY_SLOTS.contains(i) && s.is_present())
+    }
+
+    pub fn stack_at(&self, slot: usize) -> Option<&ItemStack> {
+        self.slots.get(slot).filter(|s| s.is_present())
+    }
+
+    /// Total of one item across every slot
+    pub fn count_of(&self, item: &str) -> u32 {
+        self.stacks()
+            .filter(|(_, s)| item_id(s) == item)
+            .map(|(_, s)| s.count() as u32)
+            .sum()
+    }
+
+    /// Every item we carry, summed by id
+    pub fn counts(&self) -> HashMap<String, u32> {
+        let mut counts = HashMap::new();
+        for (_, stack) in self.stacks() {
+            *counts.entry(item_id(stack)).or_insert(0) += stack.count() as u32;
+        }
+        counts
+    }
+
+    /// Same as `counts`, minus the hotbar — what's just riding along
+    pub fn backpack_counts(&self) -> HashMap<String, u32> {
+        let mut counts = HashMap::new();
+        for (_, stack) in self.stacks().filter(|(i, _)| !HOTBAR_SLOTS.contains(i)) {
+            *counts.entry(item_id(stack)).or_insert(0) += stack.count() as u32;
+        }
+        counts
+    }
+
+    /// First slot holding this item
+    pub fn slot_of(&self, item: &str) -> Option<usize> {
+        self.stacks().find(|(_, s)| item_id(s) == item).map(|(i, _)| i)
+    }
+
+    pub fn free_slots(&self) -> usize {
+        INVENTORY_SLOTS.filter(|i| self.slots.get(*i).is_none_or(|s| !s.is_present())).count()
+    }
+
+    /// Item in the main hand right now
+    pub fn held(&self) -> Option<String> {
+        self.stack_at(*HOTBAR_SLOTS.start() + self.selected as usize).map(item_id)
+    }
+
+    /// Most filling food we'd eat. `desperate` unlocks golden apples and the gross stuff
+    pub fn best_food(&self, desperate: bool) -> Option<SlotPick> {
+        self.stacks()
+            .filter_map(|(i, s)| {
+                let food = s.get_component::<components::Food>()?;
+                let item = item_id(s);
+                let gross = BAD_FOOD.contains(&item.as_str()) || SAVED_FOOD.contains(&item.as_str());
+                (desperate || !gross).then(|| (food.nutrition, SlotPick { slot: i, item }))
+            })
+            .max_by_key(|(nutrition, _)| *nutrition)
+            .map(|(_, pick)| pick)
+    }
+
+    /// Anything we'd eat without being desperate
+    pub fn has_food(&self) -> bool {
+        self.best_food(false).is_some()
+    }
+
+    /// Fastest thing to mine this block with. None = bare hands are as good as anything
+    pub fn best_tool_for(&self, block: BlockState) -> Option<SlotPick> {
+        let block = Box::<dyn BlockTrait>::from(block);
+        let mut physics = Physics::default();
+        physics.set_on_ground(true);
+        let speed = |kind: ItemKind| get_mine_progress(
+            block.as_ref(),
+            kind,
+            &FluidOnEyes::new(FluidKind::Empty),
+            &physics,
+            &Attributes::new(EntityKind::Player),
+            &ActiveEffects::default(),
+        );
+        let bare = speed(ItemKind::Air);
+        self.stacks()
+            .map(|(i, s)| (speed(s.kind()), i, s))
+            .filter(|(v, _, _)| *v > bare)
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, slot, s)| SlotPick { slot, item: item_id(s) })
+    }
+
+    /// Best pickaxe material we carry (see `material_tier`)
+    pub fn pickaxe_tier(&self) -> u8 {
+        self.stacks()
+            .map(|(_, s)| item_id(s))
+            .filter(|item| item.ends_with("_pickaxe"))
+            .map(|item| material_tier(&item))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Hardest-hitting weapon: swords first, then axes
+    pub fn best_weapon(&self) -> Option<SlotPick> {
+        self.stacks()
+            .filter_map(|(i, s)| {
+                let item = item_id(s);
+                let rank = if item.ends_with("_sword") {
+                    10 + material_tier(&item)
+                } else if item.ends_with("_axe") {
+                    material_tier(&item)
+                } else {
+                    return None;
+                };
+                Some((rank, SlotPick { slot: i, item }))
+            })
+            .max_by_key(|(rank, _)| *rank)
+            .map(|(_, pick)| pick)
+    }
+
+    /// Every tool of a kind ("pickaxe", "axe", "sword"...), best material first
+    pub fn tools_of(&self, kind: &str) -> Vec<SlotPick> {
+        let suffix = format!("_{}", kind);
+        let mut tools: Vec<SlotPick> = self.stacks()
+            .map(|(slot, s)| SlotPick { slot, item: item_id(s) })
+            .filter(|pick| pick.item.ends_with(&suffix))
+            .collect();
+        tools.sort_by_key(|pick| std::cmp::Reverse(material_tier(&pick.item)));
+        tools
+    }
+
+    pub fn has_shield(&self) -> bool {
+        self.stack_at(OFFHAND_SLOT).is_some_and(|s| item_id(s) == "shield") || self.count_of("shield") > 0
+    }
+}
+
+/// Put a slot in the main hand: select it if it's on the hotbar, otherwise swap it into the current one
+pub fn equip(bot: &Client, slot: usize) -> bool {
+    if HOTBAR_SLOTS.contains(&slot) {
+        bot.set_selected_hotbar_slot((slot - HOTBAR_SLOTS.start()) as u8);
+        return true;
+    }
+    let Some(inventory) = bot.open_inventory() else {
+        return false; // A container is open
+    };
+    inventory.click(SwapClick { source_slot: slot as u16, target_slot: bot.selected_hotbar_slot() });
+    inventory.close();
+    true
+}
@@ -1,10 +1,13 @@
 use azalea::prelude::*;
+use azalea::inventory::{components, operations::{PickupClick, SwapClick}, ItemStack};
 use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::time::{Duration, Instant};
+use crate::systems::inventory::{item_id, material_tier, Inventory, INVENTORY_SLOTS};
+use tracing::info;
 
 // ============================================================
-// INVENTORY MANAGER — Hotbar OCD + Chest Organization
+// INVENTORY MANAGER — Hotbar OCD + Chest Organization + best armor on
 // ============================================================
 
 /// Ideal hotbar layout (slot 0-8)
@@ -46,6 +49,16 @@ impl Default for HotbarPreference {
     }
 }
 
+impl HotbarPreference {
+    /// Category wanted in each hotbar slot, 0-8
+    pub fn layout(&self) -> [&ItemCategory; 9] {
+        [
+            &self.slot_0, &self.slot_1, &self.slot_2, &self.slot_3, &self.slot_4,
+            &self.slot_5, &self.slot_6, &self.slot_7, &self.slot_8,
+        ]
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum ItemCategory {
     Sword,
@@ -64,6 +77,40 @@ pub enum ItemCategory {
     Other,
 }
 
+impl ItemCategory {
+    /// "minérios", "comida", "blocos"... → the category, for "!guardar comida"
+    pub fn from_word(word: &str) -> Option<Self> {
+        Some(match word.to_lowercase().trim_end_matches('s') {
+            "espada" | "sword" => ItemCategory::Sword,
+            "picareta" | "pickaxe" => ItemCategory::Pickaxe,
+            "machado" | "axe" => ItemCategory::Axe,
+            "pá" | "pa" | "shovel" => ItemCategory::Shovel,
+            "arco" | "bow" => ItemCategory::Ranged,
+            "bloco" | "block" => ItemCategory::BuildingBlock,
+            "tocha" | "torch" => ItemCategory::Torch,
+            "comida" | "food" => ItemCategory::Food,
+            "valioso" | "minério" | "minerio" | "ore" | "valuable" => ItemCategory::Valuable,
+            "lixo" | "junk" => ItemCategory::Junk,
+            "redstone" => ItemCategory::Redstone,
+            "armadura" | "armor" => ItemCategory::Armor,
+            "ferramenta" | "tool" => ItemCategory::Tool,
+            "outro" | "other" => ItemCategory::Other,
+            _ => return None,
+        })
+    }
+}
+
+/// Items that are junk as they are. categorize_item matches these loosely, is_junk exactly
+const JUNK: &[&str] = &[
+    "dirt", "gravel", "rotten_flesh", "poisonous_potato",
+    "dead_bush", "stick", "feather", "string", "bone",
+];
+
+/// Exactly one of the junk items — "bone" is, "bone_meal" and "sticky_piston" aren't
+pub fn is_junk(item: &str) -> bool {
+    JUNK.contains(&item.trim_start_matches("minecraft:"))
+}
+
 /// Categorize an item name
 pub fn categorize_item(item_name: &str) -> ItemCategory {
     let name = item_name.to_lowercase();
@@ -93,11 +140,7 @@ pub fn categorize_item(item_name: &str) -> ItemCategory {
     if valuables.iter().any(|v| name.contains(v)) { return ItemCategory::Valuable; }
 
     // Junk
-    let junk = [
-        "dirt", "gravel", "rotten_flesh", "poisonous_potato",
-        "dead_bush", "stick", "feather", "string", "bone",
-    ];
-    if junk.iter().any(|j| name.contains(j)) { return ItemCategory::Junk; }
+    if JUNK.iter().any(|j| name.contains(j)) { return ItemCategory::Junk; }
 
     // Redstone
     let redstone = [
@@ -143,7 +186,7 @@ pub fn chest_sort_order(cat: &ItemCategory) -> u8 {
     }
 }
 
-/// Generate a snarky comment about messy chests
+/// Generate a snarky comment about messy chests (storage calls this when it opens one)
 pub fn chest_comment(items: &[String]) -> Option<String> {
     use rand::Rng;
     let mut rng = rand::thread_rng();
@@ -172,10 +215,155 @@ pub fn chest_comment(items: &[String]) -> Option<String> {
     None
 }
 
+/// How often we look at the hotbar
+const SORT_EVERY: Duration = Duration::from_secs(5);
+/// Hotbar starts here in the player menu
+const HOTBAR_START: usize = 36;
+/// Complaining about the mess, at most this often
+const COMMENT_COOLDOWN: Duration = Duration::from_secs(600);
+
+/// Armor slots in the player menu, head to feet
+const ARMOR_SLOTS: [(&str, usize); 4] = [("helmet", 5), ("chestplate", 6), ("leggings", 7), ("boots", 8)];
+/// Offhand slot in the player menu, and its SwapClick target
+const OFFHAND_SLOT: usize = 45;
+const OFFHAND_SWAP: u8 = 40;
+
+/// Armor material, worst to best (gold's a bit better than leather, chainmail sits under iron)
+fn armor_tier(item: &str) -> i32 {
+    match item.split('_').next().unwrap_or("") {
+        "leather" => 1,
+        "golden" => 2,
+        "chainmail" | "turtle" => 3,
+        "iron" => 4,
+        "diamond" => 5,
+        "netherite" => 6,
+        _ => 0,
+    }
+}
+
+/// How good a piece of armor is: material first, enchantment levels break ties (and beat one tier down)
+fn armor_score(stack: &ItemStack) -> i32 {
+    let enchanted: i32 = stack.get_component::<components::Enchantments>()
+        .map(|e| e.levels.values().sum())
+        .unwrap_or(0);
+    armor_tier(&item_id(stack)) * 10 + enchanted * 2
+}
+
+/// Next armor upgrade → (menu slot of the better piece, armor slot it goes in)
+pub fn next_armor(inventory: &Inventory) -> Option<(usize, usize)> {
+    ARMOR_SLOTS.iter().find_map(|(piece, armor_slot)| {
+        let suffix = format!("_{}", piece);
+        let worn = inventory.stack_at(*armor_slot).map(armor_score);
+        INVENTORY_SLOTS
+            .filter_map(|i| inventory.stack_at(i).map(|s| (i, s)))
+            .filter(|(_, s)| item_id(s).ends_with(&suffix))
+            .max_by_key(|(_, s)| armor_score(s))
+            .filter(|(_, s)| worn.is_none_or(|w| armor_score(s) > w))
+            .map(|(slot, _)| (slot, *armor_slot))
+    })
+}
+
+/// Shield into the offhand: when the offhand's free, or whatever's there when we're fighting
+pub fn shield_slot(inventory: &Inventory, fighting: bool) -> Option<usize> {
+    let offhand = inventory.stack_at(OFFHAND_SLOT).map(item_id);
+    if offhand.as_deref() == Some("shield") || (offhand.is_some() && !fighting) {
+        return None;
+    }
+    INVENTORY_SLOTS.clone().find(|i| inventory.stack_at(*i).is_some_and(|s| item_id(s) == "shield"))
+}
+
+/// Put on every armor upgrade we carry and get the shield in hand. Returns pieces changed
+fn dress(bot: &Client, fighting: bool) -> u32 {
+    let mut changed = 0;
+    if let Some(slot) = shield_slot(&Inventory::read(bot), fighting)
+        && let Some(menu) = bot.open_inventory()
+    {
+        menu.click(SwapClick { source_slot: slot as u16, target_slot: OFFHAND_SWAP });
+        menu.close();
+        info!("[INVENTORY] 🛡️ Escudo na mão esquerda");
+        changed += 1;
+    }
+    if fighting {
+        return changed; // Armor shuffling waits for a quiet moment
+    }
+    while let Some((from, to)) = next_armor(&Inventory::read(bot)) && changed < 5 {
+        let Some(menu) = bot.open_inventory() else { break };
+        let (item, worn) = {
+            let inventory = Inventory::read(bot);
+            (inventory.stack_at(from).map(item_id).unwrap_or_default(), inventory.stack_at(to).is_some())
+        };
+        menu.click(PickupClick::Left { slot: Some(from as u16) });
+        menu.click(PickupClick::Left { slot: Some(to as u16) });
+        if worn {
+            menu.click(PickupClick::Left { slot: Some(from as u16) }); // Old piece back where the new one was
+        }
+        menu.close();
+        info!("[INVENTORY] 🥋 Vesti {}", item);
+        changed += 1;
+    }
+    changed
+}
+
+/// Which of two same-category stacks deserves the hotbar slot
+fn rank(stack: &ItemStack, category: &ItemCategory) -> i32 {
+    match category {
+        ItemCategory::Sword | ItemCategory::Pickaxe | ItemCategory::Axe | ItemCategory::Shovel => {
+            material_tier(&item_id(stack)) as i32
+        }
+        ItemCategory::Food => stack.get_component::<components::Food>().map(|f| f.nutrition).unwrap_or(0),
+        ItemCategory::Ranged => item_id(stack).contains("crossbow") as i32,
+        _ => stack.count(),
+    }
+}
+
+/// Next swap that brings the hotbar closer to the preference → (menu slot, hotbar index)
+pub fn next_swap(inventory: &Inventory, pref: &HotbarPreference) -> Option<(usize, u8)> {
+    let layout = pref.layout();
+    let fits = |slot: usize, stack: &ItemStack| {
+        (HOTBAR_START..HOTBAR_START + 9).contains(&slot)
+            && categorize_item(&item_id(stack)) == *layout[slot - HOTBAR_START]
+    };
+    for (h, category) in layout.iter().enumerate() {
+        let target = HOTBAR_START + h;
+        let current = inventory.stack_at(target).filter(|s| fits(target, s)).map(|s| rank(s, category));
+        // Best stack of this kind that isn't already sitting in its own slot
+        let best = INVENTORY_SLOTS
+            .filter(|i| *i != target)
+            .filter_map(|i| inventory.stack_at(i).map(|s| (i, s)))
+            .filter(|(i, s)| categorize_item(&item_id(s)) == **category && !fits(*i, s))
+            .max_by_key(|(_, s)| rank(s, category));
+        if let Some((slot, stack)) = best
+            && current.is_none_or(|r| rank(stack, category) > r)
+        {
+            return Some((slot, h as u8));
+        }
+    }
+    None
+}
+
+impl State {
+    /// We just looked inside a chest — if it's a mess, queue a roast (cooldown permitting)
+    pub fn notice_chest(&self, items: &[String]) {
+        let mut last_comment = self.last_comment.lock().unwrap();
+        if last_comment.elapsed() < COMMENT_COOLDOWN {
+            return;
+        }
+        if let Some(comment) = chest_comment(items) {
+            *last_comment = Instant::now();
+            *self.pending_comment.lock().unwrap() = Some(comment);
+        }
+    }
+}
+
 #[derive(Clone, Component)]
 pub struct State {
     pub hotbar_pref: Arc<Mutex<HotbarPreference>>,
     pub last_sort: Arc<Mutex<Instant>>,
+    /// Set by the combat feed — never shuffle slots mid-fight
+    pub in_combat: Arc<Mutex<bool>>,
+    pub last_comment: Arc<Mutex<Instant>>,
+    /// Snarky line waiting to be said in chat (a messy chest we opened)
+    pub pending_comment: Arc<Mutex<Option<String>>>,
 }
 
 impl Default for State {
@@ -183,17 +371,62 @@ impl Default for State {
         Self {
             hotbar_pref: Arc::new(Mutex::new(HotbarPreference::default())),
             last_sort: Arc::new(Mutex::new(Instant::now())),
+            in_combat: Arc::new(Mutex::new(false)),
+            last_comment: Arc::new(Mutex::new(Instant::now())),
+            pending_comment: Arc::new(Mutex::new(None)),
         }
     }
 }
 
-pub async fn handle(_bot: Client, event: Event, _state: State) -> anyhow::Result<()> {
+impl crate::plugins::registry::BotSystem for State {
+    fn name(&self) -> &'static str {
+        "inventory_manager"
+    }
+
+    /// Hotbar sorting
+    fn on_event(&self, bot: Client, event: Event) -> crate::plugins::registry::SystemFuture {
+        Box::pin(handle(bot, event, self.clone()))
+    }
+
+    fn context_summary(&self) -> Option<String> {
+        (*self.in_combat.lock().unwrap()).then(|| "em combate, hotbar parada".to_string())
+    }
+}
+
+pub async fn handle(bot: Client, event: Event, state: State) -> anyhow::Result<()> {
     if let Event::Tick = event {
-        // In a real implementation:
-        // 1. Check if hotbar matches preferences
-        // 2. If not, swap items to correct slots
-        // 3. If a chest is open, sort it by category
-        // Azalea's inventory API would be used here.
+        {
+            let mut last = state.last_sort.lock().unwrap();
+            if last.elapsed() < SORT_EVERY {
+                return Ok(());
+            }
+            *last = Instant::now();
+        }
+        // Best armor on, shield in hand — the shield even mid-fight, that's when it counts
+        let fighting = *state.in_combat.lock().unwrap();
+        dress(&bot, fighting);
+        if fighting {
+            return Ok(());
+        }
+
+        let pref = state.hotbar_pref.lock().unwrap().clone();
+        let Some(mut swap) = next_swap(&Inventory::read(&bot), &pref) else {
+            return Ok(());
+        };
+        let Some(inventory) = bot.open_inventory() else {
+            return Ok(()); // Chest/furnace open, not now
+        };
+        let mut swaps = 0;
+        loop {
+            inventory.click(SwapClick { source_slot: swap.0 as u16, target_slot: swap.1 });
+            swaps += 1;
+            match next_swap(&Inventory::read(&bot), &pref) {
+                Some(next) if swaps < 9 => swap = next,
+                _ => break,
+            }
+        }
+        inventory.close();
+        info!("[INVENTORY] 🧹 Hotbar arrumada ({} trocas)", swaps);
     }
     Ok(())
 }
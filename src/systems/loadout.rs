@@ -0,0 +1,261 @@
+use azalea::prelude::*;
+use azalea::inventory::operations::{PickupClick, SwapClick};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use crate::cognitive::goal_planner::Goal;
+use crate::cognitive::memory::Memory;
+use crate::systems::inventory::{item_id, material_tier, Inventory};
+use crate::systems::inventory_manager::{categorize_item, HotbarPreference, ItemCategory};
+use crate::systems::storage;
+use tracing::info;
+
+// ============================================================
+// LOADOUT — Dress for the job
+// "vou minerar" → picareta reserva, tocha, bloco pra tampar lava
+// ============================================================
+
+/// Armor pieces in wearing order, with their player menu slot
+const ARMOR: [(&str, usize); 4] = [("helmet", 5), ("chestplate", 6), ("leggings", 7), ("boots", 8)];
+/// SwapClick target for the offhand
+const OFFHAND_SWAP: u8 = 40;
+/// Offhand slot in the player menu
+const OFFHAND_SLOT: usize = 45;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kit {
+    Mining,
+    Combat,
+    Building,
+}
+
+/// What a kit wants on us
+#[derive(Debug, Clone)]
+pub struct Loadout {
+    pub hotbar: HotbarPreference,
+    pub armor: bool,                           // Put on the best armor we own
+    pub offhand: Option<&'static str>,
+    pub bring: &'static [(ItemCategory, u32)], // Carry at least this much of each
+}
+
+impl Kit {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Kit::Mining => "kit de mineração",
+            Kit::Combat => "kit de combate",
+            Kit::Building => "kit de construção",
+        }
+    }
+
+    pub fn loadout(&self) -> Loadout {
+        use ItemCategory::*;
+        match self {
+            Kit::Mining => Loadout {
+                hotbar: HotbarPreference {
+                    slot_0: Pickaxe, slot_1: Pickaxe, slot_2: Shovel, slot_3: Torch, slot_4: BuildingBlock,
+                    slot_5: BuildingBlock, slot_6: Sword, slot_7: Food, slot_8: Food,
+                },
+                armor: true,
+                offhand: Some("torch"),
+                bring: &[(Pickaxe, 2), (Torch, 32), (BuildingBlock, 64), (Food, 8)],
+            },
+            Kit::Combat => Loadout {
+                hotbar: HotbarPreference {
+                    slot_0: Sword, slot_1: Axe, slot_2: Ranged, slot_3: Food, slot_4: Food,
+                    slot_5: BuildingBlock, slot_6: Torch, slot_7: Pickaxe, slot_8: Food,
+                },
+                armor: true,
+                offhand: Some("shield"),
+                bring: &[(Sword, 1), (Ranged, 1), (Food, 16)],
+            },
+            Kit::Building => Loadout {
+                hotbar: HotbarPreference {
+                    slot_0: BuildingBlock, slot_1: BuildingBlock, slot_2: BuildingBlock, slot_3: BuildingBlock,
+                    slot_4: Axe, slot_5: Pickaxe, slot_6: Shovel, slot_7: Torch, slot_8: Food,
+                },
+                armor: false,
+                offhand: None,
+                bring: &[(BuildingBlock, 192), (Axe, 1), (Food, 8)],
+            },
+        }
+    }
+}
+
+/// Which kit a goal calls for, by what it says it's about. Whole words only, and only ones
+/// that name the job: "base" or "caverna" show up in goals that have nothing to do with it
+pub fn kit_for(goal: &Goal) -> Option<Kit> {
+    let text = format!("{} {}", goal.name, goal.description).to_lowercase();
+    let words: Vec<&str> = text.split(|c: char| !c.is_alphanumeric()).collect();
+    let has = |wanted: &[&str]| wanted.iter().any(|w| words.contains(w));
+    if has(&["minerar", "minerando", "mine", "diamante", "ferro"]) {
+        Some(Kit::Mining)
+    } else if has(&["matar", "lutar", "defender", "caçar", "raid", "combate"]) {
+        Some(Kit::Combat)
+    } else if has(&["construir", "construindo", "obra", "build"]) {
+        Some(Kit::Building)
+    } else {
+        None
+    }
+}
+
+/// The kit on us right now and what to undo when it's over
+#[derive(Debug, Default)]
+pub struct LoadoutState {
+    pub active: Option<Kit>,
+    pub previous_hotbar: Option<HotbarPreference>,
+    pub previous_offhand: Option<String>,
+    pub borrowed: HashMap<String, u32>, // Pulled out of storage for this kit
+}
+
+/// Stored item of a category worth bringing: best material, then biggest pile
+fn pick_stored(stored: &HashMap<String, u32>, category: &ItemCategory, suffix: &str) -> Option<String> {
+    stored.iter()
+        .filter(|(item, n)| **n > 0 && categorize_item(item) == *category && item.ends_with(suffix))
+        .max_by_key(|(item, n)| (material_tier(item), **n))
+        .map(|(item, _)| item.clone())
+}
+
+/// Pick a stack up and drop it in another slot
+fn move_stack(bot: &Client, from: usize, to: usize) -> bool {
+    let Some(inventory) = bot.open_inventory() else {
+        return false; // A container is open
+    };
+    inventory.click(PickupClick::Left { slot: Some(from as u16) });
+    inventory.click(PickupClick::Left { slot: Some(to as u16) });
+    inventory.close();
+    true
+}
+
+pub fn offhand_item(inventory: &Inventory) -> Option<String> {
+    inventory.stack_at(OFFHAND_SLOT).map(item_id)
+}
+
+/// Swap whatever we carry of this item into the offhand
+fn hold_offhand(bot: &Client, item: &str) -> bool {
+    let inventory = Inventory::read(bot);
+    if offhand_item(&inventory).as_deref() == Some(item) {
+        return true;
+    }
+    let Some(slot) = inventory.slot_of(item) else {
+        return false;
+    };
+    let Some(menu) = bot.open_inventory() else {
+        return false;
+    };
+    menu.click(SwapClick { source_slot: slot as u16, target_slot: OFFHAND_SWAP });
+    menu.close();
+    true
+}
+
+/// Fetch from storage and note it down as borrowed
+async fn borrow(bot: &Client, memory: &Arc<Mutex<Memory>>, borrowed: &mut HashMap<String, u32>, item: String, quantity: u32) {
+    let got = storage::fetch(bot, memory, &item, quantity).await;
+    if got > 0 {
+        *borrowed.entry(item).or_insert(0) += got;
+    }
+}
+
+/// Pull what the kit needs out of storage and put it on. Returns what we borrowed
+pub async fn equip(bot: &Client, memory: &Arc<Mutex<Memory>>, kit: Kit) -> HashMap<String, u32> {
+    let loadout = kit.loadout();
+    let mut borrowed = HashMap::new();
+
+    for (category, wanted) in loadout.bring {
+        let have: u32 = Inventory::read(bot).counts().iter()
+            .filter(|(item, _)| categorize_item(item) == *category)
+            .map(|(_, n)| n)
+            .sum();
+        if have >= *wanted {
+            continue;
+        }
+        let stored = memory.lock().unwrap().inventory.stored.clone();
+        if let Some(item) = pick_stored(&stored, category, "") {
+            borrow(bot, memory, &mut borrowed, item, wanted - have).await;
+        }
+    }
+
+    if loadout.armor {
+        for (piece, slot) in ARMOR {
+            let suffix = format!("_{}", piece);
+            let carrying = Inventory::read(bot);
+            if carrying.stack_at(slot).is_some() {
+                continue;
+            }
+            let carried = carrying.counts().into_keys()
+                .filter(|item| item.ends_with(&suffix))
+                .max_by_key(|item| material_tier(item));
+            let item = match carried {
+                Some(item) => item,
+                None => {
+                    let stored = memory.lock().unwrap().inventory.stored.clone();
+                    let Some(item) = pick_stored(&stored, &ItemCategory::Armor, &suffix) else { continue };
+                    borrow(bot, memory, &mut borrowed, item.clone(), 1).await;
+                    item
+                }
+            };
+            if let Some(from) = Inventory::read(bot).slot_of(&item) {
+                move_stack(bot, from, slot);
+                bot.wait_ticks(2).await;
+            }
+        }
+    }
+
+    if let Some(item) = loadout.offhand {
+        if Inventory::read(bot).count_of(item) == 0 {
+            borrow(bot, memory, &mut borrowed, item.to_string(), 1).await;
+        }
+        hold_offhand(bot, item);
+    }
+    info!("[LOADOUT] 🎒 {} montado ({} itens do baú)", kit.name(), borrowed.values().sum::<u32>());
+    borrowed
+}
+
+/// Kit's over: old offhand back, borrowed stuff back in the chests — as much as we borrowed,
+/// what we had before stays with us. Armor we put on stays on
+pub async fn put_back(bot: &Client, memory: &Arc<Mutex<Memory>>, previous_offhand: Option<String>, borrowed: HashMap<String, u32>) {
+    if let Some(item) = previous_offhand {
+        hold_offhand(bot, &item);
+    }
+    let here = bot.position();
+    let here = [here.x.floor() as i32, here.y.floor() as i32, here.z.floor() as i32];
+    let mut trips: Vec<([i32; 3], Vec<_>)> = vec![];
+    {
+        let memory = memory.lock().unwrap();
+        for (item, quantity) in borrowed {
+            let Some(pos) = memory.inventory.storage.deposit_target(&item, here) else { continue };
+            match trips.iter_mut().find(|(p, _)| *p == pos) {
+                Some((_, list)) => list.push((item, quantity)),
+                None => trips.push((pos, vec![(item, quantity)])),
+            }
+        }
+    }
+    for (pos, items) in trips {
+        if let Some((_, contents)) = storage::deposit_at_most(bot, pos, &items).await {
+            storage::update_index(memory, pos, contents);
+        }
+    }
+    info!("[LOADOUT] 🎒 Devolvi o kit");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cognitive::goal_planner::GoalPriority;
+
+    fn kit(name: &str, description: &str) -> Option<Kit> {
+        kit_for(&Goal::new(name, description, GoalPriority::Medium))
+    }
+
+    #[test]
+    fn test_kit_for_the_job_words() {
+        assert_eq!(kit("Minerar Ferro", "Descer pra caverna ou strip mine e pegar ferro"), Some(Kit::Mining));
+        assert_eq!(kit("Construir pro time", "Tocar a obra"), Some(Kit::Building));
+        assert_eq!(kit("Defender a base", ""), Some(Kit::Combat));
+    }
+
+    #[test]
+    fn test_kit_for_ignores_places_and_partial_words() {
+        assert_eq!(kit("Carregar o loot do time", "Mochila dele encheu, eu levo pra base"), None);
+        assert_eq!(kit("Sair da caverna", "Vida baixa no subsolo, voltando pela rota conhecida"), None);
+        assert_eq!(kit("Voltar pra casa", "Falar do minecraft com o pessoal"), None);
+    }
+}
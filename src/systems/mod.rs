@@ -3,13 +3,39 @@ pub mod builder;
 pub mod farmer;
 pub mod combat;
 pub mod social;
+pub mod presence;
+pub mod conversation;
+pub mod truce;
+pub mod tone;
+pub mod chat_channel;
+pub mod chat_format;
+pub mod auth;
+pub mod queue;
+pub mod teleport;
+pub mod server_events;
+pub mod player_model;
 pub mod world_scanner;
+pub mod grief_watch;
+pub mod poi;
+pub mod cave_map;
+pub mod signs;
+pub mod storage;
+pub mod ender_chest;
+pub mod death_recovery;
+pub mod tool_crafter;
+pub mod loadout;
+pub mod shulker;
 pub mod natural_look;
 pub mod typos;
 pub mod judge;
 pub mod inventory_manager;
+pub mod inventory;
 pub mod reaction_delay;
 pub mod visual_cortex;
 pub mod economy;
+pub mod negotiation;
+pub mod escrow;
 pub mod spider_sense;
 pub mod motor;
+pub mod trader;
+pub mod chest_shop;
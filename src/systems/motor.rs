@@ -1,21 +1,25 @@
 use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 use rand::Rng;
-use azalea::prelude::*;
 use azalea::BlockPos;
-use azalea::pathfinder::goals::BlockPosGoal;
-use azalea::pathfinder::PathfinderClientExt;
+use tokio::sync::{mpsc, watch};
+use crate::client::BotClient;
+use tracing::{debug, Instrument};
 
 // ============================================================
 // MOTOR SYSTEM — Translates intentions into actions
 // "O cérebro manda, o corpo executa"
+// An actor: its own task owns the queue, everyone else sends messages and reads a MotorView
 // ============================================================
 
 #[derive(Debug, Clone)]
 pub enum MotorCommand {
     /// Chat a message in-game
     Chat(String),
+    /// Private message to one player (/msg)
+    Whisper { player: String, message: String },
     /// Look at a specific yaw/pitch
     LookAt { yaw: f32, pitch: f32 },
     /// Random head movement (fidget)
@@ -34,51 +38,156 @@ pub enum MotorCommand {
     GotoBlock { x: i32, y: i32, z: i32 },
     /// Wander to a random nearby point (autonomous exploration)
     WanderRandom,
+    /// Walk a chain of waypoints, one pathfinder hop at a time (cave corridors)
+    FollowRoute(Vec<[i32; 3]>),
     /// Log something to console (for debugging)
     Log(String),
 }
 
 #[derive(Debug, Clone)]
-struct ActiveAction {
-    command: MotorCommand,
-    ticks_remaining: u32,
-    started_at: Instant,
+pub struct ActiveAction {
+    pub command: MotorCommand,
+    pub ticks_remaining: u32,
+    pub started_at: Instant,
 }
 
+/// What the rest of the bot can tell the motor
+#[derive(Debug)]
+enum MotorMsg {
+    Command(MotorCommand),
+    Urgent(MotorCommand),
+    StopWalking,
+    /// Unexplored spot the next WanderRandom should head for
+    Frontier(Option<[i32; 3]>),
+    Tick(MotorTick),
+}
+
+/// What the tick loop saw this tick — all the motor needs from the world
+#[derive(Debug, Clone, Copy)]
+pub struct MotorTick {
+    pub position: [f64; 3],
+    pub nearby_players: bool,
+}
+
+/// The motor as of its last message. Read it, don't wait on it
+#[derive(Debug, Clone, Copy)]
+pub struct MotorView {
+    /// Commands waiting, counting the ones still on their way to the actor
+    pub queued: usize,
+    pub is_walking: bool,
+    pub last_movement_time: Instant,
+    pub commands_executed: u64,
+    /// Ticks handled so far
+    pub ticks: u64,
+    /// Last tick the actor got through — the watchdog checks it's keeping up
+    pub handled_at: Instant,
+}
+
+type Mailbox = (mpsc::UnboundedReceiver<MotorMsg>, watch::Sender<MotorView>);
+
+/// Handle to the motor actor. Cloning shares it
 #[derive(Clone)]
 pub struct MotorState {
-    pub inner: Arc<Mutex<MotorInner>>,
+    tx: mpsc::UnboundedSender<MotorMsg>,
+    view: watch::Receiver<MotorView>,
+    /// Commands sent but not yet taken off the channel
+    in_flight: Arc<AtomicUsize>,
+    /// Handed to the actor by start(); None once it's running
+    mailbox: Arc<Mutex<Option<Mailbox>>>,
 }
 
 impl Default for MotorState {
     fn default() -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let (view_tx, view) = watch::channel(MotorInner::default().view(0));
         Self {
-            inner: Arc::new(Mutex::new(MotorInner::default())),
+            tx,
+            view,
+            in_flight: Arc::new(AtomicUsize::new(0)),
+            mailbox: Arc::new(Mutex::new(Some((rx, view_tx)))),
         }
     }
 }
 
-pub struct MotorInner {
+impl MotorState {
+    /// Spawn the actor for this connection. Anything sent before now is waiting for it
+    pub fn start(&self, bot: &impl BotClient) {
+        let Some((rx, view)) = self.mailbox.lock().unwrap().take() else { return };
+        let in_flight = self.in_flight.clone();
+        tokio::spawn(run(bot.clone(), rx, view, in_flight).instrument(tracing::info_span!("motor")));
+    }
+
+    /// Started and hasn't died
+    pub fn is_running(&self) -> bool {
+        self.mailbox.lock().unwrap().is_none() && !self.tx.is_closed()
+    }
+
+    fn send(&self, msg: MotorMsg) {
+        if matches!(msg, MotorMsg::Command(_) | MotorMsg::Urgent(_)) {
+            self.in_flight.fetch_add(1, Ordering::SeqCst);
+        }
+        // Closed only once the connection is gone — nothing left to move
+        let _ = self.tx.send(msg);
+    }
+
+    /// Queue a command for execution
+    pub fn queue(&self, cmd: MotorCommand) {
+        self.send(MotorMsg::Command(cmd));
+    }
+
+    /// Queue a command at the FRONT (high priority)
+    pub fn queue_urgent(&self, cmd: MotorCommand) {
+        self.send(MotorMsg::Urgent(cmd));
+    }
+
+    /// Drop every queued or running movement — talking and looking stay
+    pub fn stop_walking(&self) {
+        self.send(MotorMsg::StopWalking);
+    }
+
+    /// Wander, towards `frontier` if there is one
+    pub fn explore(&self, frontier: Option<[i32; 3]>) {
+        self.send(MotorMsg::Frontier(frontier));
+        self.queue(MotorCommand::WanderRandom);
+    }
+
+    /// Called once per game tick with what the tick loop saw
+    pub fn tick(&self, tick: MotorTick) {
+        self.send(MotorMsg::Tick(tick));
+    }
+
+    pub fn view(&self) -> MotorView {
+        let mut view = *self.view.borrow();
+        view.queued += self.in_flight.load(Ordering::SeqCst);
+        view
+    }
+}
+
+struct MotorInner {
     /// Queue of commands to execute
-    pub command_queue: VecDeque<MotorCommand>,
+    command_queue: VecDeque<MotorCommand>,
     /// Currently active timed action (sprint, sneak, walk)
-    pub active_action: Option<ActiveAction>,
+    active_action: Option<ActiveAction>,
     /// Tick counter for fidgets
-    pub tick_counter: u64,
+    tick_counter: u64,
     /// Whether there are nearby players (for social fidgets)
-    pub nearby_players: bool,
+    nearby_players: bool,
     /// Total commands executed (stats)
-    pub commands_executed: u64,
+    commands_executed: u64,
     /// Is the bot currently sprinting?
-    pub is_sprinting: bool,
+    is_sprinting: bool,
     /// Is the bot currently sneaking?
-    pub is_sneaking: bool,
+    is_sneaking: bool,
     /// Is the bot currently walking to a destination?
-    pub is_walking: bool,
+    is_walking: bool,
     /// Last time the bot moved (for idle detection)
-    pub last_movement_time: Instant,
+    last_movement_time: Instant,
     /// Current bot position (updated from world state)
-    pub bot_position: [f64; 3],
+    bot_position: [f64; 3],
+    /// Unexplored spot WanderRandom should head for instead of a random one
+    frontier: Option<[i32; 3]>,
+    /// Waypoints left on a FollowRoute
+    route: VecDeque<[i32; 3]>,
 }
 
 impl Default for MotorInner {
@@ -94,40 +203,88 @@ impl Default for MotorInner {
             is_walking: false,
             last_movement_time: Instant::now(),
             bot_position: [0.0, 64.0, 0.0],
+            frontier: None,
+            route: VecDeque::new(),
         }
     }
 }
 
 impl MotorInner {
-    /// Queue a command for execution
-    pub fn queue(&mut self, cmd: MotorCommand) {
+    fn queue(&mut self, cmd: MotorCommand) {
         self.command_queue.push_back(cmd);
     }
 
-    /// Queue a command at the FRONT (high priority)
-    pub fn queue_urgent(&mut self, cmd: MotorCommand) {
-        self.command_queue.push_front(cmd);
+    fn queue_len(&self) -> usize {
+        self.command_queue.len()
     }
 
-    /// Clear all queued commands (emergency reset)
-    pub fn clear_queue(&mut self) {
-        self.command_queue.clear();
+    /// Teleported, or plans changed — old paths and walk targets are meaningless
+    fn stop_walking(&mut self) {
+        self.command_queue.retain(|c| !matches!(c,
+            MotorCommand::GotoBlock { .. } | MotorCommand::WanderRandom | MotorCommand::FollowRoute(_)
+            | MotorCommand::WalkForward { .. } | MotorCommand::FleeDirection { .. }));
+        self.route.clear();
         self.active_action = None;
+        self.is_walking = false;
+        self.last_movement_time = Instant::now();
     }
 
-    /// How many commands are waiting?
-    pub fn queue_len(&self) -> usize {
-        self.command_queue.len()
+    fn view(&self, queued: usize) -> MotorView {
+        MotorView {
+            queued,
+            is_walking: self.is_walking,
+            last_movement_time: self.last_movement_time,
+            commands_executed: self.commands_executed,
+            ticks: self.tick_counter,
+            handled_at: Instant::now(),
+        }
     }
 }
 
-/// Main tick handler — call this every Event::Tick
-pub async fn handle(bot: Client, _event: Event, state: MotorState) -> anyhow::Result<()> {
-    let mut motor = state.inner.lock().unwrap();
+/// The actor: one message at a time, then publish what things look like now
+async fn run(bot: impl BotClient, mut rx: mpsc::UnboundedReceiver<MotorMsg>, view: watch::Sender<MotorView>, in_flight: Arc<AtomicUsize>) {
+    let mut motor = MotorInner::default();
+    let mut handled_at = Instant::now();
+    while let Some(msg) = rx.recv().await {
+        let command = matches!(msg, MotorMsg::Command(_) | MotorMsg::Urgent(_));
+        match msg {
+            MotorMsg::Command(cmd) => motor.queue(cmd),
+            MotorMsg::Urgent(cmd) => motor.command_queue.push_front(cmd),
+            MotorMsg::StopWalking => motor.stop_walking(),
+            MotorMsg::Frontier(frontier) => motor.frontier = frontier,
+            MotorMsg::Tick(tick) => {
+                motor.bot_position = tick.position;
+                motor.nearby_players = tick.nearby_players;
+                step(&bot, &mut motor);
+                handled_at = Instant::now();
+            }
+        }
+        // Publish before letting go of the in-flight count, so readers never see it missing from both
+        view.send_replace(MotorView { handled_at, ..motor.view(motor.queue_len()) });
+        if command {
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+    debug!("[MOTOR] 🛑 Conexão acabou, motor parado");
+}
+
+/// One game tick: fidgets, routes, timed actions, then the next queued command
+fn step(bot: &impl BotClient, motor: &mut MotorInner) {
     motor.tick_counter += 1;
 
     // === 1. HUMAN FIDGETS (random look, shift toggle) ===
-    inject_fidgets(&mut motor);
+    inject_fidgets(motor);
+
+    // === 1.5 ROUTE — Next waypoint once the pathfinder gets to the last one ===
+    if !motor.route.is_empty() && bot.goto_reached() {
+        if let Some([x, y, z]) = motor.route.pop_front() {
+            motor.last_movement_time = Instant::now();
+            bot.goto(BlockPos::new(x, y, z));
+        }
+        if motor.route.is_empty() {
+            debug!("[MOTOR] 🏁 Último ponto da rota");
+        }
+    }
 
     // === 2. PROCESS ACTIVE TIMED ACTION ===
     if let Some(ref mut action) = motor.active_action {
@@ -138,23 +295,23 @@ pub async fn handle(bot: Client, _event: Event, state: MotorState) -> anyhow::Re
                 MotorCommand::StartSprint { .. } => {
                     motor.is_sprinting = false;
                     // bot.sprint(SprintDirection::Stop); // Azalea sprint stop
-                    println!("[MOTOR] 🏃 Sprint finished");
+                    debug!("[MOTOR] 🏃 Sprint finished");
                 }
                 MotorCommand::SneakPulse { .. } => {
                     motor.is_sneaking = false;
                     // bot.set_sneaking(false);
-                    println!("[MOTOR] 🧎 Sneak pulse finished");
+                    debug!("[MOTOR] 🧎 Sneak pulse finished");
                 }
                 MotorCommand::WalkForward { .. } => {
                     // bot.walk(WalkDirection::None);
-                    println!("[MOTOR] 🚶 Walk finished");
+                    debug!("[MOTOR] 🚶 Walk finished");
                 }
                 _ => {}
             }
             motor.active_action = None;
         } else {
             // Action still running, skip processing new commands
-            return Ok(());
+            return;
         }
     }
 
@@ -164,25 +321,29 @@ pub async fn handle(bot: Client, _event: Event, state: MotorState) -> anyhow::Re
 
         match cmd {
             MotorCommand::Chat(ref msg) => {
-                println!("[MOTOR] 💬 Sending chat: {}", msg);
-                bot.chat(msg);
+                debug!("[MOTOR] 💬 Sending chat: {}", msg);
+                crate::events::say(bot, msg.as_str());
+            }
+            MotorCommand::Whisper { ref player, ref message } => {
+                debug!("[MOTOR] 🤫 Whisper to {}: {}", player, message);
+                crate::events::say(bot, format!("/msg {} {}", player, message));
             }
             MotorCommand::LookAt { yaw, pitch } => {
                 // Clamp pitch to valid range
                 let pitch = pitch.clamp(-90.0, 90.0);
                 // bot.set_rotation(yaw, pitch); // Azalea rotation
-                println!("[MOTOR] 👀 Looking at yaw:{:.1} pitch:{:.1}", yaw, pitch);
+                debug!("[MOTOR] 👀 Looking at yaw:{:.1} pitch:{:.1}", yaw, pitch);
             }
             MotorCommand::RandomLook => {
                 let mut rng = rand::thread_rng();
                 let _yaw_delta: f32 = rng.gen_range(-60.0..60.0);
                 let _pitch_delta: f32 = rng.gen_range(-20.0..20.0);
                 // bot.set_rotation(current_yaw + yaw_delta, current_pitch + pitch_delta);
-                println!("[MOTOR] 🔄 Random look: yaw±{:.0}° pitch±{:.0}°", _yaw_delta, _pitch_delta);
+                debug!("[MOTOR] 🔄 Random look: yaw±{:.0}° pitch±{:.0}°", _yaw_delta, _pitch_delta);
             }
             MotorCommand::Jump => {
                 bot.jump();
-                println!("[MOTOR] ⬆️ Jump");
+                debug!("[MOTOR] ⬆️ Jump");
             }
             MotorCommand::StartSprint { duration_ticks } => {
                 motor.is_sprinting = true;
@@ -192,7 +353,7 @@ pub async fn handle(bot: Client, _event: Event, state: MotorState) -> anyhow::Re
                     ticks_remaining: duration_ticks,
                     started_at: Instant::now(),
                 });
-                println!("[MOTOR] 🏃 Sprint started ({} ticks)", duration_ticks);
+                debug!("[MOTOR] 🏃 Sprint started ({} ticks)", duration_ticks);
             }
             MotorCommand::SneakPulse { duration_ticks } => {
                 motor.is_sneaking = true;
@@ -202,7 +363,7 @@ pub async fn handle(bot: Client, _event: Event, state: MotorState) -> anyhow::Re
                     ticks_remaining: duration_ticks,
                     started_at: Instant::now(),
                 });
-                println!("[MOTOR] 🧎 Sneak pulse ({} ticks)", duration_ticks);
+                debug!("[MOTOR] 🧎 Sneak pulse ({} ticks)", duration_ticks);
             }
             MotorCommand::WalkForward { duration_ticks } => {
                 // bot.walk(WalkDirection::Forward);
@@ -211,7 +372,7 @@ pub async fn handle(bot: Client, _event: Event, state: MotorState) -> anyhow::Re
                     ticks_remaining: duration_ticks,
                     started_at: Instant::now(),
                 });
-                println!("[MOTOR] 🚶 Walk forward ({} ticks)", duration_ticks);
+                debug!("[MOTOR] 🚶 Walk forward ({} ticks)", duration_ticks);
             }
             MotorCommand::FleeDirection { yaw } => {
                 // bot.set_rotation(yaw, 0.0);
@@ -221,41 +382,49 @@ pub async fn handle(bot: Client, _event: Event, state: MotorState) -> anyhow::Re
                     ticks_remaining: 40,
                     started_at: Instant::now(),
                 });
-                println!("[MOTOR] 🏃💨 FLEE! yaw:{:.1}", yaw);
+                debug!("[MOTOR] 🏃💨 FLEE! yaw:{:.1}", yaw);
             }
             MotorCommand::GotoBlock { x, y, z } => {
-                println!("[MOTOR] 🚶 Goto ({}, {}, {})", x, y, z);
+                debug!("[MOTOR] 🚶 Goto ({}, {}, {})", x, y, z);
                 motor.is_walking = true;
                 motor.last_movement_time = Instant::now();
-                let target = BlockPosGoal(BlockPos::new(x, y, z));
-                // Drop the lock before calling start_goto (it's non-blocking)
-                drop(motor);
-                bot.start_goto(target);
-                return Ok(());
+                bot.goto(BlockPos::new(x, y, z));
             }
             MotorCommand::WanderRandom => {
                 let mut rng = rand::thread_rng();
                 let pos = motor.bot_position;
-                let dx: i32 = rng.gen_range(-25..25);
-                let dz: i32 = rng.gen_range(-25..25);
-                let target_x = pos[0] as i32 + dx;
-                let target_y = pos[1] as i32;
-                let target_z = pos[2] as i32 + dz;
-                println!("[MOTOR] 🌍 Wander to ({}, {}, {})", target_x, target_y, target_z);
+                let (target_x, target_y, target_z) = match motor.frontier.take() {
+                    // Somewhere we haven't been, at most ~40 blocks per hop
+                    Some([fx, fy, fz]) => {
+                        let (dx, dz) = (fx as f64 - pos[0], fz as f64 - pos[2]);
+                        let scale = (40.0 / (dx * dx + dz * dz).sqrt()).min(1.0);
+                        (pos[0] as i32 + (dx * scale) as i32, fy, pos[2] as i32 + (dz * scale) as i32)
+                    }
+                    None => (
+                        pos[0] as i32 + rng.gen_range(-25..25),
+                        pos[1] as i32,
+                        pos[2] as i32 + rng.gen_range(-25..25),
+                    ),
+                };
+                debug!("[MOTOR] 🌍 Wander to ({}, {}, {})", target_x, target_y, target_z);
                 motor.is_walking = true;
                 motor.last_movement_time = Instant::now();
-                let target = BlockPosGoal(BlockPos::new(target_x, target_y, target_z));
-                drop(motor);
-                bot.start_goto(target);
-                return Ok(());
+                bot.goto(BlockPos::new(target_x, target_y, target_z));
+            }
+            MotorCommand::FollowRoute(waypoints) => {
+                debug!("[MOTOR] 🗺️ Rota com {} pontos", waypoints.len());
+                motor.route = waypoints.into_iter().collect();
+                motor.is_walking = true;
+                motor.last_movement_time = Instant::now();
+                if let Some([x, y, z]) = motor.route.pop_front() {
+                    bot.goto(BlockPos::new(x, y, z));
+                }
             }
             MotorCommand::Log(ref msg) => {
-                println!("[MOTOR] 📋 {}", msg);
+                debug!("[MOTOR] 📋 {}", msg);
             }
         }
     }
-
-    Ok(())
 }
 
 /// Inject natural human fidgets into the command queue
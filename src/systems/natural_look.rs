@@ -1,7 +1,9 @@
 use azalea::prelude::*;
 use rand::Rng;
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::time::{Instant, Duration};
+use tracing::debug;
 
 // ============================================================
 // NATURAL LOOK BEHAVIOR — No more staring at the horizon
@@ -17,8 +19,31 @@ pub struct NaturalLookState {
     pub idle_since: Instant,
     pub base_yaw: f32,
     pub base_pitch: f32,
+    pub gazes: HashMap<String, Gaze>, // Players currently looking straight at us
 }
 
+/// Someone looking at us without looking away
+#[derive(Debug, Clone)]
+pub struct Gaze {
+    pub since: Instant,
+    pub looked_back: bool,
+    pub commented: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StareReaction {
+    None,
+    LookBack, // Stared for a few seconds — glance back
+    Comment,  // Still staring — "q foi mn"
+}
+
+/// Only count stares from this close
+const STARE_RANGE: f64 = 12.0;
+/// cos of the max angle between their view and the line to our head (~12°)
+const STARE_CONE_COS: f64 = 0.978;
+const LOOK_BACK_AFTER: Duration = Duration::from_secs(3);
+const COMMENT_AFTER: Duration = Duration::from_secs(8);
+
 impl Default for NaturalLookState {
     fn default() -> Self {
         Self {
@@ -29,6 +54,7 @@ impl Default for NaturalLookState {
             idle_since: Instant::now(),
             base_yaw: 0.0,
             base_pitch: 0.0,
+            gazes: HashMap::new(),
         }
     }
 }
@@ -63,13 +89,16 @@ pub fn compute_look_offset(state: &mut NaturalLookState) -> (f32, f32) {
 
     // === IDLE HEAD BOBBING (Perlin-like noise) ===
     // Slow, organic head movements when idle
-    let yaw_noise = smooth_noise(tick, 0.02, 25.0) as f32;  // ±25 degrees, slow
-    let pitch_noise = smooth_noise(tick, 0.015, 12.0) as f32; // ±12 degrees, slower
+    let config = crate::config::Config::load();
+    let sway = config.humanize_sway_deg;
+    let yaw_noise = smooth_noise(tick, 0.02, sway) as f32;  // ±25 degrees by default, slow
+    let pitch_noise = smooth_noise(tick, 0.015, sway / 2.0) as f32; // Half that, slower
 
     // === OCCASIONAL GLANCE ===
-    // Every 3-7 seconds, do a quick glance in a random direction
+    // Every few seconds ([humanize] glance_secs, 3-7), do a quick glance in a random direction
     let _seconds_idle = state.idle_since.elapsed().as_secs_f32();
-    let glance_yaw = if state.last_fidget.elapsed() > Duration::from_secs(rng.r#gen::<u64>() % 5 + 3) {
+    let [glance_min, glance_max] = config.humanize_glance_secs;
+    let glance_yaw = if state.last_fidget.elapsed() > Duration::from_secs(rng.gen_range(glance_min..=glance_max.max(glance_min))) {
         state.last_fidget = Instant::now();
         // Quick glance: 45-120 degrees in random direction (player really looking around)
         let glance: f32 = rng.r#gen::<f32>() * 75.0 + 45.0;
@@ -144,6 +173,81 @@ pub fn on_player_chat(state: &mut NaturalLookState, player: &str) {
     state.last_speaker_time = Instant::now();
 }
 
+/// Track whether `player` keeps looking at our head, and how we should react
+pub fn observe_gaze(
+    state: &mut NaturalLookState,
+    player: &str,
+    their_eye: [f64; 3],
+    their_look: [f64; 3],
+    our_eye: [f64; 3],
+) -> StareReaction {
+    let to_us = [our_eye[0] - their_eye[0], our_eye[1] - their_eye[1], our_eye[2] - their_eye[2]];
+    let distance = (to_us[0] * to_us[0] + to_us[1] * to_us[1] + to_us[2] * to_us[2]).sqrt();
+    let staring = distance > 0.5 && distance <= STARE_RANGE && {
+        let cos = (to_us[0] * their_look[0] + to_us[1] * their_look[1] + to_us[2] * their_look[2]) / distance;
+        cos >= STARE_CONE_COS
+    };
+    if !staring {
+        state.gazes.remove(player);
+        return StareReaction::None;
+    }
+
+    let gaze = state.gazes.entry(player.to_string()).or_insert_with(|| Gaze {
+        since: Instant::now(),
+        looked_back: false,
+        commented: false,
+    });
+    let held = gaze.since.elapsed();
+    if held >= COMMENT_AFTER && !gaze.commented {
+        gaze.commented = true;
+        StareReaction::Comment
+    } else if held >= LOOK_BACK_AFTER && !gaze.looked_back {
+        gaze.looked_back = true;
+        StareReaction::LookBack
+    } else {
+        StareReaction::None
+    }
+}
+
+impl NaturalLookState {
+    /// Players out of sight aren't staring anymore
+    pub fn forget_gazes_except(&mut self, visible: &[&str]) {
+        self.gazes.retain(|name, _| visible.contains(&name.as_str()));
+    }
+}
+
+/// What we say after being stared at for too long
+pub fn stare_comment(player: &str, hostile: bool) -> String {
+    let mut rng = rand::thread_rng();
+    let lines = if hostile {
+        vec![format!("ta olhando oq {}?", player), "perdeu alguma coisa?".to_string(), "vaza".to_string()]
+    } else {
+        vec!["q foi mn".to_string(), format!("oq foi {}", player), "?".to_string(), "q q foi kkk".to_string()]
+    };
+    lines[rng.gen_range(0..lines.len())].clone()
+}
+
+impl crate::plugins::registry::BotSystem for State {
+    fn name(&self) -> &'static str {
+        "natural_look"
+    }
+
+    /// Head bobbing, fidgets, staring back
+    fn on_event(&self, bot: Client, event: Event) -> crate::plugins::registry::SystemFuture {
+        Box::pin(handle(bot, event, self.clone()))
+    }
+
+    fn context_summary(&self) -> Option<String> {
+        let inner = self.inner.lock().unwrap();
+        let speaker = inner.last_speaker.as_ref().filter(|_| inner.last_speaker_time.elapsed() < Duration::from_secs(10));
+        match (speaker, inner.gazes.len()) {
+            (Some(name), _) => Some(format!("olhando pro {}", name)),
+            (None, 0) => None,
+            (None, n) => Some(format!("{} me encarando", n)),
+        }
+    }
+}
+
 pub async fn handle(bot: Client, event: Event, state: State) -> anyhow::Result<()> {
     if let Event::Tick = event {
         let mut inner = state.inner.lock().unwrap();
@@ -156,40 +260,40 @@ pub async fn handle(bot: Client, event: Event, state: State) -> anyhow::Result<(
         // In practice, you'd call:
         // bot.look(yaw_offset, pitch_offset);
         // For now, we just log occasionally
-        if inner.tick_counter % 200 == 0 {
-            println!("[LOOK] 👀 Head offset: yaw={:.1}° pitch={:.1}°", yaw_offset, pitch_offset);
+        if inner.tick_counter.is_multiple_of(200) {
+            debug!("[LOOK] 👀 Head offset: yaw={:.1}° pitch={:.1}°", yaw_offset, pitch_offset);
         }
 
         // Check for fidgets
         let fidget = maybe_fidget(&mut inner);
         match fidget {
             FidgetAction::Jump => {
-                println!("[FIDGET] 🦘 Random hop");
+                debug!("[FIDGET] 🦘 Random hop");
                 bot.jump();
             }
             FidgetAction::SwapHands => {
-                println!("[FIDGET] 🔄 Swapping hands");
+                debug!("[FIDGET] 🔄 Swapping hands");
                 // bot.swap_hands(); // When API available
             }
             FidgetAction::PunchAir => {
-                println!("[FIDGET] 👊 Punching air");
+                debug!("[FIDGET] 👊 Punching air");
                 // bot.swing(); // API not available in azalea 0.15
             }
             FidgetAction::Sneak => {
-                println!("[FIDGET] 🐾 Quick crouch");
+                debug!("[FIDGET] 🐾 Quick crouch");
                 // bot.set_sneaking(true); // API not available in azalea 0.15
                 // tokio::spawn to unsneak would go here
             }
             FidgetAction::LookAtGround => {
-                println!("[FIDGET] ⬇️ Looking down");
+                debug!("[FIDGET] ⬇️ Looking down");
                 inner.base_pitch = 45.0;
             }
             FidgetAction::LookAtSky => {
-                println!("[FIDGET] ⬆️ Looking up");
+                debug!("[FIDGET] ⬆️ Looking up");
                 inner.base_pitch = -60.0;
             }
             FidgetAction::SpinAround => {
-                println!("[FIDGET] 🔃 Quick spin");
+                debug!("[FIDGET] 🔃 Quick spin");
                 inner.base_yaw += 180.0;
             }
             FidgetAction::None => {}
@@ -0,0 +1,137 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use crate::cognitive::personality::Mood;
+
+// ============================================================
+// NEGOTIATION — Haggling that survives more than one message
+// "10 ferro? tá maluco, faço por 20" → "15" → "fechou"
+// ============================================================
+
+/// Give up haggling after this many counter-offers
+const MAX_ROUNDS: u32 = 4;
+/// Forget negotiations nobody touched for this long
+const STALE_AFTER_MINUTES: i64 = 10;
+/// We open above fair value to leave room to concede
+const OPENING_MARKUP: f32 = 1.3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum NegotiationPhase {
+    Opened,     // We named a price, waiting for their offer
+    Countered,  // Back and forth in progress
+    Agreed,     // Deal — time to deliver
+    WalkedAway, // One side gave up
+}
+
+/// What we do in response to their latest offer
+#[derive(Debug, Clone, PartialEq)]
+pub enum NegotiationMove {
+    Accept,
+    Counter(f32), // New asking price in iron equivalents
+    WalkAway,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Negotiation {
+    pub item: String,  // What they want from us
+    pub quantity: u32,
+    pub ask: f32,      // Our current asking price (iron equivalents)
+    pub floor: f32,    // Lowest we'll go — never told to the player
+    pub last_offer: Option<(String, u32, f32)>, // item, qty, value in iron
+    pub round: u32,
+    pub phase: NegotiationPhase,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// How much room we give: trust and good mood soften us, pricey items harden us.
+/// 0.0 = won't budge, 0.6 = pushover
+pub fn flexibility(mood: &Mood, trust: i32, value: f32) -> f32 {
+    let mut flex = 0.15 + trust.clamp(-100, 100) as f32 / 400.0;
+    flex += match mood {
+        Mood::Generous => 0.15,
+        Mood::Hyped => 0.1,
+        Mood::Grumpy | Mood::Annoyed => -0.1,
+        Mood::Suspicious => -0.05,
+        _ => 0.0,
+    };
+    if value > 20.0 {
+        flex -= 0.05;
+    }
+    flex.clamp(0.0, 0.6)
+}
+
+impl Negotiation {
+    /// Open with a marked-up price; `value` is what we think it's worth
+    pub fn open(item: &str, quantity: u32, value: f32, flexibility: f32) -> Self {
+        let value = value.max(1.0);
+        Self {
+            item: item.to_string(),
+            quantity,
+            ask: (value * OPENING_MARKUP).ceil(),
+            floor: (value * (1.0 - flexibility)).max(1.0),
+            last_offer: None,
+            round: 0,
+            phase: NegotiationPhase::Opened,
+            updated_at: Utc::now(),
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        matches!(self.phase, NegotiationPhase::Opened | NegotiationPhase::Countered)
+    }
+
+    pub fn is_stale(&self) -> bool {
+        Utc::now().signed_duration_since(self.updated_at).num_minutes() > STALE_AFTER_MINUTES
+    }
+
+    /// They offered `qty` of `item`, worth `value` iron — accept, counter or leave
+    pub fn respond(&mut self, item: &str, qty: u32, value: f32, flexibility: f32) -> NegotiationMove {
+        self.round += 1;
+        self.last_offer = Some((item.to_string(), qty, value));
+        self.updated_at = Utc::now();
+
+        let decision = if value >= self.ask || (value >= self.floor && self.round >= 2) {
+            NegotiationMove::Accept
+        } else if self.round > MAX_ROUNDS || (value < self.floor * 0.5 && self.round >= 2) {
+            NegotiationMove::WalkAway
+        } else {
+            // Meet them part of the way — more flexible moods concede more
+            let concession = 0.25 + flexibility;
+            let next = (self.ask - (self.ask - value) * concession).max(self.floor).ceil();
+            NegotiationMove::Counter(next)
+        };
+
+        match decision {
+            NegotiationMove::Accept => self.phase = NegotiationPhase::Agreed,
+            NegotiationMove::WalkAway => self.phase = NegotiationPhase::WalkedAway,
+            NegotiationMove::Counter(ask) => {
+                self.ask = ask;
+                self.phase = NegotiationPhase::Countered;
+            }
+        }
+        decision
+    }
+
+    pub fn walk_away(&mut self) {
+        self.phase = NegotiationPhase::WalkedAway;
+        self.updated_at = Utc::now();
+    }
+
+    /// State + allowed moves, for the LLM prompt
+    pub fn describe(&self, player: &str) -> String {
+        let offer = self.last_offer.as_ref()
+            .map(|(item, qty, value)| format!("{} x{} (~{:.0} ferro)", item, qty, value))
+            .unwrap_or_else(|| "nenhuma ainda".into());
+        let moves = match self.phase {
+            NegotiationPhase::Opened | NegotiationPhase::Countered => format!(
+                "pedir {:.0} ferro (ou equivalente), baixar um pouco, ou desistir. NUNCA aceite menos que o mínimo e NUNCA revele o mínimo",
+                self.ask
+            ),
+            NegotiationPhase::Agreed => "confirmar o acordo e pedir o pagamento".into(),
+            NegotiationPhase::WalkedAway => "encerrar, não tem mais acordo".into(),
+        };
+        format!(
+            "\n🤝 NEGOCIAÇÃO com {}: quer {} x{}. Rodada {}/{} ({:?}). Seu preço atual: {:.0} ferro. Oferta dele: {}. Movimentos permitidos: {}.",
+            player, self.item, self.quantity, self.round, MAX_ROUNDS, self.phase, self.ask, offer, moves
+        )
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+use crate::systems::spider_sense::distance;
+
+// ============================================================
+// PLAYER MODEL — What's everyone around us up to?
+// Picareta na mão + bloco sumindo do lado = tá minerando
+// ============================================================
+
+/// Only the last half minute says anything about "right now"
+const WINDOW: Duration = Duration::from_secs(30);
+/// One position sample per second is plenty
+const SAMPLE_EVERY: Duration = Duration::from_secs(1);
+/// Block changes further than this from everyone aren't anyone's doing
+const REACH: f64 = 6.0;
+/// Out of sight this long → forget them
+const FORGET_AFTER: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Activity {
+    Idle,
+    Walking,
+    Mining,
+    Building,
+    Farming,
+    StalkingUs,
+}
+
+impl Activity {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Activity::Idle => "parado",
+            Activity::Walking => "andando por aí",
+            Activity::Mining => "minerando",
+            Activity::Building => "construindo",
+            Activity::Farming => "mexendo na farm",
+            Activity::StalkingUs => "de olho em você (seguindo/rondando)",
+        }
+    }
+
+    /// Busy with their own stuff — not here for us
+    pub fn is_busy(&self) -> bool {
+        matches!(self, Activity::Mining | Activity::Building | Activity::Farming)
+    }
+}
+
+const CROPS: &[&str] = &[
+    "wheat", "carrots", "potatoes", "beetroots", "farmland", "melon", "pumpkin",
+    "sugar_cane", "nether_wart", "cocoa", "sweet_berry_bush",
+];
+const DIGGING_TOOLS: &[&str] = &["_pickaxe", "_shovel"];
+const FARMING_TOOLS: &[&str] = &["_hoe", "wheat_seeds", "carrot", "potato", "beetroot_seeds", "bone_meal"];
+
+fn is_crop(block: &str) -> bool {
+    CROPS.iter().any(|c| block.contains(c))
+}
+
+#[derive(Debug)]
+struct PlayerModel {
+    positions: VecDeque<(Instant, [f64; 3])>,
+    held_item: Option<String>,
+    broken: VecDeque<(Instant, String)>,
+    placed: VecDeque<(Instant, String)>,
+    stalking: bool,
+    last_seen: Instant,
+}
+
+impl PlayerModel {
+    fn new() -> Self {
+        Self {
+            positions: VecDeque::new(),
+            held_item: None,
+            broken: VecDeque::new(),
+            placed: VecDeque::new(),
+            stalking: false,
+            last_seen: Instant::now(),
+        }
+    }
+
+    fn trim(&mut self) {
+        while self.positions.front().is_some_and(|(t, _)| t.elapsed() > WINDOW) {
+            self.positions.pop_front();
+        }
+        while self.broken.front().is_some_and(|(t, _)| t.elapsed() > WINDOW) {
+            self.broken.pop_front();
+        }
+        while self.placed.front().is_some_and(|(t, _)| t.elapsed() > WINDOW) {
+            self.placed.pop_front();
+        }
+    }
+
+    fn holding(&self, tools: &[&str]) -> bool {
+        self.held_item.as_deref().is_some_and(|h| tools.iter().any(|t| h.contains(t)))
+    }
+
+    /// Path length walked inside the window
+    fn travelled(&self) -> f64 {
+        self.positions.iter()
+            .zip(self.positions.iter().skip(1))
+            .map(|((_, a), (_, b))| distance(*a, *b))
+            .sum()
+    }
+
+    fn infer(&self) -> Activity {
+        if self.stalking {
+            return Activity::StalkingUs;
+        }
+
+        let broken = self.broken.len();
+        let placed = self.placed.len();
+        let farm_touches = self.broken.iter().chain(self.placed.iter())
+            .filter(|(_, b)| is_crop(b))
+            .count();
+
+        if farm_touches >= 2 || (farm_touches >= 1 && self.holding(FARMING_TOOLS)) {
+            return Activity::Farming;
+        }
+        if placed >= 3 && placed >= broken {
+            return Activity::Building;
+        }
+        if broken >= 3 || (broken >= 1 && self.holding(DIGGING_TOOLS)) {
+            return Activity::Mining;
+        }
+        // Deep underground with a pickaxe out — mining even if we can't see the blocks
+        let underground = self.positions.back().is_some_and(|(_, p)| p[1] < 40.0);
+        if underground && self.holding(DIGGING_TOOLS) {
+            return Activity::Mining;
+        }
+
+        if self.travelled() < 2.0 { Activity::Idle } else { Activity::Walking }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct PlayerModels {
+    players: HashMap<String, PlayerModel>,
+    digging: HashMap<[i32; 3], String>, // Block → who's been cracking it
+}
+
+impl PlayerModels {
+    /// Feed a visible player's position every tick (sampled down to 1/s)
+    pub fn observe_position(&mut self, player: &str, pos: [f64; 3]) {
+        let model = self.players.entry(player.to_string()).or_insert_with(PlayerModel::new);
+        model.last_seen = Instant::now();
+        if model.positions.back().is_none_or(|(t, _)| t.elapsed() >= SAMPLE_EVERY) {
+            model.positions.push_back((Instant::now(), pos));
+        }
+        model.trim();
+    }
+
+    pub fn on_held_item(&mut self, player: &str, item: Option<String>) {
+        let model = self.players.entry(player.to_string()).or_insert_with(PlayerModel::new);
+        model.held_item = item;
+    }
+
+    /// Spider sense flagged them following / circling us
+    pub fn set_stalking(&mut self, player: &str, stalking: bool) {
+        if let Some(model) = self.players.get_mut(player) {
+            model.stalking = stalking;
+        }
+    }
+
+    /// Block crack animation — the server tells us exactly who's digging
+    pub fn on_dig_progress(&mut self, player: &str, pos: [i32; 3]) {
+        self.digging.insert(pos, player.to_string());
+    }
+
+    /// A block changed. Blame whoever was digging it, else the nearest player in reach.
+    /// `nearby` is every visible player's (name, position)
+    pub fn on_block_changed(&mut self, pos: [i32; 3], block: &str, is_air: bool, nearby: &[(String, [f64; 3])]) {
+        let center = [pos[0] as f64 + 0.5, pos[1] as f64 + 0.5, pos[2] as f64 + 0.5];
+        let digger = self.digging.remove(&pos).filter(|_| is_air);
+        let who = digger.or_else(|| {
+            nearby.iter()
+                .map(|(name, p)| (name, distance(*p, center)))
+                .filter(|(_, d)| *d <= REACH)
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(name, _)| name.clone())
+        });
+        let Some(player) = who else { return };
+
+        let model = self.players.entry(player).or_insert_with(PlayerModel::new);
+        let entry = (Instant::now(), block.to_string());
+        if is_air {
+            model.broken.push_back(entry);
+        } else {
+            model.placed.push_back(entry);
+        }
+        model.trim();
+    }
+
+    pub fn infer(&self, player: &str) -> Option<Activity> {
+        self.players.get(player).map(|m| m.infer())
+    }
+
+    pub fn held_item(&self, player: &str) -> Option<&str> {
+        self.players.get(player)?.held_item.as_deref()
+    }
+
+    pub fn forget_stale(&mut self) {
+        self.players.retain(|_, m| m.last_seen.elapsed() < FORGET_AFTER);
+        if self.digging.len() > 64 {
+            self.digging.clear();
+        }
+    }
+
+    /// One line per player we can read, for the LLM
+    pub fn context_summary(&self) -> String {
+        let mut lines: Vec<String> = self.players.iter()
+            .filter(|(_, m)| m.last_seen.elapsed() < Duration::from_secs(10))
+            .map(|(name, m)| match &m.held_item {
+                Some(item) => format!("{}: {} (segurando {})", name, m.infer().describe(), item),
+                None => format!("{}: {}", name, m.infer().describe()),
+            })
+            .collect();
+        if lines.is_empty() {
+            return "Ninguém por perto.".into();
+        }
+        lines.sort();
+        lines.join("\n")
+    }
+}
@@ -0,0 +1,131 @@
+use chrono::Utc;
+use crate::cognitive::memory::{Location, LocationType, SpatialMemory};
+use crate::systems::world_scanner::AreaScan;
+
+// ============================================================
+// POINTS OF INTEREST — Recognize structures from their blocks
+// bell + lecterns → vila / spawner → sala de spawner / crying obsidian → portal em ruínas
+// ============================================================
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PoiKind {
+    Village,
+    DesertTemple,
+    JungleTemple,
+    Spawner,
+    RuinedPortal,
+    Ravine,
+}
+
+#[derive(Debug, Clone)]
+pub struct Poi {
+    pub kind: PoiKind,
+    pub pos: [i32; 3],
+}
+
+/// Village workstations — two of them plus paths is a village
+const JOB_BLOCKS: &[&str] = &[
+    "composter", "lectern", "smithing_table", "fletching_table", "cartography_table", "barrel",
+    "brewing_stand", "grindstone", "stonecutter", "loom", "smoker", "blast_furnace",
+];
+/// Blocks whose position we keep during a scan
+const LANDMARKS: &[&str] = &["spawner", "trial_spawner", "bell", "crying_obsidian", "blue_terracotta", "tripwire_hook"];
+/// Same kind of place within this = the one we already know
+const SAME_PLACE: f64 = 48.0;
+
+pub fn is_landmark(block: &str) -> bool {
+    LANDMARKS.contains(&block) || JOB_BLOCKS.contains(&block)
+}
+
+impl PoiKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PoiKind::Village => "Vila",
+            PoiKind::DesertTemple => "Templo do deserto",
+            PoiKind::JungleTemple => "Templo da selva",
+            PoiKind::Spawner => "Spawner",
+            PoiKind::RuinedPortal => "Portal em ruínas",
+            PoiKind::Ravine => "Ravina",
+        }
+    }
+
+    pub fn location_type(&self) -> LocationType {
+        match self {
+            PoiKind::Village => LocationType::Village,
+            PoiKind::DesertTemple | PoiKind::JungleTemple => LocationType::Custom("Templo".into()),
+            PoiKind::Spawner => LocationType::SpawnerRoom,
+            PoiKind::RuinedPortal => LocationType::Portal,
+            PoiKind::Ravine => LocationType::Custom("Ravina".into()),
+        }
+    }
+
+    fn notes(&self) -> &'static str {
+        match self {
+            PoiKind::Village => "Tem aldeão pra trocar, cama e comida",
+            PoiKind::DesertTemple => "Baú embaixo com loot, cuidado com a TNT no chão",
+            PoiKind::JungleTemple => "Armadilha de flecha, alavancas no fundo",
+            PoiKind::Spawner => "Dá pra fazer farm de xp",
+            PoiKind::RuinedPortal => "Baú com ouro, obsidian pra consertar",
+            PoiKind::Ravine => "Minério exposto nas paredes",
+        }
+    }
+}
+
+/// Read a scan for structure signatures
+pub fn detect(scan: &AreaScan) -> Vec<Poi> {
+    let counts = &scan.blocks.block_counts;
+    let count = |name: &str| counts.get(name).copied().unwrap_or(0);
+    let first = |names: &[&str]| scan.landmarks.iter().find(|(b, _)| names.contains(&b.as_str())).map(|(_, p)| *p);
+    let mut found = vec![];
+
+    let jobs = JOB_BLOCKS.iter().filter(|b| count(b) > 0).count();
+    if (count("bell") > 0 || (jobs >= 2 && count("dirt_path") >= 5))
+        && let Some(pos) = first(&["bell"]).or_else(|| first(JOB_BLOCKS))
+    {
+        found.push(Poi { kind: PoiKind::Village, pos });
+    }
+    if count("blue_terracotta") > 0 && count("orange_terracotta") >= 4
+        && let Some(pos) = first(&["blue_terracotta"])
+    {
+        found.push(Poi { kind: PoiKind::DesertTemple, pos });
+    }
+    if count("mossy_cobblestone") >= 10 && count("chiseled_stone_bricks") > 0
+        && (count("dispenser") > 0 || count("tripwire_hook") > 0)
+    {
+        found.push(Poi { kind: PoiKind::JungleTemple, pos: first(&["tripwire_hook"]).unwrap_or(scan.blocks.center) });
+    }
+    for (_, pos) in scan.landmarks.iter().filter(|(b, _)| b == "spawner" || b == "trial_spawner") {
+        found.push(Poi { kind: PoiKind::Spawner, pos: *pos });
+    }
+    if count("crying_obsidian") > 0 && (count("obsidian") >= 2 || count("netherrack") >= 2)
+        && let Some(pos) = first(&["crying_obsidian"])
+    {
+        found.push(Poi { kind: PoiKind::RuinedPortal, pos });
+    }
+    if scan.tall_air_columns >= 12 {
+        found.push(Poi { kind: PoiKind::Ravine, pos: scan.blocks.center });
+    }
+    found
+}
+
+/// Put it on the map unless we already know this place. True when it's new
+pub fn remember(spatial: &mut SpatialMemory, poi: &Poi) -> bool {
+    let kind = poi.kind.location_type();
+    let known = spatial.locations.iter().any(|l| {
+        l.location_type == kind && crate::systems::spider_sense::distance(
+            [l.coords[0] as f64, l.coords[1] as f64, l.coords[2] as f64],
+            [poi.pos[0] as f64, poi.pos[1] as f64, poi.pos[2] as f64],
+        ) <= SAME_PLACE
+    });
+    if known {
+        return false;
+    }
+    spatial.remember_location(Location {
+        name: format!("{} ({}, {})", poi.kind.label(), poi.pos[0], poi.pos[2]),
+        coords: poi.pos,
+        location_type: kind,
+        notes: poi.kind.notes().into(),
+        discovered_at: Utc::now(),
+    });
+    true
+}
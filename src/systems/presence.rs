@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+
+// ============================================================
+// PRESENCE — Who's online, who's AFK, how long we've played together
+// "jogamos 3h juntos hoje"
+// ============================================================
+
+/// No movement or chat for this long → AFK
+const AFK_AFTER_MINS: i64 = 5;
+/// Moving less than this (blocks) between checks doesn't count as activity
+const MOVE_THRESHOLD: f64 = 0.5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OnlinePlayer {
+    pub joined_at: DateTime<Utc>,
+    pub last_activity: DateTime<Utc>,
+    pub last_pos: Option<[f64; 3]>,
+    pub afk: bool,
+}
+
+impl OnlinePlayer {
+    fn new() -> Self {
+        Self {
+            joined_at: Utc::now(),
+            last_activity: Utc::now(),
+            last_pos: None,
+            afk: false,
+        }
+    }
+
+    fn touch(&mut self) {
+        self.last_activity = Utc::now();
+        self.afk = false;
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PresenceTracker {
+    pub online: HashMap<String, OnlinePlayer>,
+}
+
+impl PresenceTracker {
+    /// True if they weren't on the list yet
+    pub fn on_join(&mut self, player: &str) -> bool {
+        let new = !self.online.contains_key(player);
+        self.online.entry(player.to_string()).or_insert_with(OnlinePlayer::new);
+        new
+    }
+
+    /// Returns how long their session lasted
+    pub fn on_leave(&mut self, player: &str) -> Option<Duration> {
+        self.online.remove(player)
+            .map(|p| Utc::now().signed_duration_since(p.joined_at))
+    }
+
+    pub fn on_chat(&mut self, player: &str) {
+        if let Some(p) = self.online.get_mut(player) {
+            p.touch();
+        }
+    }
+
+    /// We can see them — did they move since last time?
+    pub fn observe_position(&mut self, player: &str, pos: [f64; 3]) {
+        let Some(p) = self.online.get_mut(player) else { return };
+        let moved = p.last_pos.is_none_or(|last| {
+            let (dx, dy, dz) = (pos[0] - last[0], pos[1] - last[1], pos[2] - last[2]);
+            (dx * dx + dy * dy + dz * dz).sqrt() > MOVE_THRESHOLD
+        });
+        p.last_pos = Some(pos);
+        if moved {
+            p.touch();
+        }
+    }
+
+    /// Reconcile with the tab list (catches joins/leaves we missed, e.g. on login)
+    pub fn sync(&mut self, online_names: &[String]) {
+        for name in online_names {
+            self.on_join(name);
+        }
+        self.online.retain(|name, _| online_names.contains(name));
+    }
+
+    /// Re-evaluate AFK flags; returns players who just went AFK
+    pub fn update_afk(&mut self) -> Vec<String> {
+        let mut went_afk = vec![];
+        for (name, p) in self.online.iter_mut() {
+            let idle = Utc::now().signed_duration_since(p.last_activity) > Duration::minutes(AFK_AFTER_MINS);
+            if idle && !p.afk {
+                went_afk.push(name.clone());
+            }
+            p.afk = idle;
+        }
+        went_afk
+    }
+
+    pub fn is_afk(&self, player: &str) -> bool {
+        self.online.get(player).is_some_and(|p| p.afk)
+    }
+
+    /// Players online and actually playing
+    pub fn active_players(&self) -> Vec<&String> {
+        self.online.iter().filter(|(_, p)| !p.afk).map(|(n, _)| n).collect()
+    }
+
+    pub fn context_summary(&self) -> String {
+        if self.online.is_empty() {
+            return "ninguém".into();
+        }
+        let mut names: Vec<String> = self.online.iter()
+            .map(|(name, p)| {
+                let mins = Utc::now().signed_duration_since(p.joined_at).num_minutes();
+                format!("{} ({}min{})", name, mins, if p.afk { ", AFK" } else { "" })
+            })
+            .collect();
+        names.sort();
+        names.join(", ")
+    }
+}
@@ -0,0 +1,116 @@
+use azalea::prelude::*;
+use azalea::BlockPos;
+use std::time::{Duration, Instant};
+use tracing::info;
+
+// ============================================================
+// QUEUE — 2b2t-style queues and limbo lobbies
+// "Position in queue: 412" / void world → sit still, no wandering, no LLM
+// ============================================================
+
+/// Standing over nothing this long (after chunks had time to load) = limbo
+const VOID_FOR: Duration = Duration::from_secs(10);
+/// No queue message for this long and ground under us = we're in
+const QUIET_FOR: Duration = Duration::from_secs(15);
+/// How far down we look for ground
+const VOID_DEPTH: i32 = 64;
+/// World checks once a second is plenty
+const CHECK_EVERY: Duration = Duration::from_secs(1);
+
+/// Server lines only — specific enough that players chatting about queues don't trip it
+const QUEUE_MARKERS: &[&str] = &[
+    "position in queue", "place in queue", "you are in queue", "queue position",
+    "posição na fila", "posicao na fila", "você está na fila", "voce esta na fila",
+    "connecting to the server", "conectando ao servidor", "you are now in limbo", "você está no limbo",
+];
+
+/// "Position in queue: 412" → 412
+pub fn parse_queue_position(message: &str) -> Option<u32> {
+    let lower = message.to_lowercase();
+    if !QUEUE_MARKERS.iter().any(|m| lower.contains(m)) {
+        return None;
+    }
+    lower.split(|c: char| !c.is_ascii_digit())
+        .find(|n| !n.is_empty())
+        .and_then(|n| n.parse().ok())
+}
+
+#[derive(Debug)]
+pub struct QueueWatch {
+    pub waiting: bool,
+    pub position: Option<u32>,
+    last_queue_message: Option<Instant>,
+    void_since: Option<Instant>,
+    last_check: Instant,
+}
+
+impl Default for QueueWatch {
+    fn default() -> Self {
+        Self {
+            waiting: false,
+            position: None,
+            last_queue_message: None,
+            void_since: None,
+            last_check: Instant::now(),
+        }
+    }
+}
+
+impl QueueWatch {
+    /// Feed every chat line; true when it was queue chatter (don't treat it as chat)
+    pub fn on_message(&mut self, message: &str) -> bool {
+        let lower = message.to_lowercase();
+        if !QUEUE_MARKERS.iter().any(|m| lower.contains(m)) {
+            return false;
+        }
+        self.last_queue_message = Some(Instant::now());
+        let position = parse_queue_position(message);
+        if position.is_some() && position != self.position {
+            info!("[QUEUE] ⏳ Posição na fila: {}", position.unwrap_or_default());
+        }
+        self.position = position.or(self.position);
+        if !self.waiting {
+            info!("[QUEUE] 💤 Fila detectada — modo espera (sem andar, scan ou LLM)");
+            self.waiting = true;
+        }
+        true
+    }
+
+    /// Once a second: look at the ground under us. Returns true while we should stay idle
+    pub fn tick(&mut self, bot: &Client) -> bool {
+        if self.last_check.elapsed() < CHECK_EVERY {
+            return self.waiting;
+        }
+        self.last_check = Instant::now();
+
+        if is_void(bot) {
+            let since = *self.void_since.get_or_insert_with(Instant::now);
+            if !self.waiting && since.elapsed() >= VOID_FOR {
+                info!("[QUEUE] 💤 Mundo vazio (limbo?) — modo espera");
+                self.waiting = true;
+            }
+            return self.waiting;
+        }
+
+        self.void_since = None;
+        let quiet = self.last_queue_message.is_none_or(|t| t.elapsed() >= QUIET_FOR);
+        if self.waiting && quiet {
+            info!("[QUEUE] ✅ Chão de verdade debaixo do pé — voltando ao normal");
+            self.waiting = false;
+            self.position = None;
+        }
+        self.waiting
+    }
+}
+
+/// Nothing but air (or unloaded chunks) in the column under us
+fn is_void(bot: &Client) -> bool {
+    let pos = bot.position();
+    let (x, y, z) = (pos.x.floor() as i32, pos.y.floor() as i32, pos.z.floor() as i32);
+    let world = bot.world();
+    let world = world.read();
+    (0..VOID_DEPTH).all(|dy| {
+        world.get_block_state(BlockPos::new(x, y - dy, z))
+            .is_none_or(|b| b.is_air())
+    })
+}
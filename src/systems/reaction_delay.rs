@@ -1,8 +1,9 @@
 use azalea::prelude::*;
-use azalea::SprintDirection;
+use azalea::{SprintDirection, WalkDirection};
 use rand::Rng;
 use std::sync::{Arc, Mutex};
 use std::time::{Instant, Duration};
+use tracing::info;
 
 // ============================================================
 // REACTION DELAY — Humanized damage response
@@ -65,18 +66,21 @@ pub fn on_damage(state: &mut ReactionState, damage_amount: f32, attacker_yaw: Op
     state.hits_in_last_5s += 1;
     state.last_damage_direction = attacker_yaw;
 
-    // Randomize reaction time (200ms - 400ms for first hit)
+    // Randomize reaction time ([humanize] panic_ms, 200-400ms by default)
     // Gets faster with repeated hits (muscle memory)
+    let config = crate::config::Config::load();
+    let [panic_min, panic_max] = config.humanize_panic_ms;
     let base_panic = if state.hits_in_last_5s > 3 {
-        100 // Experienced at being hit, faster reaction
+        panic_min / 2 // Experienced at being hit, faster reaction
     } else {
-        rng.r#gen::<u64>() % 200 + 200 // 200-400ms first time
+        rng.gen_range(panic_min..=panic_max.max(panic_min))
     };
 
     state.panic_duration_ms = base_panic;
-    state.assess_duration_ms = rng.r#gen::<u64>() % 100 + 100; // 100-200ms
+    let [assess_min, assess_max] = config.humanize_assess_ms;
+    state.assess_duration_ms = rng.gen_range(assess_min..=assess_max.max(assess_min));
 
-    println!(
+    info!(
         "[REACTION] 😰 Hit! Damage: {:.1} | Panic: {}ms | Assess: {}ms",
         damage_amount, state.panic_duration_ms, state.assess_duration_ms
     );
@@ -166,33 +170,30 @@ pub fn get_panic_look_direction(state: &ReactionState) -> (f32, f32) {
     }
 }
 
-pub async fn handle(bot: Client, event: Event, state: State) -> anyhow::Result<()> {
-    if let Event::Tick = event {
-        let mut inner = state.inner.lock().unwrap();
-        let action = get_reaction_action(&mut inner);
-
-        match action {
-            ReactionAction::JumpAndRun => {
-                bot.jump();
-                bot.sprint(SprintDirection::Forward);
-                // Look direction during panic
-                let (_yaw, _pitch) = get_panic_look_direction(&inner);
-                // bot.set_rotation(yaw, pitch);
-            }
-            ReactionAction::LookAround => {
-                let (_yaw, _pitch) = get_panic_look_direction(&inner);
-                // bot.set_rotation(yaw, pitch);
-            }
-            ReactionAction::FightOrFlight => {
-                // Stop sprinting — just don't call sprint
-                // bot.sprint() with no-sprint not available; combat system handles this
-                // Now we can fight — actual combat logic in combat.rs
-            }
-            ReactionAction::Sprint => {
-                bot.sprint(SprintDirection::Forward);
-            }
-            ReactionAction::Nothing => {}
+/// Every tick: act out whatever phase we're in
+pub fn on_tick(bot: &Client, state: &State) {
+    let mut inner = state.inner.lock().unwrap();
+    let action = get_reaction_action(&mut inner);
+
+    match action {
+        ReactionAction::JumpAndRun => {
+            bot.jump();
+            bot.sprint(SprintDirection::Forward);
+            // Look direction during panic
+            let (_yaw, _pitch) = get_panic_look_direction(&inner);
+            // bot.set_rotation(yaw, pitch);
+        }
+        ReactionAction::LookAround => {
+            bot.walk(WalkDirection::None); // Panic's over — stop running blind
+            let (_yaw, _pitch) = get_panic_look_direction(&inner);
+            // bot.set_rotation(yaw, pitch);
+        }
+        ReactionAction::FightOrFlight => {
+            // Now we can fight — combat::step takes the keys from here
+        }
+        ReactionAction::Sprint => {
+            bot.sprint(SprintDirection::Forward);
         }
+        ReactionAction::Nothing => {}
     }
-    Ok(())
 }
@@ -0,0 +1,201 @@
+use rand::Rng;
+use crate::cognitive::memory::Relationship;
+
+// ============================================================
+// SERVER EVENTS — Other players dying and getting advancements
+// "Fulano was slain by Zombie" → "como vc morreu pra zumbi kkk"
+// "Fulano has made the advancement [Diamonds!]" → "boa Fulano"
+// ============================================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeathCause {
+    Mob(String),
+    Player(String),
+    Fall,
+    Lava,
+    Fire,
+    Drowned,
+    Explosion,
+    Starved,
+    Void,
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AdvancementKind {
+    Advancement,
+    Goal,
+    Challenge,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ServerEvent {
+    Death { player: String, cause: DeathCause },
+    Advancement { player: String, title: String, kind: AdvancementKind },
+}
+
+/// Vanilla death message verbs (after the name) → cause when there's no killer
+const DEATH_PHRASES: &[(&str, DeathCause)] = &[
+    ("fell from a high place", DeathCause::Fall),
+    ("hit the ground too hard", DeathCause::Fall),
+    ("fell off", DeathCause::Fall),
+    ("tried to swim in lava", DeathCause::Lava),
+    ("burned to death", DeathCause::Fire),
+    ("went up in flames", DeathCause::Fire),
+    ("walked into fire", DeathCause::Fire),
+    ("drowned", DeathCause::Drowned),
+    ("blew up", DeathCause::Explosion),
+    ("starved to death", DeathCause::Starved),
+    ("fell out of the world", DeathCause::Void),
+    ("left the confines of this world", DeathCause::Void),
+    ("suffocated", DeathCause::Other),
+    ("froze to death", DeathCause::Other),
+    ("withered away", DeathCause::Other),
+    ("was pricked to death", DeathCause::Other),
+    ("experienced kinetic energy", DeathCause::Other),
+    ("died", DeathCause::Other),
+];
+
+/// "... by X" verbs — X is a mob or a player
+const KILLED_BY: &[&str] = &[
+    "was slain by", "was shot by", "was killed by", "was blown up by", "was fireballed by",
+    "was impaled by", "was stung to death by", "was squashed by", "was pummeled by",
+    "was doomed to fall by", "was knocked into the void by", "was burned to a crisp while fighting",
+    "walked into danger zone due to", "drowned whilst trying to escape", "was struck by lightning whilst fighting",
+];
+
+const ADVANCEMENTS: &[(&str, AdvancementKind)] = &[
+    (" has made the advancement ", AdvancementKind::Advancement),
+    (" has reached the goal ", AdvancementKind::Goal),
+    (" has completed the challenge ", AdvancementKind::Challenge),
+    (" conquistou o avanço ", AdvancementKind::Advancement),
+];
+
+fn valid_name(name: &str) -> bool {
+    !name.is_empty() && name.len() <= 16 && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// A system chat line → death or advancement. `is_player` tells players from mobs ("Zombie")
+pub fn parse(message: &str, is_player: impl Fn(&str) -> bool) -> Option<ServerEvent> {
+    let message = message.trim();
+
+    if let Some((index, marker, kind)) = ADVANCEMENTS.iter()
+        .find_map(|(m, kind)| message.find(m).map(|i| (i, *m, *kind)))
+    {
+        let player = message[..index].rsplit(' ').next()?;
+        let title = message[index + marker.len()..].trim().trim_matches(['[', ']']).to_string();
+        return valid_name(player).then(|| ServerEvent::Advancement { player: player.to_string(), title, kind });
+    }
+
+    let (player, rest) = message.split_once(' ')?;
+    if !valid_name(player) {
+        return None;
+    }
+
+    if let Some(phrase) = KILLED_BY.iter().find(|p| rest.starts_with(*p)) {
+        let killer = rest[phrase.len()..].trim();
+        let killer = killer.split(" using ").next().unwrap_or(killer).trim_end_matches('.').trim();
+        let cause = if is_player(killer) {
+            DeathCause::Player(killer.to_string())
+        } else if phrase.contains("blown up") {
+            DeathCause::Explosion
+        } else {
+            DeathCause::Mob(killer.trim_start_matches("a ").trim_start_matches("an ").to_string())
+        };
+        return Some(ServerEvent::Death { player: player.to_string(), cause });
+    }
+
+    DEATH_PHRASES.iter()
+        .find(|(p, _)| rest.starts_with(p))
+        .map(|(_, cause)| ServerEvent::Death { player: player.to_string(), cause: cause.clone() })
+}
+
+/// Mob names the way the gang says them
+fn mob_name(mob: &str) -> String {
+    match mob.to_lowercase().as_str() {
+        "zombie" => "zumbi".into(),
+        "skeleton" => "esqueleto".into(),
+        "spider" => "aranha".into(),
+        "drowned" => "afogado".into(),
+        "witch" => "bruxa".into(),
+        other => other.to_string(),
+    }
+}
+
+impl ServerEvent {
+    pub fn player(&self) -> &str {
+        match self {
+            ServerEvent::Death { player, .. } | ServerEvent::Advancement { player, .. } => player,
+        }
+    }
+
+    /// Episode text for memory
+    pub fn describe(&self) -> String {
+        match self {
+            ServerEvent::Death { player, cause } => match cause {
+                DeathCause::Mob(mob) => format!("{} morreu pra {}", player, mob_name(mob)),
+                DeathCause::Player(killer) => format!("{} foi morto pelo {}", player, killer),
+                DeathCause::Fall => format!("{} morreu de queda", player),
+                DeathCause::Lava => format!("{} morreu na lava", player),
+                DeathCause::Fire => format!("{} morreu queimado", player),
+                DeathCause::Drowned => format!("{} morreu afogado", player),
+                DeathCause::Explosion => format!("{} explodiu", player),
+                DeathCause::Starved => format!("{} morreu de fome", player),
+                DeathCause::Void => format!("{} caiu no void", player),
+                DeathCause::Other => format!("{} morreu", player),
+            },
+            ServerEvent::Advancement { player, title, .. } => format!("{} conseguiu a conquista [{}]", player, title),
+        }
+    }
+
+    /// Something to say about it, depending on how we feel about them. None = let it slide
+    pub fn reaction(&self, relationship: Option<&Relationship>) -> Option<String> {
+        let mut rng = rand::thread_rng();
+        let friend = matches!(relationship, Some(Relationship::Friend | Relationship::BestFriend));
+        let enemy = matches!(relationship, Some(Relationship::Rival | Relationship::Enemy));
+        // Strangers only get a comment sometimes
+        if !friend && !enemy && rng.gen_bool(0.5) {
+            return None;
+        }
+
+        let options: Vec<String> = match self {
+            ServerEvent::Death { player, cause } if enemy => match cause {
+                DeathCause::Player(killer) => vec![format!("boa {} kkkkk", killer), format!("bem feito {}", player)],
+                _ => vec![format!("kkkkkk bem feito {}", player), "karma ne".into()],
+            },
+            ServerEvent::Death { player, cause } => match cause {
+                DeathCause::Mob(mob) => vec![
+                    "F".into(),
+                    format!("como vc morreu pra {} {} kkk", mob_name(mob), player),
+                    format!("{} morreu pra {} kkkkk", player, mob_name(mob)),
+                ],
+                DeathCause::Player(killer) if friend => vec![format!("eita {} oq vc fez com o {}", killer, player), format!("F {}", player)],
+                DeathCause::Player(_) => vec![format!("F {}", player)],
+                DeathCause::Fall => vec![format!("{} achou q sabia voar kkk", player), "F".into()],
+                DeathCause::Lava => vec![format!("F pros itens do {}", player), format!("{} foi nadar na lava ne kkk", player)],
+                DeathCause::Void => vec![format!("F {} os itens ja era", player)],
+                DeathCause::Starved => vec![format!("{} morreu de fome?? come alguma coisa mn", player)],
+                _ => vec!["F".into(), format!("F {}", player)],
+            },
+            ServerEvent::Advancement { .. } if enemy => return None,
+            ServerEvent::Advancement { player, title, kind } => match kind {
+                AdvancementKind::Challenge => vec![format!("BRABO {}!! {}", player, title), format!("caraca {} pegou {} gg", player, title)],
+                _ if friend => vec![format!("boa {}!", player), format!("gg {} kkk", player), format!("eae {} {} hein", player, title)],
+                _ => vec![format!("gg {}", player)],
+            },
+        };
+        Some(options[rng.gen_range(0..options.len())].clone())
+    }
+
+    /// How it feels to remember it (-5..5)
+    pub fn emotional_impact(&self, relationship: Option<&Relationship>) -> i8 {
+        let friend = matches!(relationship, Some(Relationship::Friend | Relationship::BestFriend));
+        let enemy = matches!(relationship, Some(Relationship::Rival | Relationship::Enemy));
+        match self {
+            ServerEvent::Death { .. } if friend => -1,
+            ServerEvent::Death { .. } if enemy => 1,
+            ServerEvent::Advancement { .. } if friend => 1,
+            _ => 0,
+        }
+    }
+}
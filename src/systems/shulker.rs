@@ -0,0 +1,188 @@
+use azalea::prelude::*;
+use azalea::auto_tool::AutoToolClientExt;
+use azalea::inventory::{components, ItemStack};
+use azalea::pathfinder::goals::RadiusGoal;
+use azalea::{BlockPos, Vec3};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use crate::cognitive::memory::Memory;
+use crate::systems::inventory::{self, item_id, Inventory};
+use crate::systems::inventory_manager::{categorize_item, ItemCategory};
+use crate::systems::storage;
+use tracing::{info, warn};
+
+// ============================================================
+// SHULKER — A chest that fits in the pocket
+// "3 mil de pedra?" → "tá tudo em 2 shulker cinza, etiquetada"
+// ============================================================
+
+/// A shulker box and what's packed in it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShulkerLabel {
+    pub color: String, // Item id ("shulker_box", "red_shulker_box"...)
+    pub label: String, // What it's for, by its main category
+    pub items: HashMap<String, u32>,
+}
+
+pub fn is_shulker(item: &str) -> bool {
+    item.ends_with("shulker_box")
+}
+
+/// Items inside a shulker box item stack
+pub fn contents_of(stack: &ItemStack) -> HashMap<String, u32> {
+    let mut items = HashMap::new();
+    let Some(container) = stack.get_component::<components::Container>() else {
+        return items;
+    };
+    for inner in container.items.iter().filter(|s| s.is_present()) {
+        *items.entry(item_id(inner)).or_insert(0) += inner.count() as u32;
+    }
+    items
+}
+
+/// Name for a box by whatever category fills most of it
+pub fn label_for(items: &HashMap<String, u32>) -> String {
+    let mut by_category: Vec<(ItemCategory, u32)> = vec![];
+    for (item, n) in items {
+        let category = categorize_item(item);
+        match by_category.iter_mut().find(|(c, _)| *c == category) {
+            Some((_, total)) => *total += n,
+            None => by_category.push((category, *n)),
+        }
+    }
+    let main = by_category.into_iter().max_by_key(|(_, n)| *n).map(|(c, _)| c);
+    match main {
+        None => "vazia",
+        Some(ItemCategory::BuildingBlock) => "blocos",
+        Some(ItemCategory::Food) => "comida",
+        Some(ItemCategory::Valuable) => "minérios e valiosos",
+        Some(ItemCategory::Redstone) => "redstone",
+        Some(ItemCategory::Junk) => "tralha",
+        Some(ItemCategory::Torch) => "tochas",
+        Some(ItemCategory::Armor) => "armadura",
+        Some(_) => "ferramentas e variados",
+    }.to_string()
+}
+
+/// Every shulker box in a list of slots, labeled
+pub fn labels_in(slots: &[ItemStack]) -> Vec<ShulkerLabel> {
+    slots.iter()
+        .filter(|s| s.is_present() && is_shulker(&item_id(s)))
+        .map(|s| {
+            let items = contents_of(s);
+            ShulkerLabel { color: item_id(s), label: label_for(&items), items }
+        })
+        .collect()
+}
+
+/// Carried shulker with nothing in it
+pub fn empty_shulker_slot(inventory: &Inventory) -> Option<usize> {
+    inventory::INVENTORY_SLOTS.into_iter().find(|i| {
+        inventory.stack_at(*i).is_some_and(|s| is_shulker(&item_id(s)) && contents_of(s).is_empty())
+    })
+}
+
+/// Carried shulker holding some of an item
+fn shulker_with(inventory: &Inventory, item: &str) -> Option<usize> {
+    inventory::INVENTORY_SLOTS.into_iter().find(|i| {
+        inventory.stack_at(*i).is_some_and(|s| is_shulker(&item_id(s)) && contents_of(s).contains_key(item))
+    })
+}
+
+/// Biggest category riding in the backpack that's worth boxing up → its items
+pub fn overflow(inventory: &Inventory) -> Option<Vec<String>> {
+    let counts = inventory.backpack_counts();
+    let mut groups: Vec<(ItemCategory, u32, Vec<String>)> = vec![];
+    for item in storage::stashable(inventory).into_iter().filter(|i| !is_shulker(i)) {
+        let n = counts.get(&item).copied().unwrap_or(0);
+        let category = categorize_item(&item);
+        match groups.iter_mut().find(|(c, _, _)| *c == category) {
+            Some((_, total, items)) => {
+                *total += n;
+                items.push(item);
+            }
+            None => groups.push((category, n, vec![item])),
+        }
+    }
+    groups.into_iter().max_by_key(|(_, n, _)| *n).map(|(_, _, items)| items)
+}
+
+/// Break a placed shulker and walk over it so it comes back to the inventory
+async fn pick_up(bot: &Client, spot: [i32; 3]) {
+    bot.mine_with_auto_tool(BlockPos::new(spot[0], spot[1], spot[2])).await;
+    bot.wait_ticks(4).await;
+    let center = Vec3::new(spot[0] as f64 + 0.5, spot[1] as f64, spot[2] as f64 + 0.5);
+    if tokio::time::timeout(Duration::from_secs(10), bot.goto(RadiusGoal::new(center, 0.5))).await.is_err() {
+        bot.stop_pathfinding();
+    }
+    bot.wait_ticks(10).await;
+}
+
+/// Box up the biggest overflow category into an empty shulker we carry. Returns items packed
+pub async fn pack(bot: &Client) -> u32 {
+    let inventory = Inventory::read(bot);
+    let (Some(slot), Some(items)) = (empty_shulker_slot(&inventory), overflow(&inventory)) else {
+        return 0;
+    };
+    let Some(spot) = storage::place_beside(bot, slot).await else {
+        warn!("[SHULKER] ❌ Não achei lugar pra colocar a shulker");
+        return 0;
+    };
+    let packed = storage::deposit(bot, spot, &items).await;
+    pick_up(bot, spot).await;
+    let Some((moved, contents)) = packed else {
+        return 0;
+    };
+    let total: u32 = moved.values().sum();
+    info!("[SHULKER] 📦 Empacotei {} itens ({})", total, label_for(&contents.items));
+    total
+}
+
+/// Take `quantity` of an item out of a shulker we carry. Returns how many came out
+pub async fn unpack(bot: &Client, item: &str, quantity: u32) -> u32 {
+    let Some(slot) = shulker_with(&Inventory::read(bot), item) else {
+        return 0;
+    };
+    let Some(spot) = storage::place_beside(bot, slot).await else {
+        return 0;
+    };
+    let taken = storage::withdraw(bot, spot, item, quantity).await.map(|(n, _)| n).unwrap_or(0);
+    pick_up(bot, spot).await;
+    if taken > 0 {
+        info!("[SHULKER] 📤 Tirei {} x{} da shulker", item, taken);
+    }
+    taken
+}
+
+/// Go get the stored shulker holding an item, so `unpack` can open it. True if we're carrying it now
+pub async fn pull_from_storage(bot: &Client, memory: &Arc<Mutex<Memory>>, item: &str) -> bool {
+    let found = memory.lock().unwrap().inventory.storage.find_shulkered(item).map(|(pos, s)| (pos, s.label.clone()));
+    let Some((pos, label)) = found else {
+        return false;
+    };
+    let Some(container) = storage::open_at(bot, pos).await else {
+        return false;
+    };
+    let Some(slots) = container.contents() else {
+        container.close();
+        return false;
+    };
+    let box_slot = slots.iter()
+        .position(|s| s.is_present() && is_shulker(&item_id(s)) && contents_of(s).contains_key(item));
+    if let Some(box_slot) = box_slot {
+        container.shift_click(box_slot);
+        bot.wait_ticks(4).await;
+    }
+    let after = container.contents().map(|slots| storage::tally(&slots));
+    container.close();
+    if let Some(after) = after {
+        storage::update_index(memory, pos, after);
+    }
+    let carried = shulker_with(&Inventory::read(bot), item).is_some();
+    if carried {
+        info!("[SHULKER] 📦 Peguei a shulker de {} do baú {:?}", label, pos);
+    }
+    carried
+}
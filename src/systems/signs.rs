@@ -0,0 +1,122 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+// ============================================================
+// SIGNS — Everything written on the signs we walk past
+// "[Warp] /warp mina" / "PROIBIDO PVP NA VILA" / "casa do Fulano, n mexe"
+// ============================================================
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum SignKind {
+    Shop,
+    Warp,
+    Rules,
+    Message, // Player-written: "casa do fulano", "n mexe", directions
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignNote {
+    pub pos: [i32; 3],
+    pub kind: SignKind,
+    pub text: String, // Lines joined with " / "
+    pub seen_at: DateTime<Utc>,
+}
+
+const WARP_WORDS: &[&str] = &["warp", "/spawn", "/home", "teleport", "portal", "[tp]"];
+const RULE_WORDS: &[&str] = &[
+    "regra", "proibido", "proibida", "não pode", "nao pode", "rules", "no pvp", "no grief",
+    "sem grief", "sem pvp", "ban", "respeite",
+];
+/// Words that make someone ask about signs
+const ASK_WORDS: &[&str] = &["placa", "sign", "escrito", "regra", "warp", "loja"];
+/// Oldest notes go past this
+const MAX_NOTES: usize = 300;
+
+impl SignKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SignKind::Shop => "loja",
+            SignKind::Warp => "warp",
+            SignKind::Rules => "regra",
+            SignKind::Message => "recado",
+        }
+    }
+}
+
+/// Sign lines → what kind of sign it is. None for blank or one-word filler
+pub fn classify(lines: &[String], is_shop: bool) -> Option<SignKind> {
+    let text = lines.iter().map(|l| l.trim()).filter(|l| !l.is_empty()).collect::<Vec<_>>().join(" ");
+    if is_shop {
+        return Some(SignKind::Shop);
+    }
+    if text.chars().filter(|c| c.is_alphanumeric()).count() < 4 {
+        return None;
+    }
+    let lower = text.to_lowercase();
+    if WARP_WORDS.iter().any(|w| lower.contains(w)) {
+        Some(SignKind::Warp)
+    } else if RULE_WORDS.iter().any(|w| lower.contains(w)) {
+        Some(SignKind::Rules)
+    } else {
+        Some(SignKind::Message)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SignBoard {
+    pub notes: Vec<SignNote>,
+}
+
+impl SignBoard {
+    /// Remember a sign. True when it's new or its text changed
+    pub fn record(&mut self, pos: [i32; 3], kind: SignKind, lines: &[String]) -> bool {
+        let text = lines.iter().map(|l| l.trim()).filter(|l| !l.is_empty()).collect::<Vec<_>>().join(" / ");
+        if let Some(note) = self.notes.iter_mut().find(|n| n.pos == pos) {
+            note.seen_at = Utc::now();
+            if note.text == text {
+                return false;
+            }
+            note.text = text;
+            note.kind = kind;
+            return true;
+        }
+        self.notes.push(SignNote { pos, kind, text, seen_at: Utc::now() });
+        if self.notes.len() > MAX_NOTES {
+            self.notes.sort_by_key(|n| n.seen_at);
+            self.notes.drain(0..self.notes.len() - MAX_NOTES);
+        }
+        true
+    }
+
+    /// Signs a chat message is about: asked about signs outright, or shares a word with one
+    pub fn relevant(&self, message: &str, limit: usize) -> Vec<&SignNote> {
+        let lower = message.to_lowercase();
+        let asking = ASK_WORDS.iter().any(|w| lower.contains(w));
+        let words: Vec<&str> = lower.split(|c: char| !c.is_alphanumeric() && c != '_')
+            .filter(|w| w.chars().count() >= 4)
+            .collect();
+        let mut hits: Vec<(usize, &SignNote)> = self.notes.iter()
+            .map(|n| {
+                let text = n.text.to_lowercase();
+                let shared = words.iter().filter(|w| text.contains(*w)).count();
+                let asked_kind = lower.contains(n.kind.label()) as usize;
+                (shared * 2 + asked_kind, n)
+            })
+            .filter(|(score, _)| *score > 0 || asking)
+            .collect();
+        hits.sort_by_key(|(score, n)| (std::cmp::Reverse(*score), std::cmp::Reverse(n.seen_at)));
+        hits.into_iter().take(limit).map(|(_, n)| n).collect()
+    }
+
+    /// For the LLM: signs worth quoting for this message
+    pub fn context_for(&self, message: &str) -> String {
+        let notes = self.relevant(message, 5);
+        if notes.is_empty() {
+            return "Nenhuma placa relevante.".into();
+        }
+        notes.iter()
+            .map(|n| format!("[{}] em [{}, {}, {}]: \"{}\"", n.kind.label(), n.pos[0], n.pos[1], n.pos[2], n.text))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
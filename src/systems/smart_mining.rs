@@ -1,11 +1,27 @@
-// use azalea::prelude::*;
-use azalea::BlockPos;
+use azalea::prelude::*;
+use azalea::registry::builtin::BlockKind;
+use azalea::{BlockPos, Vec3};
 use serde::{Deserialize, Serialize};
 use rand::Rng;
+use std::time::Duration;
+use crate::systems::inventory::Inventory;
+use tracing::info;
 
 // ============================================================
 // SMART MINING — Veteran mining strategies
 // ============================================================
+//
+// Tunnel strategies (strip/branch) walk down to the target Y and dig a 1x2 corridor,
+// one step at a time; the rest go for the nearest matching block in sight. Every
+// step looks at the faces it just opened and takes any ore showing. Lava next to
+// the next block turns the tunnel instead of digging into it.
+
+/// Longest we'll hammer one block
+const DIG_TIMEOUT: Duration = Duration::from_secs(10);
+/// How far "in sight" goes for the non-tunnel strategies
+pub const LOOK_RADIUS: i32 = 12;
+/// Target blocks dug before a specific goal ("Minerar Ferro") counts as done
+const ENOUGH: u32 = 8;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum MiningStrategy {
@@ -34,6 +50,48 @@ pub enum MiningTarget {
 }
 
 impl MiningTarget {
+    /// "Minerar Ferro", "Minerar diamond ore" → what to dig for. None = not a mining goal
+    pub fn from_goal(name: &str) -> Option<MiningTarget> {
+        let name = name.to_lowercase();
+        let has = |words: &[&str]| words.iter().any(|w| name.contains(w));
+        if !has(&["minerar", "mine ", "mining", "cavar", "minério"]) && !has(&["cortar madeira", "pegar madeira"]) {
+            return None;
+        }
+        Some(match () {
+            _ if has(&["diamante", "diamond"]) => MiningTarget::Diamond,
+            _ if has(&["ferro", "iron"]) => MiningTarget::Iron,
+            _ if has(&["ouro", "gold"]) => MiningTarget::Gold,
+            _ if has(&["redstone"]) => MiningTarget::Redstone,
+            _ if has(&["lapis"]) => MiningTarget::Lapis,
+            _ if has(&["esmeralda", "emerald"]) => MiningTarget::Emerald,
+            _ if has(&["cobre", "copper"]) => MiningTarget::Copper,
+            _ if has(&["carvão", "carvao", "coal"]) => MiningTarget::Coal,
+            _ if has(&["debris", "netherite"]) => MiningTarget::AncientDebris,
+            _ if has(&["madeira", "wood", "log", "tronco"]) => MiningTarget::Wood,
+            _ if has(&["pedra", "stone", "cobble"]) => MiningTarget::Stone,
+            _ => MiningTarget::Any,
+        })
+    }
+
+    /// Is `block` what we came for
+    pub fn matches(&self, block: &str) -> bool {
+        let ore = |name: &str| block == format!("{}_ore", name) || block == format!("deepslate_{}_ore", name);
+        match self {
+            MiningTarget::Coal => ore("coal"),
+            MiningTarget::Iron => ore("iron"),
+            MiningTarget::Gold => ore("gold") || block == "nether_gold_ore",
+            MiningTarget::Diamond => ore("diamond"),
+            MiningTarget::Redstone => ore("redstone"),
+            MiningTarget::Lapis => ore("lapis"),
+            MiningTarget::Emerald => ore("emerald"),
+            MiningTarget::Copper => ore("copper"),
+            MiningTarget::AncientDebris => block == "ancient_debris",
+            MiningTarget::Wood => block.ends_with("_log"),
+            MiningTarget::Stone => matches!(block, "stone" | "cobblestone" | "deepslate" | "cobbled_deepslate"),
+            MiningTarget::Any => is_ore(block),
+        }
+    }
+
     /// Optimal Y level for this resource (1.21+)
     pub fn optimal_y(&self) -> i32 {
         match self {
@@ -52,6 +110,17 @@ impl MiningTarget {
         }
     }
 
+    /// Pickaxe material needed for the drop to count (see `inventory::material_tier`)
+    pub fn min_pickaxe_tier(&self) -> u8 {
+        match self {
+            MiningTarget::Wood => 0,
+            MiningTarget::Coal | MiningTarget::Stone | MiningTarget::Any => 1,
+            MiningTarget::Iron | MiningTarget::Lapis | MiningTarget::Copper => 2,
+            MiningTarget::Gold | MiningTarget::Diamond | MiningTarget::Redstone | MiningTarget::Emerald => 3,
+            MiningTarget::AncientDebris => 4,
+        }
+    }
+
     /// Best strategy for this target
     pub fn best_strategy(&self) -> MiningStrategy {
         match self {
@@ -74,6 +143,8 @@ pub struct SmartMiner {
     pub ores_found: u32,
     pub blocks_mined: u32,
     pub efficiency_score: f32, // ores_found / blocks_mined
+    #[serde(default)]
+    pub target_found: u32, // Blocks matching current_target this session
 }
 
 impl Default for SmartMiner {
@@ -87,16 +158,22 @@ impl Default for SmartMiner {
             ores_found: 0,
             blocks_mined: 0,
             efficiency_score: 0.0,
+            target_found: 0,
         }
     }
 }
 
 impl SmartMiner {
-    /// Start a mining session for a specific target
-    pub fn start_mining(&mut self, target: MiningTarget, current_pos: [i32; 3]) {
+    /// Start a mining session for a specific target. False if our pickaxe can't harvest it
+    pub fn start_mining(&mut self, target: MiningTarget, current_pos: [i32; 3], inventory: &Inventory) -> bool {
+        let tier = inventory.pickaxe_tier();
+        if tier < target.min_pickaxe_tier() {
+            info!("[MINER] ⛏️ Picareta fraca pra {:?} (tier {} < {})", target, tier, target.min_pickaxe_tier());
+            return false;
+        }
         let strategy = target.best_strategy();
         let y_target = target.optimal_y();
-        println!(
+        info!(
             "[MINER] 🪨 Starting {:?} for {:?}. Target Y: {}. Current Y: {}",
             strategy, target, y_target, current_pos[1]
         );
@@ -107,6 +184,21 @@ impl SmartMiner {
         self.tunnel_progress = 0;
         self.ores_found = 0;
         self.blocks_mined = 0;
+        self.target_found = 0;
+        true
+    }
+
+    /// Strip or branch mining: a corridor at a fixed Y, driven by next_block_to_mine
+    pub fn tunnels(&self) -> bool {
+        matches!(self.current_strategy, Some(MiningStrategy::StripMine | MiningStrategy::BranchMine))
+    }
+
+    /// Lava ahead: start a fresh corridor from here, a quarter turn over
+    pub fn turn(&mut self, here: [i32; 3]) {
+        self.tunnel_direction = (self.tunnel_direction + 1) % 4;
+        self.tunnel_progress = 0;
+        self.mining_origin = Some(here);
+        info!("[MINER] ↪️ Lava no caminho, virando o túnel");
     }
 
     /// Get next block to mine based on strategy
@@ -168,7 +260,7 @@ impl SmartMiner {
         };
 
         if self.tunnel_progress > 200 {
-            println!("[MINER] Tunnel complete (200 blocks). Switching direction.");
+            info!("[MINER] Tunnel complete (200 blocks). Switching direction.");
             self.tunnel_direction = (self.tunnel_direction + 1) % 4;
             self.tunnel_progress = 0;
         }
@@ -176,6 +268,19 @@ impl SmartMiner {
         pos
     }
 
+    /// Count one broken block, ore or not
+    pub fn record(&mut self, block: &str) {
+        if self.current_target.matches(block) {
+            self.target_found += 1;
+        }
+        if is_ore(block) { self.record_ore_found() } else { self.record_block_mined() }
+    }
+
+    /// Dug enough of a specific target. "Any" is never done
+    pub fn satisfied(&self) -> bool {
+        self.current_target != MiningTarget::Any && self.target_found >= ENOUGH
+    }
+
     pub fn record_ore_found(&mut self) {
         self.ores_found += 1;
         self.blocks_mined += 1;
@@ -203,3 +308,92 @@ impl SmartMiner {
         }
     }
 }
+
+/// Ores, ancient debris — anything worth a detour when a face shows it
+pub fn is_ore(block: &str) -> bool {
+    block.ends_with("_ore") || block == "ancient_debris"
+}
+
+/// Block name at `pos` without the namespace, "air" for any air. None if the chunk isn't loaded
+pub fn block_at(bot: &Client, pos: BlockPos) -> Option<String> {
+    let state = bot.world().read().get_block_state(pos)?;
+    if state.is_air() {
+        return Some("air".into());
+    }
+    Some(BlockKind::from(state).to_string().trim_start_matches("minecraft:").to_string())
+}
+
+/// The six blocks touching `pos`
+pub fn faces(pos: BlockPos) -> [BlockPos; 6] {
+    [pos.up(1), pos.down(1), pos.north(1), pos.south(1), pos.east(1), pos.west(1)]
+}
+
+/// Lava touching `pos` — digging it out would let the lava in
+pub fn lava_next_to(bot: &Client, pos: BlockPos) -> bool {
+    faces(pos).iter().any(|p| block_at(bot, *p).as_deref() == Some("lava"))
+}
+
+/// Nearest block within LOOK_RADIUS the target wants, from the loaded chunks
+pub fn nearest_wanted(bot: &Client, target: &MiningTarget) -> Option<BlockPos> {
+    let here = BlockPos::from(bot.position());
+    let r = LOOK_RADIUS;
+    let mut best: Option<(i32, BlockPos)> = None;
+    for dx in -r..=r {
+        for dy in -r..=r {
+            for dz in -r..=r {
+                let pos = BlockPos::new(here.x + dx, here.y + dy, here.z + dz);
+                let d = dx.abs() + dy.abs() + dz.abs();
+                if best.is_some_and(|(b, _)| b <= d) {
+                    continue;
+                }
+                if block_at(bot, pos).is_some_and(|b| target.matches(&b)) {
+                    best = Some((d, pos));
+                }
+            }
+        }
+    }
+    best.map(|(_, pos)| pos)
+}
+
+/// How digging one block went
+#[derive(Debug, Clone, PartialEq)]
+pub enum Dig {
+    Broke(String), // What it was
+    Empty,         // Air already, nothing to do
+    Lava,          // Left alone: lava behind it
+    Unbreakable,   // Bedrock and friends, no tool gets through
+    Failed,
+}
+
+/// Survival can't break these
+const UNBREAKABLE: &[&str] = &["bedrock", "barrier", "end_portal_frame", "reinforced_deepslate", "command_block"];
+
+/// Break the block at `pos` with the best tool we carry. Leaves it if lava is next to it or it can't break
+pub async fn dig(bot: &Client, pos: BlockPos) -> Dig {
+    let Some(block) = block_at(bot, pos) else { return Dig::Failed };
+    if matches!(block.as_str(), "air" | "cave_air") {
+        return Dig::Empty;
+    }
+    if UNBREAKABLE.contains(&block.as_str()) {
+        return Dig::Unbreakable;
+    }
+    if block == "lava" || lava_next_to(bot, pos) {
+        return Dig::Lava;
+    }
+    let state = bot.world().read().get_block_state(pos);
+    if let Some(state) = state
+        && let Some(tool) = Inventory::read(bot).best_tool_for(state)
+    {
+        crate::systems::inventory::equip(bot, tool.slot);
+        bot.wait_ticks(2).await;
+    }
+    bot.look_at(Vec3::new(pos.x as f64 + 0.5, pos.y as f64 + 0.5, pos.z as f64 + 0.5));
+    if tokio::time::timeout(DIG_TIMEOUT, bot.mine(pos)).await.is_err() {
+        return Dig::Failed;
+    }
+    bot.wait_ticks(2).await;
+    match block_at(bot, pos).as_deref() {
+        Some("air" | "cave_air") => Dig::Broke(block),
+        _ => Dig::Failed,
+    }
+}
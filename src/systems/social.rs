@@ -1,6 +1,12 @@
 use serde::{Deserialize, Serialize};
-use crate::cognitive::memory::{SocialMemory, Relationship};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use crate::cognitive::memory::{EpisodeType, EpisodicMemory, PlayerProfile, SocialMemory, Relationship};
+use crate::systems::presence::PresenceTracker;
+use crate::systems::conversation::{self, Conversation};
+use crate::systems::truce::Truce;
 use rand::Rng;
+use tracing::info;
 
 // ============================================================
 // SOCIAL ENGINE — Natural social behavior
@@ -9,24 +15,98 @@ use rand::Rng;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SocialEngine {
     pub nearby_players: Vec<String>,
-    pub conversations_active: Vec<String>, // Player names we're chatting with
+    pub conversations: HashMap<String, Conversation>, // Who we're chatting with, and where it's at
     pub total_messages_sent: u32,
     pub help_requests_made: u32,
     pub help_threshold: u32, // How many failures before asking
+    pub last_join_greeting: HashMap<String, DateTime<Utc>>, // Don't re-greet relog spam
+    pub presence: PresenceTracker,
+    pub greeted_this_session: Vec<String>,
+    pub last_enemy_warning: HashMap<String, DateTime<Utc>>,
+    pub pending_help: HashMap<String, HelpRequest>, // item → who we asked
+    pub recent_needs: Vec<PlayerNeed>,
+    pub last_gift: Option<DateTime<Utc>>,
+    pub last_small_talk: HashMap<String, DateTime<Utc>>,
+    pub newcomers: HashMap<String, DateTime<Utc>>, // Welcomed first-timers still owed a starter kit
+    pub truces: HashMap<String, Truce>, // De-escalation talks with rivals/enemies
 }
 
+/// Someone said in chat they're short on something
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerNeed {
+    pub player: String,
+    pub item: String,
+    pub quantity: u32,
+    pub said_at: DateTime<Utc>,
+}
+
+/// Needs older than this are probably sorted already
+const NEED_MEMORY_MINS: i64 = 15;
+/// At most one spontaneous gift per this many minutes
+const GIFT_COOLDOWN_MINS: i64 = 15;
+/// Always keep at least this many of whatever we give away
+const GIFT_RESERVE: u32 = 8;
+/// Don't pester the same friend with small talk more often than this
+const SMALL_TALK_COOLDOWN_MINS: i64 = 20;
+
+/// What a first-timer gets if we have it to spare (item, quantity)
+const STARTER_KIT: &[(&str, u32)] = &[
+    ("bread", 8),
+    ("cooked_beef", 8),
+    ("stone_pickaxe", 1),
+    ("stone_axe", 1),
+    ("oak_log", 16),
+    ("torch", 16),
+];
+/// A newcomer who never walks up to us within this long doesn't get the kit
+const NEWCOMER_KIT_MINS: i64 = 30;
+
+/// We asked someone for an item and are waiting to see if they come through
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HelpRequest {
+    pub player: String,
+    pub item: String,
+    pub asked_at: DateTime<Utc>,
+}
+
+/// How long a friend has to help before we count it as a no
+const HELP_WAIT_MINS: i64 = 10;
+
+/// What to do about a player who just walked up
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProximityReaction {
+    Greet(String),
+    EnemyAtBase(String),
+}
+
+/// Players inside this radius count as "nearby"
+pub const GREETING_RADIUS: f64 = 10.0;
+
 impl Default for SocialEngine {
     fn default() -> Self {
         Self {
             nearby_players: vec![],
-            conversations_active: vec![],
+            conversations: HashMap::new(),
             total_messages_sent: 0,
             help_requests_made: 0,
             help_threshold: 3,
+            last_join_greeting: HashMap::new(),
+            presence: PresenceTracker::default(),
+            greeted_this_session: vec![],
+            last_enemy_warning: HashMap::new(),
+            pending_help: HashMap::new(),
+            recent_needs: vec![],
+            last_gift: None,
+            last_small_talk: HashMap::new(),
+            newcomers: HashMap::new(),
+            truces: HashMap::new(),
         }
     }
 }
 
+/// Minimum gap between two join greetings for the same player
+const JOIN_GREETING_COOLDOWN_MINS: i64 = 10;
+
 impl SocialEngine {
     /// Decide how to respond to a message based on relationship
     pub fn should_respond(&self, player: &str, social: &SocialMemory) -> ResponseStyle {
@@ -95,8 +175,8 @@ impl SocialEngine {
             }
             Some(p) if p.relationship == Relationship::Enemy => {
                 let greetings = [
-                    format!("..."),
-                    format!("la vem"),
+                    "...".to_string(),
+                    "la vem".to_string(),
                 ];
                 greetings[rng.gen_range(0..greetings.len())].clone()
             }
@@ -104,6 +184,132 @@ impl SocialEngine {
         }
     }
 
+    /// Someone logged in (presence already has them). Returns a greeting for friends
+    pub fn on_player_join(&mut self, player: &str, social: &SocialMemory) -> Option<String> {
+        let is_friend = social.players.get(player)
+            .is_some_and(|p| matches!(p.relationship, Relationship::Friend | Relationship::BestFriend));
+        if !is_friend {
+            return None;
+        }
+        let recently = self.last_join_greeting.get(player)
+            .is_some_and(|t| Utc::now().signed_duration_since(*t).num_minutes() < JOIN_GREETING_COOLDOWN_MINS);
+        if recently {
+            return None;
+        }
+        self.last_join_greeting.insert(player.to_string(), Utc::now());
+        Some(self.generate_greeting(player, social))
+    }
+
+    /// Someone logged off. Notes it, and sometimes comments if they mattered to us
+    pub fn on_player_leave(&mut self, player: &str, social: &mut SocialMemory) -> Option<String> {
+        self.nearby_players.retain(|p| p != player);
+        self.conversations.remove(player);
+        if let Some(session) = self.presence.on_leave(player) {
+            info!("[SOCIAL] ⏱️ {} ficou {}min online", player, session.num_minutes());
+        }
+
+        let profile = social.players.get_mut(player)?;
+        profile.last_seen = Utc::now();
+
+        let mut rng = rand::thread_rng();
+        match profile.relationship {
+            Relationship::BestFriend | Relationship::Friend if rng.r#gen::<f32>() < 0.5 => {
+                let lines = [
+                    format!("o {} caiu", player),
+                    format!("xi, {} saiu", player),
+                    format!("f pro {}", player),
+                ];
+                Some(lines[rng.gen_range(0..lines.len())].clone())
+            }
+            Relationship::Enemy if rng.r#gen::<f32>() < 0.3 => Some("finalmente foi embora".into()),
+            _ => None,
+        }
+    }
+
+    /// Feed in every visible player this tick; returns reactions for newcomers
+    pub fn update_proximity(
+        &mut self,
+        visible: &[(String, f64, bool)], // (name, distance to us, near our base)
+        social: &SocialMemory,
+    ) -> Vec<(String, ProximityReaction)> {
+        let mut reactions = vec![];
+        for (name, distance, near_base) in visible {
+            let is_enemy = social.players.get(name)
+                .is_some_and(|p| p.relationship == Relationship::Enemy);
+
+            if is_enemy && *near_base {
+                let warned_recently = self.last_enemy_warning.get(name)
+                    .is_some_and(|t| Utc::now().signed_duration_since(*t).num_minutes() < 5);
+                if !warned_recently {
+                    self.last_enemy_warning.insert(name.clone(), Utc::now());
+                    let mut rng = rand::thread_rng();
+                    let warnings = [
+                        format!("{} oq vc ta fazendo perto da minha base?", name),
+                        format!("{} se afasta daqui", name),
+                        format!("to de olho em vc {}", name),
+                    ];
+                    reactions.push((name.clone(), ProximityReaction::EnemyAtBase(
+                        warnings[rng.gen_range(0..warnings.len())].clone()
+                    )));
+                }
+            } else if *distance <= GREETING_RADIUS
+                && social.players.contains_key(name)
+                && !self.greeted_this_session.contains(name)
+                && self.should_greet(name, social)
+            {
+                self.greeted_this_session.push(name.clone());
+                reactions.push((name.clone(), ProximityReaction::Greet(self.generate_greeting(name, social))));
+            }
+        }
+        self.nearby_players = visible.iter()
+            .filter(|(_, d, _)| *d <= GREETING_RADIUS)
+            .map(|(n, _, _)| n.clone())
+            .collect();
+        reactions
+    }
+
+    // === Conversations ===
+
+    pub fn is_conversing(&self, player: &str) -> bool {
+        self.conversations.get(player).is_some_and(|c| !c.is_idle())
+    }
+
+    /// We're answering them: start a conversation or move the current one along
+    pub fn on_conversation_message(&mut self, player: &str, message: &str) {
+        if !self.is_conversing(player) {
+            self.conversations.insert(player.to_string(), Conversation::new(player));
+        }
+        if let Some(convo) = self.conversations.get_mut(player) {
+            convo.on_their_message(message);
+        }
+    }
+
+    pub fn on_our_reply(&mut self, player: &str, reply: &str) {
+        let ended = self.conversations.get_mut(player)
+            .is_some_and(|c| c.on_our_reply(reply));
+        if ended {
+            self.conversations.remove(player);
+            info!("[CONVO] 👋 Conversa com {} encerrada", player);
+        }
+    }
+
+    /// Drop conversations that just fizzled out
+    pub fn expire_conversations(&mut self) {
+        self.conversations.retain(|player, c| {
+            if c.is_idle() {
+                info!("[CONVO] 💤 Conversa com {} morreu ({} msgs)", player, c.turns);
+            }
+            !c.is_idle()
+        });
+    }
+
+    pub fn conversation_hint(&self, player: &str) -> String {
+        self.conversations.get(player)
+            .filter(|c| !c.is_idle())
+            .map(|c| c.describe())
+            .unwrap_or_default()
+    }
+
     /// Should the bot ask for help?
     pub fn should_ask_for_help(&self, _task: &str, failures: u32) -> bool {
         failures >= self.help_threshold
@@ -125,10 +331,162 @@ impl SocialEngine {
             format!("{} eu tentei de tudo mas n to achando {}, ce me ajuda?", player, item),
         ];
 
-        self.help_requests_made;
         Some(requests[rng.gen_range(0..requests.len())].clone())
     }
 
+    /// Most trusted friend who's online, not AFK and hasn't been flaking on us
+    pub fn pick_helper(&self, social: &SocialMemory) -> Option<String> {
+        let asked: Vec<&String> = self.pending_help.values().map(|r| &r.player).collect();
+        self.presence.active_players().into_iter()
+            .filter(|name| !asked.contains(name))
+            .filter_map(|name| social.players.get(name.as_str()))
+            .filter(|p| p.trust_level >= 40)
+            .filter(|p| p.help_requests_made <= p.help_requests_fulfilled + 2)
+            .max_by_key(|p| p.trust_level)
+            .map(|p| p.name.clone())
+    }
+
+    /// Actually ask — returns the chat line and starts waiting on them.
+    /// The caller counts the request on their profile
+    pub fn ask_for_help(&mut self, player: &str, item: &str, social: &SocialMemory) -> Option<String> {
+        let request = self.generate_help_request(player, item, social)?;
+        self.help_requests_made += 1;
+        self.pending_help.insert(item.to_string(), HelpRequest {
+            player: player.to_string(),
+            item: item.to_string(),
+            asked_at: Utc::now(),
+        });
+        info!("[SOCIAL] 🙏 Pedindo {} pro {}", item, player);
+        Some(request)
+    }
+
+    /// They dropped us something — was it what we asked them for?
+    pub fn on_help_received(&mut self, player: &str, item: &str) -> Option<HelpRequest> {
+        let matches = self.pending_help.get(item).is_some_and(|r| r.player == player);
+        if matches { self.pending_help.remove(item) } else { None }
+    }
+
+    /// Requests nobody answered in time
+    pub fn expire_help_requests(&mut self) -> Vec<HelpRequest> {
+        let expired: Vec<String> = self.pending_help.iter()
+            .filter(|(_, r)| Utc::now().signed_duration_since(r.asked_at).num_minutes() >= HELP_WAIT_MINS)
+            .map(|(item, _)| item.clone())
+            .collect();
+        expired.iter().filter_map(|item| self.pending_help.remove(item)).collect()
+    }
+
+    // === Generosity ===
+
+    pub fn note_need(&mut self, player: &str, item: &str, quantity: u32) {
+        self.recent_needs.retain(|n| !(n.player == player && n.item == item));
+        self.recent_needs.push(PlayerNeed {
+            player: player.to_string(),
+            item: item.to_string(),
+            quantity,
+            said_at: Utc::now(),
+        });
+        info!("[SOCIAL] 📝 {} tá precisando de {} x{}", player, item, quantity);
+    }
+
+    /// A friend's recent need we can cover from surplus (keeping a reserve for ourselves)
+    pub fn gift_candidate(&mut self, social: &SocialMemory, holdings: &HashMap<String, u32>) -> Option<PlayerNeed> {
+        let cooling_down = self.last_gift
+            .is_some_and(|t| Utc::now().signed_duration_since(t).num_minutes() < GIFT_COOLDOWN_MINS);
+        self.recent_needs.retain(|n| Utc::now().signed_duration_since(n.said_at).num_minutes() < NEED_MEMORY_MINS);
+        if cooling_down {
+            return None;
+        }
+
+        let index = self.recent_needs.iter().position(|need| {
+            let is_friend = social.players.get(&need.player)
+                .is_some_and(|p| matches!(p.relationship, Relationship::Friend | Relationship::BestFriend));
+            let have = holdings.get(&need.item).copied().unwrap_or(0);
+            is_friend
+                && self.presence.online.contains_key(&need.player)
+                && have >= need.quantity + GIFT_RESERVE
+        })?;
+        self.last_gift = Some(Utc::now());
+        Some(self.recent_needs.remove(index))
+    }
+
+    pub fn generate_gift_line(&self, player: &str, item: &str) -> String {
+        let mut rng = rand::thread_rng();
+        let item = item.replace('_', " ");
+        let lines = [
+            format!("{} vc tava precisando de {} ne? toma ai", player, item),
+            format!("toma {}, presente kkk", player),
+            format!("sobrou {} aqui, pega ai {}", item, player),
+            format!("{} n precisa devolver n, é presente", player),
+        ];
+        lines[rng.gen_range(0..lines.len())].clone()
+    }
+
+    // === Newcomers ===
+
+    /// A never-seen name showed up: welcome them, and if we can spare a starter kit, owe them one
+    pub fn welcome_newcomer(&mut self, player: &str, safe_spot: Option<[i32; 3]>, holdings: &HashMap<String, u32>) -> String {
+        let kit = !spare_starter_kit(holdings).is_empty();
+        if kit {
+            self.newcomers.insert(player.to_string(), Utc::now());
+        }
+        let mut rng = rand::thread_rng();
+        let hello = [
+            format!("bem vindo {}! primeira vez aqui ne?", player),
+            format!("eai {}, novo no server? seja bem vindo", player),
+            format!("opa {} chegou gente nova kkk bem vindo", player),
+        ];
+        let hello = hello[rng.gen_range(0..hello.len())].clone();
+        let hello = match safe_spot {
+            Some([x, y, z]) => format!("{} se precisar de um lugar seguro cola em {} {} {}.", hello, x, y, z),
+            None => hello,
+        };
+        if kit {
+            format!("{} passa aqui perto q eu te dou uns itens pra começar", hello)
+        } else {
+            hello
+        }
+    }
+
+    /// A welcomed newcomer walked up: what of the starter kit we can spare.
+    /// Returns (player, items) and drops them from the owed list either way
+    pub fn starter_kit_for_nearby(&mut self, holdings: &HashMap<String, u32>) -> Option<(String, Vec<(String, u32)>)> {
+        self.newcomers.retain(|_, t| Utc::now().signed_duration_since(*t).num_minutes() < NEWCOMER_KIT_MINS);
+        let player = self.newcomers.keys().find(|n| self.nearby_players.contains(n))?.clone();
+        self.newcomers.remove(&player);
+
+        let kit = spare_starter_kit(holdings);
+        if kit.is_empty() {
+            return None;
+        }
+        Some((player, kit))
+    }
+
+    // === Small talk ===
+
+    /// Idle next to a friend: maybe strike up a conversation about something we remember.
+    /// Returns (player, opener) and opens the conversation on our side
+    pub fn start_small_talk(&mut self, social: &SocialMemory, episodes: &EpisodicMemory) -> Option<(String, String)> {
+        let candidate = self.nearby_players.iter()
+            .filter(|name| !self.presence.is_afk(name) && !self.is_conversing(name))
+            .filter(|name| {
+                self.last_small_talk.get(*name)
+                    .is_none_or(|t| Utc::now().signed_duration_since(*t).num_minutes() >= SMALL_TALK_COOLDOWN_MINS)
+            })
+            .filter_map(|name| social.players.get(name))
+            .find(|p| matches!(p.relationship, Relationship::Friend | Relationship::BestFriend))?;
+
+        let (opener, topic) = small_talk_opener(candidate, episodes)?;
+        let player = candidate.name.clone();
+        self.last_small_talk.insert(player.clone(), Utc::now());
+
+        let mut convo = Conversation::new(&player);
+        convo.topic = topic;
+        convo.on_our_reply(&opener);
+        self.conversations.insert(player.clone(), convo);
+        info!("[SOCIAL] 💭 Puxando assunto com {}", player);
+        Some((player, opener))
+    }
+
     /// Should we warn a player about danger?
     pub fn should_warn_player(&self, player: &str, social: &SocialMemory) -> bool {
         social
@@ -140,18 +498,69 @@ impl SocialEngine {
 
     pub fn context_summary(&self) -> String {
         format!(
-            "Jogadores próximos: {} | Msgs enviadas: {} | Pedidos de ajuda: {}",
+            "Jogadores próximos: {} | Online: {} | Msgs enviadas: {} | Pedidos de ajuda: {}",
             if self.nearby_players.is_empty() {
                 "nenhum".into()
             } else {
                 self.nearby_players.join(", ")
             },
+            self.presence.context_summary(),
             self.total_messages_sent,
             self.help_requests_made,
         )
     }
 }
 
+/// The starter kit items we have enough of to give away and still keep the reserve
+fn spare_starter_kit(holdings: &HashMap<String, u32>) -> Vec<(String, u32)> {
+    STARTER_KIT.iter()
+        .filter(|(item, quantity)| holdings.get(*item).copied().unwrap_or(0) >= quantity + GIFT_RESERVE)
+        .map(|(item, quantity)| (item.to_string(), *quantity))
+        .collect()
+}
+
+/// Something to say grounded in what they told us or what we did together
+fn small_talk_opener(profile: &PlayerProfile, episodes: &EpisodicMemory) -> Option<(String, Option<String>)> {
+    let mut rng = rand::thread_rng();
+    let player = &profile.name;
+
+    // What they were up to last time they talked
+    let last_topic = profile.last_messages.iter().rev()
+        .find_map(|m| conversation::detect_topic(m));
+    if let Some(topic) = last_topic {
+        let options: Vec<String> = match topic {
+            "mineração" => vec![format!("{} conseguiu os minerio q vc tava atras?", player), format!("e ai {}, achou diamante?", player)],
+            "farms" => vec![format!("{} e a farm, ficou pronta?", player), format!("{} a farm ta rendendo?", player)],
+            "construção" => vec![format!("{} terminou a build?", player), format!("e a casa {}, ficou como?", player)],
+            "redstone" => vec![format!("{} e a redstone, funcionou?", player)],
+            "troca" => vec![format!("{} ainda ta precisando trocar algo?", player)],
+            "pvp" => vec![format!("{} e a treta, resolveu?", player)],
+            "encantamentos" => vec![format!("{} conseguiu o encantamento q queria?", player)],
+            "progressão" => vec![format!("{} ja foi pro nether?", player)],
+            _ => vec![format!("{} e o servidor, ta laggando ai tb?", player)],
+        };
+        return Some((options[rng.gen_range(0..options.len())].clone(), Some(topic.to_string())));
+    }
+
+    // Something we lived through together
+    let episode = episodes.involving(player, 5).into_iter()
+        .find(|e| Utc::now().signed_duration_since(e.timestamp).num_days() <= 3)?;
+    let when = match Utc::now().signed_duration_since(episode.timestamp).num_days() {
+        0 => "hj",
+        1 => "ontem",
+        _ => "aqueles dias",
+    };
+    let line = match episode.event_type {
+        EpisodeType::ReceivedGift => format!("{} valeu dnv pelo q vc me deu {}", player, when),
+        EpisodeType::GaveGift => format!("{} usou aquilo q te dei {}?", player, when),
+        EpisodeType::TradeCompleted => format!("{} bora fazer outra troca q nem {}?", player, when),
+        EpisodeType::WasAttacked | EpisodeType::Kill => format!("{} lembra da treta de {} kkk", player, when),
+        EpisodeType::BuiltStructure | EpisodeType::FoundResource => format!("{} viu oq eu fiz {}?", player, when),
+        _ => return None,
+    };
+    Some((line, None))
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ResponseStyle {
     Friendly,   // Talkative, uses emoji, shares info
@@ -160,3 +569,49 @@ pub enum ResponseStyle {
     Cold,       // Minimal interaction
     Hostile,    // Aggressive or ignoring
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gifts_keep_the_reserve() {
+        let mut social = SocialMemory::default();
+        social.get_or_create("Steve").relationship = Relationship::Friend;
+        let holdings = |n: u32| HashMap::from([("iron_ingot".to_string(), n)]);
+        let candidate = |have: u32| {
+            let mut engine = SocialEngine::default();
+            engine.presence.on_join("Steve");
+            engine.note_need("Steve", "iron_ingot", 20);
+            engine.gift_candidate(&social, &holdings(have)).is_some()
+        };
+        // 20 asked + 8 kept back, not 20 + 20
+        assert!(!candidate(20 + GIFT_RESERVE - 1));
+        assert!(candidate(20 + GIFT_RESERVE));
+    }
+
+    #[test]
+    fn only_new_tab_entries_are_joins() {
+        let mut engine = SocialEngine::default();
+        let mut social = SocialMemory::default();
+        assert!(engine.presence.on_join("Steve"));
+        // Server re-sent the entry: same session, not a second join
+        assert!(!engine.presence.on_join("Steve"));
+        engine.on_player_leave("Steve", &mut social);
+        assert!(engine.presence.on_join("Steve"));
+    }
+
+    #[test]
+    fn kit_promised_only_when_we_have_it() {
+        let mut engine = SocialEngine::default();
+        let broke = engine.welcome_newcomer("Novato", None, &HashMap::new());
+        assert!(!broke.contains("itens"));
+        assert!(engine.newcomers.is_empty());
+
+        let stocked: HashMap<String, u32> = STARTER_KIT.iter().map(|(item, n)| (item.to_string(), n + GIFT_RESERVE)).collect();
+        let line = engine.welcome_newcomer("Novato", None, &stocked);
+        assert!(line.contains("itens"));
+        engine.nearby_players.push("Novato".into());
+        assert!(engine.starter_kit_for_nearby(&stocked).is_some());
+    }
+}
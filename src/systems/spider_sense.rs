@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
-use crate::cognitive::memory::SocialMemory;
+use crate::systems::player_model::Activity;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tracing::info;
 // use rand::Rng;
 
 // ============================================================
@@ -8,6 +11,9 @@ use crate::cognitive::memory::SocialMemory;
 // "Aquele cara com trust -50 tá vindo com lava na mão"
 // ============================================================
 
+/// Closing faster than this (blocks/s) counts as coming at us — a stroll is ~4.3, drifting around is under 1
+const APPROACH_SPEED: f64 = 1.0;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum ThreatLevel {
     None,
@@ -26,6 +32,13 @@ pub struct PredictedThreat {
     pub time_to_impact_ms: u64,
 }
 
+impl PredictedThreat {
+    /// Put it on the bus — the motor and the event stream take it from there
+    pub fn publish(&self, player: Option<&str>) {
+        crate::bus::publish(crate::bus::ThreatDetected { threat: self.clone(), player: player.map(str::to_string) });
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum PredictionType {
     PlayerGriefing,       // Player with lava/TNT approaching
@@ -54,12 +67,62 @@ pub enum PredictedAction {
     WarnChat(String),     // Warn in chat
 }
 
+/// What kind of creepy behavior a tracked player is showing
+#[derive(Debug, Clone, PartialEq)]
+pub enum StalkingBehavior {
+    SneakingNearby,   // Crouched and hovering close to us
+    Following,        // Stays near while we walk around
+    CirclingBase,     // Walking laps around our home
+}
+
+/// Per-player observation history used to spot stalkers
+#[derive(Debug, Clone)]
+pub struct StalkerTrack {
+    pub last_seen: Instant,
+    pub sneak_ticks: u32,          // Ticks spent crouched within 10 blocks
+    pub follow_ticks: u32,         // Ticks spent within 24 blocks of us
+    pub bot_travel: f64,           // How far WE walked while they stayed close
+    pub last_bot_pos: [f64; 3],
+    pub base_angle: Option<f64>,   // Last angle around home (radians)
+    pub base_sweep: f64,           // Accumulated angle swept around home
+    pub last_callout: Option<Instant>,
+}
+
+impl StalkerTrack {
+    fn new(bot_pos: [f64; 3]) -> Self {
+        Self {
+            last_seen: Instant::now(),
+            sneak_ticks: 0,
+            follow_ticks: 0,
+            bot_travel: 0.0,
+            last_bot_pos: bot_pos,
+            base_angle: None,
+            base_sweep: 0.0,
+            last_callout: None,
+        }
+    }
+
+    /// Strongest creepy behavior seen so far, if any crossed its threshold
+    pub fn behavior(&self) -> Option<StalkingBehavior> {
+        if self.base_sweep.abs() >= std::f64::consts::TAU {
+            Some(StalkingBehavior::CirclingBase)
+        } else if self.follow_ticks >= 1200 && self.bot_travel >= 60.0 {
+            Some(StalkingBehavior::Following) // ~1 min on our tail while we moved 60+ blocks
+        } else if self.sneak_ticks >= 200 {
+            Some(StalkingBehavior::SneakingNearby) // ~10s crouched next to us
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct SpiderSense {
     pub active_predictions: Vec<PredictedThreat>,
     pub predictions_made: u32,
     pub predictions_correct: u32,
     pub accuracy: f32,
+    pub stalkers: HashMap<String, StalkerTrack>,
 }
 
 impl SpiderSense {
@@ -69,9 +132,9 @@ impl SpiderSense {
         player: &str,
         held_item: &str,
         distance: f64,
-        approaching: bool,
+        closing_speed: f64,
+        activity: Option<Activity>,
         trust: i32,
-        _social: &SocialMemory,
     ) -> Option<PredictedThreat> {
         let dangerous_items = [
             "lava_bucket", "flint_and_steel", "tnt", "fire_charge",
@@ -85,6 +148,12 @@ impl SpiderSense {
 
         let is_dangerous_item = dangerous_items.iter().any(|i| held_item.contains(i));
         let is_weapon = weapons.iter().any(|i| held_item.contains(i));
+        // Someone busy mining/building with a sword on the hotbar isn't coming for us
+        let busy = activity.is_some_and(|a| a.is_busy());
+        let stalking = activity == Some(Activity::StalkingUs);
+        let approaching = closing_speed > APPROACH_SPEED;
+        // Real closing speed when they're coming; a flat-out sprint when they aren't yet
+        let time_to_impact_ms = if approaching { (distance / closing_speed * 1000.0) as u64 } else { (distance * 200.0) as u64 };
 
         // Enemy + dangerous item + approaching = CRITICAL
         if trust < -20 && is_dangerous_item && approaching && distance < 30.0 {
@@ -93,12 +162,12 @@ impl SpiderSense {
                 level: ThreatLevel::Critical,
                 description: format!("{} (trust:{}) vindo com {} a {}m", player, trust, held_item, distance as i32),
                 recommended_action: PredictedAction::AttackFirst,
-                time_to_impact_ms: (distance * 200.0) as u64, // ~200ms per block sprint
+                time_to_impact_ms,
             });
         }
 
         // Low trust + weapon + approaching
-        if trust < 10 && is_weapon && approaching && distance < 20.0 {
+        if trust < 10 && is_weapon && (approaching || stalking) && !busy && distance < 20.0 {
             return Some(PredictedThreat {
                 threat_type: PredictionType::PlayerAmbush,
                 level: ThreatLevel::High,
@@ -108,12 +177,12 @@ impl SpiderSense {
                 } else {
                     PredictedAction::Sprint
                 },
-                time_to_impact_ms: (distance * 200.0) as u64,
+                time_to_impact_ms,
             });
         }
 
         // Unknown player sneaking nearby
-        if trust == 20 && distance < 15.0 && is_weapon {
+        if trust == 20 && distance < 15.0 && is_weapon && !busy {
             return Some(PredictedThreat {
                 threat_type: PredictionType::PlayerAmbush,
                 level: ThreatLevel::Medium,
@@ -220,6 +289,127 @@ impl SpiderSense {
         None
     }
 
+    /// Feed one tick of observation about a visible player
+    pub fn observe_player(
+        &mut self,
+        player: &str,
+        player_pos: [f64; 3],
+        sneaking: bool,
+        bot_pos: [f64; 3],
+        home: Option<[i32; 3]>,
+    ) {
+        let track = self.stalkers
+            .entry(player.to_string())
+            .or_insert_with(|| StalkerTrack::new(bot_pos));
+
+        // Lost sight for a while → start a fresh track
+        if track.last_seen.elapsed() > Duration::from_secs(30) {
+            let last_callout = track.last_callout;
+            *track = StalkerTrack::new(bot_pos);
+            track.last_callout = last_callout;
+        }
+        track.last_seen = Instant::now();
+
+        let dist = distance(player_pos, bot_pos);
+
+        if sneaking && dist < 10.0 {
+            track.sneak_ticks += 1;
+        } else {
+            track.sneak_ticks = track.sneak_ticks.saturating_sub(2);
+        }
+
+        let bot_step = distance(track.last_bot_pos, bot_pos);
+        track.last_bot_pos = bot_pos;
+        if dist < 24.0 {
+            track.follow_ticks += 1;
+            track.bot_travel += bot_step;
+        } else {
+            track.follow_ticks = 0;
+            track.bot_travel = 0.0;
+        }
+
+        // Angle swept around our home while they hang around it
+        match home {
+            Some(h) => {
+                let dx = player_pos[0] - h[0] as f64;
+                let dz = player_pos[2] - h[2] as f64;
+                let radius = (dx * dx + dz * dz).sqrt();
+                if (6.0..48.0).contains(&radius) {
+                    let angle = dz.atan2(dx);
+                    if let Some(prev) = track.base_angle {
+                        let mut delta = angle - prev;
+                        if delta > std::f64::consts::PI {
+                            delta -= std::f64::consts::TAU;
+                        } else if delta < -std::f64::consts::PI {
+                            delta += std::f64::consts::TAU;
+                        }
+                        track.base_sweep += delta;
+                    }
+                    track.base_angle = Some(angle);
+                } else {
+                    track.base_angle = None;
+                    track.base_sweep = 0.0;
+                }
+            }
+            None => {
+                track.base_angle = None;
+                track.base_sweep = 0.0;
+            }
+        }
+    }
+
+    /// Analyze: Is this player stalking us? Returns at most one callout per 5 min
+    pub fn predict_stalking(&mut self, player: &str, trust: i32) -> Option<PredictedThreat> {
+        // Close friends hanging around is normal
+        if trust >= 50 {
+            return None;
+        }
+
+        let track = self.stalkers.get_mut(player)?;
+        let behavior = track.behavior()?;
+
+        if track.last_callout.is_some_and(|t| t.elapsed() < Duration::from_secs(300)) {
+            return None;
+        }
+        track.last_callout = Some(Instant::now());
+        // Reset counters so the same evidence isn't reused for the next callout
+        track.sneak_ticks = 0;
+        track.follow_ticks = 0;
+        track.bot_travel = 0.0;
+        track.base_sweep = 0.0;
+
+        let (level, description, callout) = match behavior {
+            StalkingBehavior::SneakingNearby => (
+                ThreatLevel::Medium,
+                format!("{} agachado do meu lado faz tempo", player),
+                format!("{} pq ce ta agachadinho atras de mim? eu to vendo kkkk", player),
+            ),
+            StalkingBehavior::Following => (
+                if trust < 0 { ThreatLevel::High } else { ThreatLevel::Medium },
+                format!("{} me seguindo faz mais de 1 min", player),
+                format!("{} ta me seguindo pq mn? qual foi", player),
+            ),
+            StalkingBehavior::CirclingBase => (
+                if trust < 0 { ThreatLevel::High } else { ThreatLevel::Medium },
+                format!("{} rodando em volta da minha base", player),
+                format!("{} para de ficar rodando minha base, to de olho", player),
+            ),
+        };
+
+        Some(PredictedThreat {
+            threat_type: PredictionType::PlayerAmbush,
+            level,
+            description,
+            recommended_action: PredictedAction::WarnChat(callout),
+            time_to_impact_ms: 5000,
+        })
+    }
+
+    /// Drop tracks of players we haven't seen in a while
+    pub fn forget_stale_stalkers(&mut self) {
+        self.stalkers.retain(|_, t| t.last_seen.elapsed() < Duration::from_secs(600));
+    }
+
     /// Get the most urgent prediction
     pub fn most_urgent(&self) -> Option<&PredictedThreat> {
         self.active_predictions.iter().min_by_key(|p| {
@@ -235,7 +425,7 @@ impl SpiderSense {
 
     pub fn record_prediction(&mut self, threat: PredictedThreat) {
         self.predictions_made += 1;
-        println!("[SPIDER] 🕷️ {:?}: {} → {:?}", threat.level, threat.description, threat.recommended_action);
+        info!("[SPIDER] 🕷️ {:?}: {} → {:?}", threat.level, threat.description, threat.recommended_action);
         self.active_predictions.push(threat);
 
         // Keep only recent predictions
@@ -257,3 +447,10 @@ impl SpiderSense {
         )
     }
 }
+
+pub fn distance(a: [f64; 3], b: [f64; 3]) -> f64 {
+    let dx = a[0] - b[0];
+    let dy = a[1] - b[1];
+    let dz = a[2] - b[2];
+    (dx * dx + dy * dy + dz * dz).sqrt()
+}
@@ -0,0 +1,533 @@
+use azalea::prelude::*;
+use azalea::container::{ContainerClientExt, ContainerHandle};
+use azalea::inventory::operations::PickupClick;
+use azalea::pathfinder::goals::RadiusGoal;
+use azalea::registry::builtin::BlockKind;
+use azalea::{BlockPos, Vec3};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use crate::cognitive::memory::Memory;
+use crate::systems::economy::normalize_item_name;
+use crate::systems::inventory::{item_id, Inventory};
+use crate::systems::inventory_manager::{categorize_item, chest_sort_order, ItemCategory};
+use crate::systems::shulker::{self, ShulkerLabel};
+use tracing::{info, warn};
+
+// ============================================================
+// STORAGE — What's in every chest and barrel we know
+// "temos ferro?" → "tem 34 no baú do lado da porta"
+// ============================================================
+
+/// Blocks worth indexing
+const CONTAINERS: &[&str] = &["chest", "trapped_chest", "barrel"];
+/// Re-open a container after this long to refresh it
+const RESURVEY_MINS: i64 = 30;
+/// A chest holding this many kinds of items is "full of junk" for deposits
+const MESSY: usize = 12;
+/// Stuff we never put away — we'd just have to come back for it
+const KEEP_ON_US: &[ItemCategory] = &[
+    ItemCategory::Sword, ItemCategory::Pickaxe, ItemCategory::Axe, ItemCategory::Shovel,
+    ItemCategory::Ranged, ItemCategory::Torch, ItemCategory::Food, ItemCategory::Armor,
+];
+
+pub fn is_container(block: &str) -> bool {
+    CONTAINERS.contains(&block) || block.ends_with("shulker_box")
+}
+
+/// Where a chest goes in the index. A double chest is one inventory in two blocks, so both halves
+/// map to the left one (`half` and `facing` are the block's "type" and "facing" properties)
+pub fn index_pos(pos: [i32; 3], facing: &str, half: &str) -> [i32; 3] {
+    if half != "right" {
+        return pos;
+    }
+    // The left half sits counterclockwise of the right one, looking down
+    let [dx, dz] = match facing {
+        "north" => [-1, 0],
+        "west" => [0, 1],
+        "south" => [1, 0],
+        "east" => [0, -1],
+        _ => [0, 0],
+    };
+    [pos[0] + dx, pos[1], pos[2] + dz]
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredContainer {
+    pub pos: [i32; 3],
+    pub kind: String,
+    pub items: HashMap<String, u32>,
+    pub surveyed_at: Option<DateTime<Utc>>, // None = seen, never opened
+    #[serde(default)]
+    pub shulkers: Vec<ShulkerLabel>, // Shulker boxes sitting in here, with what they hold
+}
+
+/// What we saw inside a container
+#[derive(Debug, Clone, Default)]
+pub struct Contents {
+    pub items: HashMap<String, u32>,
+    pub shulkers: Vec<ShulkerLabel>,
+}
+
+impl StoredContainer {
+    /// The category most of this chest is made of
+    fn main_category(&self) -> Option<ItemCategory> {
+        let mut tally: Vec<(ItemCategory, u32)> = vec![];
+        for (item, n) in &self.items {
+            let category = categorize_item(item);
+            match tally.iter_mut().find(|(c, _)| *c == category) {
+                Some((_, total)) => *total += n,
+                None => tally.push((category, *n)),
+            }
+        }
+        tally.into_iter().max_by_key(|(_, n)| *n).map(|(c, _)| c)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct StorageIndex {
+    pub containers: Vec<StoredContainer>,
+}
+
+fn dist(a: [i32; 3], b: [i32; 3]) -> f64 {
+    (((a[0] - b[0]).pow(2) + (a[1] - b[1]).pow(2) + (a[2] - b[2]).pow(2)) as f64).sqrt()
+}
+
+impl StorageIndex {
+    /// A container block showed up in a scan
+    pub fn discover(&mut self, pos: [i32; 3], kind: &str) -> bool {
+        if self.containers.iter().any(|c| c.pos == pos) {
+            return false;
+        }
+        self.containers.push(StoredContainer {
+            pos,
+            kind: kind.to_string(),
+            items: HashMap::new(),
+            surveyed_at: None,
+            shulkers: vec![],
+        });
+        true
+    }
+
+    /// The container is gone (broken, moved)
+    pub fn forget(&mut self, pos: [i32; 3]) {
+        self.containers.retain(|c| c.pos != pos);
+    }
+
+    /// We looked inside
+    pub fn record(&mut self, pos: [i32; 3], kind: &str, contents: Contents) {
+        self.discover(pos, kind);
+        if let Some(c) = self.containers.iter_mut().find(|c| c.pos == pos) {
+            c.items = contents.items;
+            c.shulkers = contents.shulkers;
+            c.surveyed_at = Some(Utc::now());
+        }
+    }
+
+    /// Closest container within `reach` we haven't looked in lately
+    pub fn needs_survey(&self, near: [i32; 3], reach: f64) -> Option<&StoredContainer> {
+        self.containers.iter()
+            .filter(|c| dist(c.pos, near) <= reach)
+            .filter(|c| c.surveyed_at.is_none_or(|t| Utc::now().signed_duration_since(t).num_minutes() >= RESURVEY_MINS))
+            .min_by(|a, b| dist(a.pos, near).total_cmp(&dist(b.pos, near)))
+    }
+
+    /// Everything we have stored, summed
+    pub fn totals(&self) -> HashMap<String, u32> {
+        let mut totals = HashMap::new();
+        for c in &self.containers {
+            let packed = c.shulkers.iter().flat_map(|s| s.items.iter());
+            for (item, n) in c.items.iter().chain(packed) {
+                *totals.entry(item.clone()).or_insert(0) += n;
+            }
+        }
+        totals
+    }
+
+    /// Chest with a shulker box holding the most of an item
+    pub fn find_shulkered(&self, item: &str) -> Option<([i32; 3], &ShulkerLabel)> {
+        self.containers.iter()
+            .flat_map(|c| c.shulkers.iter().map(move |s| (c.pos, s)))
+            .filter(|(_, s)| s.items.contains_key(item))
+            .max_by_key(|(_, s)| s.items[item])
+    }
+
+    /// Where an item is, biggest pile first
+    pub fn find(&self, item: &str) -> Vec<([i32; 3], u32)> {
+        let mut found: Vec<([i32; 3], u32)> = self.containers.iter()
+            .filter_map(|c| c.items.get(item).map(|n| (c.pos, *n)))
+            .collect();
+        found.sort_by_key(|(_, n)| std::cmp::Reverse(*n));
+        found
+    }
+
+    /// Where to put an item away: with its own kind, else with its category, else the tidiest nearby chest
+    pub fn deposit_target(&self, item: &str, near: [i32; 3]) -> Option<[i32; 3]> {
+        let surveyed = || self.containers.iter().filter(|c| c.surveyed_at.is_some());
+        let category = categorize_item(item);
+        surveyed()
+            .filter(|c| c.items.contains_key(item))
+            .min_by(|a, b| dist(a.pos, near).total_cmp(&dist(b.pos, near)))
+            .or_else(|| surveyed()
+                .filter(|c| c.main_category() == Some(category.clone()) && c.items.len() < MESSY)
+                .min_by(|a, b| dist(a.pos, near).total_cmp(&dist(b.pos, near))))
+            .or_else(|| surveyed()
+                .filter(|c| c.items.len() < MESSY)
+                .min_by_key(|c| (c.items.len(), dist(c.pos, near) as i64)))
+            .map(|c| c.pos)
+    }
+
+    /// Containers to empty for `quantity` of an item, nearest first → (pos, how many to take)
+    pub fn withdraw_plan(&self, item: &str, quantity: u32, near: [i32; 3]) -> Vec<([i32; 3], u32)> {
+        let mut piles = self.find(item);
+        piles.sort_by(|a, b| dist(a.0, near).total_cmp(&dist(b.0, near)));
+        let mut left = quantity;
+        let mut plan = vec![];
+        for (pos, have) in piles {
+            if left == 0 {
+                break;
+            }
+            let take = have.min(left);
+            plan.push((pos, take));
+            left -= take;
+        }
+        plan
+    }
+
+    /// For the LLM: stock of whatever items the message mentions ("temos ferro?")
+    pub fn context_for(&self, message: &str) -> String {
+        let words: Vec<String> = message.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .filter(|w| w.len() >= 3)
+            .map(normalize_item_name)
+            .collect();
+        let totals = self.totals();
+        let mut lines: Vec<String> = totals.iter()
+            .filter(|(item, _)| words.iter().any(|w| *item == w || item.starts_with(&format!("{}_", w))))
+            .filter_map(|(item, total)| {
+                let loose = self.find(item).first().map(|(pos, _)| (*pos, ""));
+                let (pos, packed) = loose.or_else(|| self.find_shulkered(item).map(|(pos, _)| (pos, " numa shulker")))?;
+                Some(format!("{}: {} guardado (mais em [{}, {}, {}]{})", item, total, pos[0], pos[1], pos[2], packed))
+            })
+            .collect();
+        if lines.is_empty() {
+            let surveyed = self.containers.iter().filter(|c| c.surveyed_at.is_some()).count();
+            return format!("{} baús conferidos, {} itens diferentes guardados.", surveyed, totals.len());
+        }
+        lines.sort();
+        lines.join("\n")
+    }
+}
+
+/// Open a container, count what's inside, close it. None if it wouldn't open
+pub async fn survey(bot: &Client, pos: [i32; 3]) -> Option<Contents> {
+    let container = bot.open_container_at(BlockPos::new(pos[0], pos[1], pos[2])).await?;
+    bot.wait_ticks(4).await; // Let the contents packet land
+    let contents = tally(&container.contents()?);
+    container.close();
+    judge_mess(bot, &contents);
+    Some(contents)
+}
+
+/// Diamonds next to rotten flesh? The inventory manager gets to say something about it
+fn judge_mess(bot: &Client, contents: &Contents) {
+    let items: Vec<String> = contents.items.keys().cloned().collect();
+    if let Some(session) = crate::session::all().into_iter().find(|s| s.bot.entity == bot.entity) {
+        session.state.inventory_mgr.notice_chest(&items);
+    }
+}
+
+// ============================================================
+// MOVING STUFF — Walk over, open, shift-click
+// ============================================================
+
+/// What the backpack (not the hotbar) carries that belongs in a chest, in chest order. Junk doesn't
+pub fn stashable(inventory: &Inventory) -> Vec<String> {
+    let mut items: Vec<String> = inventory.backpack_counts()
+        .into_keys()
+        .filter(|item| !KEEP_ON_US.contains(&categorize_item(item)) && categorize_item(item) != ItemCategory::Junk)
+        .collect();
+    items.sort_by_key(|item| (chest_sort_order(&categorize_item(item)), item.clone()));
+    items
+}
+
+/// Walk up to a container and open it
+pub async fn open_at(bot: &Client, pos: [i32; 3]) -> Option<ContainerHandle> {
+    let center = Vec3::new(pos[0] as f64 + 0.5, pos[1] as f64 + 0.5, pos[2] as f64 + 0.5);
+    let arrived = tokio::time::timeout(Duration::from_secs(30), bot.goto(RadiusGoal::new(center, 3.0))).await;
+    if arrived.is_err() {
+        bot.stop_pathfinding();
+        warn!("[STORAGE] ❌ Não consegui chegar no baú {:?}", pos);
+        return None;
+    }
+    let container = bot.open_container_at(BlockPos::new(pos[0], pos[1], pos[2])).await?;
+    bot.wait_ticks(4).await;
+    if let Some(slots) = container.contents() {
+        judge_mess(bot, &tally(&slots));
+    }
+    Some(container)
+}
+
+/// Container slots → item counts, plus what any shulker boxes in there hold
+pub fn tally(slots: &[azalea::inventory::ItemStack]) -> Contents {
+    let mut items = HashMap::new();
+    for stack in slots.iter().filter(|s| s.is_present()) {
+        *items.entry(item_id(stack)).or_insert(0) += stack.count() as u32;
+    }
+    Contents { items, shulkers: shulker::labels_in(slots) }
+}
+
+/// Put the block in this inventory slot down next to us. Where it went, if it worked
+pub async fn place_beside(bot: &Client, slot: usize) -> Option<[i32; 3]> {
+    if !crate::systems::inventory::equip(bot, slot) {
+        return None;
+    }
+    bot.wait_ticks(2).await;
+    let pos = bot.position();
+    let [x, y, z] = [pos.x.floor() as i32, pos.y.floor() as i32, pos.z.floor() as i32];
+    for (dx, dz) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+        let target = BlockPos::new(x + dx, y, z + dz);
+        let floor = BlockPos::new(x + dx, y - 1, z + dz);
+        let (open, solid) = {
+            let world = bot.world();
+            let world = world.read();
+            let open = world.get_block_state(target).is_some_and(|s| s.is_air());
+            let solid = world.get_block_state(floor).is_some_and(|s| {
+                !s.is_air() && !matches!(BlockKind::from(s), BlockKind::Water | BlockKind::Lava)
+            });
+            (open, solid)
+        };
+        if !(open && solid) {
+            continue;
+        }
+        bot.look_at(Vec3::new(floor.x as f64 + 0.5, floor.y as f64 + 1.0, floor.z as f64 + 0.5));
+        bot.wait_ticks(4).await;
+        bot.block_interact(floor);
+        bot.wait_ticks(6).await;
+        if bot.world().read().get_block_state(target).is_some_and(|s| !s.is_air()) {
+            return Some([target.x, target.y, target.z]);
+        }
+    }
+    None
+}
+
+/// Shift-click these items from the backpack into the chest. → (what went in, chest contents after)
+pub async fn deposit(bot: &Client, pos: [i32; 3], items: &[String]) -> Option<(HashMap<String, u32>, Contents)> {
+    let container = open_at(bot, pos).await?;
+    let before = Inventory::read(bot).counts();
+    let menu = container.menu()?;
+    let hotbar = menu.hotbar_slots_range();
+    for slot in menu.player_slots_range().filter(|i| !hotbar.contains(i)) {
+        if menu.slot(slot).is_some_and(|s| s.is_present() && items.contains(&item_id(s))) {
+            container.shift_click(slot);
+            bot.wait_ticks(2).await;
+        }
+    }
+    bot.wait_ticks(4).await;
+    let contents = tally(&container.contents()?);
+    container.close();
+    let after = Inventory::read(bot).counts();
+    let moved = before.into_iter()
+        .map(|(item, n)| (item.clone(), n.saturating_sub(after.get(&item).copied().unwrap_or(0))))
+        .filter(|(_, n)| *n > 0)
+        .collect();
+    Some((moved, contents))
+}
+
+/// Like `deposit`, but no more than the given amount of each item, hotbar included. Whole stacks
+/// while they fit, then the last one split by hand. → (what went in, chest contents after)
+pub async fn deposit_at_most(bot: &Client, pos: [i32; 3], items: &[(String, u32)]) -> Option<(HashMap<String, u32>, Contents)> {
+    let container = open_at(bot, pos).await?;
+    let before = Inventory::read(bot).counts();
+    for (item, quantity) in items {
+        let menu = container.menu()?;
+        let stacks: Vec<(usize, u32)> = menu.player_slots_range()
+            .filter_map(|slot| menu.slot(slot)
+                .filter(|s| s.is_present() && item_id(s) == *item)
+                .map(|s| (slot, s.count() as u32)))
+            .collect();
+        for (slot, count, take) in take_from(&stacks, *quantity) {
+            if take == count {
+                container.shift_click(slot);
+            } else {
+                // Carry the stack, drop `take` of it one by one in a free chest slot, put the rest back
+                let Some(free) = container.contents()?.iter().position(|s| !s.is_present()) else { break };
+                container.click(PickupClick::Left { slot: Some(slot as u16) });
+                for _ in 0..take {
+                    container.click(PickupClick::Right { slot: Some(free as u16) });
+                }
+                container.click(PickupClick::Left { slot: Some(slot as u16) });
+            }
+            bot.wait_ticks(2).await;
+        }
+    }
+    bot.wait_ticks(4).await;
+    let contents = tally(&container.contents()?);
+    container.close();
+    let after = Inventory::read(bot).counts();
+    let moved = before.into_iter()
+        .map(|(item, n)| (item.clone(), n.saturating_sub(after.get(&item).copied().unwrap_or(0))))
+        .filter(|(_, n)| *n > 0)
+        .collect();
+    Some((moved, contents))
+}
+
+/// Which of these (slot, count) stacks to hand over for `quantity`: (slot, count, how many of it)
+fn take_from(stacks: &[(usize, u32)], quantity: u32) -> Vec<(usize, u32, u32)> {
+    let mut left = quantity;
+    let mut plan = vec![];
+    for &(slot, count) in stacks {
+        if left == 0 {
+            break;
+        }
+        let take = count.min(left);
+        plan.push((slot, count, take));
+        left -= take;
+    }
+    plan
+}
+
+/// Shift-click whole stacks of an item out until we have `quantity`. → (how many came out, chest contents after)
+pub async fn withdraw(bot: &Client, pos: [i32; 3], item: &str, quantity: u32) -> Option<(u32, Contents)> {
+    let container = open_at(bot, pos).await?;
+    let before = Inventory::read(bot).count_of(item);
+    let slots = container.contents()?;
+    for (slot, _) in slots.iter().enumerate().filter(|(_, s)| s.is_present() && item_id(s) == item) {
+        if Inventory::read(bot).count_of(item).saturating_sub(before) >= quantity {
+            break;
+        }
+        container.shift_click(slot);
+        bot.wait_ticks(2).await;
+    }
+    bot.wait_ticks(4).await;
+    let contents = tally(&container.contents()?);
+    container.close();
+    Some((Inventory::read(bot).count_of(item).saturating_sub(before), contents))
+}
+
+/// Write a chest's new contents back into the index
+pub fn update_index(memory: &Arc<Mutex<Memory>>, pos: [i32; 3], contents: Contents) {
+    let mut memory = memory.lock().unwrap();
+    let kind = memory.inventory.storage.containers.iter()
+        .find(|c| c.pos == pos)
+        .map(|c| c.kind.clone())
+        .unwrap_or_else(|| "chest".into());
+    memory.inventory.storage.record(pos, &kind, contents);
+    memory.inventory.stored = memory.inventory.storage.totals();
+    memory.save();
+}
+
+/// Get `quantity` of an item: shulkers we carry, then loose in the chests, then shulkers in the chests.
+/// Returns how many we pulled
+pub async fn fetch(bot: &Client, memory: &Arc<Mutex<Memory>>, item: &str, quantity: u32) -> u32 {
+    let mut got = shulker::unpack(bot, item, quantity).await;
+    let here = bot.position();
+    let here = [here.x.floor() as i32, here.y.floor() as i32, here.z.floor() as i32];
+    let plan = memory.lock().unwrap().inventory.storage.withdraw_plan(item, quantity, here);
+    for (pos, _) in plan {
+        if got >= quantity {
+            break;
+        }
+        let Some((taken, contents)) = withdraw(bot, pos, item, quantity - got).await else { continue };
+        update_index(memory, pos, contents);
+        got += taken;
+    }
+    if got < quantity && shulker::pull_from_storage(bot, memory, item).await {
+        got += shulker::unpack(bot, item, quantity - got).await;
+    }
+    if got > 0 {
+        info!("[STORAGE] 📤 Peguei {} x{} do baú", item, got);
+    }
+    got
+}
+
+/// Put the backpack away, each item in the chest the index picks for it. Returns how many items went in
+pub async fn stash(bot: &Client, memory: &Arc<Mutex<Memory>>) -> u32 {
+    stash_items(bot, memory, stashable(&Inventory::read(bot))).await
+}
+
+/// Put away just one category from the backpack ("!guardar comida"), even what we'd normally keep on us
+pub async fn stash_category(bot: &Client, memory: &Arc<Mutex<Memory>>, category: &ItemCategory) -> u32 {
+    let mut items: Vec<String> = Inventory::read(bot).backpack_counts()
+        .into_keys()
+        .filter(|item| categorize_item(item) == *category)
+        .collect();
+    items.sort();
+    stash_items(bot, memory, items).await
+}
+
+async fn stash_items(bot: &Client, memory: &Arc<Mutex<Memory>>, items: Vec<String>) -> u32 {
+    let here = bot.position();
+    let here = [here.x.floor() as i32, here.y.floor() as i32, here.z.floor() as i32];
+    // One trip per chest, chests visited in the order their first item sorts
+    let mut trips: Vec<([i32; 3], Vec<String>)> = vec![];
+    {
+        let memory = memory.lock().unwrap();
+        for item in items {
+            let Some(pos) = memory.inventory.storage.deposit_target(&item, here) else { continue };
+            match trips.iter_mut().find(|(p, _)| *p == pos) {
+                Some((_, list)) => list.push(item),
+                None => trips.push((pos, vec![item])),
+            }
+        }
+    }
+    let mut total = 0;
+    for (pos, list) in trips {
+        let Some((moved, contents)) = deposit(bot, pos, &list).await else { continue };
+        update_index(memory, pos, contents);
+        let n: u32 = moved.values().sum();
+        info!("[STORAGE] 📥 Guardei {} itens em {:?}", n, pos);
+        total += n;
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contents(items: &[(&str, u32)]) -> Contents {
+        Contents { items: items.iter().map(|(i, n)| (i.to_string(), *n)).collect(), shulkers: vec![] }
+    }
+
+    #[test]
+    fn test_double_chest_halves_meet() {
+        // Left half first, then where the right half sits for that facing
+        for (facing, right) in [("north", [11, 64, 10]), ("south", [9, 64, 10]), ("east", [10, 64, 11]), ("west", [10, 64, 9])] {
+            let left = [10, 64, 10];
+            assert_eq!(index_pos(left, facing, "left"), left, "{}", facing);
+            assert_eq!(index_pos(right, facing, "right"), left, "{}", facing);
+        }
+        assert_eq!(index_pos([1, 2, 3], "north", "single"), [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_totals_count_each_chest_once() {
+        let mut index = StorageIndex::default();
+        index.record([0, 64, 0], "chest", contents(&[("iron_ingot", 34)]));
+        // Re-opening the same chest replaces what we knew, it doesn't add to it
+        index.record([0, 64, 0], "chest", contents(&[("iron_ingot", 30), ("coal", 5)]));
+        let mut packed = contents(&[("iron_ingot", 2)]);
+        packed.shulkers.push(ShulkerLabel {
+            color: "shulker_box".into(),
+            label: "minérios".into(),
+            items: HashMap::from([("iron_ingot".to_string(), 64)]),
+        });
+        index.record([5, 64, 0], "barrel", packed);
+        let totals = index.totals();
+        assert_eq!(totals["iron_ingot"], 96);
+        assert_eq!(totals["coal"], 5);
+        assert_eq!(index.containers.len(), 2);
+    }
+
+    #[test]
+    fn test_deposit_takes_no_more_than_asked() {
+        // 20 borrowed, 64 + 10 carried: the 10 go whole, 10 split off the 64
+        assert_eq!(take_from(&[(9, 10), (10, 64)], 20), vec![(9, 10, 10), (10, 64, 10)]);
+        assert_eq!(take_from(&[(9, 64), (10, 64)], 64), vec![(9, 64, 64)]);
+        assert_eq!(take_from(&[(9, 5)], 20), vec![(9, 5, 5)]);
+        assert!(take_from(&[(9, 5)], 0).is_empty());
+    }
+}
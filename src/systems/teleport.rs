@@ -0,0 +1,125 @@
+use std::time::{Duration, Instant};
+
+// ============================================================
+// TELEPORT — /tpa requests and position jumps
+// "Fulano has requested to teleport to you." → /tpaccept (se for parça)
+// ============================================================
+
+/// Moving more than this in a single tick can't be walking
+const JUMP_DISTANCE: f64 = 16.0;
+/// An accepted request that hasn't teleported anyone by now never will
+const EXPECT_FOR: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TpaKind {
+    ToUs,   // /tpa — they come to us
+    ToThem, // /tpahere — they pull us to them
+}
+
+#[derive(Debug, Clone)]
+pub struct TpaRequest {
+    pub player: String,
+    pub kind: TpaKind,
+}
+
+/// Server prompt text (Essentials / CMI / pt-br plugins) → request
+const PROMPTS: &[(&str, TpaKind)] = &[
+    (" has requested to teleport to you", TpaKind::ToUs),
+    (" wants to teleport to you", TpaKind::ToUs),
+    (" has requested that you teleport to them", TpaKind::ToThem),
+    (" wants you to teleport to them", TpaKind::ToThem),
+    (" quer se teleportar até você", TpaKind::ToUs),
+    (" quer se teleportar ate voce", TpaKind::ToUs),
+    (" pediu para teleportar até você", TpaKind::ToUs),
+    (" pediu para teleportar ate voce", TpaKind::ToUs),
+    (" quer que você se teleporte", TpaKind::ToThem),
+    (" quer que voce se teleporte", TpaKind::ToThem),
+    (" pediu para você teleportar até ele", TpaKind::ToThem),
+];
+
+/// A system line that is a plugin's prompt, not someone's chat quoting one.
+/// The name has to open the line — only a [TAG] or a symbol may come before it
+pub fn parse_tpa_request(message: &str) -> Option<TpaRequest> {
+    let lower = message.to_lowercase();
+    let (index, kind) = PROMPTS.iter().find_map(|(p, kind)| lower.find(p).map(|i| (i, *kind)))?;
+    let before = &message[..index];
+    let player = before
+        .rsplit([' ', ']', ')', '»', '>'])
+        .next()
+        .filter(|n| is_username(n))?;
+    if !only_tags(&before[..before.len() - player.len()]) {
+        return None; // "[VIP] Bob » Steve has requested..." is Bob talking
+    }
+    Some(TpaRequest { player: player.to_string(), kind })
+}
+
+fn is_username(name: &str) -> bool {
+    (1..=16).contains(&name.len()) && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// "[TPA] » " yes; "<Bob> " or "Bob: " no
+fn only_tags(prefix: &str) -> bool {
+    let mut depth = 0;
+    for c in prefix.chars() {
+        match c {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth -= 1,
+            '<' | ':' => return false,
+            c if depth == 0 && c.is_alphanumeric() => return false,
+            _ => {}
+        }
+    }
+    true
+}
+
+/// A position jump we noticed
+#[derive(Debug, Clone)]
+pub struct Teleport {
+    pub from: [f64; 3],
+    pub to: [f64; 3],
+    pub via: Option<String>, // Whose request caused it, if we accepted one
+}
+
+#[derive(Debug, Default)]
+pub struct TeleportWatch {
+    last_pos: Option<[f64; 3]>,
+    expecting: Option<(String, Instant)>,
+}
+
+impl TeleportWatch {
+    /// We accepted someone's request — the next jump is probably theirs
+    pub fn expect(&mut self, player: &str) {
+        self.expecting = Some((player.to_string(), Instant::now()));
+    }
+
+    /// Feed our position every tick; returns a teleport when we jumped
+    pub fn observe(&mut self, pos: [f64; 3]) -> Option<Teleport> {
+        let last = self.last_pos.replace(pos)?;
+        let (dx, dy, dz) = (pos[0] - last[0], pos[1] - last[1], pos[2] - last[2]);
+        if (dx * dx + dy * dy + dz * dz).sqrt() < JUMP_DISTANCE {
+            return None;
+        }
+        let via = self.expecting.take()
+            .filter(|(_, at)| at.elapsed() < EXPECT_FOR)
+            .map(|(player, _)| player);
+        Some(Teleport { from: last, to: pos, via })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prompts_not_chat() {
+        let request = parse_tpa_request("Steve has requested to teleport to you.").unwrap();
+        assert_eq!((request.player.as_str(), request.kind), ("Steve", TpaKind::ToUs));
+        let request = parse_tpa_request("[TPA] » Alex quer que você se teleporte até ele").unwrap();
+        assert_eq!((request.player.as_str(), request.kind), ("Alex", TpaKind::ToThem));
+        // Someone typing the prompt, however the chat plugin dressed it up
+        assert!(parse_tpa_request("<Bob> Steve has requested to teleport to you").is_none());
+        assert!(parse_tpa_request("[VIP] Bob » Steve has requested to teleport to you").is_none());
+        assert!(parse_tpa_request("Bob: Steve has requested to teleport to you").is_none());
+        assert!(parse_tpa_request("§x has requested to teleport to you").is_none());
+    }
+}
@@ -0,0 +1,49 @@
+// ============================================================
+// TONE — Is that message an insult, a threat or a compliment?
+// Pure keyword matching: no LLM call just to get offended
+// ============================================================
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Tone {
+    Threat,
+    Insult,
+    Compliment,
+    Neutral,
+}
+
+const THREATS: &[&str] = &[
+    "vou te matar", "vou te caçar", "vou te cacar", "te pego", "vai morrer",
+    "vou griefar", "vou roubar tua", "vou roubar sua", "vou explodir", "vou queimar tua",
+    "vou queimar sua", "cuidado comigo", "vc ta morto", "vc tá morto",
+];
+
+const INSULTS: &[&str] = &[
+    "lixo", "noob", "burro", "idiota", "otario", "otário", "trouxa", "lerdo", "imbecil",
+    "fdp", "arrombado", "corno", "merda", "bosta", "ruim demais", "inutil", "inútil", "retardado",
+];
+
+const COMPLIMENTS: &[&str] = &[
+    "brabo", "monstro", "mito", "pika", "mandou bem", "muito bom", "gênio", "genio",
+    "craque", "lindo", "ficou top", "ficou foda", "o melhor", "salvou", "gente boa",
+];
+
+fn contains_word(lower: &str, needle: &str) -> bool {
+    if needle.contains(' ') {
+        return lower.contains(needle);
+    }
+    lower.split(|c: char| !c.is_alphanumeric()).any(|w| w == needle)
+}
+
+/// Threats beat insults beat compliments ("brabo mas lixo" is still an insult)
+pub fn classify(message: &str) -> Tone {
+    let lower = message.to_lowercase();
+    if THREATS.iter().any(|t| contains_word(&lower, t)) {
+        Tone::Threat
+    } else if INSULTS.iter().any(|t| contains_word(&lower, t)) {
+        Tone::Insult
+    } else if COMPLIMENTS.iter().any(|t| contains_word(&lower, t)) {
+        Tone::Compliment
+    } else {
+        Tone::Neutral
+    }
+}
@@ -0,0 +1,396 @@
+use azalea::prelude::*;
+use azalea::block::BlockStates;
+use azalea::container::ContainerHandleRef;
+use azalea::inventory::operations::PickupClick;
+use azalea::pathfinder::goals::RadiusGoal;
+use azalea::registry::builtin::BlockKind;
+use azalea::BlockPos;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use crate::cognitive::memory::Memory;
+use crate::systems::inventory::{durability, item_id, Inventory};
+use crate::systems::storage;
+use tracing::info;
+
+// ============================================================
+// TOOL CRAFTER — The pickaxe broke, make another one
+// "quebrou a picareta" → tábua, graveto, bancada, pronto
+// ============================================================
+
+/// Tools we never want to be without
+pub const ESSENTIAL: &[&str] = &["pickaxe", "axe", "sword"];
+/// After a failed attempt, leave that tool alone for a while
+const RETRY_AFTER: Duration = Duration::from_secs(300);
+/// Crafting table must be this close to use it instead of placing our own
+const TABLE_REACH: f64 = 16.0;
+/// Best first: material → item prefix
+const MATERIALS: &[(&str, &str)] = &[
+    ("diamond", "diamond"),
+    ("iron_ingot", "iron"),
+    ("cobblestone", "stone"),
+    ("planks", "wooden"),
+];
+
+/// Shapes on the 3x3 grid (slots 1-9, row by row) → (material slots, stick slots)
+fn shape(kind: &str) -> Option<(&'static [usize], &'static [usize])> {
+    match kind {
+        "pickaxe" => Some((&[1, 2, 3], &[5, 8])),
+        "axe" => Some((&[1, 2, 4], &[5, 8])),
+        "sword" => Some((&[2, 5], &[8])),
+        "shovel" => Some((&[2], &[5, 8])),
+        _ => None,
+    }
+}
+
+/// How we say it in chat/goals
+pub fn tool_name(kind: &str) -> &'static str {
+    match kind {
+        "pickaxe" => "picareta",
+        "axe" => "machado",
+        "sword" => "espada",
+        "shovel" => "pá",
+        _ => "ferramenta",
+    }
+}
+
+fn is_log(item: &str) -> bool {
+    (item.ends_with("_log") || item.ends_with("_stem")) && item != "mushroom_stem"
+}
+
+/// "planks" and "log" stand for any wood; everything else is an exact id
+fn matches(item: &str, wanted: &str) -> bool {
+    match wanted {
+        "planks" => item.ends_with("_planks"),
+        "log" => is_log(item),
+        _ => item == wanted,
+    }
+}
+
+fn count_matching(counts: &HashMap<String, u32>, wanted: &str) -> u32 {
+    counts.iter().filter(|(item, _)| matches(item, wanted)).map(|(_, n)| n).sum()
+}
+
+// ============================================================
+// WEAR — Notice when a tool breaks or is about to
+// ============================================================
+
+#[derive(Debug, Default)]
+pub struct ToolWear {
+    seen: HashMap<String, String>,      // kind → item we had last check
+    retry_at: HashMap<String, Instant>, // kind → don't try before this
+}
+
+impl ToolWear {
+    /// Essential tools that are gone or under `worn_percent` of their uses (if it's the last one). Logs breakages
+    pub fn check(&mut self, inventory: &Inventory, worn_percent: u32) -> Vec<&'static str> {
+        let mut needs = vec![];
+        for kind in ESSENTIAL {
+            let tools = inventory.tools_of(kind);
+            match tools.first() {
+                None => {
+                    if let Some(item) = self.seen.remove(*kind) {
+                        info!("[TOOLS] 💥 Quebrou/sumiu: {}", item);
+                    }
+                    needs.push(*kind);
+                }
+                Some(best) => {
+                    self.seen.insert(kind.to_string(), best.item.clone());
+                    let worn = inventory.stack_at(best.slot)
+                        .and_then(durability)
+                        .is_some_and(|(left, max)| left * 100 < max * worn_percent);
+                    if worn && tools.len() == 1 {
+                        needs.push(*kind);
+                    }
+                }
+            }
+        }
+        needs.retain(|kind| self.retry_at.get(*kind).is_none_or(|t| Instant::now() >= *t));
+        needs
+    }
+
+    pub fn failed(&mut self, kind: &str) {
+        self.retry_at.insert(kind.to_string(), Instant::now() + RETRY_AFTER);
+    }
+}
+
+// ============================================================
+// PLAN — What to make it out of and what has to happen first
+// ============================================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CraftStep {
+    Planks,        // 1 log → 4 planks (2x2 grid)
+    Sticks,        // 2 planks → 4 sticks (2x2 grid)
+    Table,         // 4 planks → crafting table, then put it down
+    Tool(String),  // The tool itself, on the table
+}
+
+#[derive(Debug, Clone)]
+pub struct ToolPlan {
+    pub tool: String,      // "stone_pickaxe"
+    pub material: String,  // "cobblestone" / "planks"
+    pub material_count: u32,
+    pub sticks: u32,
+    pub table: bool,       // No table around, we make one
+    pub steps: Vec<CraftStep>,
+}
+
+/// Why a replacement didn't get made
+#[derive(Debug, Clone, PartialEq)]
+pub enum CraftError {
+    Missing(String),      // Item id we'd have to go out and get
+    Stuck(&'static str),  // Had everything, the crafting itself went wrong
+}
+
+impl std::fmt::Display for CraftError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            CraftError::Missing(item) => write!(f, "falta {}", item),
+            CraftError::Stuck(why) => write!(f, "não deu pra {}", why),
+        }
+    }
+}
+
+/// What to go get when a material runs short ("planks" isn't an item)
+fn wanted(material: &str) -> String {
+    match material {
+        "planks" => "oak_log".to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Best tool of a kind we can make from what we carry plus what's stored.
+/// Err = the item id we'd have to go out and get
+pub fn plan(kind: &str, carrying: &HashMap<String, u32>, stored: &HashMap<String, u32>, have_table: bool) -> Result<ToolPlan, String> {
+    let (head, handle) = shape(kind).ok_or_else(|| kind.to_string())?;
+    let available = |wanted: &str| count_matching(carrying, wanted) + count_matching(stored, wanted);
+    let wood = available("planks") + available("log") * 4;
+    let sticks_needed = handle.len() as u32;
+    let stick_crafts = sticks_needed.saturating_sub(available("stick")).div_ceil(4);
+    let table = !have_table && available("crafting_table") == 0;
+    let support_planks = stick_crafts * 2 + if table { 4 } else { 0 };
+
+    let (material, prefix) = MATERIALS.iter()
+        .find(|(material, _)| match *material {
+            "planks" => wood >= support_planks + head.len() as u32,
+            _ => available(material) >= head.len() as u32 && wood >= support_planks,
+        })
+        .ok_or_else(|| if wood < support_planks { wanted("planks") } else { "cobblestone".to_string() })?;
+
+    let planks = support_planks + if *material == "planks" { head.len() as u32 } else { 0 };
+    let mut steps = vec![];
+    if planks > count_matching(carrying, "planks") {
+        steps.push(CraftStep::Planks);
+    }
+    if stick_crafts > 0 {
+        steps.push(CraftStep::Sticks);
+    }
+    if table {
+        steps.push(CraftStep::Table);
+    }
+    let tool = format!("{}_{}", prefix, kind);
+    steps.push(CraftStep::Tool(tool.clone()));
+    Ok(ToolPlan {
+        tool,
+        material: material.to_string(),
+        material_count: head.len() as u32,
+        sticks: sticks_needed,
+        table,
+        steps,
+    })
+}
+
+// ============================================================
+// DOING IT — Storage, 2x2 grid, crafting table
+// ============================================================
+
+/// Make sure we carry `quantity` of something, pulling the difference out of storage
+async fn gather(bot: &Client, memory: &Arc<Mutex<Memory>>, wanted: &str, quantity: u32) {
+    let have = count_matching(&Inventory::read(bot).counts(), wanted);
+    let mut missing = quantity.saturating_sub(have);
+    let sources: Vec<String> = memory.lock().unwrap().inventory.stored.keys()
+        .filter(|item| matches(item, wanted))
+        .cloned()
+        .collect();
+    for item in sources {
+        if missing == 0 {
+            break;
+        }
+        missing = missing.saturating_sub(storage::fetch(bot, memory, &item, missing).await);
+    }
+}
+
+/// Lay one matching item in each grid slot, then shift-click the result out
+async fn craft_grid(bot: &Client, handle: &ContainerHandleRef, pattern: &[(&str, &[usize])]) -> bool {
+    for (wanted, grid) in pattern {
+        for slot in grid.iter() {
+            let Some(menu) = handle.menu() else {
+                return false;
+            };
+            let source = menu.player_slots_range().find(|i| {
+                menu.slot(*i).is_some_and(|s| s.is_present() && matches(&item_id(s), wanted))
+            });
+            let Some(source) = source else {
+                return false;
+            };
+            // Pick up the stack, drop one, put the rest back
+            handle.click(PickupClick::Left { slot: Some(source as u16) });
+            handle.click(PickupClick::Right { slot: Some(*slot as u16) });
+            handle.click(PickupClick::Left { slot: Some(source as u16) });
+            bot.wait_ticks(1).await;
+        }
+    }
+    bot.wait_ticks(4).await;
+    handle.shift_click(0usize);
+    bot.wait_ticks(4).await;
+    true
+}
+
+/// Craft in our own 2x2 grid (player menu slots 1-4)
+async fn craft_in_hand(bot: &Client, pattern: &[(&str, &[usize])]) -> bool {
+    let Some(inventory) = bot.open_inventory() else {
+        return false; // A container is open
+    };
+    let done = craft_grid(bot, &inventory, pattern).await;
+    inventory.close();
+    done
+}
+
+fn nearby_table(bot: &Client) -> Option<BlockPos> {
+    let states = BlockStates::from(&[BlockKind::CraftingTable][..]);
+    let table = bot.world().read().find_block(bot.position(), &states)?;
+    (table.center().distance_to(bot.position()) <= TABLE_REACH).then_some(table)
+}
+
+/// Make a replacement for a tool kind, from storage and scratch. Ok = what we made
+pub async fn replace(bot: &Client, memory: &Arc<Mutex<Memory>>, kind: &str) -> Result<String, CraftError> {
+    let plan = {
+        let memory = memory.lock().unwrap();
+        plan(kind, &Inventory::read(bot).counts(), &memory.inventory.stored, nearby_table(bot).is_some())
+            .map_err(CraftError::Missing)?
+    };
+    info!("[TOOLS] 🛠️ Plano pra {}: {:?}", plan.tool, plan.steps);
+
+    if plan.material != "planks" {
+        gather(bot, memory, &plan.material, plan.material_count).await;
+    }
+    gather(bot, memory, "stick", plan.sticks).await;
+    if !plan.table && nearby_table(bot).is_none() {
+        gather(bot, memory, "crafting_table", 1).await;
+    }
+    // Planks still needed: stick batches we're short, the table, a wooden head
+    let sticks_short = plan.sticks.saturating_sub(count_matching(&Inventory::read(bot).counts(), "stick"));
+    let planks = sticks_short.div_ceil(4) * 2
+        + if plan.table { 4 } else { 0 }
+        + if plan.material == "planks" { plan.material_count } else { 0 };
+    gather(bot, memory, "planks", planks).await;
+    let planks_short = planks.saturating_sub(count_matching(&Inventory::read(bot).counts(), "planks"));
+    gather(bot, memory, "log", planks_short.div_ceil(4)).await;
+
+    for step in &plan.steps {
+        match step {
+            CraftStep::Planks => {
+                while count_matching(&Inventory::read(bot).counts(), "planks") < planks {
+                    if !craft_in_hand(bot, &[("log", &[1])]).await {
+                        return Err(CraftError::Missing(wanted("planks")));
+                    }
+                }
+            }
+            CraftStep::Sticks => {
+                while count_matching(&Inventory::read(bot).counts(), "stick") < plan.sticks {
+                    if !craft_in_hand(bot, &[("planks", &[1, 3])]).await {
+                        return Err(CraftError::Missing("stick".into()));
+                    }
+                }
+            }
+            CraftStep::Table => {
+                if Inventory::read(bot).count_of("crafting_table") == 0
+                    && !craft_in_hand(bot, &[("planks", &[1, 2, 3, 4])]).await
+                {
+                    return Err(CraftError::Missing("crafting_table".into()));
+                }
+            }
+            CraftStep::Tool(tool) => {
+                let table = match nearby_table(bot) {
+                    Some(table) => table,
+                    None => {
+                        let slot = Inventory::read(bot).slot_of("crafting_table")
+                            .ok_or_else(|| CraftError::Missing("crafting_table".into()))?;
+                        let spot = storage::place_beside(bot, slot).await.ok_or(CraftError::Stuck("pôr a bancada"))?;
+                        BlockPos::new(spot[0], spot[1], spot[2])
+                    }
+                };
+                let arrived = tokio::time::timeout(Duration::from_secs(20), bot.goto(RadiusGoal::new(table.center(), 3.0))).await;
+                if arrived.is_err() {
+                    bot.stop_pathfinding();
+                    return Err(CraftError::Stuck("chegar na bancada"));
+                }
+                let Some(container) = bot.open_container_at(table).await else {
+                    return Err(CraftError::Stuck("abrir a bancada"));
+                };
+                bot.wait_ticks(4).await;
+                let (head, handle) = shape(kind).ok_or(CraftError::Stuck("achar a receita"))?;
+                let before = Inventory::read(bot).count_of(tool);
+                craft_grid(bot, &container, &[(plan.material.as_str(), head), ("stick", handle)]).await;
+                container.close();
+                bot.wait_ticks(4).await;
+                if Inventory::read(bot).count_of(tool) <= before {
+                    return Err(CraftError::Missing(wanted(&plan.material)));
+                }
+            }
+        }
+    }
+    info!("[TOOLS] ✅ Fiz {}", plan.tool);
+    Ok(plan.tool)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn items(list: &[(&str, u32)]) -> HashMap<String, u32> {
+        list.iter().map(|(item, n)| (item.to_string(), *n)).collect()
+    }
+
+    #[test]
+    fn test_plan_from_wood_only() {
+        let plan = plan("pickaxe", &items(&[("oak_log", 3)]), &HashMap::new(), true).unwrap();
+        assert_eq!(plan.tool, "wooden_pickaxe");
+        assert_eq!(plan.material, "planks");
+        assert!(!plan.table);
+        assert_eq!(plan.steps, vec![
+            CraftStep::Planks,
+            CraftStep::Sticks,
+            CraftStep::Tool("wooden_pickaxe".into()),
+        ]);
+    }
+
+    #[test]
+    fn test_plan_prefers_stone_from_storage() {
+        let carrying = items(&[("oak_planks", 4)]);
+        let stored = items(&[("cobblestone", 16)]);
+        let plan = plan("pickaxe", &carrying, &stored, true).unwrap();
+        assert_eq!(plan.tool, "stone_pickaxe");
+        assert_eq!(plan.material, "cobblestone");
+        assert_eq!(plan.material_count, 3);
+        assert!(!plan.steps.contains(&CraftStep::Planks));
+    }
+
+    #[test]
+    fn test_plan_makes_a_table_when_none_around() {
+        let plan = plan("sword", &items(&[("oak_log", 2), ("cobblestone", 2), ("stick", 1)]), &HashMap::new(), false).unwrap();
+        assert_eq!(plan.tool, "stone_sword");
+        assert!(plan.table);
+        assert!(plan.steps.contains(&CraftStep::Table));
+        assert!(!plan.steps.contains(&CraftStep::Sticks));
+    }
+
+    #[test]
+    fn test_plan_missing_is_an_item_id() {
+        assert_eq!(plan("axe", &HashMap::new(), &HashMap::new(), true).unwrap_err(), "oak_log");
+        // Wood for the handle and table, not enough for a wooden head
+        let short = items(&[("oak_planks", 6)]);
+        assert_eq!(plan("pickaxe", &short, &HashMap::new(), false).unwrap_err(), "cobblestone");
+    }
+}
@@ -0,0 +1,231 @@
+use azalea::prelude::*;
+use azalea::entity::{metadata::ItemItem, Position};
+use azalea::inventory::operations::ThrowClick;
+use azalea::pathfinder::goals::RadiusGoal;
+use azalea::protocol::packets::game::{ClientboundAddEntity, ClientboundTakeItemEntity};
+use azalea::registry::builtin::EntityKind;
+use azalea::world::MinecraftEntityId;
+use azalea::Vec3;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use crate::systems::inventory::{item_id, Inventory};
+use crate::systems::world_scanner::WorldState;
+use tracing::{info, warn};
+
+// ============================================================
+// TRADER — Physically hands items over after a deal
+// "toma ai" → walk over, pick the item, toss it at their feet
+// ============================================================
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GiveOutcome {
+    /// Thrown and confirmed gone from our inventory
+    Delivered { item: String, quantity: u32 },
+    /// We only had part of it, delivered what we had
+    Partial { item: String, quantity: u32 },
+    NotInInventory,
+    PlayerNotFound,
+    CouldNotReach,
+    ThrowFailed,
+}
+
+/// An item entity that spawned right at a player's hands — they tossed it
+#[derive(Debug, Clone)]
+pub struct ThrownItem {
+    pub thrower: String,
+    pub item: Option<String>, // Filled once the entity metadata arrives
+    pub spawned_at: Instant,
+}
+
+/// Something a player threw that we picked up
+#[derive(Debug, Clone)]
+pub struct ReceivedItem {
+    pub player: String,
+    pub item: String,
+    pub quantity: u32,
+}
+
+#[derive(Clone, Default)]
+pub struct State {
+    /// Only one trade at a time — the body can't be in two places
+    pub busy: Arc<Mutex<bool>>,
+    /// Item entities thrown by players, keyed by entity id
+    pub thrown_items: Arc<Mutex<HashMap<i32, ThrownItem>>>,
+}
+
+impl State {
+    /// Claim the trader; returns false if a trade is already running
+    pub fn try_begin(&self) -> bool {
+        let mut busy = self.busy.lock().unwrap();
+        if *busy {
+            return false;
+        }
+        *busy = true;
+        true
+    }
+
+    pub fn finish(&self) {
+        *self.busy.lock().unwrap() = false;
+    }
+
+    /// Wait until no other trade is using the body, then claim it
+    pub async fn wait_turn(&self) {
+        while !self.try_begin() {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+    }
+}
+
+/// Where is this player right now? (tab list → entity → position)
+pub fn player_position(bot: &Client, player: &str) -> Option<Vec3> {
+    bot.player_uuid_by_username(player)
+        .and_then(|uuid| bot.entity_by_uuid(uuid))
+        .and_then(|entity| bot.get_entity_component::<Position>(entity))
+        .map(|pos| *pos)
+}
+
+/// Walk to a player and toss them `quantity` of `item`
+pub async fn give_item(bot: Client, player: &str, item: &str, quantity: u32) -> GiveOutcome {
+    let have = Inventory::read(&bot).count_of(item);
+    if have == 0 {
+        warn!("[TRADER] ❌ Não tenho {} pra dar pro {}", item, player);
+        return GiveOutcome::NotInInventory;
+    }
+    let requested = quantity;
+    let quantity = quantity.min(have);
+
+    // === 1. Go to them ===
+    let Some(target) = player_position(&bot, player) else {
+        warn!("[TRADER] ❌ Não achei {} no mundo", player);
+        return GiveOutcome::PlayerNotFound;
+    };
+    info!("[TRADER] 🚶 Indo entregar {} x{} pro {}", item, quantity, player);
+    let arrived = tokio::time::timeout(
+        Duration::from_secs(30),
+        bot.goto(RadiusGoal::new(target, 3.0)),
+    ).await;
+    if arrived.is_err() {
+        bot.stop_pathfinding();
+        warn!("[TRADER] ❌ Não consegui chegar no {}", player);
+        return GiveOutcome::CouldNotReach;
+    }
+
+    // === 2. Face them (they may have moved while we walked) ===
+    let target = player_position(&bot, player).unwrap_or(target);
+    bot.look_at(target.up(1.0));
+    bot.wait_ticks(4).await;
+
+    // === 3. Select and throw ===
+    let before = Inventory::read(&bot).count_of(item);
+    let Some(inventory) = bot.open_inventory() else {
+        warn!("[TRADER] ❌ Inventário ocupado (container aberto?)");
+        return GiveOutcome::ThrowFailed;
+    };
+
+    let mut remaining = quantity;
+    while remaining > 0 {
+        let held = Inventory::read(&bot);
+        let Some(slot) = held.slot_of(item) else { break };
+        let count = held.stack_at(slot).map(|s| s.count() as u32).unwrap_or(0);
+        if count <= remaining {
+            inventory.click(ThrowClick::All { slot: slot as u16 });
+            remaining -= count;
+        } else {
+            inventory.click(ThrowClick::Single { slot: slot as u16 });
+            remaining -= 1;
+        }
+        bot.wait_ticks(2).await; // Human-ish pace, and lets the server ack
+    }
+    inventory.close();
+    bot.wait_ticks(10).await;
+
+    // === 4. Verify it actually left the inventory ===
+    let after = Inventory::read(&bot).count_of(item);
+    let thrown = before.saturating_sub(after);
+    if thrown == 0 {
+        warn!("[TRADER] ❌ Joguei mas o item não saiu do inventário");
+        return GiveOutcome::ThrowFailed;
+    }
+
+    info!("[TRADER] 🤝 Entreguei {} x{} pro {}", item, thrown, player);
+    if thrown < requested {
+        GiveOutcome::Partial { item: item.to_string(), quantity: thrown }
+    } else {
+        GiveOutcome::Delivered { item: item.to_string(), quantity: thrown }
+    }
+}
+
+// ============================================================
+// RECEIVING — Who threw what at us?
+// ============================================================
+
+/// Items spawn at eye height (~1.3 above feet) when a player drops them
+const THROW_SPAWN_RADIUS: f64 = 2.0;
+/// Forget thrown items nobody picked up after this long
+const THROWN_ITEM_TTL: Duration = Duration::from_secs(60);
+
+/// Item entity appeared — if it popped out of a player's hands, remember who threw it
+pub fn on_add_entity(state: &State, world: &Mutex<WorldState>, packet: &ClientboundAddEntity) {
+    if packet.entity_type != EntityKind::Item {
+        return;
+    }
+
+    let seen = crate::bot::visible_players(&world.lock().unwrap().nearby_entities);
+    let thrower = seen
+        .into_iter()
+        .map(|p| {
+            let dx = p.pos[0] - packet.position.x;
+            let dy = p.pos[1] + 1.3 - packet.position.y;
+            let dz = p.pos[2] - packet.position.z;
+            (p.name, (dx * dx + dy * dy + dz * dz).sqrt())
+        })
+        .filter(|(_, dist)| *dist <= THROW_SPAWN_RADIUS)
+        .min_by(|a, b| a.1.total_cmp(&b.1));
+
+    if let Some((thrower, _)) = thrower {
+        state.thrown_items.lock().unwrap().insert(packet.id.0, ThrownItem {
+            thrower,
+            item: None,
+            spawned_at: Instant::now(),
+        });
+    }
+}
+
+/// Per-tick: resolve item kinds for tracked throws (metadata lands after the spawn)
+/// and drop throws that went stale
+pub fn tick_thrown_items(bot: &Client, state: &State) {
+    let mut thrown = state.thrown_items.lock().unwrap();
+    thrown.retain(|_, t| t.spawned_at.elapsed() < THROWN_ITEM_TTL);
+    for (id, t) in thrown.iter_mut().filter(|(_, t)| t.item.is_none()) {
+        t.item = item_entity_kind(bot, *id);
+    }
+}
+
+/// We picked something up — was it thrown to us by a player?
+pub fn on_take_item(bot: &Client, state: &State, packet: &ClientboundTakeItemEntity) -> Option<ReceivedItem> {
+    let own_id = bot.get_component::<MinecraftEntityId>()?;
+    if packet.player_id != own_id {
+        return None;
+    }
+
+    let id = packet.item_id as i32;
+    let thrown = state.thrown_items.lock().unwrap().remove(&id)?;
+    let item = thrown.item.or_else(|| item_entity_kind(bot, id))?;
+
+    info!("[TRADER] 📥 Peguei {} x{} que o {} jogou", item, packet.amount, thrown.thrower);
+    Some(ReceivedItem {
+        player: thrown.thrower,
+        item,
+        quantity: packet.amount,
+    })
+}
+
+fn item_entity_kind(bot: &Client, id: i32) -> Option<String> {
+    let entity = bot.ecs_entity_by_minecraft_entity(MinecraftEntityId(id))?;
+    let stack = bot.get_entity_component::<ItemItem>(entity)?;
+    if stack.0.is_empty() {
+        return None;
+    }
+    Some(item_id(&stack.0))
+}
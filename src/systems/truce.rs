@@ -0,0 +1,161 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use crate::cognitive::memory::Grudge;
+use crate::systems::tone::{self, Tone};
+
+// ============================================================
+// TRUCE — De-escalation with rivals and enemies
+// grievance → restitution → truce offer → settled (or refused)
+// "lembra q vc me roubou? paga 3 diamante e a gente fica de boa"
+// ============================================================
+
+/// A truce talk nobody advanced for this long is off
+const STALE_MINS: i64 = 15;
+/// After a refusal (or a settled truce) don't start another one for this long
+const COOLDOWN_MINS: i64 = 30;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TrucePhase {
+    Grievance,   // We told them what they did, waiting for their side
+    Restitution, // We named a price, waiting for the items
+    Offered,     // They paid, we offered a truce
+    Settled,     // Truce accepted
+    Refused,     // They told us where to stick it
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Truce {
+    pub player: String,
+    pub phase: TrucePhase,
+    pub grievance: String,
+    pub demand: Option<(String, u32)>,
+    pub last_step: DateTime<Utc>,
+}
+
+/// What the truce talk wants done after a message
+#[derive(Debug, Clone, PartialEq)]
+pub enum TruceStep {
+    Say(String),
+    /// Name the price — (line, item, quantity). Caller books it as a debt
+    Demand(String, String, u32),
+    Settle(String),
+    Refuse(String),
+}
+
+const REFUSALS: &[&str] = &[
+    "nunca", "n vou pagar", "não vou pagar", "nao vou pagar", "nem a pau", "dane-se",
+    "foda-se", "se fode", "vai sonhando", "sonha", "problema seu",
+];
+const ACCEPTS: &[&str] = &[
+    "sim", "fechou", "fechado", "trégua", "tregua", "paz", "bora", "beleza", "blz",
+    "ok", "ta bom", "tá bom", "firmeza", "tranquilo", "suave", "de boa",
+];
+
+fn refuses(message: &str) -> bool {
+    let lower = message.to_lowercase();
+    matches!(tone::classify(message), Tone::Insult | Tone::Threat)
+        || REFUSALS.iter().any(|r| lower.contains(r))
+}
+
+fn accepts(message: &str) -> bool {
+    let lower = message.to_lowercase();
+    lower.split(|c: char| !c.is_alphanumeric() && c != '-')
+        .any(|w| ACCEPTS.contains(&w))
+        || ACCEPTS.iter().any(|a| a.contains(' ') && lower.contains(a))
+}
+
+/// Price of making things right: one diamond per point of grudge severity
+fn restitution_for(grudges: &[Grudge]) -> (String, u32) {
+    let severity: u32 = grudges.iter().map(|g| g.severity as u32).sum();
+    ("diamond".into(), severity.clamp(1, 5))
+}
+
+impl Truce {
+    /// A rival/enemy spoke to us: bring up what they did. Returns the talk and our opener
+    pub fn open(player: &str, grudges: &[Grudge]) -> (Self, String) {
+        let worst = grudges.iter().max_by_key(|g| (g.severity, g.created_at));
+        let (grievance, line) = match worst {
+            Some(g) => (
+                g.reason.clone(),
+                format!("{} vc lembra q {}? eu n esqueci n. oq vc quer?", player, g.reason),
+            ),
+            None => (
+                "sei lá, a gente se estranhou".into(),
+                format!("{} a gente n ta de boa e vc sabe disso. fala ai", player),
+            ),
+        };
+        let truce = Self {
+            player: player.to_string(),
+            phase: TrucePhase::Grievance,
+            grievance,
+            demand: None,
+            last_step: Utc::now(),
+        };
+        (truce, line)
+    }
+
+    /// Still talking (not settled, refused or abandoned)
+    pub fn is_open(&self) -> bool {
+        matches!(self.phase, TrucePhase::Grievance | TrucePhase::Restitution | TrucePhase::Offered)
+            && Utc::now().signed_duration_since(self.last_step).num_minutes() < STALE_MINS
+    }
+
+    /// Too soon to start over after this one ended
+    pub fn cooling_down(&self) -> bool {
+        !self.is_open() && Utc::now().signed_duration_since(self.last_step).num_minutes() < COOLDOWN_MINS
+    }
+
+    /// They said something. `debt` is the oldest thing they already owe us, if any
+    pub fn on_message(&mut self, message: &str, grudges: &[Grudge], debt: Option<(String, u32)>) -> Option<TruceStep> {
+        self.last_step = Utc::now();
+        if refuses(message) {
+            self.phase = TrucePhase::Refused;
+            return Some(TruceStep::Refuse(format!("entao fica assim {}. fica longe de mim", self.player)));
+        }
+
+        match self.phase {
+            TrucePhase::Grievance => {
+                self.phase = TrucePhase::Restitution;
+                match debt {
+                    // They already owe us — that's the price
+                    Some((item, quantity)) => {
+                        self.demand = Some((item.clone(), quantity));
+                        Some(TruceStep::Say(format!(
+                            "primeiro paga os {} {} q vc me deve, dai a gente conversa",
+                            quantity, item.replace('_', " ")
+                        )))
+                    }
+                    None => {
+                        let (item, quantity) = restitution_for(grudges);
+                        self.demand = Some((item.clone(), quantity));
+                        Some(TruceStep::Demand(
+                            format!("quer ficar de boa? me paga {} {} pelo q vc fez e a gente zera", quantity, item.replace('_', " ")),
+                            item,
+                            quantity,
+                        ))
+                    }
+                }
+            }
+            TrucePhase::Restitution => {
+                let (item, quantity) = self.demand.clone()?;
+                Some(TruceStep::Say(format!("sem os {} {} sem papo", quantity, item.replace('_', " "))))
+            }
+            TrucePhase::Offered if accepts(message) => {
+                self.phase = TrucePhase::Settled;
+                Some(TruceStep::Settle(format!("fechou {}, trégua. zerado entre nós, n vacila", self.player)))
+            }
+            TrucePhase::Offered => Some(TruceStep::Say(format!("e ai {}, trégua ou n?", self.player))),
+            TrucePhase::Settled | TrucePhase::Refused => None,
+        }
+    }
+
+    /// Everything they owed came in — offer the truce
+    pub fn on_paid(&mut self) -> Option<String> {
+        if self.phase != TrucePhase::Restitution {
+            return None;
+        }
+        self.phase = TrucePhase::Offered;
+        self.last_step = Utc::now();
+        Some(format!("recebido {}. trégua entao? sem treta daqui pra frente", self.player))
+    }
+}
@@ -60,10 +60,8 @@ pub fn apply_typos(text: &str, mood: &Mood) -> String {
                 result.push_str(" pqp");
             }
         }
-        Mood::Scared => {
-            if rng.r#gen::<f32>() < 0.25 {
-                result = result.to_uppercase(); // PANIC CAPS
-            }
+        Mood::Scared if rng.r#gen::<f32>() < 0.25 => {
+            result = result.to_uppercase(); // PANIC CAPS
         }
         _ => {}
     }
@@ -79,6 +77,134 @@ pub fn apply_typos(text: &str, mood: &Mood) -> String {
     result.trim().to_string()
 }
 
+// === Long replies ===
+
+/// Above this, a real player would hit enter and keep typing
+const SPLIT_AT: usize = 120;
+/// Minecraft chat limit is 256, keep some slack
+const MAX_LINE: usize = 250;
+const MAX_PARTS: usize = 3;
+
+/// Break a long reply into 2-3 chat lines on natural boundaries
+pub fn split_reply(text: &str) -> Vec<String> {
+    let text = text.trim();
+    if text.chars().count() <= SPLIT_AT {
+        return vec![text.to_string()];
+    }
+
+    // Sentence/clause pieces, punctuation kept with the piece before it
+    let mut pieces: Vec<String> = vec![];
+    let mut current = String::new();
+    let chars: Vec<char> = text.chars().collect();
+    for (i, c) in chars.iter().enumerate() {
+        if *c == '\n' {
+            pieces.push(std::mem::take(&mut current));
+            continue;
+        }
+        current.push(*c);
+        let boundary = matches!(c, '.' | '!' | '?' | ',' | ';')
+            && chars.get(i + 1).is_none_or(|n| n.is_whitespace());
+        if boundary {
+            pieces.push(std::mem::take(&mut current));
+        }
+    }
+    pieces.push(current);
+
+    // Greedily pack pieces into lines
+    let mut parts: Vec<String> = vec![];
+    let mut line = String::new();
+    for piece in pieces.iter().map(|p| p.trim()).filter(|p| !p.is_empty()) {
+        for chunk in wrap_words(piece, SPLIT_AT) {
+            if !line.is_empty() && line.chars().count() + 1 + chunk.chars().count() > SPLIT_AT {
+                parts.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&chunk);
+        }
+    }
+    if !line.is_empty() {
+        parts.push(line);
+    }
+
+    // Nobody sends 5 lines in a row — squash the tail into the last one
+    if parts.len() > MAX_PARTS {
+        let tail = parts.split_off(MAX_PARTS - 1).join(" ");
+        parts.push(tail);
+    }
+    // The squashed tail can still run past the chat limit — cut it at a word and say so
+    parts.into_iter()
+        .map(|p| {
+            if p.chars().count() <= MAX_LINE {
+                return p;
+            }
+            let mut cut = wrap_words(&p, MAX_LINE - 1).into_iter().next().unwrap_or_default();
+            cut.push('…');
+            cut
+        })
+        .collect()
+}
+
+/// Split on spaces into chunks of at most `width` chars (never mid-word unless a word is huge)
+fn wrap_words(text: &str, width: usize) -> Vec<String> {
+    let mut chunks = vec![];
+    let mut chunk = String::new();
+    for word in text.split_whitespace() {
+        let word: String = word.chars().take(width).collect();
+        if !chunk.is_empty() && chunk.chars().count() + 1 + word.chars().count() > width {
+            chunks.push(std::mem::take(&mut chunk));
+        }
+        if !chunk.is_empty() {
+            chunk.push(' ');
+        }
+        chunk.push_str(&word);
+    }
+    if !chunk.is_empty() {
+        chunks.push(chunk);
+    }
+    chunks
+}
+
+/// If a fat-finger typo slipped into `typed`, the "*palavra" a player would send to fix it
+pub fn typo_correction(original: &str, typed: &str) -> Option<String> {
+    let original: Vec<&str> = original.split_whitespace().collect();
+    let typed: Vec<&str> = typed.split_whitespace().collect();
+    if original.len() != typed.len() {
+        return None; // Abbreviations/fillers shifted things, can't line words up
+    }
+    let mut rng = rand::thread_rng();
+    original.iter().zip(typed.iter())
+        .map(|(o, t)| {
+            let clean = |w: &str| strip_accents(&w.to_lowercase())
+                .trim_matches(|c: char| !c.is_alphanumeric())
+                .to_string();
+            (o.to_lowercase().trim_matches(|c: char| !c.is_alphanumeric()).to_string(), clean(o), clean(t))
+        })
+        .find(|(_, o, t)| {
+            o != t
+                && !ABBREVIATIONS.iter().any(|(from, to)| strip_accents(from) == *o && to == t)
+                && o.chars().count() >= 3
+                && o.chars().count().abs_diff(t.chars().count()) <= 1
+        })
+        .filter(|_| rng.r#gen::<f32>() < 0.3) // Usually we just don't bother
+        .map(|(word, _, _)| format!("*{}", word))
+}
+
+fn strip_accents(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            'á' | 'à' | 'ã' | 'â' => 'a',
+            'é' | 'è' | 'ê' => 'e',
+            'í' | 'ì' => 'i',
+            'ó' | 'ò' | 'õ' | 'ô' => 'o',
+            'ú' | 'ù' => 'u',
+            'ç' => 'c',
+            other => other,
+        })
+        .collect()
+}
+
 /// Swap two adjacent characters at a random position
 fn maybe_swap_letters(text: &str, chance: f32, rng: &mut impl Rng) -> String {
     if text.len() < 4 {
@@ -169,36 +295,36 @@ fn remove_some_accents(text: &str, rng: &mut impl Rng) -> String {
         .collect()
 }
 
+const ABBREVIATIONS: &[(&str, &str)] = &[
+    ("porque", "pq"),
+    ("também", "tb"),
+    ("você", "vc"),
+    ("voce", "vc"),
+    ("não", "n"),
+    ("nao", "n"),
+    ("para", "pra"),
+    ("está", "ta"),
+    ("esta", "ta"),
+    ("estou", "to"),
+    ("muito", "mt"),
+    ("quando", "qnd"),
+    ("quanto", "qnt"),
+    ("aqui", "aki"),
+    ("beleza", "blz"),
+    ("tranquilo", "tranks"),
+    ("obrigado", "vlw"),
+    ("obrigada", "vlw"),
+    ("verdade", "vdd"),
+    ("comigo", "cmg"),
+    ("contigo", "ctg"),
+    ("demais", "dms"),
+];
+
 /// Apply common chat abbreviations
 fn apply_abbreviations(text: &str, rng: &mut impl Rng) -> String {
     let mut result = text.to_string();
 
-    let replacements = [
-        ("porque", "pq"),
-        ("também", "tb"),
-        ("você", "vc"),
-        ("voce", "vc"),
-        ("não", "n"),
-        ("nao", "n"),
-        ("para", "pra"),
-        ("está", "ta"),
-        ("esta", "ta"),
-        ("estou", "to"),
-        ("muito", "mt"),
-        ("quando", "qnd"),
-        ("quanto", "qnt"),
-        ("aqui", "aki"),
-        ("beleza", "blz"),
-        ("tranquilo", "tranks"),
-        ("obrigado", "vlw"),
-        ("obrigada", "vlw"),
-        ("verdade", "vdd"),
-        ("comigo", "cmg"),
-        ("contigo", "ctg"),
-        ("demais", "dms"),
-    ];
-
-    for (from, to) in &replacements {
+    for (from, to) in ABBREVIATIONS {
         if rng.r#gen::<f32>() < 0.6 { // 60% chance to abbreviate
             result = result.replace(from, to);
         }
@@ -230,6 +356,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_split_long_reply() {
+        let input = "mano eu fiz uma iron farm com 20 vilagers, demorou umas 3 horas pra terminar tudo. \
+            agora to pensando em fazer uma de ouro no nether, mas preciso de muita obsidian pro portal. \
+            se vc tiver sobrando me fala que eu troco por ferro, tenho uns 5 pack guardado no bau da base";
+        let parts = split_reply(input);
+        assert!(parts.len() >= 2 && parts.len() <= 3);
+        assert!(parts.iter().all(|p| p.chars().count() <= 250));
+        assert_eq!(parts.join(" ").split_whitespace().count(), input.split_whitespace().count());
+        assert_eq!(split_reply("salve mn"), vec!["salve mn".to_string()]);
+    }
+
+    #[test]
+    fn test_split_too_long_marks_the_cut() {
+        let input = "bora minerar mais um pouco, ".repeat(30);
+        let parts = split_reply(&input);
+        assert_eq!(parts.len(), 3);
+        assert!(parts.iter().all(|p| p.chars().count() <= 250));
+        assert!(parts[2].ends_with('…'));
+        assert!(!parts[0].ends_with('…'));
+    }
+
     #[test]
     fn test_abbreviations() {
         let input = "porque você não está aqui comigo";
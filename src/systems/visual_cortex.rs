@@ -1,12 +1,21 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use crate::config::Config;
+use tracing::error;
 
 // ============================================================
 // VISUAL CORTEX — Architectural Judgment via Gemini
 // Scans 16x16x16, builds a heatmap, asks Gemini to judge
 // ============================================================
 
+/// What the world generates by itself — a box full of these is terrain, not a build
+const NATURAL: &[&str] = &[
+    "stone", "deepslate", "tuff", "granite", "diorite", "andesite", "dirt", "grass_block", "coarse_dirt",
+    "podzol", "gravel", "sand", "sandstone", "red_sand", "clay", "water", "lava", "bedrock", "netherrack",
+    "short_grass", "tall_grass", "fern", "snow", "ice", "moss_block",
+];
+/// Fewer placed-looking blocks than this and there's nothing to judge
+const MIN_BUILT: u32 = 12;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockScan {
     pub block_counts: HashMap<String, u32>,
@@ -18,6 +27,21 @@ pub struct BlockScan {
 }
 
 impl BlockScan {
+    /// Blocks that aren't air, terrain, leaves or ore — what somebody probably put there
+    pub fn built_blocks(&self) -> u32 {
+        self.block_counts.iter()
+            .filter(|(block, _)| {
+                !NATURAL.contains(&block.as_str()) && !block.ends_with("_leaves") && !block.ends_with("_ore") && *block != "air" && *block != "cave_air"
+            })
+            .map(|(_, count)| count)
+            .sum()
+    }
+
+    /// Worth asking the LLM about: someone built something here
+    pub fn worth_judging(&self) -> bool {
+        self.built_blocks() >= MIN_BUILT
+    }
+
     /// Analyze scanned blocks into a human-readable summary
     pub fn to_summary(&self) -> String {
         let mut lines = vec![];
@@ -34,6 +58,9 @@ impl BlockScan {
 
         lines.push(format!("Posição: [{}, {}, {}]", self.center[0], self.center[1], self.center[2]));
         lines.push(format!("Blocos sólidos: {} | Tipos únicos: {}", self.total_blocks, self.unique_types));
+        lines.push(format!("Blocos colocados (fora terreno natural): {}", self.built_blocks()));
+        lines.push(format!("Ar: {:.0}% | Luz média: {:.1}/15{}", self.air_percentage, self.light_avg,
+            if self.light_avg < 7.0 { " (escuro, spawna mob)" } else { "" }));
 
         // Top 8 materials
         lines.push("Materiais principais:".into());
@@ -162,7 +189,7 @@ Responda SOMENTE o comentário que o jogador diria no chat."#,
 }
 
 /// Decide if we should scan and judge (not too often)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct VisualCortexState {
     pub last_scan_pos: Option<[i32; 3]>,
     pub scans_done: u32,
@@ -170,17 +197,6 @@ pub struct VisualCortexState {
     pub tick_counter: u32,
 }
 
-impl Default for VisualCortexState {
-    fn default() -> Self {
-        Self {
-            last_scan_pos: None,
-            scans_done: 0,
-            cooldown_ticks: 0,
-            tick_counter: 0,
-        }
-    }
-}
-
 impl VisualCortexState {
     /// Should we do a scan this tick?
     pub fn should_scan(&mut self, current_pos: [i32; 3]) -> bool {
@@ -192,7 +208,7 @@ impl VisualCortexState {
         }
 
         // Only scan every ~60 seconds
-        if self.tick_counter % 1200 != 0 {
+        if !self.tick_counter.is_multiple_of(1200) {
             return false;
         }
 
@@ -212,43 +228,13 @@ impl VisualCortexState {
     }
 }
 
-/// Send scan to Gemini for judgment (async, non-blocking)
+/// Send scan to the LLM for judgment (async, non-blocking). Pro model when the provider has one
 pub async fn judge_with_gemini(scan: &BlockScan) -> Option<String> {
-    let config = Config::load();
     let prompt = build_judgment_prompt(scan);
-
-    let client = reqwest::Client::new();
-    let url = format!(
-        "https://generativelanguage.googleapis.com/v1beta/models/{}:generateContent?key={}",
-        config.model_pro, config.gemini_api_key
-    );
-
-    #[derive(serde::Serialize)]
-    struct Req { contents: Vec<C>, #[serde(rename = "generationConfig")] generation_config: G }
-    #[derive(serde::Serialize)]
-    struct C { parts: Vec<P> }
-    #[derive(serde::Serialize)]
-    struct P { text: String }
-    #[derive(serde::Serialize)]
-    struct G { #[serde(rename = "maxOutputTokens")] max: u32, temperature: f32 }
-
-    let body = Req {
-        contents: vec![C { parts: vec![P { text: prompt }] }],
-        generation_config: G { max: 80, temperature: 0.9 },
-    };
-
-    match client.post(&url).json(&body).send().await {
-        Ok(resp) => {
-            if let Ok(json) = resp.json::<serde_json::Value>().await {
-                json["candidates"][0]["content"]["parts"][0]["text"]
-                    .as_str()
-                    .map(|s| s.trim().to_string())
-            } else {
-                None
-            }
-        }
+    match crate::llm::complete(true, prompt, 80, 0.9).await {
+        Ok((reply, _)) => Some(reply.text),
         Err(e) => {
-            println!("[VISUAL] ❌ Gemini error: {}", e);
+            error!("[VISUAL] ❌ LLM error: {}", e);
             None
         }
     }
@@ -1,6 +1,16 @@
 use serde::{Deserialize, Serialize};
-// use azalea::BlockPos;
+use azalea::prelude::*;
+use azalea::BlockPos;
+use azalea::block::BlockTrait;
+use azalea::ecs::query::{With, Without};
+use azalea::entity::{metadata::{AbstractLiving, IsIgnited, SwellDir}, Crouching, Dead, EntityKindComponent, LocalEntity, LookDirection, Position};
+use azalea::player::GameProfileComponent;
+use azalea::registry::builtin::BlockKind;
+use azalea::world::MinecraftEntityId;
 use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+use crate::systems::visual_cortex::BlockScan;
 
 // ============================================================
 // WORLD SCANNER — Environmental awareness
@@ -47,6 +57,44 @@ pub enum Biome {
     Unknown,
 }
 
+impl Biome {
+    /// "minecraft:old_growth_pine_taiga" → Taiga. Datapack biomes end up Unknown
+    pub fn from_name(name: &str) -> Self {
+        let name = name.rsplit(':').next().unwrap_or(name);
+        let has = |words: &[&str]| words.iter().any(|w| name.contains(w));
+        if has(&["nether", "crimson", "warped", "soul_sand", "basalt"]) {
+            Biome::Nether
+        } else if has(&["end_", "the_end", "small_end"]) {
+            Biome::End
+        } else if has(&["ocean", "river", "beach"]) {
+            Biome::Ocean
+        } else if has(&["swamp"]) {
+            Biome::Swamp
+        } else if has(&["jungle", "bamboo"]) {
+            Biome::Jungle
+        } else if has(&["taiga", "snowy", "frozen", "ice", "grove"]) {
+            Biome::Taiga
+        } else if has(&["desert", "badlands", "savanna"]) {
+            Biome::Desert
+        } else if has(&["peaks", "hills", "slopes", "mountain", "windswept", "stony"]) {
+            Biome::Mountain
+        } else if has(&["forest", "birch", "garden"]) {
+            Biome::Forest
+        } else if has(&["plains", "meadow", "field", "cherry"]) {
+            Biome::Plains
+        } else {
+            Biome::Unknown
+        }
+    }
+}
+
+/// Biome under our feet, by its registry name. None while the chunk isn't loaded
+pub fn read_biome(bot: &Client, pos: [i32; 3]) -> Option<Biome> {
+    let biome = bot.world().read().get_biome(BlockPos::new(pos[0], pos[1], pos[2]))?;
+    let name = bot.resolve_registry_name(&biome)?;
+    Some(Biome::from_name(&name.to_string()))
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NearbyResource {
     pub block_type: String,
@@ -54,6 +102,31 @@ pub struct NearbyResource {
     pub distance: f64,
 }
 
+/// Something alive around us, as the entity feed saw it this tick
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NearbyEntity {
+    pub name: String, // Username for players, mob type otherwise ("zombie")
+    pub kind: String, // "player", "zombie", "cow"...
+    pub position: [f64; 3],
+    pub distance: f64,
+    pub entity_id: i32,
+    pub hostile: bool,
+    #[serde(default)]
+    pub closing_speed: f64, // Blocks/s toward us, smoothed over the last ticks; negative = moving off
+    #[serde(default)]
+    pub fusing: bool, // Creeper hissing (or lit by flint)
+    #[serde(default)]
+    pub sneaking: bool,
+    #[serde(default)]
+    pub look: [f64; 3], // Unit view vector
+}
+
+impl NearbyEntity {
+    pub fn is_player(&self) -> bool {
+        self.kind == "player"
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WorldState {
     pub time_of_day: TimeOfDay,
@@ -62,11 +135,21 @@ pub struct WorldState {
     pub nearby_resources: Vec<NearbyResource>,
     pub nearby_mobs: Vec<String>,
     pub nearby_players: Vec<String>,
+    #[serde(skip)]
+    pub nearby_entities: Vec<NearbyEntity>, // Everything alive within ENTITY_RADIUS, nearest first
     pub light_level: u8,
     pub is_raining: bool,
     pub is_underground: bool,
     pub explored_chunks: u32,
     pub last_scan: DateTime<Utc>,
+    #[serde(skip)]
+    pub visited_chunks: HashSet<[i32; 2]>,
+    #[serde(skip)]
+    pub frontier: HashSet<[i32; 2]>, // Unvisited chunks touching visited ones
+    #[serde(skip)]
+    pub last_block_scan: Option<BlockScan>, // Latest real look at the blocks around us
+    #[serde(skip)]
+    pub spawn_heatmap: HashMap<[i32; 3], DarkCell>, // Dark spawnable corners, by 4x4x4 cell
 }
 
 impl Default for WorldState {
@@ -78,16 +161,469 @@ impl Default for WorldState {
             nearby_resources: vec![],
             nearby_mobs: vec![],
             nearby_players: vec![],
+            nearby_entities: vec![],
             light_level: 15,
             is_raining: false,
             is_underground: false,
             explored_chunks: 0,
             last_scan: Utc::now(),
+            visited_chunks: HashSet::new(),
+            frontier: HashSet::new(),
+            last_block_scan: None,
+            spawn_heatmap: HashMap::new(),
         }
     }
 }
 
+/// Cube half-size the periodic scan reads (17x17x17 around us)
+pub const SCAN_RADIUS: i32 = 8;
+/// How far above the scan box we look for a roof
+const SKY_CHECK: i32 = 24;
+/// Solid blocks over our head before we call it underground
+const UNDERGROUND_ROOF: u32 = 3;
+/// Only the closest few resources are worth remembering
+const MAX_RESOURCES: usize = 10;
+
+/// Blocks that light up their surroundings → light level (azalea keeps no light data)
+const LIGHT_SOURCES: &[(&str, u8)] = &[
+    ("lantern", 15), ("glowstone", 15), ("sea_lantern", 15), ("jack_o_lantern", 15),
+    ("lava", 15), ("shroomlight", 15), ("campfire", 15), ("beacon", 15), ("fire", 15),
+    ("froglight", 15), ("torch", 14), ("end_rod", 14), ("soul_torch", 10), ("soul_lantern", 10),
+    ("redstone_torch", 7), ("magma_block", 3),
+];
+
+/// How far the entity feed looks
+pub const ENTITY_RADIUS: f64 = 32.0;
+/// How much of the closing speed is this tick's reading — the rest is history, so one laggy packet doesn't spike it
+const CLOSING_SMOOTHING: f64 = 0.2;
+
+/// Mobs that come for us on sight
+const HOSTILE_MOBS: &[&str] = &[
+    "zombie", "husk", "drowned", "zombie_villager", "skeleton", "stray", "bogged", "creeper",
+    "spider", "cave_spider", "witch", "slime", "magma_cube", "phantom", "pillager", "vindicator",
+    "evoker", "ravager", "vex", "blaze", "ghast", "wither_skeleton", "piglin_brute", "hoglin",
+    "zoglin", "silverfish", "endermite", "guardian", "elder_guardian", "breeze", "warden", "creaking",
+];
+
+fn emitted_light(block: &str) -> Option<u8> {
+    LIGHT_SOURCES.iter()
+        .filter(|(name, _)| block.ends_with(name))
+        .map(|(_, level)| *level)
+        .max()
+}
+
+fn is_resource(block: &str) -> bool {
+    block.ends_with("_ore") || block == "ancient_debris" || block == "amethyst_cluster"
+}
+
+/// What one pass over the blocks around us found
+pub struct AreaScan {
+    pub blocks: BlockScan,
+    pub resources: Vec<NearbyResource>,
+    pub light_here: u8,
+    pub roof: u32, // Roofing blocks straight above our head (not leaves or water)
+    pub dark_spots: Vec<DarkSpot>,
+    pub landmarks: Vec<(String, [i32; 3])>, // Where the telltale blocks sit (spawner, bell...)
+    pub tall_air_columns: u32, // Covered columns with a long drop of air — ravine-shaped
+    pub openings: Vec<[i32; 3]>, // Covered air running out of the box — tunnels going somewhere
+    pub containers: Vec<([i32; 3], String)>, // Chests, barrels, shulkers
+    pub box_min: [i32; 3], // What the scan covered, to clear spots that got lit since
+    pub box_max: [i32; 3],
+}
+
+impl AreaScan {
+    pub fn is_underground(&self) -> bool {
+        self.roof >= UNDERGROUND_ROOF
+    }
+}
+
+/// Floor where a mob could spawn: solid below, two air above, no torch reaching it
+#[derive(Debug, Clone, Copy)]
+pub struct DarkSpot {
+    pub pos: [i32; 3],
+    pub covered: bool, // Under a roof → dark day and night. Open sky only spawns at night
+}
+
+/// One 4x4x4 cell of the spawn heatmap
+#[derive(Debug, Clone)]
+pub struct DarkCell {
+    pub spot: [i32; 3],
+    pub hits: u32, // Scans that found it dark
+    pub last_seen: Instant,
+}
+
+/// Heatmap cell size
+const DARK_CELL: i32 = 4;
+/// Open-sky dark spots only matter this close to the base
+pub const BASE_RADIUS: f64 = 32.0;
+/// Seen dark this many times = real, not a glitchy read
+pub const DARK_HITS: u32 = 2;
+/// Heatmap cap, oldest go first
+const MAX_DARK_CELLS: usize = 256;
+
+/// Blocks mobs can't spawn on (or that aren't a full floor)
+const NO_SPAWN_FLOOR: &[&str] = &[
+    "slab", "stairs", "glass", "leaves", "fence", "wall", "carpet", "water", "lava", "bedrock",
+    "torch", "lantern", "door", "trapdoor", "pane", "bars", "rail", "sign", "bed", "chest", "farmland",
+    "dirt_path", "button", "pressure_plate", "flower", "short_grass", "tall_grass", "fern", "bush",
+    "sapling", "mushroom",
+];
+
+fn spawnable_floor(block: &str) -> bool {
+    block != "snow" && !NO_SPAWN_FLOOR.iter().any(|b| block.contains(b))
+}
+
+/// Above our head but not a roof: fluids, foliage, thin stuff the sky gets through
+const NOT_ROOF: &[&str] = &[
+    "water", "lava", "bubble_column", "leaves", "vine", "seagrass", "kelp", "sapling", "cobweb", "torch",
+    "pane", "bars",
+];
+
+/// A block that makes a roof: solid, not foliage, not fluid
+fn roofing(block: &str) -> bool {
+    !matches!(block, "air" | "cave_air" | "void_air" | "snow" | "short_grass" | "tall_grass" | "fern"
+        | "large_fern" | "bush" | "sugar_cane" | "bamboo")
+        && !NOT_ROOF.iter().any(|b| block.contains(b))
+}
+
+/// Both halves of a double chest come out as the same position
+fn chest_index_pos(world: &azalea::world::Instance, pos: [i32; 3]) -> [i32; 3] {
+    let Some(state) = world.get_block_state(BlockPos::new(pos[0], pos[1], pos[2])) else { return pos };
+    let block = Box::<dyn BlockTrait>::from(state);
+    match (block.get_property("facing"), block.get_property("type")) {
+        (Some(facing), Some(half)) => crate::systems::storage::index_pos(pos, facing, half),
+        _ => pos,
+    }
+}
+
+/// Read the blocks around `center` from the loaded chunks
+pub fn scan_area(bot: &Client, center: [i32; 3], radius: i32) -> AreaScan {
+    let world = bot.world();
+    let world = world.read();
+    let name_at = |x: i32, y: i32, z: i32| -> Option<String> {
+        let state = world.get_block_state(BlockPos::new(x, y, z))?;
+        if state.is_air() {
+            return Some("air".into());
+        }
+        Some(BlockKind::from(state).to_string().trim_start_matches("minecraft:").to_string())
+    };
+
+    let [cx, cy, cz] = center;
+    let mut block_counts: HashMap<String, u32> = HashMap::new();
+    let mut air = vec![];
+    let mut emitters = vec![];
+    let mut resources = vec![];
+    let mut landmarks = vec![];
+    let mut containers = vec![];
+    let mut scanned = 0u32;
+
+    for x in cx - radius..=cx + radius {
+        for z in cz - radius..=cz + radius {
+            for y in cy - radius..=cy + radius {
+                let Some(block) = name_at(x, y, z) else { continue }; // Chunk not loaded
+                scanned += 1;
+                if block == "air" || block == "cave_air" {
+                    air.push([x, y, z]);
+                    continue;
+                }
+                if let Some(level) = emitted_light(&block) {
+                    emitters.push(([x, y, z], level));
+                }
+                if crate::systems::storage::is_container(&block) || block == "ender_chest" {
+                    let pos = chest_index_pos(&world, [x, y, z]);
+                    if !containers.iter().any(|(p, _)| *p == pos) {
+                        containers.push((pos, block.clone()));
+                    }
+                }
+                if crate::systems::poi::is_landmark(&block) {
+                    landmarks.push((block.clone(), [x, y, z]));
+                }
+                if is_resource(&block) {
+                    let d = (((x - cx).pow(2) + (y - cy).pow(2) + (z - cz).pow(2)) as f64).sqrt();
+                    resources.push(NearbyResource { block_type: block.clone(), position: [x, y, z], distance: d });
+                }
+                *block_counts.entry(block).or_insert(0) += 1;
+            }
+        }
+    }
+
+    // Lava lakes would make the light pass crawl — the closest sources are what matter
+    emitters.sort_by_key(|([x, y, z], _)| (x - cx).abs() + (y - cy).abs() + (z - cz).abs());
+    emitters.truncate(64);
+
+    // Top roofing block of each column (looking a bit above the box too) → anything under it is covered
+    let mut roof_height: HashMap<[i32; 2], i32> = HashMap::new();
+    for x in cx - radius..=cx + radius {
+        for z in cz - radius..=cz + radius {
+            let top = (cy - radius..=cy + radius + SKY_CHECK).rev()
+                .find(|y| name_at(x, *y, z).is_some_and(|b| roofing(&b)));
+            if let Some(top) = top {
+                roof_height.insert([x, z], top);
+            }
+        }
+    }
+
+    // Sky-lit air counts as 15, covered air gets whatever the nearest torch/lava gives it
+    let covered_at = |[x, y, z]: [i32; 3]| roof_height.get(&[x, z]).is_some_and(|top| *top > y);
+    let block_light_at = |[x, y, z]: [i32; 3]| -> u8 {
+        emitters.iter()
+            .map(|([ex, ey, ez], level)| {
+                let d = (ex - x).abs() + (ey - y).abs() + (ez - z).abs();
+                (*level as i32 - d).max(0) as u8
+            })
+            .max()
+            .unwrap_or(0)
+    };
+    let light_at = |p: [i32; 3]| -> u8 {
+        if covered_at(p) { block_light_at(p) } else { 15 }
+    };
+
+    // Ravines: covered columns with 12+ blocks of air stacked up
+    let mut air_per_column: HashMap<[i32; 2], u32> = HashMap::new();
+    for p in air.iter().filter(|p| covered_at(**p)) {
+        *air_per_column.entry([p[0], p[2]]).or_insert(0) += 1;
+    }
+    let tall_air_columns = air_per_column.values().filter(|n| **n >= 12).count() as u32;
+
+    // Tunnels leaving the box: covered air on each side face (and the floor), one point per face
+    let faces = [(0, cx - radius), (0, cx + radius), (2, cz - radius), (2, cz + radius), (1, cy - radius)];
+    let openings: Vec<[i32; 3]> = faces.iter()
+        .filter_map(|(axis, at)| {
+            let cells: Vec<&[i32; 3]> = air.iter().filter(|p| p[*axis] == *at && covered_at(**p)).collect();
+            if cells.len() < 6 {
+                return None;
+            }
+            let n = cells.len() as i32;
+            Some([0, 1, 2].map(|i| cells.iter().map(|p| p[i]).sum::<i32>() / n))
+        })
+        .collect();
+
+    // Mobs need block light 0 (1.18+), a real floor and room for their head
+    let is_air = |b: &str| b == "air" || b == "cave_air";
+    let mut dark_spots: Vec<DarkSpot> = air.iter()
+        .filter(|[x, y, z]| {
+            name_at(*x, y - 1, *z).is_some_and(|b| !is_air(&b) && spawnable_floor(&b))
+                && name_at(*x, y + 1, *z).is_some_and(|b| is_air(&b))
+        })
+        .filter(|p| block_light_at(**p) == 0)
+        .map(|p| DarkSpot { pos: *p, covered: covered_at(*p) })
+        .collect();
+    dark_spots.sort_by_key(|s| (s.pos[0] - cx).abs() + (s.pos[1] - cy).abs() + (s.pos[2] - cz).abs());
+    let light_avg = if air.is_empty() {
+        0.0
+    } else {
+        air.iter().map(|p| light_at(*p) as f32).sum::<f32>() / air.len() as f32
+    };
+    let light_here = light_at([cx, cy + 1, cz]);
+    let roof = (cy + 2..=cy + 2 + SKY_CHECK)
+        .filter(|y| name_at(cx, *y, cz).is_some_and(|b| roofing(&b)))
+        .count() as u32;
+
+    resources.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+    resources.truncate(MAX_RESOURCES);
+
+    let solid: u32 = block_counts.values().sum();
+    AreaScan {
+        blocks: BlockScan {
+            unique_types: block_counts.len() as u32,
+            block_counts,
+            total_blocks: solid,
+            air_percentage: if scanned == 0 { 100.0 } else { air.len() as f32 / scanned as f32 * 100.0 },
+            light_avg,
+            center,
+        },
+        resources,
+        light_here,
+        roof,
+        dark_spots,
+        landmarks,
+        tall_air_columns,
+        openings,
+        containers,
+        box_min: [cx - radius, cy - radius, cz - radius],
+        box_max: [cx + radius, cy + radius, cz + radius],
+    }
+}
+
+/// One query over every living entity loaded around us (players and mobs), nearest first
+pub fn scan_entities(bot: &Client) -> Vec<NearbyEntity> {
+    let pos = bot.position();
+    let here = [pos.x, pos.y, pos.z];
+    bot.nearest_entities_by::<(), (With<AbstractLiving>, Without<LocalEntity>, Without<Dead>)>(|_: ()| true)
+        .into_iter()
+        .filter_map(|entity| {
+            let kind = bot.get_entity_component::<EntityKindComponent>(entity)?;
+            let at = bot.get_entity_component::<Position>(entity)?;
+            let position = [at.x, at.y, at.z];
+            let distance = crate::systems::spider_sense::distance(position, here);
+            if distance > ENTITY_RADIUS {
+                return None;
+            }
+            let kind = kind.0.to_string().trim_start_matches("minecraft:").to_string();
+            let name = match bot.get_entity_component::<GameProfileComponent>(entity) {
+                Some(profile) => profile.name.clone(),
+                None => kind.clone(),
+            };
+            let entity_id = bot.get_entity_component::<MinecraftEntityId>(entity).map_or(0, |id| id.0);
+            let fusing = bot.get_entity_component::<SwellDir>(entity).is_some_and(|s| s.0 > 0)
+                || bot.get_entity_component::<IsIgnited>(entity).is_some_and(|i| i.0);
+            let sneaking = bot.get_entity_component::<Crouching>(entity).is_some_and(|c| *c);
+            let look = bot.get_entity_component::<LookDirection>(entity)
+                .map(azalea::entity::view_vector)
+                .unwrap_or_default();
+            Some(NearbyEntity {
+                hostile: HOSTILE_MOBS.contains(&kind.as_str()),
+                name,
+                kind,
+                position,
+                distance,
+                entity_id,
+                closing_speed: 0.0,
+                fusing,
+                sneaking,
+                look: [look.x, look.y, look.z],
+            })
+        })
+        .collect()
+}
+
 impl WorldState {
+    /// Take in a fresh scan of the area around us
+    pub fn apply_scan(&mut self, scan: AreaScan, home: Option<[i32; 3]>) {
+        self.mark_dark_spots(&scan, home);
+        self.is_underground = scan.is_underground();
+        self.nearby_resources = scan.resources;
+        self.light_level = scan.light_here;
+        self.last_block_scan = Some(scan.blocks);
+        self.last_scan = Utc::now();
+    }
+
+    /// Fold a scan's dark spots into the heatmap. Open-sky spots only count around the base
+    fn mark_dark_spots(&mut self, scan: &AreaScan, home: Option<[i32; 3]>) {
+        let inside = |p: &[i32; 3]| (0..3).all(|i| p[i] >= scan.box_min[i] && p[i] <= scan.box_max[i]);
+        let near_base = |p: &[i32; 3]| home.is_some_and(|h| {
+            crate::systems::spider_sense::distance(
+                [p[0] as f64, p[1] as f64, p[2] as f64],
+                [h[0] as f64, h[1] as f64, h[2] as f64],
+            ) <= BASE_RADIUS
+        });
+
+        let mut dark_now: HashSet<[i32; 3]> = HashSet::new();
+        for spot in scan.dark_spots.iter().filter(|s| s.covered || near_base(&s.pos)) {
+            let cell = spot.pos.map(|v| v.div_euclid(DARK_CELL));
+            if !dark_now.insert(cell) {
+                continue;
+            }
+            let entry = self.spawn_heatmap.entry(cell)
+                .or_insert(DarkCell { spot: spot.pos, hits: 0, last_seen: Instant::now() });
+            entry.hits += 1;
+            entry.last_seen = Instant::now();
+        }
+
+        // Scanned and not dark anymore = someone lit it up
+        self.spawn_heatmap.retain(|cell, c| dark_now.contains(cell) || !inside(&c.spot));
+
+        if self.spawn_heatmap.len() > MAX_DARK_CELLS {
+            let mut cells: Vec<([i32; 3], Instant)> = self.spawn_heatmap.iter().map(|(k, c)| (*k, c.last_seen)).collect();
+            cells.sort_by_key(|(_, seen)| *seen);
+            for (cell, _) in cells.iter().take(self.spawn_heatmap.len() - MAX_DARK_CELLS) {
+                self.spawn_heatmap.remove(cell);
+            }
+        }
+    }
+
+    /// Confirmed dark corners within `radius` of a point, most-seen first
+    pub fn dark_spots_near(&self, pos: [i32; 3], radius: f64) -> Vec<&DarkCell> {
+        let mut cells: Vec<&DarkCell> = self.spawn_heatmap.values()
+            .filter(|c| c.hits >= DARK_HITS)
+            .filter(|c| crate::systems::spider_sense::distance(
+                [c.spot[0] as f64, c.spot[1] as f64, c.spot[2] as f64],
+                [pos[0] as f64, pos[1] as f64, pos[2] as f64],
+            ) <= radius)
+            .collect();
+        cells.sort_by_key(|c| std::cmp::Reverse(c.hits));
+        cells
+    }
+
+    /// This tick's entity feed → the mob/player lists everyone else reads.
+    /// Closing speeds come from how much nearer each one got since last tick
+    pub fn apply_entities(&mut self, mut entities: Vec<NearbyEntity>) {
+        for e in &mut entities {
+            if let Some(before) = self.nearby_entities.iter().find(|b| b.entity_id == e.entity_id) {
+                let now = (before.distance - e.distance) * 20.0;
+                e.closing_speed = before.closing_speed * (1.0 - CLOSING_SMOOTHING) + now * CLOSING_SMOOTHING;
+            }
+        }
+        self.nearby_players = entities.iter().filter(|e| e.is_player()).map(|e| e.name.clone()).collect();
+        self.nearby_mobs = entities.iter().filter(|e| e.hostile).map(|e| e.kind.clone()).collect();
+        self.nearby_entities = entities;
+    }
+
+    /// Hostiles only, nearest first
+    pub fn hostiles(&self) -> impl Iterator<Item = &NearbyEntity> {
+        self.nearby_entities.iter().filter(|e| e.hostile)
+    }
+
+    /// "zombie x2, creeper (mais perto: creeper a 5m)"
+    fn mobs_summary(&self) -> String {
+        let mut counts: Vec<(&str, u32)> = vec![];
+        for mob in &self.nearby_mobs {
+            match counts.iter_mut().find(|(m, _)| m == mob) {
+                Some((_, n)) => *n += 1,
+                None => counts.push((mob, 1)),
+            }
+        }
+        if counts.is_empty() {
+            return "nenhum".into();
+        }
+        let list = counts.iter()
+            .map(|(m, n)| if *n > 1 { format!("{} x{}", m, n) } else { m.to_string() })
+            .collect::<Vec<_>>()
+            .join(", ");
+        match self.hostiles().next() {
+            Some(closest) => format!("{} (mais perto: {} a {:.0}m)", list, closest.kind, closest.distance),
+            None => list,
+        }
+    }
+
+    /// Count chunks as we walk into them for the first time, and push the frontier out
+    pub fn visit(&mut self, pos: [i32; 3]) {
+        let chunk = [pos[0].div_euclid(16), pos[2].div_euclid(16)];
+        if !self.visited_chunks.insert(chunk) {
+            return;
+        }
+        self.explored_chunks += 1;
+        self.frontier.remove(&chunk);
+        for [dx, dz] in [[1, 0], [-1, 0], [0, 1], [0, -1]] {
+            let next = [chunk[0] + dx, chunk[1] + dz];
+            if !self.visited_chunks.contains(&next) {
+                self.frontier.insert(next);
+            }
+        }
+    }
+
+    /// Block coords of an unexplored chunk next to where we've been, among the closest few.
+    /// None until we've walked somewhere
+    pub fn frontier_target(&self) -> Option<[i32; 3]> {
+        let [x, y, z] = self.current_position;
+        let here = [x.div_euclid(16), z.div_euclid(16)];
+        let mut closest: Vec<(i32, [i32; 2])> = self.frontier.iter()
+            .map(|c| ((c[0] - here[0]).pow(2) + (c[1] - here[1]).pow(2), *c))
+            .collect();
+        closest.sort_by_key(|(d, _)| *d);
+        closest.truncate(3);
+        if closest.is_empty() {
+            return None;
+        }
+        let (_, chunk) = closest[rand::random::<usize>() % closest.len()];
+        Some([chunk[0] * 16 + 8, y, chunk[1] * 16 + 8])
+    }
+
+    /// "34 chunks explorados, 12 na fronteira"
+    pub fn coverage_summary(&self) -> String {
+        format!("{} chunks explorados, {} na fronteira", self.explored_chunks, self.frontier.len())
+    }
+
     /// Should the bot seek shelter?
     pub fn should_seek_shelter(&self, hp: f32) -> bool {
         (self.time_of_day.is_dangerous() && !self.is_underground && hp < 14.0)
@@ -117,15 +653,40 @@ impl WorldState {
 
     pub fn context_summary(&self) -> String {
         format!(
-            "Posição: [{}, {}, {}] | Horário: {:?} | Bioma: {:?} | Perigo: {}/10 | Mobs: {} | Players: {}",
+            "Posição: [{}, {}, {}] | Horário: {:?} | Bioma: {:?} | Perigo: {}/10 | Mobs: {} | Players: {} | {}{}{}{}",
             self.current_position[0],
             self.current_position[1],
             self.current_position[2],
             self.time_of_day,
             self.current_biome,
             self.danger_level(),
-            self.nearby_mobs.len(),
+            self.mobs_summary(),
             if self.nearby_players.is_empty() { "nenhum".into() } else { self.nearby_players.join(", ") },
+            self.coverage_summary(),
+            if self.is_underground { " | No subsolo" } else { "" },
+            match self.nearby_resources.first() {
+                Some(r) => format!(" | Minério perto: {} a {:.0}m", r.block_type, r.distance),
+                None => String::new(),
+            },
+            match self.dark_spots_near(self.current_position, 12.0).len() {
+                0 => String::new(),
+                n => format!(" | {} canto(s) escuro(s) aqui perto (spawn de mob)", n),
+            },
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_leaves_and_water_arent_a_roof() {
+        for block in ["oak_leaves", "water", "vine", "tall_grass", "snow", "air"] {
+            assert!(!roofing(block), "{}", block);
+        }
+        for block in ["stone", "deepslate", "grass_block", "snow_block", "oak_planks", "oak_stairs", "bamboo_planks"] {
+            assert!(roofing(block), "{}", block);
+        }
+    }
+}
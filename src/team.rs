@@ -0,0 +1,201 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use tracing::info;
+use crate::bus::{Ctx, ThreatDetected};
+use crate::cognitive::goal_planner::{Goal, GoalPriority};
+use crate::config::Config;
+use crate::session::Session;
+use crate::systems::spider_sense::PredictionType;
+
+// ============================================================
+// TEAM — Our own bots passing notes to each other, off the server's chat
+// Zezin_ vê alguém chegando com TNT → PedroRTX já sabe, e baixa a confiança no cara
+// ============================================================
+//
+// Notes never go through game chat. Bots in this process get them right away; bots
+// on other hosts get them through the knowledge hub on their next sync, so that needs
+// [knowledge] on. What each kind does when it lands:
+// - warning: trust in the player drops, the LLM hears why
+// - meetup: a goal to go meet whoever asked
+// - trade: a goal to go get it, if one of ours already wants that item
+
+/// Warnings about the same player, per bot, at most this often
+const WARN_EVERY: Duration = Duration::from_secs(300);
+/// Notes kept per bot for the LLM
+const KEEP_HEARD: usize = 5;
+/// Note ids remembered so one that arrives twice (locally and via the hub) lands once
+const KEEP_SEEN: usize = 500;
+/// Notes waiting for the next sync, per bot; older ones give way
+const MAX_OUTBOX: usize = 50;
+/// What a warning costs the player, in trust
+const WARNING_TRUST: i32 = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Signal {
+    Meetup { at: [i32; 3], reason: String },
+    Trade { item: String, quantity: u32 }, // Spare, for whoever on the team needs it
+    Warning { player: String, reason: String },
+}
+
+impl Signal {
+    fn describe(&self) -> String {
+        match self {
+            Signal::Meetup { at, reason } => format!("quer te encontrar em {} {} {} ({})", at[0], at[1], at[2], reason),
+            Signal::Trade { item, quantity } => format!("tem {} x{} sobrando pro time", item, quantity),
+            Signal::Warning { player, reason } => format!("avisou pra tomar cuidado com {}: {}", player, reason),
+        }
+    }
+}
+
+/// One note from one of our bots to the others, or to one of them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Note {
+    pub id: String,
+    pub from: String,
+    pub to: Option<String>, // None = the whole team
+    pub at: DateTime<Utc>,
+    pub signal: Signal,
+}
+
+impl Note {
+    fn reaches(&self, bot: &str) -> bool {
+        bot != self.from && self.to.as_deref().is_none_or(|to| to == bot)
+    }
+}
+
+#[derive(Default)]
+struct Mailroom {
+    outbox: HashMap<String, Vec<Note>>,         // Sender → waiting for the hub
+    seen: (HashSet<String>, VecDeque<String>),  // Ids already delivered, and their order
+    heard: HashMap<String, VecDeque<String>>,   // Bot → last notes, for the LLM
+    warned: HashMap<(String, String), Instant>, // (bot, player) → last warning
+}
+
+static MAIL: LazyLock<Mutex<Mailroom>> = LazyLock::new(Default::default);
+
+/// Send `signal` from `from` to `to` (None = everyone else on the team)
+pub fn send(from: &str, to: Option<&str>, signal: Signal) {
+    let note = Note {
+        id: uuid::Uuid::new_v4().to_string(),
+        from: from.to_string(),
+        to: to.map(str::to_string),
+        at: Utc::now(),
+        signal,
+    };
+    info!("[TEAM] 📨 {} → {}: {}", from, to.unwrap_or("time"), note.signal.describe());
+    let config = Config::load();
+    if config.knowledge_serve || !config.knowledge_url.is_empty() {
+        let mut mail = MAIL.lock().unwrap();
+        let outbox = mail.outbox.entry(from.to_string()).or_default();
+        outbox.push(note.clone());
+        let overflow = outbox.len().saturating_sub(MAX_OUTBOX);
+        outbox.drain(..overflow);
+    }
+    incoming(vec![note]);
+}
+
+/// What `me` has waiting for the hub, taken out of the outbox
+pub fn outgoing(me: &str) -> Vec<Note> {
+    MAIL.lock().unwrap().outbox.remove(me).unwrap_or_default()
+}
+
+/// Hand notes to every bot of ours they're meant for
+pub fn incoming(notes: Vec<Note>) {
+    let sessions = crate::session::all();
+    for note in notes {
+        {
+            let mut mail = MAIL.lock().unwrap();
+            let (ids, order) = &mut mail.seen;
+            if !ids.insert(note.id.clone()) {
+                continue;
+            }
+            order.push_back(note.id.clone());
+            if order.len() > KEEP_SEEN && let Some(old) = order.pop_front() {
+                ids.remove(&old);
+            }
+        }
+        for session in sessions.iter().filter(|s| note.reaches(&s.bot.username())) {
+            receive(session, &note);
+        }
+    }
+}
+
+fn receive(session: &Session, note: &Note) {
+    let me = session.bot.username();
+    info!("[TEAM] 📬 {} recebeu do {}: {}", me, note.from, note.signal.describe());
+    {
+        let mut mail = MAIL.lock().unwrap();
+        let heard = mail.heard.entry(me).or_default();
+        heard.push_back(format!("{} {}", note.from, note.signal.describe()));
+        if heard.len() > KEEP_HEARD {
+            heard.pop_front();
+        }
+    }
+    let brain = &session.state.brain;
+    // Goals land in the receiver's planner — so does the GoalChanged, not the sender's
+    crate::bus::for_bot(session.bot.entity, || match &note.signal {
+        Signal::Warning { player, .. } => {
+            let mut memory = brain.memory.lock().unwrap();
+            let profile = memory.social.get_or_create(player);
+            profile.trust_level = (profile.trust_level - WARNING_TRUST).max(-100);
+            profile.update_relationship();
+        }
+        Signal::Meetup { at, reason } => {
+            let name = format!("Encontrar o {}", note.from);
+            let mut goals = brain.goals.lock().unwrap();
+            if !goals.goals.iter().any(|g| g.name == name && g.is_open()) {
+                let mut goal = Goal::new(&name, &format!("Em {} {} {}: {}", at[0], at[1], at[2], reason), GoalPriority::Medium);
+                goal.assigned_by = Some(note.from.clone());
+                goals.add_goal(goal);
+            }
+        }
+        Signal::Trade { item, .. } => {
+            let mut goals = brain.goals.lock().unwrap();
+            if goals.wanted_items().contains(item) {
+                let mut goal = Goal::new(&format!("Pegar {} com o {}", item.replace('_', " "), note.from), "Ele tem sobrando", GoalPriority::Medium)
+                    .wanting(item);
+                goal.assigned_by = Some(note.from.clone());
+                goals.add_goal(goal);
+            }
+        }
+    });
+}
+
+/// The last notes `me` got, for the LLM. Empty when there are none
+pub fn context_for(me: &str) -> String {
+    let mail = MAIL.lock().unwrap();
+    match mail.heard.get(me) {
+        Some(heard) if !heard.is_empty() => format!("Recados do seu time:\n{}", heard.iter().map(|h| format!("  - {}", h)).collect::<Vec<_>>().join("\n")),
+        _ => String::new(),
+    }
+}
+
+/// Bus: a player coming at us with lava, TNT or a sword — tell the others
+pub fn on_threat(ctx: &Ctx, seen: &ThreatDetected) {
+    let Some(player) = &seen.player else { return };
+    if !matches!(seen.threat.threat_type, PredictionType::PlayerGriefing | PredictionType::PlayerAmbush) {
+        return;
+    }
+    let config = Config::load();
+    let me = ctx.state.brain.me.name(&config);
+    if config.is_own_account(player) {
+        return;
+    }
+    {
+        let mut mail = MAIL.lock().unwrap();
+        let key = (me.clone(), player.clone());
+        if mail.warned.get(&key).is_some_and(|last| last.elapsed() < WARN_EVERY) {
+            return;
+        }
+        mail.warned.insert(key, Instant::now());
+    }
+    // Nobody to tell outside a swarm or fleet
+    if crate::session::all().len() < 2 && config.knowledge_url.is_empty() && !config.knowledge_serve {
+        return;
+    }
+    send(&me, None, Signal::Warning { player: player.clone(), reason: seen.threat.description.clone() });
+}
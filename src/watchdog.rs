@@ -0,0 +1,176 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{LazyLock, Mutex, TryLockError};
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
+use crate::config::Config;
+
+// ============================================================
+// WATCHDOG — Is the bot alive, or just connected?
+// "tick parado há 45s" → desconecta e reconecta; não resolveu → sai e o systemd sobe de novo
+// ============================================================
+
+const CHECK_EVERY: Duration = Duration::from_secs(5);
+
+static LAST_TICK: LazyLock<Mutex<Instant>> = LazyLock::new(|| Mutex::new(Instant::now()));
+static LLM_IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+static REPORT: LazyLock<Mutex<Health>> = LazyLock::new(|| Mutex::new(Health { ok: true, ..Default::default() }));
+
+/// What /healthz answers
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Health {
+    pub ok: bool,
+    pub connected: bool,
+    pub last_tick_secs: Option<u64>,
+    pub llm_in_flight: usize,
+    pub problems: Vec<String>,
+}
+
+/// The game loop ran. Called every tick
+pub fn tick() {
+    *LAST_TICK.lock().unwrap() = Instant::now();
+}
+
+/// Counts an LLM call as in flight until dropped
+pub struct LlmCall;
+
+pub fn llm_started() -> LlmCall {
+    LLM_IN_FLIGHT.fetch_add(1, Ordering::Relaxed);
+    LlmCall
+}
+
+impl Drop for LlmCall {
+    fn drop(&mut self) {
+        LLM_IN_FLIGHT.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+pub fn health() -> Health {
+    REPORT.lock().unwrap().clone()
+}
+
+/// Check every few seconds on a plain thread — if the runtime itself is stuck, this still runs
+pub fn spawn() {
+    std::thread::Builder::new()
+        .name("watchdog".into())
+        .spawn(run)
+        .expect("watchdog thread");
+}
+
+fn run() {
+    let mut busy_since: HashMap<&'static str, Instant> = HashMap::new(); // Locks we found taken, since when
+    let mut restarted: Option<Instant> = None;
+    loop {
+        std::thread::sleep(CHECK_EVERY);
+        let config = Config::load();
+        let stall = Duration::from_secs(config.watchdog_stall_secs);
+        let session = crate::session::current();
+        let in_flight = LLM_IN_FLIGHT.load(Ordering::Relaxed);
+        let mut problems = vec![];
+        let mut brain_dead = false;
+
+        let last_tick = session.as_ref().map(|_| LAST_TICK.lock().unwrap().elapsed());
+        if let (Some(session), Some(since_tick)) = (&session, last_tick)
+            && since_tick > stall
+            && session.since.elapsed() > stall
+        {
+            problems.push(format!("tick parado há {}s", since_tick.as_secs()));
+            brain_dead = true;
+        }
+
+        // A lock nobody lets go of for this long is a deadlock, or something just as bad
+        if let Some(session) = &session {
+            let brain = &session.state.brain;
+            let probes = [
+                ("memory", probe(&brain.memory)),
+                ("goals", probe(&brain.goals)),
+                ("world", probe(&brain.world)),
+                ("social", probe(&brain.social)),
+            ];
+            for (name, result) in probes {
+                match result {
+                    Probe::Free => {
+                        busy_since.remove(name);
+                    }
+                    Probe::Poisoned => {
+                        problems.push(format!("lock {} envenenado (uma thread morreu segurando)", name));
+                        brain_dead = true;
+                    }
+                    Probe::Busy => {
+                        let since = busy_since.entry(name).or_insert_with(Instant::now);
+                        if since.elapsed() > stall {
+                            problems.push(format!("lock {} preso há {}s", name, since.elapsed().as_secs()));
+                            brain_dead = true;
+                        }
+                    }
+                }
+            }
+
+            // The motor has no lock to hold — it's an actor, so ask whether it's still answering
+            let motor = &session.state.motor;
+            let behind = motor.view().handled_at.elapsed();
+            if session.since.elapsed() > stall && !motor.is_running() {
+                problems.push("motor morreu".into());
+                brain_dead = true;
+            } else if last_tick.is_some_and(|t| t < stall) && session.since.elapsed() > stall && behind > stall {
+                problems.push(format!("motor sem processar tick há {}s", behind.as_secs()));
+                brain_dead = true;
+            }
+        } else {
+            busy_since.clear();
+        }
+
+        if in_flight > config.watchdog_llm_backlog {
+            problems.push(format!("{} chamadas de LLM na fila", in_flight));
+        }
+
+        let health = Health {
+            ok: problems.is_empty(),
+            connected: session.is_some(),
+            last_tick_secs: last_tick.map(|t| t.as_secs()),
+            llm_in_flight: in_flight,
+            problems,
+        };
+        if !health.ok {
+            warn!("[WATCHDOG] ⚠️ {}", health.problems.join(" | "));
+        }
+        *REPORT.lock().unwrap() = health;
+
+        if !brain_dead {
+            restarted = None;
+            continue;
+        }
+        if !config.watchdog_restart {
+            continue;
+        }
+        match (restarted, session) {
+            // Reconnecting didn't fix it — let systemd/docker start us fresh
+            (Some(at), _) if at.elapsed() > stall => {
+                error!("[WATCHDOG] ❌ Continua travado depois de reconectar, saindo pro supervisor reiniciar");
+                std::process::exit(1);
+            }
+            (None, Some(session)) => {
+                info!("[WATCHDOG] 🔁 Bot conectado mas sem reagir, derrubando a conexão pra reconectar");
+                restarted = Some(Instant::now());
+                // Disconnecting goes through the ECS, which may be what's stuck
+                std::thread::spawn(move || session.bot.disconnect());
+            }
+            _ => {}
+        }
+    }
+}
+
+enum Probe {
+    Free,
+    Busy,
+    Poisoned,
+}
+
+fn probe<T>(lock: &Mutex<T>) -> Probe {
+    match lock.try_lock() {
+        Ok(_) => Probe::Free,
+        Err(TryLockError::WouldBlock) => Probe::Busy,
+        Err(TryLockError::Poisoned(_)) => Probe::Poisoned,
+    }
+}